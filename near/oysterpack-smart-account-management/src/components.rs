@@ -1,3 +1,7 @@
+pub mod account_garbage_collection;
+pub mod account_key_value_store;
 pub mod account_management;
 pub mod account_repository;
 pub(crate) mod account_storage_usage;
+pub mod admin_audit_log;
+pub mod storage_usage_bounds;
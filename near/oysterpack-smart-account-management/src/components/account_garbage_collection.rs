@@ -0,0 +1,418 @@
+//! [`AccountGarbageCollectionComponent`]
+//! - constructor: [`AccountGarbageCollectionComponent::new`]
+//!   - [`AccountManagementComponent`]
+//!   - [`AccountGcGuard`] functions provided by contract components
+
+use crate::components::account_management::AccountManagementComponent;
+use crate::*;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    env,
+    json_types::U64,
+    serde_json::json,
+};
+use oysterpack_smart_near::{asserts::ERR_INVALID, domain::YoctoNear, TERA};
+use std::fmt::Debug;
+
+/// gas that must remain before the next account is scanned
+const GAS_PER_ACCOUNT: u64 = 10 * TERA;
+
+/// Returns true if the account holds value that is managed by a contract component, e.g., a token
+/// balance, which retains the account, i.e., blocks the account from being garbage collected.
+pub type AccountGcGuard = fn(&str) -> bool;
+
+/// Implements [`AccountGarbageCollection`]
+///
+/// Contract components that manage account data outside of [`crate::AccountDataObject`] must provide
+/// an [`AccountGcGuard`] - otherwise, [`StorageManagementEvent::PreUnregister`] handlers may abort
+/// the garbage collection call because accounts are not unregistered with `force`.
+pub struct AccountGarbageCollectionComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    account_manager: AccountManagementComponent<T>,
+    guards: Vec<AccountGcGuard>,
+}
+
+impl<T> AccountGarbageCollectionComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub fn new(
+        account_manager: AccountManagementComponent<T>,
+        guards: Vec<AccountGcGuard>,
+    ) -> Self {
+        Self {
+            account_manager,
+            guards,
+        }
+    }
+
+    fn is_garbage(&self, account_id: &str, account: &AccountNearData, idle_epochs: u64) -> bool {
+        account.idle_epochs() > idle_epochs
+            && account.permissions().is_none()
+            && !account.is_frozen()
+            && AccountMigration::load(account_id).is_none()
+            && SponsoredAccounts::load(account_id).is_empty()
+            && self
+                .account_manager
+                .load_account_data(account_id)
+                .map_or(true, |data| **data == T::default())
+            && !self
+                .guards
+                .iter()
+                .any(|retains_account| retains_account(account_id))
+    }
+}
+
+impl<T> AccountGarbageCollection for AccountGarbageCollectionComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_accounts_gc(&mut self, limit: Option<u32>) -> AccountGcReport {
+        self.account_manager.assert_operator("ops_accounts_gc");
+        let mut settings = AccountGcSettings::load();
+        ERR_ACCOUNT_GC_DISABLED.assert(|| settings.idle_epochs.is_some());
+        let idle_epochs = settings.idle_epochs.unwrap().0;
+
+        // each account is scanned at most once per call
+        let limit =
+            (limit.unwrap_or(ACCOUNT_GC_DEFAULT_LIMIT) as u64).min(AccountRegistry::count());
+        let mut cursor = settings.cursor.0;
+        let mut scanned = 0;
+        let mut unregistered = vec![];
+        while scanned < limit {
+            if env::prepaid_gas() - env::used_gas() < GAS_PER_ACCOUNT {
+                break;
+            }
+            if cursor >= AccountRegistry::count() {
+                cursor = 0;
+            }
+            let account_id = AccountRegistry::get(cursor).unwrap();
+            scanned += 1;
+            let account = self
+                .account_manager
+                .registered_account_near_data(&account_id);
+            if self.is_garbage(&account_id, &account, idle_epochs) {
+                let refund = self.account_manager.unregister_account(
+                    &account_id,
+                    account,
+                    false,
+                    YoctoNear::ZERO,
+                );
+                unregistered.push(GarbageCollectedAccount { account_id, refund });
+                // the cursor is not advanced because the last registered account was moved into
+                // the unregistered account's position
+            } else {
+                cursor += 1;
+            }
+        }
+        settings.cursor = cursor.into();
+        settings.save();
+
+        let total_refund = unregistered
+            .iter()
+            .fold(YoctoNear::ZERO, |total, account| total + account.refund);
+        LOG_EVENT_ACCOUNTS_GC.log(format!(
+            "scanned: {}, unregistered: {}, refund: {}",
+            scanned,
+            unregistered.len(),
+            total_refund
+        ));
+        AuditLog::record(
+            "ops_accounts_gc",
+            None,
+            Some(
+                json!({
+                    "scanned": scanned,
+                    "unregistered": unregistered.len(),
+                    "refund": total_refund,
+                })
+                .to_string(),
+            ),
+        );
+
+        AccountGcReport {
+            scanned: scanned as u32,
+            unregistered,
+            cursor: cursor.into(),
+            registered_accounts: AccountRegistry::count().into(),
+        }
+    }
+
+    fn ops_accounts_gc_set_idle_epochs(&mut self, idle_epochs: Option<U64>) {
+        self.account_manager
+            .assert_admin("ops_accounts_gc_set_idle_epochs");
+        ERR_INVALID.assert(
+            || idle_epochs.map_or(true, |epochs| epochs.0 > 0),
+            || "idle epochs must be greater than zero",
+        );
+        let mut settings = AccountGcSettings::load();
+        settings.idle_epochs = idle_epochs;
+        settings.save();
+        AuditLog::record(
+            "ops_accounts_gc_set_idle_epochs",
+            None,
+            Some(json!({ "idle_epochs": idle_epochs }).to_string()),
+        );
+    }
+
+    fn ops_accounts_gc_settings(&self) -> AccountGcSettings {
+        AccountGcSettings::load()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::account_management::AccountManagementComponentConfig;
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::near_sdk::{self, test_utils, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<u128>;
+    type AccountGc = AccountGarbageCollectionComponent<u128>;
+
+    const ADMIN: &str = "admin";
+    const IDLE_EPOCHS: u64 = 10;
+
+    fn retains_account(account_id: &str) -> bool {
+        account_id == "retained"
+    }
+
+    fn deploy() -> (VMContext, AccountGc) {
+        let ctx = new_context(ADMIN);
+        testing_env!(ctx.clone());
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(StorageUsageBounds {
+                min: AccountManager::measure_storage_usage(0),
+                max: None,
+            }),
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: None,
+        });
+        let mut account_gc = AccountGc::new(
+            AccountManager::new(Default::default()),
+            vec![retains_account],
+        );
+        account_gc.ops_accounts_gc_set_idle_epochs(Some(IDLE_EPOCHS.into()));
+        (ctx, account_gc)
+    }
+
+    fn register(ctx: &mut VMContext, account_gc: &mut AccountGc, account_id: &str) {
+        ctx.predecessor_account_id = account_id.to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_gc.account_manager.storage_deposit(None, None);
+    }
+
+    fn gc(ctx: &mut VMContext, account_gc: &mut AccountGc, limit: Option<u32>) -> AccountGcReport {
+        ctx.predecessor_account_id = ADMIN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        account_gc.ops_accounts_gc(limit)
+    }
+
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(action) => (receipt.receiver_id.clone(), action.deposit),
+                _ => panic!("expected Transfer"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn idle_accounts_are_unregistered_and_refunded() {
+        // Arrange
+        let (mut ctx, mut account_gc) = deploy();
+        register(&mut ctx, &mut account_gc, "alice");
+        register(&mut ctx, &mut account_gc, "bob");
+        // bob is active at epoch 5
+        ctx.epoch_height = 5;
+        register(&mut ctx, &mut account_gc, "bob");
+        assert_eq!(
+            account_gc
+                .account_manager
+                .registered_account_near_data("bob")
+                .last_active()
+                .epoch,
+            5.into()
+        );
+
+        // Act - at the idle epochs threshold, accounts are not yet eligible
+        ctx.epoch_height = IDLE_EPOCHS;
+        let report = gc(&mut ctx, &mut account_gc, None);
+        assert_eq!(report.scanned, 3);
+        assert!(report.unregistered.is_empty());
+
+        // Act
+        ctx.epoch_height = IDLE_EPOCHS + 1;
+        let report = gc(&mut ctx, &mut account_gc, None);
+
+        // Assert
+        assert_eq!(report.scanned, 3);
+        assert_eq!(
+            report.unregistered,
+            vec![GarbageCollectedAccount {
+                account_id: "alice".to_string(),
+                refund: YOCTO.into(),
+            }]
+        );
+        assert_eq!(report.registered_accounts, 2.into());
+        assert_eq!(transfers(), vec![("alice".to_string(), YOCTO)]);
+        let logs = test_utils::get_logs();
+        assert!(logs.contains(
+            &"[INFO] [ACCOUNTS_GC] scanned: 3, unregistered: 1, refund: 1000000000000000000000000"
+                .to_string()
+        ));
+        assert!(!account_gc.account_manager.account_exists("alice"));
+        assert!(AccountRegistry::index_of("alice").is_none());
+        assert_eq!(
+            AccountManager::account_metrics()
+                .total_registered_accounts
+                .value(),
+            2
+        );
+        assert_eq!(
+            AuditLog::entries(AuditLog::oldest_id(), 100)
+                .last()
+                .unwrap()
+                .action,
+            "ops_accounts_gc"
+        );
+    }
+
+    #[test]
+    fn accounts_holding_value_are_skipped() {
+        // Arrange
+        let (mut ctx, mut account_gc) = deploy();
+        for account_id in ["data", "retained", "frozen", "migrating"].iter() {
+            register(&mut ctx, &mut account_gc, account_id);
+        }
+        let mut data = account_gc.account_manager.registered_account_data("data");
+        **data = 1;
+        data.save();
+
+        ctx.predecessor_account_id = "migrating".to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        account_gc
+            .account_manager
+            .ops_account_migrate(to_valid_account_id("migrating-new"));
+
+        ctx.predecessor_account_id = ADMIN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        account_gc.account_manager.ops_account_freeze(
+            to_valid_account_id("frozen"),
+            "compromised".to_string(),
+            None,
+        );
+
+        // Act
+        ctx.epoch_height = IDLE_EPOCHS + 1;
+        let report = gc(&mut ctx, &mut account_gc, None);
+
+        // Assert
+        assert_eq!(report.scanned, 5);
+        assert!(report.unregistered.is_empty());
+        assert_eq!(report.registered_accounts, 5.into());
+        assert!(transfers().is_empty());
+    }
+
+    #[test]
+    fn sponsored_balance_is_refunded_to_sponsor() {
+        // Arrange
+        let (mut ctx, mut account_gc) = deploy();
+        ctx.predecessor_account_id = "sponsor".to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_gc
+            .account_manager
+            .storage_deposit(Some(to_valid_account_id("alice")), None);
+
+        // Act
+        ctx.epoch_height = IDLE_EPOCHS + 1;
+        let report = gc(&mut ctx, &mut account_gc, None);
+
+        // Assert
+        assert_eq!(
+            report.unregistered,
+            vec![GarbageCollectedAccount {
+                account_id: "alice".to_string(),
+                refund: YoctoNear::ZERO,
+            }]
+        );
+        assert_eq!(transfers(), vec![("sponsor".to_string(), YOCTO)]);
+        assert!(SponsoredAccounts::load("sponsor").is_empty());
+    }
+
+    #[test]
+    fn scan_resumes_from_cursor() {
+        // Arrange
+        let (mut ctx, mut account_gc) = deploy();
+        for account_id in ["alice", "bob", "charlie"].iter() {
+            register(&mut ctx, &mut account_gc, account_id);
+        }
+        assert_eq!(
+            AccountRegistry::accounts(0, 10),
+            vec![ADMIN, "alice", "bob", "charlie"]
+        );
+        ctx.epoch_height = IDLE_EPOCHS + 1;
+
+        // Act - admin is skipped and alice is replaced by charlie in the registry
+        let report = gc(&mut ctx, &mut account_gc, Some(2));
+
+        // Assert
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.unregistered.len(), 1);
+        assert_eq!(report.unregistered[0].account_id, "alice");
+        assert_eq!(report.cursor, 1.into());
+        assert_eq!(account_gc.ops_accounts_gc_settings().cursor, 1.into());
+
+        // Act - resumes with charlie
+        let report = gc(&mut ctx, &mut account_gc, Some(2));
+
+        // Assert
+        assert_eq!(
+            report
+                .unregistered
+                .iter()
+                .map(|account| account.account_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["charlie", "bob"]
+        );
+        assert_eq!(report.registered_accounts, 1.into());
+
+        // Act - the scan wraps around
+        let report = gc(&mut ctx, &mut account_gc, None);
+        assert_eq!(report.scanned, 1);
+        assert_eq!(report.cursor, 1.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_GC_DISABLED]")]
+    fn gc_disabled() {
+        let (mut ctx, mut account_gc) = deploy();
+        account_gc.ops_accounts_gc_set_idle_epochs(None);
+        gc(&mut ctx, &mut account_gc, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
+    fn gc_not_operator() {
+        let (mut ctx, mut account_gc) = deploy();
+        register(&mut ctx, &mut account_gc, "alice");
+        account_gc.ops_accounts_gc(None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID]")]
+    fn set_idle_epochs_zero() {
+        let (_ctx, mut account_gc) = deploy();
+        account_gc.ops_accounts_gc_set_idle_epochs(Some(0.into()));
+    }
+}
@@ -0,0 +1,316 @@
+//! [`AccountKeyValueStoreComponent`]
+//! - constructor: [`AccountKeyValueStoreComponent::new`]
+//!   - [`AccountManagementComponent`]
+//!   - [`AccountKeyValueStoreLimits`]
+//! - [`AccountKvStore::register_storage_management_event_handler`] must be invoked when the
+//!   [`AccountManagementComponent`] is constructed, which deletes the account's entries when the
+//!   account is unregistered and moves them when the account is migrated
+
+use crate::components::account_management::AccountManagementComponent;
+use crate::*;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    env,
+    json_types::ValidAccountId,
+};
+use oysterpack_smart_near::{
+    asserts::{assert_yocto_near_attached, ERR_INVALID},
+    domain::StorageUsage,
+};
+use std::fmt::Debug;
+
+/// Implements [`AccountKeyValueStore`]
+pub struct AccountKeyValueStoreComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    account_manager: AccountManagementComponent<T>,
+    limits: AccountKeyValueStoreLimits,
+}
+
+impl<T> AccountKeyValueStoreComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub fn new(
+        account_manager: AccountManagementComponent<T>,
+        limits: AccountKeyValueStoreLimits,
+    ) -> Self {
+        Self {
+            account_manager,
+            limits,
+        }
+    }
+
+    fn assert_key(&self, key: &str) {
+        ERR_INVALID.assert(|| !key.trim().is_empty(), || "key must not be blank");
+        ERR_INVALID.assert(
+            || key.len() <= self.limits.max_key_len as usize,
+            || format!("key max length is {}", self.limits.max_key_len),
+        );
+    }
+}
+
+impl<T> AccountKeyValueStore for AccountKeyValueStoreComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    fn ops_kv_set(&mut self, key: String, value: String) -> Option<String> {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(&account_id));
+        self.assert_key(&key);
+        ERR_INVALID.assert(
+            || value.len() <= self.limits.max_value_len as usize,
+            || format!("value max length is {}", self.limits.max_value_len),
+        );
+
+        let previous_value = AccountKvStore::set(&account_id, &key, &value);
+        let storage_usage = AccountKvStore::storage_usage(&account_id);
+        ERR_KV_QUOTA_EXCEEDED.assert_with_message(
+            || storage_usage <= self.limits.max_storage_usage,
+            || {
+                format!(
+                    "storage usage = {}, max = {}",
+                    storage_usage, self.limits.max_storage_usage
+                )
+            },
+        );
+        AccountManagementComponent::<T>::record_account_activity(&account_id);
+        previous_value
+    }
+
+    fn ops_kv_delete(&mut self, key: String) -> bool {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(&account_id));
+        let deleted = AccountKvStore::delete(&account_id, &key);
+        AccountManagementComponent::<T>::record_account_activity(&account_id);
+        deleted
+    }
+
+    fn ops_kv_get(&self, account_id: ValidAccountId, key: String) -> Option<String> {
+        AccountKvStore::get(account_id.as_ref(), &key)
+    }
+
+    fn ops_kv_keys(&self, account_id: ValidAccountId) -> Vec<String> {
+        AccountKvStore::keys(account_id.as_ref())
+    }
+
+    fn ops_kv_storage_usage(&self, account_id: ValidAccountId) -> StorageUsage {
+        AccountKvStore::storage_usage(account_id.as_ref())
+    }
+
+    fn ops_kv_limits(&self) -> AccountKeyValueStoreLimits {
+        self.limits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::account_management::AccountManagementComponentConfig;
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::near_sdk::{self, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+    type KvStore = AccountKeyValueStoreComponent<()>;
+
+    const ADMIN: &str = "admin";
+    const ACCOUNT: &str = "alice";
+
+    const LIMITS: AccountKeyValueStoreLimits = AccountKeyValueStoreLimits {
+        max_key_len: 16,
+        max_value_len: 64,
+        max_storage_usage: StorageUsage(1000),
+    };
+
+    fn deploy() -> (VMContext, KvStore) {
+        let mut ctx = new_context(ADMIN);
+        testing_env!(ctx.clone());
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: None,
+        });
+        AccountKvStore::register_storage_management_event_handler();
+        let mut kv_store = KvStore::new(AccountManager::new(Default::default()), LIMITS);
+
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        kv_store.account_manager.storage_deposit(None, None);
+
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        (ctx, kv_store)
+    }
+
+    fn account_storage_usage(kv_store: &KvStore) -> StorageUsage {
+        kv_store
+            .account_manager
+            .registered_account_near_data(ACCOUNT)
+            .storage_usage()
+    }
+
+    #[test]
+    fn set_get_delete() {
+        // Arrange
+        let (_ctx, mut kv_store) = deploy();
+        let initial_storage_usage = account_storage_usage(&kv_store);
+
+        // Act
+        assert!(kv_store
+            .ops_kv_set("name".to_string(), "Alice".to_string())
+            .is_none());
+        assert!(kv_store
+            .ops_kv_set("email".to_string(), "alice@example.com".to_string())
+            .is_none());
+
+        // Assert
+        let account_id = to_valid_account_id(ACCOUNT);
+        assert_eq!(
+            kv_store.ops_kv_get(account_id.clone(), "name".to_string()),
+            Some("Alice".to_string())
+        );
+        assert_eq!(
+            kv_store.ops_kv_keys(account_id.clone()),
+            vec!["email".to_string(), "name".to_string()]
+        );
+        let kv_storage_usage = kv_store.ops_kv_storage_usage(account_id.clone());
+        assert!(kv_storage_usage.value() > 0);
+        assert_eq!(
+            AccountStorageUsageBreakdown::load(ACCOUNT)[COMPONENT_ID_ACCOUNT_KV_STORE.name()],
+            kv_storage_usage
+        );
+        // the account is also charged for the KV store entry in its storage usage breakdown
+        assert!(
+            account_storage_usage(&kv_store).value()
+                > initial_storage_usage.value() + kv_storage_usage.value()
+        );
+
+        // Act - overwrite
+        assert_eq!(
+            kv_store.ops_kv_set("name".to_string(), "Alice Smith".to_string()),
+            Some("Alice".to_string())
+        );
+        assert_eq!(
+            kv_store.ops_kv_storage_usage(account_id.clone()).value(),
+            kv_storage_usage.value() + "Alice Smith".len() as u64 - "Alice".len() as u64
+        );
+
+        // Act - delete
+        assert!(kv_store.ops_kv_delete("name".to_string()));
+        assert!(!kv_store.ops_kv_delete("name".to_string()));
+        assert!(kv_store.ops_kv_delete("email".to_string()));
+
+        // Assert - all storage is released
+        assert!(kv_store.ops_kv_keys(account_id.clone()).is_empty());
+        assert!(kv_store
+            .ops_kv_get(account_id.clone(), "name".to_string())
+            .is_none());
+        assert_eq!(kv_store.ops_kv_storage_usage(account_id), 0.into());
+        assert_eq!(account_storage_usage(&kv_store), initial_storage_usage);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID] key max length is 16")]
+    fn key_too_long() {
+        let (_ctx, mut kv_store) = deploy();
+        kv_store.ops_kv_set("k".repeat(17), "value".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID] key must not be blank")]
+    fn blank_key() {
+        let (_ctx, mut kv_store) = deploy();
+        kv_store.ops_kv_set(" ".to_string(), "value".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID] value max length is 64")]
+    fn value_too_long() {
+        let (_ctx, mut kv_store) = deploy();
+        kv_store.ops_kv_set("key".to_string(), "v".repeat(65));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [KV_QUOTA_EXCEEDED]")]
+    fn quota_exceeded() {
+        let (_ctx, mut kv_store) = deploy();
+        for i in 0..10 {
+            kv_store.ops_kv_set(format!("key-{}", i), "v".repeat(64));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_NOT_REGISTERED]")]
+    fn account_not_registered() {
+        let (mut ctx, mut kv_store) = deploy();
+        ctx.predecessor_account_id = "bob".to_string();
+        testing_env!(ctx);
+        kv_store.ops_kv_set("key".to_string(), "value".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn yocto_required() {
+        let (mut ctx, mut kv_store) = deploy();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx);
+        kv_store.ops_kv_set("key".to_string(), "value".to_string());
+    }
+
+    #[test]
+    fn entries_are_deleted_when_account_is_unregistered() {
+        // Arrange
+        let (_ctx, mut kv_store) = deploy();
+        kv_store.ops_kv_set("name".to_string(), "Alice".to_string());
+
+        // Act
+        assert!(kv_store.account_manager.storage_unregister(None));
+
+        // Assert
+        let account_id = to_valid_account_id(ACCOUNT);
+        assert!(kv_store.ops_kv_keys(account_id.clone()).is_empty());
+        assert!(kv_store
+            .ops_kv_get(account_id.clone(), "name".to_string())
+            .is_none());
+        assert_eq!(kv_store.ops_kv_storage_usage(account_id), 0.into());
+    }
+
+    #[test]
+    fn entries_are_moved_when_account_is_migrated() {
+        // Arrange
+        let (mut ctx, mut kv_store) = deploy();
+        kv_store.ops_kv_set("name".to_string(), "Alice".to_string());
+        let kv_storage_usage = kv_store.ops_kv_storage_usage(to_valid_account_id(ACCOUNT));
+        kv_store
+            .account_manager
+            .ops_account_migrate(to_valid_account_id("alice-new"));
+
+        // Act
+        ctx.predecessor_account_id = "alice-new".to_string();
+        testing_env!(ctx);
+        kv_store
+            .account_manager
+            .ops_account_migrate_accept(to_valid_account_id(ACCOUNT));
+
+        // Assert
+        assert!(kv_store
+            .ops_kv_keys(to_valid_account_id(ACCOUNT))
+            .is_empty());
+        let new_account_id = to_valid_account_id("alice-new");
+        assert_eq!(
+            kv_store.ops_kv_get(new_account_id.clone(), "name".to_string()),
+            Some("Alice".to_string())
+        );
+        // keys are scoped by the account ID hash - thus the storage usage is the same
+        assert_eq!(
+            kv_store.ops_kv_storage_usage(new_account_id),
+            kv_storage_usage
+        );
+    }
+}
@@ -8,34 +8,56 @@ use crate::*;
 use oysterpack_smart_near::near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
     env,
-    json_types::ValidAccountId,
-    Promise,
+    json_types::{ValidAccountId, U128, U64},
+    serde_json::json,
+    AccountId, Promise,
 };
 use oysterpack_smart_near::{
     asserts::{assert_min_near_attached, assert_yocto_near_attached},
-    domain::YoctoNear,
+    domain::{Expiration, ExpirationDuration, ExpirationSetting, YoctoNear},
     eventbus, ErrCode, ErrorConst,
 };
-use std::{fmt::Debug, ops::Deref};
+use std::mem::discriminant;
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+};
 
 use crate::components::account_repository::AccountRepositoryComponent;
 use crate::components::account_storage_usage::AccountStorageUsageComponent;
 use oysterpack_smart_near::asserts::{assert_account_not_predecessor, ERR_INVALID};
 use oysterpack_smart_near::component::Deploy;
 use oysterpack_smart_near::domain::StorageUsage;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::marker::PhantomData;
 
+const ADMIN_BIT: u8 = 63;
+const OPERATOR_BIT: u8 = 62;
+
 pub const ERR_INSUFFICIENT_STORAGE_BALANCE: ErrorConst = ErrorConst(
     ErrCode("INSUFFICIENT_STORAGE_BALANCE"),
     "account's available storage balance is insufficient to satisfy request",
 );
 
+pub const ERR_STORAGE_USAGE_MAX_EXCEEDED: ErrorConst = ErrorConst(
+    ErrCode("STORAGE_USAGE_MAX_EXCEEDED"),
+    "account storage usage exceeds the max allowed storage usage",
+);
+
+pub const ERR_STORAGE_BALANCE_BELOW_MIN: ErrorConst = ErrorConst(
+    ErrCode("STORAGE_BALANCE_BELOW_MIN"),
+    "account storage balance is below the min storage balance - top up the storage balance",
+);
+
 /// Core account management component implements the following interfaces:
 /// 1. [`AccountRepository`]
 /// 2. [`StorageManagement`] - NEP-145
 /// 3. [`AccountStorageUsage`]
 /// 4. [`PermissionsManagement`]
+/// 5. [`AccountDelegation`]
+/// 6. [`StorageSponsorship`]
+/// 7. [`AccountFreezeManagement`]
+/// 8. [`AccountMigrationManagement`]
 ///
 /// ## Deployment
 /// - [`AccountManagementComponent::deploy`]
@@ -84,35 +106,128 @@ where
     pub fn measure_storage_usage(account_data: T) -> StorageUsage {
         let mut account_manager: Self = Self::new(Default::default());
 
-        // seeds the storage required to store metrics
-        {
-            let account_id = "1953717115592535419708657925195464285";
+        // the measurement accounts are registered with a zero balance and are deleted before the
+        // checks are settled
+        Self::defer_storage_balance_coverage(|| {
+            // seeds the storage required to store metrics
+            {
+                let account_id = "1953717115592535419708657925195464285";
+                account_manager.delete_account(account_id);
+                account_manager.create_account(account_id, 0.into(), Some(account_data.clone()));
+                account_manager.delete_account(account_id);
+            }
+
+            // max length account ID because the account ID is stored in the [`AccountRegistry`], which
+            // is charged to the account
+            let account_id = "1953718041838591893489340663938715635195371804183859189348934066";
             account_manager.delete_account(account_id);
-            account_manager.create_account(account_id, 0.into(), Some(account_data.clone()));
+            let initial_storage_usage = env::storage_usage();
+            let (mut account, _data) =
+                account_manager.create_account(account_id, 0.into(), Some(account_data));
+            account.grant_operator();
+            account.save();
+            let storage_usage = env::storage_usage() - initial_storage_usage;
+
+            // clean up storage
             account_manager.delete_account(account_id);
-        }
+            // ensure all data is cleaned up
+            assert_eq!(initial_storage_usage, env::storage_usage());
 
-        let account_id = "1953718041838591893489340663938715635";
-        account_manager.delete_account(account_id);
-        let initial_storage_usage = env::storage_usage();
-        let (mut account, _data) =
-            account_manager.create_account(account_id, 0.into(), Some(account_data));
-        account.grant_operator();
-        account.save();
-        let storage_usage = env::storage_usage() - initial_storage_usage;
+            storage_usage.into()
+        })
+    }
+
+    /// Measures the account storage min, i.e., the default account storage usage plus the min
+    /// account storage required by each of the specified contract components.
+    pub fn measure_account_storage_min(
+        component_account_storage_mins: &[fn() -> StorageUsage],
+    ) -> StorageUsage {
+        component_account_storage_mins
+            .iter()
+            .fold(Self::measure_storage_usage(Default::default()), |sum, f| {
+                sum + f()
+            })
+    }
 
-        // clean up storage
-        account_manager.delete_account(account_id);
-        // ensure all data is cleaned up
-        assert_eq!(initial_storage_usage, env::storage_usage());
+    /// Returns the min storage balance that the account is held to, which is the contract min
+    /// storage balance unless the account was grandfathered at a lower balance - see
+    /// [`GrandfatheredStorageBalance`]
+    pub fn account_storage_balance_min(account_id: &str) -> YoctoNear {
+        Self::storage_balance_min(account_id.into())
+    }
 
-        storage_usage.into()
+    fn storage_balance_min(account_id_hash: AccountIdHash) -> YoctoNear {
+        AccountStorageUsageComponent::storage_balance_min(account_id_hash)
     }
 
     pub fn account_metrics() -> AccountMetrics {
         AccountMetrics::load()
     }
 
+    /// Records that the account initiated activity, e.g., moved funds, which keeps the account from
+    /// being garbage collected as inactive - see [`AccountNearData::last_active`]
+    /// - no-op if the account is not registered
+    pub fn record_account_activity(account_id: &str) {
+        if let Some(mut account) = AccountNearDataObject::load(account_id) {
+            account.record_activity();
+            account.save();
+        }
+    }
+
+    /// ## Panics
+    /// [`ERR_ACCOUNT_FROZEN`] - if the account is registered and frozen
+    pub fn assert_account_not_frozen(account_id: &str) {
+        ERR_ACCOUNT_FROZEN.assert(|| {
+            AccountNearDataObject::load(account_id).map_or(true, |account| !account.is_frozen())
+        });
+    }
+
+    /// Runs `f` with the storage balance coverage checks deferred until `f` returns.
+    ///
+    /// By default, storage balance coverage is enforced by the account storage event handler as soon
+    /// as an account's storage usage grows. Mutating contract calls that grow account storage before
+    /// crediting the account's storage balance, e.g., registering an account and then depositing
+    /// funds, defer the check until the call has settled. The accounts whose storage usage grew are
+    /// recorded on the [`StorageLedger`] and are checked when the outermost deferral completes.
+    ///
+    /// Accounts whose NEAR balance fell below the min storage balance because the account storage
+    /// min was raised must top up their storage balance before their storage usage can grow, unless
+    /// they are grandfathered - see [`StorageUsageBoundsManagement`].
+    ///
+    /// ## Panics
+    /// - [`ERR_STORAGE_USAGE_MAX_EXCEEDED`]
+    /// - [`ERR_INSUFFICIENT_STORAGE_BALANCE`]
+    /// - [`ERR_STORAGE_BALANCE_BELOW_MIN`]
+    pub fn defer_storage_balance_coverage<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        StorageLedger::begin_deferral();
+        let result = f();
+        if StorageLedger::end_deferral() {
+            Self::assert_storage_balance_coverage();
+        }
+        result
+    }
+
+    /// Settles the [`StorageLedger`], i.e., asserts that each account whose storage usage has grown
+    /// while the checks were deferred is still within the storage usage bounds and has a NEAR balance
+    /// that covers its storage usage cost.
+    ///
+    /// ## Panics
+    /// - [`ERR_STORAGE_USAGE_MAX_EXCEEDED`]
+    /// - [`ERR_INSUFFICIENT_STORAGE_BALANCE`]
+    /// - [`ERR_STORAGE_BALANCE_BELOW_MIN`]
+    fn assert_storage_balance_coverage() {
+        // accounts that were unregistered during the call are skipped
+        StorageLedger::take()
+            .into_iter()
+            .filter_map(AccountNearDataObject::load)
+            .for_each(|account| {
+                AccountStorageUsageComponent::assert_storage_balance_coverage(&account)
+            });
+    }
+
     /// if the account is not registered, then the contract will register the account and pay for its
     /// storage
     pub fn get_or_register_account(account_id: &str) -> AccountNearDataObject {
@@ -126,6 +241,7 @@ where
                 let account = AccountNearDataObject::new(account_id, storage_balance_bounds.min);
                 let storage_balance = account.storage_balance(storage_balance_bounds.min);
                 account.save();
+                AccountRegistry::add(account_id);
                 eventbus::post(&AccountStorageEvent::Registered(storage_balance));
             }
             // the account storage usage is updated by the storage event handler - thus the object state
@@ -143,6 +259,7 @@ where
             let account = AccountNearDataObject::new(account_id, storage_balance_bounds.min);
             let storage_balance = account.storage_balance(storage_balance_bounds.min);
             account.save();
+            AccountRegistry::add(account_id);
             eventbus::post(&AccountStorageEvent::Registered(storage_balance));
         }
     }
@@ -272,6 +389,13 @@ where
     fn ops_storage_usage(&self, account_id: ValidAccountId) -> Option<StorageUsage> {
         AccountStorageUsageComponent.ops_storage_usage(account_id)
     }
+
+    fn ops_storage_usage_by_component(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<String, ComponentStorageUsage>> {
+        AccountStorageUsageComponent.ops_storage_usage_by_component(account_id)
+    }
 }
 
 impl<T> StorageManagement for AccountManagementComponent<T>
@@ -296,26 +420,45 @@ where
         }
         let deposit: YoctoNear = env::attached_deposit().into();
 
-        let account: AccountNearDataObject = match self.load_account_near_data(&account_id) {
-            Some(mut account) => {
-                if registration_only {
-                    // refund the full deposit
-                    send_refund(deposit.value());
-                } else {
-                    if let Some(max) = storage_balance_bounds.max {
-                        self.deposit_with_max_bound(
-                            &mut account,
-                            Deposit(deposit),
-                            MaxStorageBalance(max),
-                        )
+        let (mut account, near_balance_before_deposit) =
+            match self.load_account_near_data(&account_id) {
+                Some(mut account) => {
+                    let near_balance_before_deposit = account.near_balance();
+                    if registration_only {
+                        // refund the full deposit
+                        send_refund(deposit.value());
                     } else {
-                        self.deposit(&mut account, deposit)
+                        if let Some(max) = storage_balance_bounds.max {
+                            self.deposit_with_max_bound(
+                                &mut account,
+                                Deposit(deposit),
+                                MaxStorageBalance(max),
+                            )
+                        } else {
+                            self.deposit(&mut account, deposit)
+                        }
                     }
+                    (account, near_balance_before_deposit)
                 }
-                account
-            }
-            None => self.register_account(&account_id, deposit, registration_only),
-        };
+                None => (
+                    self.register_account(&account_id, deposit, registration_only),
+                    YoctoNear::ZERO,
+                ),
+            };
+
+        let sponsor = env::predecessor_account_id();
+        if sponsor == account_id {
+            Self::record_account_activity(&account_id);
+        } else {
+            Self::credit_sponsor(
+                &sponsor,
+                &account_id,
+                account.near_balance() - near_balance_before_deposit,
+                storage_balance_bounds.min,
+            );
+            // tracking the sponsor changes the account's storage usage
+            account = self.registered_account_near_data(&account_id);
+        }
 
         account.storage_balance(storage_balance_bounds.min)
     }
@@ -325,27 +468,40 @@ where
 
         let account_id = env::predecessor_account_id();
         let mut account = self.registered_account_near_data(&account_id);
+        ERR_ACCOUNT_FROZEN.assert(|| !account.is_frozen());
         let storage_balance_bounds = self.storage_balance_bounds();
+        // the token credit is paid back in tokens - thus it is not available for NEAR withdrawals
+        let token_credit = StorageTokenCredit::load(&account_id);
         let account_available_balance = account
             .storage_balance(storage_balance_bounds.min)
-            .available;
-        match amount {
-            Some(amount) => {
-                if amount > YoctoNear::ZERO {
-                    ERR_INSUFFICIENT_STORAGE_BALANCE.assert(|| account_available_balance >= amount);
-                    send_refund(amount + 1);
-                    account.decr_near_balance(amount);
-                    account.save();
-                }
-            }
-            None => {
-                // withdraw the total available balance
-                if account_available_balance > YoctoNear::ZERO {
-                    send_refund(account_available_balance + 1);
-                    account.decr_near_balance(account_available_balance);
-                    account.save();
-                }
-            }
+            .available
+            .min(
+                account
+                    .near_balance()
+                    .saturating_sub(token_credit.value())
+                    .into(),
+            );
+        // if no amount is specified, then withdraw the total available balance
+        let amount = amount.unwrap_or(account_available_balance);
+        if amount > YoctoNear::ZERO {
+            ERR_INSUFFICIENT_STORAGE_BALANCE.assert(|| account_available_balance >= amount);
+            // the account's own funds are withdrawn first - the remainder is refunded to sponsors
+            // the sponsored balance and token credit are tracked independently - thus together they
+            // may exceed the account's NEAR balance
+            let own_balance: YoctoNear = account
+                .near_balance()
+                .saturating_sub(account.sponsored_balance().value())
+                .saturating_sub(token_credit.value())
+                .into();
+            let own_amount = amount.min(own_balance);
+            let sponsor_refunds = account.debit_sponsors(amount - own_amount);
+            send_refund(own_amount + 1);
+            account.decr_near_balance(amount);
+            account.record_activity();
+            account.save();
+            Self::refund_sponsors(&account_id, &account, sponsor_refunds);
+            // the account's storage usage is updated when the account is saved
+            account = self.registered_account_near_data(&account_id);
         }
 
         account.storage_balance(storage_balance_bounds.min)
@@ -356,14 +512,8 @@ where
         let account_id = env::predecessor_account_id();
         self.load_account_near_data(&account_id)
             .map_or(false, |account| {
-                let account_near_balance = account.near_balance();
-                eventbus::post(&StorageManagementEvent::PreUnregister {
-                    account_id: account_id.clone(),
-                    force: force.unwrap_or(false),
-                });
-                self.delete_account(&account_id);
-                eventbus::post(&AccountStorageEvent::Unregistered(account_near_balance));
-                send_refund(account_near_balance + 1);
+                ERR_ACCOUNT_FROZEN.assert(|| !account.is_frozen());
+                self.unregister_account(&account_id, account, force.unwrap_or(false), 1.into());
                 true
             })
     }
@@ -380,6 +530,27 @@ where
     }
 }
 
+impl<T> StorageSponsorship for AccountManagementComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    fn ops_storage_sponsored_accounts(&self, sponsor: ValidAccountId) -> Vec<AccountId> {
+        let mut accounts: Vec<AccountId> = SponsoredAccounts::load(sponsor.as_ref())
+            .into_iter()
+            .collect();
+        accounts.sort();
+        accounts
+    }
+
+    fn ops_storage_sponsors(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<AccountId, YoctoNear>> {
+        self.load_account_near_data(account_id.as_ref())
+            .and_then(|account| account.sponsors().cloned())
+    }
+}
+
 impl<T> PermissionsManagement for AccountManagementComponent<T>
 where
     T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
@@ -391,26 +562,37 @@ where
 
     fn ops_permissions_grant_admin(&mut self, account_id: ValidAccountId) {
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_grant_admin");
 
         let mut account = self.registered_account_near_data(account_id.as_ref());
         if !account.is_admin() {
             account.grant_admin();
             account.save();
-            LOG_EVENT_PERMISSIONS_GRANT.log("admin")
+            LOG_EVENT_PERMISSIONS_GRANT.log("admin");
+            AuditLog::record(
+                "ops_permissions_grant_admin",
+                Some(account_id.as_ref()),
+                None,
+            );
         }
     }
 
     fn ops_permissions_revoke_admin(&mut self, account_id: ValidAccountId) {
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_revoke_admin");
 
         let mut account = self.registered_account_near_data(account_id.as_ref());
         if account.is_admin() {
             account.revoke_admin();
             Self::clear_permissions_if_has_no_permissions(&mut account);
             account.save();
-            LOG_EVENT_PERMISSIONS_REVOKE.log("admin")
+            ExpiringPermissionsAccounts::update(account_id.as_ref(), &account);
+            LOG_EVENT_PERMISSIONS_REVOKE.log("admin");
+            AuditLog::record(
+                "ops_permissions_revoke_admin",
+                Some(account_id.as_ref()),
+                None,
+            );
         }
     }
 
@@ -419,44 +601,85 @@ where
             .map_or(false, |account| account.is_operator())
     }
 
-    fn ops_permissions_grant_operator(&mut self, account_id: ValidAccountId) {
+    fn ops_permissions_grant_operator(
+        &mut self,
+        account_id: ValidAccountId,
+        expiration: Option<ExpirationSetting>,
+    ) {
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_grant_operator");
+        let expiration = Self::assert_not_expired(expiration);
 
         let mut account = self.registered_account_near_data(account_id.as_ref());
-        if !account.is_operator() {
-            account.grant_operator();
+        account.purge_expired_permissions();
+        Self::purge_retired_permissions(&mut account);
+        if !account.is_operator() || account.permission_expiration(OPERATOR_BIT) != expiration {
+            account.grant_operator_with_expiration(expiration);
             account.save();
-            LOG_EVENT_PERMISSIONS_GRANT.log("operator")
+            ExpiringPermissionsAccounts::update(account_id.as_ref(), &account);
+            Self::log_permissions_granted("operator", expiration);
+            AuditLog::record(
+                "ops_permissions_grant_operator",
+                Some(account_id.as_ref()),
+                expiration.map(|expiration| json!({ "expiration": expiration }).to_string()),
+            );
         }
     }
 
     fn ops_permissions_revoke_operator(&mut self, account_id: ValidAccountId) {
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_revoke_operator");
 
         let mut account = self.registered_account_near_data(account_id.as_ref());
+        Self::purge_retired_permissions(&mut account);
         if account.is_operator() {
             account.revoke_operator();
             Self::clear_permissions_if_has_no_permissions(&mut account);
             account.save();
-            LOG_EVENT_PERMISSIONS_REVOKE.log("operator")
+            ExpiringPermissionsAccounts::update(account_id.as_ref(), &account);
+            LOG_EVENT_PERMISSIONS_REVOKE.log("operator");
+            AuditLog::record(
+                "ops_permissions_revoke_operator",
+                Some(account_id.as_ref()),
+                None,
+            );
         }
     }
 
-    fn ops_permissions_grant(&mut self, account_id: ValidAccountId, permissions: Permissions) {
+    fn ops_permissions_grant(
+        &mut self,
+        account_id: ValidAccountId,
+        permissions: Permissions,
+        expiration: Option<ExpirationSetting>,
+    ) {
         self.assert_contract_supports_permissions(permissions);
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_grant");
+        let expiration = Self::assert_not_expired(expiration);
 
         let mut account = self.registered_account_near_data(account_id.as_ref());
-        if !account.contains_permissions(permissions) {
-            account.grant(permissions);
+        account.purge_expired_permissions();
+        Self::purge_retired_permissions(&mut account);
+        let expiration_changed = permissions
+            .permission_bits()
+            .iter()
+            .any(|perm_bit| account.permission_expiration(*perm_bit) != expiration);
+        if !account.contains_permissions(permissions) || expiration_changed {
+            account.grant_with_expiration(permissions, expiration);
             account.save();
-            LOG_EVENT_PERMISSIONS_GRANT.log(format!(
-                "{:?}",
-                self.contract_permissions.permission_names(permissions)
-            ));
+            ExpiringPermissionsAccounts::update(account_id.as_ref(), &account);
+            Self::log_permissions_granted(
+                format!(
+                    "{:?}",
+                    self.contract_permissions().permission_names(permissions)
+                ),
+                expiration,
+            );
+            AuditLog::record(
+                "ops_permissions_grant",
+                Some(account_id.as_ref()),
+                Some(json!({ "permissions": permissions, "expiration": expiration }).to_string()),
+            );
         }
     }
 
@@ -464,27 +687,35 @@ where
         &mut self,
         account_id: ValidAccountId,
         permissions: Vec<u8>,
+        expiration: Option<ExpirationSetting>,
     ) {
         let permissions = permissions
             .iter()
             .fold(0_u64, |permissions, perm_bit| permissions | 1 << *perm_bit);
-        self.ops_permissions_grant(account_id, permissions.into());
+        self.ops_permissions_grant(account_id, permissions.into(), expiration);
     }
 
     fn ops_permissions_revoke(&mut self, account_id: ValidAccountId, permissions: Permissions) {
         self.assert_contract_supports_permissions(permissions);
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_revoke");
 
         let mut account = self.registered_account_near_data(account_id.as_ref());
+        Self::purge_retired_permissions(&mut account);
         if account.permissions().is_some() {
             account.revoke(permissions);
             Self::clear_permissions_if_has_no_permissions(&mut account);
             account.save();
+            ExpiringPermissionsAccounts::update(account_id.as_ref(), &account);
             LOG_EVENT_PERMISSIONS_REVOKE.log(format!(
                 "{:?}",
-                self.contract_permissions.permission_names(permissions)
+                self.contract_permissions().permission_names(permissions)
             ));
+            AuditLog::record(
+                "ops_permissions_revoke",
+                Some(account_id.as_ref()),
+                Some(json!({ "permissions": permissions }).to_string()),
+            );
         }
     }
 
@@ -501,12 +732,18 @@ where
 
     fn ops_permissions_revoke_all(&mut self, account_id: ValidAccountId) {
         assert_account_not_predecessor(account_id.as_ref());
-        self.assert_predecessor_is_admin();
+        self.assert_admin("ops_permissions_revoke_all");
         let mut account = self.registered_account_near_data(account_id.as_ref());
         if account.permissions().is_some() {
             account.revoke_all();
             account.save();
+            ExpiringPermissionsAccounts::remove(account_id.as_ref());
             LOG_EVENT_PERMISSIONS_REVOKE.log("all permissions were revoked");
+            AuditLog::record(
+                "ops_permissions_revoke_all",
+                Some(account_id.as_ref()),
+                None,
+            );
         }
     }
 
@@ -515,20 +752,35 @@ where
         account_id: ValidAccountId,
         permissions: Permissions,
     ) -> bool {
-        self.load_account_near_data(account_id.as_ref())
-            .map_or(false, |account| account.contains_permissions(permissions))
+        // retired permissions are revoked lazily
+        if *permissions & *Self::retired_permissions() != 0 {
+            return false;
+        }
+        self.ops_permissions(account_id).map_or(false, |perms| {
+            perms.contains(permissions) || perms.contains(Permissions::ADMIN)
+        })
     }
 
     fn ops_permissions(&self, account_id: ValidAccountId) -> Option<Permissions> {
         self.load_account_near_data(account_id.as_ref())
             .map(|account| account.permissions())
             .flatten()
+            .and_then(|mut permissions| {
+                // retired permissions are revoked lazily
+                permissions.revoke(Self::retired_permissions());
+                if permissions.has_permissions() {
+                    Some(permissions)
+                } else {
+                    None
+                }
+            })
     }
 
     fn ops_permissions_granted(&self, account_id: ValidAccountId) -> Option<HashMap<u8, String>> {
         self.ops_permissions(account_id).map(|perms| {
-            let mut account_perms = HashMap::with_capacity(self.contract_permissions.0.len() + 2);
-            for (perm_bit, name) in self.contract_permissions.0.iter() {
+            let contract_permissions = self.contract_permissions();
+            let mut account_perms = HashMap::with_capacity(contract_permissions.0.len() + 2);
+            for (perm_bit, name) in contract_permissions.0.iter() {
                 if perms.contains(1 << *perm_bit) {
                     account_perms.insert(*perm_bit, name.to_string());
                 }
@@ -545,14 +797,314 @@ where
     }
 
     fn ops_permissions_contract_permissions(&self) -> Option<HashMap<u8, String>> {
-        if self.contract_permissions.0.is_empty() {
+        let contract_permissions = self.contract_permissions();
+        if contract_permissions.0.is_empty() {
             return None;
         }
-        let mut perms = HashMap::with_capacity(self.contract_permissions.0.len());
-        for (k, value) in self.contract_permissions.0.iter() {
-            perms.insert(*k, value.to_string());
+        Some(contract_permissions.0)
+    }
+
+    fn ops_permissions_contract_permission_add(&mut self, permission: u8, name: String) {
+        self.assert_admin("ops_permissions_contract_permission_add");
+        let mut state = self.contract_permissions_state();
+        state.add(permission, &name);
+        state.save();
+        LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE.log(format!("added: {} -> {}", permission, name));
+        AuditLog::record(
+            "ops_permissions_contract_permission_add",
+            None,
+            Some(json!({ "permission": permission, "name": name }).to_string()),
+        );
+    }
+
+    fn ops_permissions_contract_permission_rename(&mut self, permission: u8, name: String) {
+        self.assert_admin("ops_permissions_contract_permission_rename");
+        let mut state = self.contract_permissions_state();
+        state.permissions.rename(permission, &name);
+        state.save();
+        LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE.log(format!("renamed: {} -> {}", permission, name));
+        AuditLog::record(
+            "ops_permissions_contract_permission_rename",
+            None,
+            Some(json!({ "permission": permission, "name": name }).to_string()),
+        );
+    }
+
+    fn ops_permissions_contract_permission_retire(&mut self, permission: u8) {
+        self.assert_admin("ops_permissions_contract_permission_retire");
+        let mut state = self.contract_permissions_state();
+        state.retire(permission);
+        state.save();
+        LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE.log(format!("retired: {}", permission));
+        AuditLog::record(
+            "ops_permissions_contract_permission_retire",
+            None,
+            Some(json!({ "permission": permission }).to_string()),
+        );
+    }
+
+    fn ops_permissions_contract_permissions_retired(&self) -> Option<Vec<u8>> {
+        let retired = Self::retired_permissions();
+        if retired.has_permissions() {
+            Some(retired.permission_bits())
+        } else {
+            None
+        }
+    }
+
+    fn ops_permissions_expiring(
+        &self,
+        within: Option<ExpirationDuration>,
+        from_index: Option<U64>,
+        limit: Option<u32>,
+    ) -> ExpiringPermissions {
+        let threshold: Option<Expiration> = within.map(Into::into);
+        let from_index = from_index.map_or(0, |index| index.0);
+        let limit = limit.unwrap_or(EXPIRING_PERMISSIONS_DEFAULT_LIMIT) as u64;
+        let account_ids = ExpiringPermissionsAccounts::accounts(from_index, limit);
+        let next_index = from_index + account_ids.len() as u64;
+
+        let mut expiring_permissions = vec![];
+        for account_id in account_ids {
+            let account = match self.load_account_near_data(&account_id) {
+                Some(account) => account,
+                None => continue,
+            };
+            let permissions = match account.permissions() {
+                Some(permissions) => permissions,
+                None => continue,
+            };
+            if let Some(expirations) = account.permission_expirations() {
+                for (perm_bit, expiration) in expirations {
+                    if !permissions.contains(1_u64 << *perm_bit) {
+                        continue;
+                    }
+                    let expires_within_threshold = match threshold {
+                        None => true,
+                        Some(threshold) => {
+                            discriminant(&threshold) == discriminant(expiration)
+                                && *expiration <= threshold
+                        }
+                    };
+                    if expires_within_threshold {
+                        expiring_permissions.push(ExpiringPermission {
+                            account_id: account_id.clone(),
+                            permission: *perm_bit,
+                            name: self.permission_name(*perm_bit),
+                            expiration: *expiration,
+                        });
+                    }
+                }
+            }
+        }
+        expiring_permissions.sort_by(|a, b| {
+            a.expiration
+                .cmp(&b.expiration)
+                .then_with(|| a.account_id.cmp(&b.account_id))
+                .then_with(|| a.permission.cmp(&b.permission))
+        });
+        ExpiringPermissions {
+            permissions: expiring_permissions,
+            next_index: if next_index < ExpiringPermissionsAccounts::count() {
+                Some(next_index.into())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl<T> AccountFreezeManagement for AccountManagementComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_account_freeze(
+        &mut self,
+        account_id: ValidAccountId,
+        reason: String,
+        expiration: Option<ExpirationSetting>,
+    ) -> AccountFreeze {
+        assert_account_not_predecessor(account_id.as_ref());
+        self.assert_admin("ops_account_freeze");
+        let reason = reason.trim().to_string();
+        ERR_INVALID.assert(|| !reason.is_empty(), || "freeze reason cannot be blank");
+        ERR_INVALID.assert(
+            || reason.len() <= ACCOUNT_FREEZE_REASON_MAX_LEN,
+            || {
+                format!(
+                    "freeze reason max length is {}",
+                    ACCOUNT_FREEZE_REASON_MAX_LEN
+                )
+            },
+        );
+
+        let mut account = self.registered_account_near_data(account_id.as_ref());
+        let account_freeze = AccountFreeze {
+            reason,
+            expiration: Self::assert_not_expired(expiration),
+        };
+        account.freeze(account_freeze.clone());
+        account.save();
+        LOG_EVENT_ACCOUNT_FREEZE.log(format!(
+            "account: {}, reason: {}",
+            account_id.as_ref(),
+            account_freeze.reason
+        ));
+        AuditLog::record(
+            "ops_account_freeze",
+            Some(account_id.as_ref()),
+            Some(json!(account_freeze).to_string()),
+        );
+        account_freeze
+    }
+
+    fn ops_account_unfreeze(&mut self, account_id: ValidAccountId) -> bool {
+        self.assert_admin("ops_account_unfreeze");
+        let mut account = self.registered_account_near_data(account_id.as_ref());
+        let frozen = account.unfreeze();
+        // expired freezes are cleared as well
+        account.save();
+        if frozen {
+            LOG_EVENT_ACCOUNT_UNFREEZE.log(account_id.as_ref());
+            AuditLog::record("ops_account_unfreeze", Some(account_id.as_ref()), None);
+        }
+        frozen
+    }
+
+    fn ops_account_freeze_status(&self, account_id: ValidAccountId) -> Option<AccountFreeze> {
+        self.load_account_near_data(account_id.as_ref())
+            .and_then(|account| account.account_freeze().cloned())
+    }
+}
+
+impl<T> AccountDelegation for AccountManagementComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_delegation_grant(
+        &mut self,
+        delegate: ValidAccountId,
+        actions: HashMap<String, Option<U128>>,
+        expiration: Option<ExpirationSetting>,
+    ) -> Delegation {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_exists(&account_id));
+        assert_account_not_predecessor(delegate.as_ref());
+        ERR_INVALID.assert(
+            || !actions.is_empty(),
+            || "at least 1 action must be specified",
+        );
+        ERR_INVALID.assert(
+            || actions.keys().all(|action| !action.trim().is_empty()),
+            || "action name cannot be blank",
+        );
+
+        let delegation = Delegation {
+            actions,
+            expiration: Self::assert_not_expired(expiration),
+        };
+        let mut delegations = AccountDelegations::load(&account_id);
+        delegations.insert(delegate.as_ref().to_string(), delegation.clone());
+        AccountDelegations::save(&account_id, delegations);
+        LOG_EVENT_DELEGATION_GRANT.log(format!(
+            "delegate: {}, actions: {:?}",
+            delegate.as_ref(),
+            delegation.actions.keys().collect::<Vec<_>>()
+        ));
+        delegation
+    }
+
+    fn ops_delegation_revoke(&mut self, delegate: ValidAccountId) -> bool {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        let mut delegations = AccountDelegations::load(&account_id);
+        if delegations.remove(delegate.as_ref()).is_none() {
+            return false;
+        }
+        AccountDelegations::save(&account_id, delegations);
+        LOG_EVENT_DELEGATION_REVOKE.log(delegate.as_ref());
+        true
+    }
+
+    fn ops_delegations(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<HashMap<AccountId, Delegation>> {
+        let delegations = AccountDelegations::load(account_id.as_ref());
+        if delegations.is_empty() {
+            None
+        } else {
+            Some(delegations)
+        }
+    }
+
+    fn ops_delegation(
+        &self,
+        account_id: ValidAccountId,
+        delegate: ValidAccountId,
+    ) -> Option<Delegation> {
+        AccountDelegations::delegation(account_id.as_ref(), delegate.as_ref())
+    }
+}
+
+impl<T> AccountMigrationManagement for AccountManagementComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_account_migrate(&mut self, new_account_id: ValidAccountId) -> AccountMigration {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        let account = self.registered_account_near_data(&account_id);
+        ERR_ACCOUNT_FROZEN.assert(|| !account.is_frozen());
+        assert_account_not_predecessor(new_account_id.as_ref());
+        ERR_ACCOUNT_ALREADY_REGISTERED.assert(|| !self.account_exists(new_account_id.as_ref()));
+
+        let migration = AccountMigration {
+            account_id,
+            new_account_id: new_account_id.as_ref().to_string(),
+        };
+        migration.save();
+        LOG_EVENT_ACCOUNT_MIGRATE.log(format!("new_account_id: {}", migration.new_account_id));
+        migration
+    }
+
+    fn ops_account_migrate_accept(&mut self, account_id: ValidAccountId) {
+        assert_yocto_near_attached();
+        let account_id = account_id.as_ref();
+        let new_account_id = env::predecessor_account_id();
+        ERR_ACCOUNT_MIGRATION_NOT_FOUND.assert(|| {
+            AccountMigration::load(account_id).map_or(false, |migration| {
+                migration.new_account_id == new_account_id
+            })
+        });
+        ERR_ACCOUNT_ALREADY_REGISTERED.assert(|| !self.account_exists(&new_account_id));
+        Self::assert_account_not_frozen(account_id);
+
+        AccountMigration::delete(account_id);
+        self.migrate_account(account_id, &new_account_id);
+        LOG_EVENT_ACCOUNT_MIGRATED.log(format!(
+            "account_id: {}, new_account_id: {}",
+            account_id, new_account_id
+        ));
+    }
+
+    fn ops_account_migrate_cancel(&mut self) -> bool {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        match AccountMigration::load(&account_id) {
+            None => false,
+            Some(migration) => {
+                AccountMigration::delete(&account_id);
+                LOG_EVENT_ACCOUNT_MIGRATE_CANCEL
+                    .log(format!("new_account_id: {}", migration.new_account_id));
+                true
+            }
         }
-        Some(perms)
+    }
+
+    fn ops_account_migration(&self, account_id: ValidAccountId) -> Option<AccountMigration> {
+        AccountMigration::load(account_id.as_ref())
     }
 }
 
@@ -575,16 +1127,118 @@ where
         account
     }
 
-    pub fn permission_by_name(&self, name: &str) -> Option<Permission> {
-        if self.contract_permissions.0.is_empty() {
-            return None;
-        }
-        for (k, v) in self.contract_permissions.0.iter() {
-            if name == *v {
-                return Some(1_u64 << *k);
+    /// Credits the account's storage balance with NEAR in exchange for fungible tokens that were
+    /// deposited to pay for storage - see [`StorageTokenCredit`]. If the account is not registered,
+    /// then the account is registered.
+    ///
+    /// Returns the amount that was credited, which is less than the specified amount if the account's
+    /// storage balance would exceed the max storage balance bound.
+    ///
+    /// ## Panics
+    /// [`ERR_INSUFFICIENT_STORAGE_BALANCE`] - if the account is not registered and the amount does
+    /// not cover the min storage balance
+    pub fn deposit_token_credit(&mut self, account_id: &str, amount: YoctoNear) -> YoctoNear {
+        let storage_balance_bounds = self.storage_balance_bounds();
+        let credit = match self.load_account_near_data(account_id) {
+            Some(mut account) => {
+                let credit = storage_balance_bounds.max.map_or(amount, |max| {
+                    amount.min(
+                        max.value()
+                            .saturating_sub(account.near_balance().value())
+                            .into(),
+                    )
+                });
+                if credit > YoctoNear::ZERO {
+                    account.incr_near_balance(credit);
+                }
+                account.record_activity();
+                account.save();
+                credit
             }
-        }
-        None
+            None => {
+                ERR_INSUFFICIENT_STORAGE_BALANCE.assert_with_message(
+                    || amount >= storage_balance_bounds.min,
+                    || {
+                        format!(
+                            "token credit = {}, min storage balance = {}",
+                            amount, storage_balance_bounds.min
+                        )
+                    },
+                );
+                let credit = storage_balance_bounds
+                    .max
+                    .map_or(amount, |max| amount.min(max));
+                let (account, _data) = self.create_account(account_id, credit, None);
+                eventbus::post(&AccountStorageEvent::Registered(
+                    account.storage_balance(storage_balance_bounds.min),
+                ));
+                credit
+            }
+        };
+        StorageTokenCredit::credit(account_id, credit);
+        credit
+    }
+
+    /// Debits the token credit from the account's storage balance. The caller is responsible for
+    /// paying back the tokens.
+    ///
+    /// ## Panics
+    /// - [`ERR_ACCOUNT_NOT_REGISTERED`]
+    /// - [`ERR_ACCOUNT_FROZEN`]
+    /// - [`ERR_INSUFFICIENT_STORAGE_BALANCE`] - if the amount exceeds the account's token credit or
+    ///   available storage balance
+    pub fn withdraw_token_credit(&mut self, account_id: &str, amount: YoctoNear) {
+        let mut account = self.registered_account_near_data(account_id);
+        ERR_ACCOUNT_FROZEN.assert(|| !account.is_frozen());
+        let available = account
+            .storage_balance(self.storage_balance_bounds().min)
+            .available;
+        ERR_INSUFFICIENT_STORAGE_BALANCE.assert_with_message(
+            || amount <= available && amount <= StorageTokenCredit::load(account_id),
+            || {
+                format!(
+                    "amount = {}, token credit = {}, available storage balance = {}",
+                    amount,
+                    StorageTokenCredit::load(account_id),
+                    available
+                )
+            },
+        );
+        account.decr_near_balance(amount);
+        account.record_activity();
+        account.save();
+        StorageTokenCredit::debit(account_id, amount);
+    }
+
+    /// Looks up the permission by its current name.
+    ///
+    /// Permissions that are renamed at runtime can still be looked up by the name they were
+    /// declared with in code, as long as the permission has not been retired. This enables
+    /// components to reference permissions by name independently of runtime renames.
+    pub fn permission_by_name(&self, name: &str) -> Option<Permission> {
+        let contract_permissions = self.contract_permissions();
+        contract_permissions
+            .0
+            .iter()
+            .find(|(_, v)| name == v.as_str())
+            .or_else(|| {
+                self.contract_permissions
+                    .0
+                    .iter()
+                    .find(|(k, v)| name == v.as_str() && contract_permissions.0.contains_key(k))
+            })
+            .map(|(k, _)| 1_u64 << *k)
+    }
+
+    /// Returns the contract permission definitions that are in effect.
+    ///
+    /// Runtime managed permission definitions take precedence over the permissions that the component
+    /// was constructed with.
+    pub fn contract_permissions(&self) -> ContractPermissions {
+        ContractPermissionsState::load().map_or_else(
+            || self.contract_permissions.clone(),
+            |state| state.permissions,
+        )
     }
 }
 
@@ -593,33 +1247,247 @@ impl<T> AccountManagementComponent<T>
 where
     T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
 {
-    fn clear_permissions_if_has_no_permissions(account: &mut AccountNearData) {
-        if let Some(permissions) = account.permissions() {
-            if !permissions.has_permissions() {
-                account.revoke_all(); // sets permissions to NONE - frees up some storage
+    /// the first time the contract permissions are changed at runtime, the state is initialized
+    /// from the permissions that the component was constructed with
+    fn contract_permissions_state(&self) -> ContractPermissionsState {
+        ContractPermissionsState::load().unwrap_or_else(|| ContractPermissionsState {
+            permissions: self.contract_permissions.clone(),
+            retired: Default::default(),
+        })
+    }
+
+    fn retired_permissions() -> Permissions {
+        ContractPermissionsState::load().map_or_else(Default::default, |state| state.retired)
+    }
+
+    /// retired permissions are revoked lazily when account permissions are updated
+    fn purge_retired_permissions(account: &mut AccountNearData) {
+        let retired = Self::retired_permissions();
+        if retired.has_permissions() {
+            if let Some(permissions) = account.permissions() {
+                if *permissions & *retired != 0 {
+                    account.revoke(retired);
+                }
             }
         }
     }
 
+    fn clear_permissions_if_has_no_permissions(account: &mut AccountNearData) {
+        // expired permissions are excluded
+        if account.permissions().is_none() {
+            account.revoke_all(); // sets permissions to NONE - frees up some storage
+        }
+    }
+
     fn assert_contract_supports_permissions(&self, permissions: Permissions) {
         ERR_INVALID.assert(
-            || self.contract_permissions.is_supported(permissions),
+            || self.contract_permissions().is_supported(permissions),
             || "contract does not support specified permissions",
         );
     }
 
-    fn assert_predecessor_is_admin(&self) {
-        let admin = self.registered_account_near_data(env::predecessor_account_id().as_str());
-        ERR_NOT_AUTHORIZED.assert(|| admin.is_admin());
+    fn permission_name(&self, perm_bit: u8) -> String {
+        match perm_bit {
+            ADMIN_BIT => "admin".to_string(),
+            OPERATOR_BIT => "operator".to_string(),
+            _ => self
+                .contract_permissions()
+                .0
+                .get(&perm_bit)
+                .map_or_else(|| perm_bit.to_string(), |name| name.to_string()),
+        }
     }
 
-    /// refunds deposit amount that is above the max allowed storage balance
-    fn deposit_with_max_bound(
-        &self,
-        account: &mut AccountNearDataObject,
-        deposit: Deposit,
-        max: MaxStorageBalance,
-    ) {
+    fn assert_not_expired(expiration: Option<ExpirationSetting>) -> Option<Expiration> {
+        expiration.map(|expiration| {
+            let expiration: Expiration = expiration.into();
+            ERR_INVALID.assert(
+                || !expiration.expired(),
+                || "expiration cannot be set to expired",
+            );
+            expiration
+        })
+    }
+
+    fn log_permissions_granted<Msg: Display>(permissions: Msg, expiration: Option<Expiration>) {
+        match expiration {
+            None => LOG_EVENT_PERMISSIONS_GRANT.log(permissions),
+            Some(expiration) => LOG_EVENT_PERMISSIONS_GRANT
+                .log(format!("{} | expiration: {}", permissions, expiration)),
+        }
+    }
+
+    /// Moves the account state to the new account ID:
+    /// 1. the new account is registered with a copy of the account's NEAR data
+    /// 2. components move their account data via [`StorageManagementEvent::PreMigrate`] hooks
+    /// 3. the account data, delegations, and sponsorship indexes are moved
+    /// 4. the old account is deleted
+    ///
+    /// Storage usage is tracked per account while the data is moved, i.e., the new account ends up
+    /// being charged for the same storage usage that the old account was charged for.
+    fn migrate_account(&mut self, account_id: &str, new_account_id: &str) {
+        let account = self.registered_account_near_data(account_id);
+        account.migrate(new_account_id).save();
+        AccountRegistry::add(new_account_id);
+
+        eventbus::post(&StorageManagementEvent::PreMigrate {
+            account_id: account_id.to_string(),
+            new_account_id: new_account_id.to_string(),
+        });
+
+        StorageTokenCredit::migrate(account_id, new_account_id);
+        GrandfatheredStorageBalance::migrate(account_id, new_account_id);
+
+        if let Some(data) = self.load_account_data(account_id) {
+            AccountDataObject::<T>::new(new_account_id, (**data).clone()).save();
+            data.delete();
+        }
+
+        let delegations = AccountDelegations::load(account_id);
+        if !delegations.is_empty() {
+            AccountDelegations::save(new_account_id, delegations);
+            AccountDelegations::delete(account_id);
+        }
+
+        if account.permission_expirations().is_some() {
+            ExpiringPermissionsAccounts::remove(account_id);
+            ExpiringPermissionsAccounts::update(new_account_id, &account);
+        }
+
+        // accounts that sponsored the account
+        if let Some(sponsors) = account.sponsors() {
+            for sponsor in sponsors.keys() {
+                SponsoredAccounts::remove(sponsor, account_id);
+                SponsoredAccounts::add(sponsor, new_account_id);
+            }
+        }
+        // accounts that the account sponsored
+        for sponsored_account_id in SponsoredAccounts::load(account_id) {
+            let mut sponsored_account =
+                AccountNearDataObject::registered_account(sponsored_account_id.as_str());
+            sponsored_account.replace_sponsor(account_id, new_account_id);
+            sponsored_account.save();
+            SponsoredAccounts::remove(account_id, &sponsored_account_id);
+            SponsoredAccounts::add(new_account_id, &sponsored_account_id);
+        }
+
+        AccountRegistry::remove(account_id);
+        // the account is reloaded because its storage usage was updated while its data was moved
+        AccountNearDataObject::registered_account(account_id).delete();
+    }
+
+    /// Unregisters the account and refunds its NEAR balance:
+    /// - sponsored balances are refunded to the sponsors
+    /// - the rest is refunded to the account, plus the specified `attached_deposit`
+    ///
+    /// Returns the amount that was refunded to the account, excluding the `attached_deposit`
+    pub(crate) fn unregister_account(
+        &mut self,
+        account_id: &str,
+        mut account: AccountNearDataObject,
+        force: bool,
+        attached_deposit: YoctoNear,
+    ) -> YoctoNear {
+        let account_near_balance = account.near_balance();
+        let sponsored_balance = account.sponsored_balance();
+        // the token credit is paid back in tokens by the PreUnregister event handlers
+        let token_credit = StorageTokenCredit::load(account_id);
+        let sponsor_refunds = account.debit_sponsors(sponsored_balance);
+        eventbus::post(&StorageManagementEvent::PreUnregister {
+            account_id: account_id.to_string(),
+            force,
+        });
+        self.delete_account(account_id);
+        eventbus::post(&AccountStorageEvent::Unregistered(account_near_balance));
+        let refund: YoctoNear = account_near_balance
+            .saturating_sub(sponsored_balance.value())
+            .saturating_sub(token_credit.value())
+            .into();
+        if refund + attached_deposit > YoctoNear::ZERO {
+            Promise::new(account_id.to_string()).transfer((refund + attached_deposit).value());
+        }
+        Self::refund_sponsors(account_id, &account, sponsor_refunds);
+        refund
+    }
+
+    /// New sponsors must deposit at least `min_deposit`, which makes it costly to take up the
+    /// account's sponsor slots with dust deposits
+    fn credit_sponsor(sponsor: &str, account_id: &str, amount: YoctoNear, min_deposit: YoctoNear) {
+        if amount == YoctoNear::ZERO {
+            return;
+        }
+        // the account is reloaded because its storage usage may have been updated by the storage
+        // event handler when the deposit was saved
+        let mut account = AccountNearDataObject::registered_account(account_id);
+        let sponsor_count = account.sponsors().map_or(0, |sponsors| sponsors.len());
+        if account
+            .sponsors()
+            .map_or(true, |sponsors| !sponsors.contains_key(sponsor))
+        {
+            ERR_SPONSOR_DEPOSIT_TOO_LOW.assert_with_message(
+                || amount >= min_deposit,
+                || {
+                    format!(
+                        "deposit = {}, min sponsor deposit = {}",
+                        amount, min_deposit
+                    )
+                },
+            );
+            ERR_MAX_ACCOUNT_SPONSORS_EXCEEDED.assert_with_message(
+                || sponsor_count < MAX_ACCOUNT_SPONSORS,
+                || format!("max sponsors = {}", MAX_ACCOUNT_SPONSORS),
+            );
+        }
+
+        let storage_usage_before = env::storage_usage();
+        account.credit_sponsor(sponsor, amount);
+        account.save();
+        SponsoredAccounts::add(sponsor, account_id);
+        // the sponsor entry storage is charged to the account - thus the sponsor must pay for it
+        let sponsor_storage_cost =
+            StorageUsage(env::storage_usage().saturating_sub(storage_usage_before)).cost();
+        ERR_SPONSOR_DEPOSIT_TOO_LOW.assert_with_message(
+            || amount >= sponsor_storage_cost,
+            || {
+                format!(
+                    "deposit = {}, sponsor storage cost = {}",
+                    amount, sponsor_storage_cost
+                )
+            },
+        );
+        LOG_EVENT_STORAGE_SPONSOR_DEPOSIT.log(format!(
+            "sponsor: {}, account: {}, amount: {}",
+            sponsor, account_id, amount
+        ));
+    }
+
+    fn refund_sponsors(
+        account_id: &str,
+        account: &AccountNearData,
+        refunds: Vec<(AccountId, YoctoNear)>,
+    ) {
+        for (sponsor, amount) in refunds {
+            if account
+                .sponsors()
+                .map_or(true, |sponsors| !sponsors.contains_key(&sponsor))
+            {
+                SponsoredAccounts::remove(&sponsor, account_id);
+            }
+            LOG_EVENT_STORAGE_SPONSOR_REFUND.log(format!(
+                "sponsor: {}, account: {}, amount: {}",
+                sponsor, account_id, amount
+            ));
+            Promise::new(sponsor).transfer(*amount);
+        }
+    }
+
+    /// refunds deposit amount that is above the max allowed storage balance
+    fn deposit_with_max_bound(
+        &self,
+        account: &mut AccountNearDataObject,
+        deposit: Deposit,
+        max: MaxStorageBalance,
+    ) {
         if account.near_balance() < *max {
             let max_allowed_deposit = *max - account.near_balance();
             let deposit = if *deposit > max_allowed_deposit {
@@ -732,7 +1600,8 @@ mod tests {
         let logs = test_utils::get_logs();
         println!("{:#?}", logs);
         assert_eq!(logs, vec![
-            "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(97)",
+            "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(124)",
+            "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(161)",
             "[INFO] [ACCOUNT_STORAGE_CHANGED] Registered(StorageBalance { total: YoctoNear(10000000000000000000000), available: YoctoNear(0) })",
         ]);
 
@@ -751,7 +1620,8 @@ mod tests {
         let logs = test_utils::get_logs();
         println!("{:#?}", logs);
         assert_eq!(logs, vec![
-            "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(97)",
+            "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(124)",
+            "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(161)",
             "[INFO] [ACCOUNT_STORAGE_CHANGED] Registered(StorageBalance { total: YoctoNear(10000000000000000000000), available: YoctoNear(0) })",
         ]);
 
@@ -2699,6 +3569,10 @@ mod tests_account_metrics {
 
         let mut service = AccountManager::new(Default::default());
         let admin_account = service.registered_account_near_data("admin");
+        // the min storage usage is measured using a max length account ID, which is stored in the
+        // account registry
+        let expected_storage_usage =
+            |account_id: &str| storage_usage_bounds.min - ((64 - account_id.len()) as u64).into();
         // Act
         let metrics = AccountManager::account_metrics();
         println!("{:?}", metrics);
@@ -2729,7 +3603,7 @@ mod tests_account_metrics {
         );
         assert_eq!(
             metrics.total_storage_usage,
-            storage_usage_bounds.min + admin_account.storage_usage()
+            expected_storage_usage(account) + admin_account.storage_usage()
         );
 
         // Arrange - deposit more funds
@@ -2746,7 +3620,7 @@ mod tests_account_metrics {
         );
         assert_eq!(
             metrics.total_storage_usage,
-            storage_usage_bounds.min + admin_account.storage_usage()
+            expected_storage_usage(account) + admin_account.storage_usage()
         );
 
         // Arrange - register another account
@@ -2766,9 +3640,14 @@ mod tests_account_metrics {
             metrics.total_near_balance,
             storage_balance.total + bob_storage_balance.total + admin_account.near_balance()
         );
+        // bob's storage deposit was sponsored, which is tracked on bob's account
+        let bob_storage_usage = service.registered_account_near_data("bob").storage_usage();
+        assert!(bob_storage_usage > expected_storage_usage("bob"));
         assert_eq!(
             metrics.total_storage_usage.value(),
-            (storage_usage_bounds.min.value() * 2) + admin_account.storage_usage().value()
+            expected_storage_usage(account).value()
+                + bob_storage_usage.value()
+                + admin_account.storage_usage().value()
         );
 
         // Arrange - unregister account
@@ -2786,7 +3665,7 @@ mod tests_account_metrics {
         );
         assert_eq!(
             metrics.total_storage_usage,
-            storage_usage_bounds.min + admin_account.storage_usage()
+            bob_storage_usage + admin_account.storage_usage()
         );
     }
 }
@@ -2902,7 +3781,7 @@ mod test_permission_management {
                     assert!(accounts_perms.is_none());
 
                     // grant operator
-                    account_manager.ops_permissions_grant_operator(to_valid_account_id(bob));
+                    account_manager.ops_permissions_grant_operator(to_valid_account_id(bob), None);
                     assert!(account_manager.ops_permissions_is_operator(to_valid_account_id(bob)));
                     assert!(!account_manager.ops_permissions_is_admin(to_valid_account_id(bob)));
                     let accounts_perms = account_manager
@@ -2916,8 +3795,11 @@ mod test_permission_management {
                     assert!(!account_manager.ops_permissions_is_operator(to_valid_account_id(bob)));
 
                     // grant permissions
-                    account_manager
-                        .ops_permissions_grant(to_valid_account_id(bob), (PERM_0 | PERM_1).into());
+                    account_manager.ops_permissions_grant(
+                        to_valid_account_id(bob),
+                        (PERM_0 | PERM_1).into(),
+                        None,
+                    );
                     assert!(account_manager
                         .ops_permissions_contains(to_valid_account_id(bob), PERM_0.into()));
                     assert!(account_manager
@@ -2955,9 +3837,12 @@ mod test_permission_management {
                     ));
 
                     // grant permissions
-                    account_manager
-                        .ops_permissions_grant(to_valid_account_id(bob), (PERM_0 | PERM_1).into());
-                    account_manager.ops_permissions_grant_operator(to_valid_account_id(bob));
+                    account_manager.ops_permissions_grant(
+                        to_valid_account_id(bob),
+                        (PERM_0 | PERM_1).into(),
+                        None,
+                    );
+                    account_manager.ops_permissions_grant_operator(to_valid_account_id(bob), None);
                     assert!(account_manager
                         .ops_permissions_contains(to_valid_account_id(bob), PERM_0.into()));
                     assert!(account_manager
@@ -3025,9 +3910,10 @@ mod test_permission_management {
                 )]
                 fn grant_operator_to_self() {
                     test(true, Default::default(), |_ctx, mut account_manager| {
-                        account_manager.ops_permissions_grant_operator(to_valid_account_id(
-                            PREDECESSOR_ACCOUNT,
-                        ));
+                        account_manager.ops_permissions_grant_operator(
+                            to_valid_account_id(PREDECESSOR_ACCOUNT),
+                            None,
+                        );
                     });
                 }
 
@@ -3040,6 +3926,7 @@ mod test_permission_management {
                         account_manager.ops_permissions_grant(
                             to_valid_account_id(PREDECESSOR_ACCOUNT),
                             PERM_0.into(),
+                            None,
                         );
                     });
                 }
@@ -3164,7 +4051,8 @@ mod test_permission_management {
 
                         // Act - grant
                         testing_env!(ctx.clone());
-                        account_manager.ops_permissions_grant_operator(to_valid_account_id(bob));
+                        account_manager
+                            .ops_permissions_grant_operator(to_valid_account_id(bob), None);
                         let logs = test_utils::get_logs();
                         println!("{:#?}", logs);
                         assert_eq!(logs.len(), 2);
@@ -3176,7 +4064,8 @@ mod test_permission_management {
 
                         // Act - grant admin again to user should have no effect
                         testing_env!(ctx.clone());
-                        account_manager.ops_permissions_grant_operator(to_valid_account_id(bob));
+                        account_manager
+                            .ops_permissions_grant_operator(to_valid_account_id(bob), None);
                         let logs = test_utils::get_logs();
                         assert!(logs.is_empty());
 
@@ -3216,8 +4105,11 @@ mod test_permission_management {
 
                         // Act - grant
                         testing_env!(ctx.clone());
-                        account_manager
-                            .ops_permissions_grant(to_valid_account_id(bob), PERM_0.into());
+                        account_manager.ops_permissions_grant(
+                            to_valid_account_id(bob),
+                            PERM_0.into(),
+                            None,
+                        );
                         let logs = test_utils::get_logs();
                         println!("{:#?}", logs);
                         assert_eq!(logs.len(), 2);
@@ -3229,14 +4121,20 @@ mod test_permission_management {
 
                         // Act - grant admin again to user should have no effect
                         testing_env!(ctx.clone());
-                        account_manager
-                            .ops_permissions_grant(to_valid_account_id(bob), PERM_0.into());
+                        account_manager.ops_permissions_grant(
+                            to_valid_account_id(bob),
+                            PERM_0.into(),
+                            None,
+                        );
                         let logs = test_utils::get_logs();
                         assert!(logs.is_empty());
 
                         testing_env!(ctx.clone());
-                        account_manager
-                            .ops_permissions_grant(to_valid_account_id(bob), PERM_1.into());
+                        account_manager.ops_permissions_grant(
+                            to_valid_account_id(bob),
+                            PERM_1.into(),
+                            None,
+                        );
                         let logs = test_utils::get_logs();
                         println!("{:#?}", logs);
                         assert_eq!(logs.len(), 1);
@@ -3272,6 +4170,7 @@ mod test_permission_management {
                         account_manager.ops_permissions_grant(
                             to_valid_account_id(bob),
                             (PERM_0 | PERM_1).into(),
+                            None,
                         );
 
                         testing_env!(ctx.clone());
@@ -3292,6 +4191,7 @@ mod test_permission_management {
                         account_manager.ops_permissions_grant_permissions(
                             to_valid_account_id(bob),
                             vec![0, 1],
+                            None,
                         );
                         let logs = test_utils::get_logs();
                         println!("{:#?}", logs);
@@ -3330,7 +4230,7 @@ mod test_permission_management {
         #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
         fn grant_operator() {
             test(false, Default::default(), |_ctx, mut account_manager| {
-                account_manager.ops_permissions_grant_operator(to_valid_account_id("bob"));
+                account_manager.ops_permissions_grant_operator(to_valid_account_id("bob"), None);
             });
         }
 
@@ -3338,7 +4238,11 @@ mod test_permission_management {
         #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
         fn grant() {
             test(false, permissions(), |_ctx, mut account_manager| {
-                account_manager.ops_permissions_grant(to_valid_account_id("bob"), (1 << 1).into());
+                account_manager.ops_permissions_grant(
+                    to_valid_account_id("bob"),
+                    (1 << 1).into(),
+                    None,
+                );
             });
         }
 
@@ -3375,6 +4279,249 @@ mod test_permission_management {
         }
     }
 
+    #[cfg(test)]
+    mod expiring_permissions {
+        use super::*;
+        use oysterpack_smart_near::domain::{Expiration, ExpirationDuration, ExpirationSetting};
+
+        #[test]
+        fn grants_with_expiration() {
+            test(true, permissions(), |mut ctx, mut account_manager| {
+                // Arrange
+                let bob = "bob";
+                let alice = "alice";
+                for account_id in [bob, alice].iter() {
+                    ctx.predecessor_account_id = account_id.to_string();
+                    ctx.attached_deposit = YOCTO;
+                    testing_env!(ctx.clone());
+                    account_manager.storage_deposit(None, None);
+                }
+
+                ctx.predecessor_account_id = PREDECESSOR_ACCOUNT.to_string();
+                ctx.attached_deposit = 0;
+                ctx.epoch_height = 10;
+                testing_env!(ctx.clone());
+
+                // Act
+                account_manager.ops_permissions_grant_operator(
+                    to_valid_account_id(bob),
+                    Some(ExpirationSetting::Relative(ExpirationDuration::Epochs(5))),
+                );
+                account_manager.ops_permissions_grant(
+                    to_valid_account_id(bob),
+                    PERM_0.into(),
+                    Some(ExpirationSetting::Absolute(Expiration::Epoch(20.into()))),
+                );
+                account_manager.ops_permissions_grant_permissions(
+                    to_valid_account_id(alice),
+                    vec![1],
+                    Some(ExpirationSetting::Absolute(Expiration::Block(1000.into()))),
+                );
+                let logs = test_utils::get_logs();
+                println!("{:#?}", logs);
+                assert!(logs.contains(
+                    &"[INFO] [PERMISSIONS_GRANT] operator | expiration: EpochHeight(15)"
+                        .to_string()
+                ));
+
+                // Assert
+                assert!(account_manager.ops_permissions_is_operator(to_valid_account_id(bob)));
+                assert!(account_manager
+                    .ops_permissions_contains(to_valid_account_id(bob), PERM_0.into()));
+                assert!(account_manager
+                    .ops_permissions_contains(to_valid_account_id(alice), PERM_1.into()));
+
+                let expiring_permissions = account_manager
+                    .ops_permissions_expiring(None, None, None)
+                    .permissions;
+                println!("{:#?}", expiring_permissions);
+                assert_eq!(expiring_permissions.len(), 3);
+                assert_eq!(
+                    expiring_permissions[0],
+                    ExpiringPermission {
+                        account_id: bob.to_string(),
+                        permission: 62,
+                        name: "operator".to_string(),
+                        expiration: Expiration::Epoch(15.into()),
+                    }
+                );
+                assert_eq!(expiring_permissions[1].account_id, bob);
+                assert_eq!(expiring_permissions[1].name, "perm_0");
+                assert_eq!(expiring_permissions[2].account_id, alice);
+                assert_eq!(expiring_permissions[2].name, "perm_1");
+
+                // scan the accounts in pages
+                let page = account_manager.ops_permissions_expiring(None, None, Some(1));
+                assert_eq!(page.permissions.len(), 2);
+                assert_eq!(page.permissions[0].account_id, bob);
+                assert_eq!(page.next_index, Some(1.into()));
+                let page = account_manager.ops_permissions_expiring(None, page.next_index, Some(1));
+                assert_eq!(page.permissions.len(), 1);
+                assert_eq!(page.permissions[0].account_id, alice);
+                assert!(page.next_index.is_none());
+
+                let expiring_permissions = account_manager
+                    .ops_permissions_expiring(Some(ExpirationDuration::Epochs(5)), None, None)
+                    .permissions;
+                assert_eq!(expiring_permissions.len(), 1);
+                assert_eq!(expiring_permissions[0].permission, 62);
+
+                // Act - operator permission expires
+                ctx.epoch_height = 16;
+                testing_env!(ctx.clone());
+
+                // Assert
+                assert!(!account_manager.ops_permissions_is_operator(to_valid_account_id(bob)));
+                assert!(account_manager
+                    .ops_permissions_contains(to_valid_account_id(bob), PERM_0.into()));
+                let accounts_perms = account_manager
+                    .ops_permissions_granted(to_valid_account_id(bob))
+                    .unwrap();
+                assert_eq!(accounts_perms.len(), 1);
+                assert_eq!(accounts_perms.get(&0).unwrap(), "perm_0");
+                let expiring_permissions = account_manager
+                    .ops_permissions_expiring(None, None, None)
+                    .permissions;
+                assert_eq!(expiring_permissions.len(), 2);
+
+                // Act - re-grant operator permanently
+                account_manager.ops_permissions_grant_operator(to_valid_account_id(bob), None);
+                assert!(account_manager.ops_permissions_is_operator(to_valid_account_id(bob)));
+                let account = account_manager.registered_account_near_data(bob);
+                assert!(account.permission_expiration(62).is_none());
+
+                // Act - all of bob's time bound permissions expire
+                ctx.epoch_height = 21;
+                testing_env!(ctx.clone());
+                assert!(account_manager.ops_permissions_is_operator(to_valid_account_id(bob)));
+                assert!(!account_manager
+                    .ops_permissions_contains(to_valid_account_id(bob), PERM_0.into()));
+                let expiring_permissions = account_manager
+                    .ops_permissions_expiring(None, None, None)
+                    .permissions;
+                assert_eq!(expiring_permissions.len(), 1);
+                assert_eq!(expiring_permissions[0].account_id, alice);
+
+                // Act - revoking alice's permissions removes alice from the index
+                account_manager.ops_permissions_revoke(to_valid_account_id(alice), PERM_1.into());
+                assert!(account_manager
+                    .ops_permissions_expiring(None, None, None)
+                    .permissions
+                    .is_empty());
+                assert!(!ExpiringPermissionsAccounts::contains(alice));
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [INVALID] expiration cannot be set to expired")]
+        fn grant_with_expired_expiration() {
+            test(true, permissions(), |mut ctx, mut account_manager| {
+                let bob = "bob";
+                ctx.predecessor_account_id = bob.to_string();
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.predecessor_account_id = PREDECESSOR_ACCOUNT.to_string();
+                ctx.attached_deposit = 0;
+                ctx.epoch_height = 10;
+                testing_env!(ctx.clone());
+                account_manager.ops_permissions_grant(
+                    to_valid_account_id(bob),
+                    PERM_0.into(),
+                    Some(ExpirationSetting::Absolute(Expiration::Epoch(9.into()))),
+                );
+            });
+        }
+
+        #[test]
+        fn unregister_removes_account_from_index() {
+            test(true, permissions(), |mut ctx, mut account_manager| {
+                let bob = "bob";
+                ctx.predecessor_account_id = bob.to_string();
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.predecessor_account_id = PREDECESSOR_ACCOUNT.to_string();
+                ctx.attached_deposit = 0;
+                testing_env!(ctx.clone());
+                account_manager.ops_permissions_grant_operator(
+                    to_valid_account_id(bob),
+                    Some(ExpirationSetting::Relative(ExpirationDuration::Epochs(5))),
+                );
+                assert!(ExpiringPermissionsAccounts::contains(bob));
+
+                ctx.predecessor_account_id = bob.to_string();
+                ctx.attached_deposit = 1;
+                testing_env!(ctx.clone());
+                account_manager.storage_unregister(None);
+                assert_eq!(ExpiringPermissionsAccounts::count(), 0);
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod audit_log {
+        use super::*;
+        use crate::components::admin_audit_log::AdminAuditLogComponent;
+
+        #[test]
+        fn permission_changes_are_recorded() {
+            test(true, permissions(), |mut ctx, mut account_manager| {
+                // Arrange
+                let bob = "bob";
+                ctx.predecessor_account_id = bob.to_string();
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.predecessor_account_id = PREDECESSOR_ACCOUNT.to_string();
+                ctx.attached_deposit = 0;
+                testing_env!(ctx.clone());
+
+                // Act
+                account_manager.ops_permissions_grant_operator(to_valid_account_id(bob), None);
+                // no-op grants are not recorded
+                account_manager.ops_permissions_grant_operator(to_valid_account_id(bob), None);
+                account_manager.ops_permissions_grant(
+                    to_valid_account_id(bob),
+                    PERM_0.into(),
+                    None,
+                );
+                account_manager.ops_permissions_revoke_all(to_valid_account_id(bob));
+
+                // Assert
+                assert_eq!(AdminAuditLogComponent.ops_audit_log_count(), 3.into());
+                let entries = AdminAuditLogComponent.ops_audit_log(None, None);
+                println!("{:#?}", entries);
+                assert_eq!(
+                    entries
+                        .iter()
+                        .map(|entry| entry.action.as_str())
+                        .collect::<Vec<_>>(),
+                    vec![
+                        "ops_permissions_grant_operator",
+                        "ops_permissions_grant",
+                        "ops_permissions_revoke_all"
+                    ]
+                );
+                for entry in entries.iter() {
+                    assert_eq!(entry.actor, PREDECESSOR_ACCOUNT);
+                    assert_eq!(entry.target, Some(bob.to_string()));
+                }
+                assert_eq!(
+                    entries[1].args,
+                    Some(r#"{"permissions":"1","expiration":null}"#.to_string())
+                );
+
+                let entries = AdminAuditLogComponent.ops_audit_log(Some(1.into()), Some(1));
+                assert_eq!(entries.len(), 1);
+                assert_eq!(entries[0].action, "ops_permissions_grant");
+            });
+        }
+    }
+
     #[cfg(test)]
     mod contract_permission_bits {
         use super::*;
@@ -3403,5 +4550,1299 @@ mod test_permission_management {
                 }
             });
         }
+
+        #[test]
+        fn runtime_managed_contract_permissions() {
+            test(true, permissions(), |mut ctx, mut account_manager| {
+                // Arrange
+                let bob = "bob";
+                ctx.predecessor_account_id = bob.to_string();
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.predecessor_account_id = PREDECESSOR_ACCOUNT.to_string();
+                ctx.attached_deposit = 0;
+                testing_env!(ctx.clone());
+
+                // Act - add permission
+                account_manager.ops_permissions_contract_permission_add(5, "minter".to_string());
+                // Assert
+                let permissions = account_manager
+                    .ops_permissions_contract_permissions()
+                    .unwrap();
+                assert_eq!(permissions.len(), 3);
+                assert_eq!(permissions.get(&5).unwrap(), "minter");
+                account_manager.ops_permissions_grant_permissions(
+                    to_valid_account_id(bob),
+                    vec![0, 5],
+                    None,
+                );
+                assert!(account_manager
+                    .ops_permissions_contains(to_valid_account_id(bob), (1 << 5).into()));
+
+                // Act - rename permission
+                account_manager.ops_permissions_contract_permission_rename(0, "burner".to_string());
+                // Assert
+                let permissions = account_manager
+                    .ops_permissions_contract_permissions()
+                    .unwrap();
+                assert_eq!(permissions.get(&0).unwrap(), "burner");
+                assert_eq!(
+                    account_manager.permission_by_name("burner").unwrap(),
+                    1 << 0
+                );
+                // can still be looked up using the name declared in code
+                assert_eq!(
+                    account_manager.permission_by_name("perm_0").unwrap(),
+                    1 << 0
+                );
+                let granted = account_manager
+                    .ops_permissions_granted(to_valid_account_id(bob))
+                    .unwrap();
+                assert_eq!(granted.get(&0).unwrap(), "burner");
+
+                // Act - retire permission
+                account_manager.ops_permissions_contract_permission_retire(0);
+                // Assert
+                let permissions = account_manager
+                    .ops_permissions_contract_permissions()
+                    .unwrap();
+                assert!(!permissions.contains_key(&0));
+                assert!(account_manager.permission_by_name("perm_0").is_none());
+                assert_eq!(
+                    account_manager.ops_permissions_contract_permissions_retired(),
+                    Some(vec![0])
+                );
+                // retired permission is excluded from queries
+                assert_eq!(
+                    account_manager.ops_permissions(to_valid_account_id(bob)),
+                    Some((1 << 5).into())
+                );
+                assert!(!account_manager
+                    .ops_permissions_contains(to_valid_account_id(bob), (1 << 0).into()));
+                assert!(account_manager
+                    .ops_permissions_contains(to_valid_account_id(bob), (1 << 5).into()));
+                // but is still stored on the account until the account permissions are updated
+                let account = account_manager.registered_account_near_data(bob);
+                assert!(account.contains_permissions((1 << 0).into()));
+
+                account_manager
+                    .ops_permissions_revoke_permissions(to_valid_account_id(bob), vec![5]);
+                let account = account_manager.registered_account_near_data(bob);
+                assert!(account.permissions().is_none());
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [INVALID] permission bit has been retired")]
+        fn add_retired_permission() {
+            test(true, permissions(), |_, mut account_manager| {
+                account_manager.ops_permissions_contract_permission_retire(0);
+                account_manager.ops_permissions_contract_permission_add(0, "perm_0".to_string());
+            });
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "[ERR] [INVALID] contract does not support specified permissions"
+        )]
+        fn grant_retired_permission() {
+            test(true, permissions(), |_, mut account_manager| {
+                account_manager.ops_permissions_contract_permission_retire(0);
+                account_manager.ops_permissions_grant_permissions(
+                    to_valid_account_id("admin"),
+                    vec![0],
+                    None,
+                );
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
+        fn add_permission_not_admin() {
+            test(false, permissions(), |_, mut account_manager| {
+                account_manager.ops_permissions_contract_permission_add(5, "minter".to_string());
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_account_delegation {
+    use super::*;
+    use oysterpack_smart_near::near_sdk;
+    use oysterpack_smart_near::near_sdk::VMContext;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    const ACCOUNT: &str = "alice";
+    const DELEGATE: &str = "bot";
+
+    fn test<F>(f: F)
+    where
+        F: FnOnce(VMContext, AccountManager),
+    {
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+
+        let storage_usage_bounds = StorageUsageBounds {
+            min: AccountManager::measure_storage_usage(()),
+            max: None,
+        };
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(storage_usage_bounds),
+            admin_account: to_valid_account_id("admin"),
+            component_account_storage_mins: None,
+        });
+
+        let mut account_manager = AccountManager::new(Default::default());
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        f(ctx, account_manager);
+    }
+
+    fn actions() -> HashMap<String, Option<U128>> {
+        let mut actions = HashMap::new();
+        actions.insert("stake".to_string(), None);
+        actions.insert("unstake".to_string(), Some(YOCTO.into()));
+        actions
+    }
+
+    #[test]
+    fn grant_and_revoke() {
+        test(|_ctx, mut account_manager| {
+            assert!(account_manager
+                .ops_delegations(to_valid_account_id(ACCOUNT))
+                .is_none());
+            let storage_usage = account_manager
+                .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                .unwrap();
+
+            // Act
+            let delegation = account_manager.ops_delegation_grant(
+                to_valid_account_id(DELEGATE),
+                actions(),
+                None,
+            );
+
+            // Assert
+            assert_eq!(delegation.actions, actions());
+            assert_eq!(
+                account_manager
+                    .ops_delegation(to_valid_account_id(ACCOUNT), to_valid_account_id(DELEGATE)),
+                Some(delegation)
+            );
+            // delegation storage is charged to the account
+            assert!(
+                account_manager
+                    .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                    .unwrap()
+                    > storage_usage
+            );
+
+            // Act
+            assert!(account_manager.ops_delegation_revoke(to_valid_account_id(DELEGATE)));
+            assert!(!account_manager.ops_delegation_revoke(to_valid_account_id(DELEGATE)));
+
+            // Assert
+            assert!(account_manager
+                .ops_delegations(to_valid_account_id(ACCOUNT))
+                .is_none());
+            assert_eq!(
+                account_manager
+                    .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                    .unwrap(),
+                storage_usage
+            );
+        });
+    }
+
+    #[test]
+    fn unregister_deletes_delegations() {
+        test(|_ctx, mut account_manager| {
+            account_manager.ops_delegation_grant(to_valid_account_id(DELEGATE), actions(), None);
+            assert!(account_manager.storage_unregister(None));
+            assert!(AccountDelegations::load(ACCOUNT).is_empty());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_NOT_REGISTERED]")]
+    fn grant_account_not_registered() {
+        test(|mut ctx, mut account_manager| {
+            ctx.predecessor_account_id = "bob".to_string();
+            testing_env!(ctx);
+            account_manager.ops_delegation_grant(to_valid_account_id(DELEGATE), actions(), None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID]")]
+    fn grant_no_actions() {
+        test(|_ctx, mut account_manager| {
+            account_manager.ops_delegation_grant(
+                to_valid_account_id(DELEGATE),
+                HashMap::new(),
+                None,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn grant_without_yocto_near_attached() {
+        test(|mut ctx, mut account_manager| {
+            ctx.attached_deposit = 0;
+            testing_env!(ctx);
+            account_manager.ops_delegation_grant(to_valid_account_id(DELEGATE), actions(), None);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_storage_sponsorship {
+    use super::*;
+    use oysterpack_smart_near::near_sdk::{self, test_utils};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    const ACCOUNT: &str = "alice";
+    const SPONSOR: &str = "sponsor";
+
+    fn deploy() -> AccountManager {
+        let storage_usage_bounds = StorageUsageBounds {
+            min: AccountManager::measure_storage_usage(()),
+            max: None,
+        };
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(storage_usage_bounds),
+            admin_account: to_valid_account_id("admin"),
+            component_account_storage_mins: None,
+        });
+        AccountManager::new(Default::default())
+    }
+
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(action) => (receipt.receiver_id.clone(), action.deposit),
+                _ => panic!("expected Transfer"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sponsored_deposit_is_refunded_to_sponsor_on_withdraw() {
+        // Arrange
+        let mut ctx = new_context(SPONSOR);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+
+        // Act - sponsor registers the account
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+
+        // Assert
+        let logs = test_utils::get_logs();
+        assert!(logs
+            .iter()
+            .any(|log| log.starts_with("[INFO] [STORAGE_SPONSOR_DEPOSIT]")));
+        let sponsors = account_manager
+            .ops_storage_sponsors(to_valid_account_id(ACCOUNT))
+            .unwrap();
+        assert_eq!(sponsors.len(), 1);
+        assert_eq!(sponsors[SPONSOR], YOCTO.into());
+        assert_eq!(
+            account_manager.ops_storage_sponsored_accounts(to_valid_account_id(SPONSOR)),
+            vec![ACCOUNT.to_string()]
+        );
+
+        // Arrange - the account deposits its own funds
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+        assert_eq!(
+            account_manager
+                .ops_storage_sponsors(to_valid_account_id(ACCOUNT))
+                .unwrap()[SPONSOR],
+            YOCTO.into()
+        );
+
+        // Act - withdraw the total available balance
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        let storage_balance = account_manager.storage_withdraw(None);
+
+        // Assert - the account's own funds are withdrawn first and the rest goes to the sponsor
+        assert_eq!(storage_balance.available, YoctoNear::ZERO);
+        let min = account_manager.storage_balance_bounds().min.value();
+        assert_eq!(
+            transfers(),
+            vec![
+                (ACCOUNT.to_string(), YOCTO + 1),
+                (SPONSOR.to_string(), YOCTO - min)
+            ]
+        );
+        assert_eq!(
+            account_manager
+                .ops_storage_sponsors(to_valid_account_id(ACCOUNT))
+                .unwrap()[SPONSOR],
+            min.into()
+        );
+        assert_eq!(
+            account_manager.ops_storage_sponsored_accounts(to_valid_account_id(SPONSOR)),
+            vec![ACCOUNT.to_string()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [SPONSOR_DEPOSIT_TOO_LOW]")]
+    fn sponsor_dust_deposit() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act
+        ctx.predecessor_account_id = SPONSOR.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [SPONSOR_DEPOSIT_TOO_LOW] deposit = ")]
+    fn sponsor_slots_cannot_be_taken_with_deposits_below_min() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+        let min = account_manager.storage_balance_bounds().min;
+
+        // Act - the deposit covers the sponsor entry storage, but not the min storage balance
+        ctx.predecessor_account_id = "griefer-0".to_string();
+        ctx.attached_deposit = min.value() - 1;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MAX_ACCOUNT_SPONSORS_EXCEEDED]")]
+    fn max_account_sponsors_exceeded() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        for i in 0..MAX_ACCOUNT_SPONSORS {
+            ctx.predecessor_account_id = format!("sponsor-{}", i);
+            testing_env!(ctx.clone());
+            account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+        }
+        // existing sponsors can top up
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+
+        // Act
+        ctx.predecessor_account_id = SPONSOR.to_string();
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+    }
+
+    #[test]
+    fn sponsored_deposit_is_refunded_to_sponsor_on_unregister() {
+        // Arrange
+        let mut ctx = new_context(SPONSOR);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1000;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        assert!(account_manager.storage_unregister(None));
+
+        // Assert
+        assert_eq!(
+            transfers(),
+            vec![
+                (ACCOUNT.to_string(), 1000 + 1),
+                (SPONSOR.to_string(), YOCTO)
+            ]
+        );
+        let logs = test_utils::get_logs();
+        assert!(logs
+            .iter()
+            .any(|log| log.starts_with("[INFO] [STORAGE_SPONSOR_REFUND]")));
+        assert!(account_manager
+            .ops_storage_sponsors(to_valid_account_id(ACCOUNT))
+            .is_none());
+        assert!(account_manager
+            .ops_storage_sponsored_accounts(to_valid_account_id(SPONSOR))
+            .is_empty());
+    }
+
+    #[test]
+    fn withdraw_when_sponsored_balance_and_token_credit_exceed_near_balance() {
+        // Arrange
+        let mut ctx = new_context(SPONSOR);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+        StorageTokenCredit::credit(ACCOUNT, 1000.into());
+
+        // Act
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        account_manager.storage_withdraw(None);
+
+        // Assert - the account has no funds of its own, thus the withdrawal goes to the sponsor
+        let min = account_manager.storage_balance_bounds().min.value();
+        assert_eq!(
+            transfers(),
+            vec![(ACCOUNT.to_string(), 1), (SPONSOR.to_string(), YOCTO - min)]
+        );
+    }
+
+    #[test]
+    fn self_deposit_is_not_sponsored() {
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(Some(to_valid_account_id(ACCOUNT)), None);
+
+        assert!(account_manager
+            .ops_storage_sponsors(to_valid_account_id(ACCOUNT))
+            .is_none());
+        assert!(account_manager
+            .ops_storage_sponsored_accounts(to_valid_account_id(ACCOUNT))
+            .is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_storage_balance_coverage {
+    use super::*;
+    use oysterpack_smart_near::near_sdk;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    // max length account ID, which is what the min account storage usage is measured with
+    const ACCOUNT: &str = "alice00000000000000000000000000000000000000000000000000000000000";
+    const DELEGATE: &str = "bot";
+
+    fn deploy(max: Option<StorageUsage>) -> AccountManager {
+        let storage_usage_bounds = StorageUsageBounds {
+            min: AccountManager::measure_storage_usage(()),
+            max,
+        };
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(storage_usage_bounds),
+            admin_account: to_valid_account_id("admin"),
+            component_account_storage_mins: None,
+        });
+        AccountManager::new(Default::default())
+    }
+
+    fn grant_delegation(account_manager: &mut AccountManager) {
+        let mut actions = HashMap::new();
+        actions.insert("stake".to_string(), None);
+        account_manager.ops_delegation_grant(to_valid_account_id(DELEGATE), actions, None);
+    }
+
+    #[test]
+    fn withdraw_leaves_storage_usage_covered() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy(None);
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        grant_delegation(&mut account_manager);
+        let account = account_manager.registered_account_near_data(ACCOUNT);
+        assert!(account.storage_usage_cost() > account_manager.storage_balance_bounds().min);
+
+        // Act
+        let storage_balance = account_manager.storage_withdraw(None);
+
+        // Assert - the storage usage cost is locked, which is above the min storage balance
+        assert_eq!(storage_balance.available, YoctoNear::ZERO);
+        assert_eq!(storage_balance.total, account.storage_usage_cost());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INSUFFICIENT_STORAGE_BALANCE]")]
+    fn storage_usage_growth_not_covered() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy(None);
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, Some(true));
+
+        // Act
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        grant_delegation(&mut account_manager);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [STORAGE_USAGE_MAX_EXCEEDED]")]
+    fn storage_usage_max_exceeded() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let min = AccountManager::measure_storage_usage(());
+        let mut account_manager = deploy(Some(min + 10.into()));
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        grant_delegation(&mut account_manager);
+    }
+
+    #[test]
+    fn deferred_storage_usage_growth_covered() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy(None);
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, Some(true));
+
+        // Act - the storage balance is credited after the storage usage grows
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        AccountManager::defer_storage_balance_coverage(|| {
+            grant_delegation(&mut account_manager);
+            assert!(StorageLedger::contains(ACCOUNT.into()));
+            let mut account = account_manager.registered_account_near_data(ACCOUNT);
+            account.incr_near_balance(YOCTO.into());
+            account.save();
+        });
+
+        // Assert
+        assert!(StorageLedger::is_empty());
+        assert!(!StorageLedger::is_deferred());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INSUFFICIENT_STORAGE_BALANCE]")]
+    fn deferred_storage_usage_growth_not_covered() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy(None);
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, Some(true));
+
+        // Act - nested deferrals are settled by the outermost deferral
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        AccountManager::defer_storage_balance_coverage(|| {
+            AccountManager::defer_storage_balance_coverage(|| {
+                grant_delegation(&mut account_manager)
+            });
+            assert!(StorageLedger::is_deferred());
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_account_freeze {
+    use super::*;
+    use crate::components::admin_audit_log::AdminAuditLogComponent;
+    use oysterpack_smart_near::domain::{Expiration, ExpirationSetting};
+    use oysterpack_smart_near::near_sdk::{self, test_utils, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    const ADMIN: &str = "admin";
+    const ACCOUNT: &str = "alice";
+
+    fn test<F>(f: F)
+    where
+        F: FnOnce(VMContext, AccountManager),
+    {
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(StorageUsageBounds {
+                min: AccountManager::measure_storage_usage(()),
+                max: None,
+            }),
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: None,
+        });
+
+        let mut account_manager = AccountManager::new(Default::default());
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        ctx.predecessor_account_id = ADMIN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        f(ctx, account_manager);
+    }
+
+    fn freeze(account_manager: &mut AccountManager, expiration: Option<ExpirationSetting>) {
+        account_manager.ops_account_freeze(
+            to_valid_account_id(ACCOUNT),
+            "compromised".to_string(),
+            expiration,
+        );
+    }
+
+    #[test]
+    fn freeze_and_unfreeze() {
+        test(|ctx, mut account_manager| {
+            assert!(account_manager
+                .ops_account_freeze_status(to_valid_account_id(ACCOUNT))
+                .is_none());
+
+            // Act
+            freeze(&mut account_manager, None);
+
+            // Assert
+            let account_freeze = account_manager
+                .ops_account_freeze_status(to_valid_account_id(ACCOUNT))
+                .unwrap();
+            assert_eq!(account_freeze.reason, "compromised");
+            assert!(account_freeze.expiration.is_none());
+            let logs = test_utils::get_logs();
+            assert!(logs.contains(
+                &"[WARN] [ACCOUNT_FREEZE] account: alice, reason: compromised".to_string()
+            ));
+
+            // Act
+            testing_env!(ctx);
+            assert!(account_manager.ops_account_unfreeze(to_valid_account_id(ACCOUNT)));
+            assert!(!account_manager.ops_account_unfreeze(to_valid_account_id(ACCOUNT)));
+
+            // Assert
+            assert!(account_manager
+                .ops_account_freeze_status(to_valid_account_id(ACCOUNT))
+                .is_none());
+            let entries = AdminAuditLogComponent.ops_audit_log(None, None);
+            assert_eq!(
+                entries
+                    .iter()
+                    .map(|entry| (entry.action.as_str(), entry.target.clone()))
+                    .collect::<Vec<_>>(),
+                vec![
+                    ("ops_account_freeze", Some(ACCOUNT.to_string())),
+                    ("ops_account_unfreeze", Some(ACCOUNT.to_string()))
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn freeze_expires() {
+        test(|mut ctx, mut account_manager| {
+            ctx.epoch_height = 10;
+            testing_env!(ctx.clone());
+            freeze(
+                &mut account_manager,
+                Some(ExpirationSetting::Absolute(Expiration::Epoch(11.into()))),
+            );
+            assert!(account_manager
+                .ops_account_freeze_status(to_valid_account_id(ACCOUNT))
+                .is_some());
+
+            // Act
+            ctx.epoch_height = 12;
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.attached_deposit = 1;
+            testing_env!(ctx.clone());
+
+            // Assert
+            assert!(account_manager
+                .ops_account_freeze_status(to_valid_account_id(ACCOUNT))
+                .is_none());
+            account_manager.storage_withdraw(None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+    fn storage_withdraw_while_frozen() {
+        test(|mut ctx, mut account_manager| {
+            freeze(&mut account_manager, None);
+
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.attached_deposit = 1;
+            testing_env!(ctx);
+            account_manager.storage_withdraw(None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+    fn storage_unregister_while_frozen() {
+        test(|mut ctx, mut account_manager| {
+            freeze(&mut account_manager, None);
+
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.attached_deposit = 1;
+            testing_env!(ctx);
+            account_manager.storage_unregister(Some(true));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
+    fn freeze_as_non_admin() {
+        test(|mut ctx, mut account_manager| {
+            ctx.predecessor_account_id = "bob".to_string();
+            ctx.attached_deposit = YOCTO;
+            testing_env!(ctx.clone());
+            account_manager.storage_deposit(None, None);
+
+            ctx.attached_deposit = 0;
+            testing_env!(ctx);
+            freeze(&mut account_manager, None);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_account_migration {
+    use super::*;
+    use oysterpack_smart_near::near_sdk::{self, test_utils, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<u128>;
+
+    const ADMIN: &str = "admin";
+    const ACCOUNT: &str = "alice";
+    const NEW_ACCOUNT: &str = "alice-new";
+
+    fn test<F>(f: F)
+    where
+        F: FnOnce(VMContext, AccountManager),
+    {
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(StorageUsageBounds {
+                min: AccountManager::measure_storage_usage(0),
+                max: None,
+            }),
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: None,
+        });
+
+        let mut account_manager = AccountManager::new(Default::default());
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        f(ctx, account_manager);
+    }
+
+    fn accept(ctx: &mut VMContext, account_manager: &mut AccountManager) {
+        ctx.predecessor_account_id = NEW_ACCOUNT.to_string();
+        testing_env!(ctx.clone());
+        account_manager.ops_account_migrate_accept(to_valid_account_id(ACCOUNT));
+    }
+
+    #[test]
+    fn migrate_and_accept() {
+        test(|mut ctx, mut account_manager| {
+            // Arrange
+            let mut account_data = account_manager.registered_account_data(ACCOUNT);
+            **account_data = 100;
+            account_data.save();
+            let mut actions = HashMap::new();
+            actions.insert("stake".to_string(), None);
+            account_manager.ops_delegation_grant(to_valid_account_id("bot"), actions, None);
+            let account = account_manager.registered_account_near_data(ACCOUNT);
+            let account_metrics = AccountManager::account_metrics();
+
+            // Act
+            let migration = account_manager.ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+            // Assert
+            assert_eq!(
+                migration,
+                AccountMigration {
+                    account_id: ACCOUNT.to_string(),
+                    new_account_id: NEW_ACCOUNT.to_string()
+                }
+            );
+            assert_eq!(
+                account_manager.ops_account_migration(to_valid_account_id(ACCOUNT)),
+                Some(migration)
+            );
+            // the pending migration is charged to the account
+            assert!(
+                account_manager
+                    .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                    .unwrap()
+                    > account.storage_usage()
+            );
+
+            // Act
+            accept(&mut ctx, &mut account_manager);
+
+            // Assert
+            let logs = test_utils::get_logs();
+            assert!(logs.contains(
+                &"[INFO] [ACCOUNT_MIGRATED] account_id: alice, new_account_id: alice-new"
+                    .to_string()
+            ));
+            assert!(!account_manager.account_exists(ACCOUNT));
+            assert!(account_manager.load_account_data(ACCOUNT).is_none());
+            assert!(account_manager
+                .ops_account_migration(to_valid_account_id(ACCOUNT))
+                .is_none());
+            assert!(AccountDelegations::load(ACCOUNT).is_empty());
+
+            let new_account = account_manager.registered_account_near_data(NEW_ACCOUNT);
+            assert_eq!(new_account.near_balance(), account.near_balance());
+            // the account registry stores the account ID
+            let account_id_len_diff = (NEW_ACCOUNT.len() - ACCOUNT.len()) as u64;
+            assert_eq!(
+                new_account.storage_usage(),
+                account.storage_usage() + account_id_len_diff.into()
+            );
+            assert_eq!(**account_manager.registered_account_data(NEW_ACCOUNT), 100);
+            assert!(account_manager
+                .ops_delegation(to_valid_account_id(NEW_ACCOUNT), to_valid_account_id("bot"))
+                .is_some());
+            let mut account_metrics = account_metrics;
+            account_metrics.total_storage_usage += account_id_len_diff.into();
+            *account_metrics
+                .storage_usage_by_component
+                .get_mut(COMPONENT_ID_ACCOUNT_REGISTRY.name())
+                .unwrap() += account_id_len_diff.into();
+            // the pending migration was deleted
+            account_metrics
+                .storage_usage_by_component
+                .insert(COMPONENT_ID_ACCOUNT_MIGRATION.to_string(), 0.into());
+            assert_eq!(AccountManager::account_metrics(), account_metrics);
+        });
+    }
+
+    #[test]
+    fn sponsor_migration() {
+        test(|mut ctx, mut account_manager| {
+            // Arrange - the account sponsors bob
+            ctx.attached_deposit = YOCTO;
+            testing_env!(ctx.clone());
+            account_manager.storage_deposit(Some(to_valid_account_id("bob")), None);
+
+            ctx.attached_deposit = 1;
+            testing_env!(ctx.clone());
+            account_manager.ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+            // Act
+            accept(&mut ctx, &mut account_manager);
+
+            // Assert
+            let sponsors = account_manager
+                .ops_storage_sponsors(to_valid_account_id("bob"))
+                .unwrap();
+            assert_eq!(sponsors.len(), 1);
+            assert_eq!(sponsors[NEW_ACCOUNT], YOCTO.into());
+            assert!(account_manager
+                .ops_storage_sponsored_accounts(to_valid_account_id(ACCOUNT))
+                .is_empty());
+            assert_eq!(
+                account_manager.ops_storage_sponsored_accounts(to_valid_account_id(NEW_ACCOUNT)),
+                vec!["bob".to_string()]
+            );
+        });
+    }
+
+    #[test]
+    fn cancel() {
+        test(|_ctx, mut account_manager| {
+            let storage_usage = account_manager
+                .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                .unwrap();
+            account_manager.ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+            // Act
+            assert!(account_manager.ops_account_migrate_cancel());
+            assert!(!account_manager.ops_account_migrate_cancel());
+
+            // Assert
+            assert!(account_manager
+                .ops_account_migration(to_valid_account_id(ACCOUNT))
+                .is_none());
+            assert_eq!(
+                account_manager
+                    .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                    .unwrap(),
+                storage_usage
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_MIGRATION_NOT_FOUND]")]
+    fn accept_from_another_account() {
+        test(|mut ctx, mut account_manager| {
+            account_manager.ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+            ctx.predecessor_account_id = "bob".to_string();
+            testing_env!(ctx);
+            account_manager.ops_account_migrate_accept(to_valid_account_id(ACCOUNT));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_ALREADY_REGISTERED]")]
+    fn migrate_to_registered_account() {
+        test(|_ctx, mut account_manager| {
+            account_manager.ops_account_migrate(to_valid_account_id(ADMIN));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+    fn accept_while_frozen() {
+        test(|mut ctx, mut account_manager| {
+            account_manager.ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+            ctx.predecessor_account_id = ADMIN.to_string();
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            account_manager.ops_account_freeze(
+                to_valid_account_id(ACCOUNT),
+                "compromised".to_string(),
+                None,
+            );
+
+            ctx.attached_deposit = 1;
+            accept(&mut ctx, &mut account_manager);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_storage_token_credit {
+    use super::*;
+    use oysterpack_smart_near::near_sdk;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    const ACCOUNT: &str = "alice";
+
+    fn deploy(max: Option<StorageUsage>) -> AccountManager {
+        let storage_usage_bounds = StorageUsageBounds {
+            min: AccountManager::measure_storage_usage(()),
+            max,
+        };
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(storage_usage_bounds),
+            admin_account: to_valid_account_id("admin"),
+            component_account_storage_mins: None,
+        });
+        AccountManager::new(Default::default())
+    }
+
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(action) => (receipt.receiver_id.clone(), action.deposit),
+                _ => panic!("expected Transfer"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn token_credit_is_not_withdrawable_as_near() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy(None);
+
+        // Act - registers the account
+        let credit = account_manager.deposit_token_credit(ACCOUNT, YOCTO.into());
+
+        // Assert
+        assert_eq!(credit, YOCTO.into());
+        assert_eq!(StorageTokenCredit::load(ACCOUNT), YOCTO.into());
+        let account = account_manager.registered_account_near_data(ACCOUNT);
+        assert_eq!(account.near_balance(), YOCTO.into());
+        assert_eq!(
+            AccountManager::account_metrics().total_near_balance,
+            account_manager
+                .registered_account_near_data("admin")
+                .near_balance()
+                + YOCTO
+        );
+
+        // Act - the account deposits NEAR
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act - only the NEAR deposit can be withdrawn as NEAR
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        account_manager.storage_withdraw(None);
+        assert_eq!(transfers(), vec![(ACCOUNT.to_string(), YOCTO + 1)]);
+        let storage_balance = account_manager
+            .storage_balance_of(to_valid_account_id(ACCOUNT))
+            .unwrap();
+        assert_eq!(storage_balance.total, YOCTO.into());
+        assert!(storage_balance.available > YoctoNear::ZERO);
+
+        // Act - withdraw the available token credit
+        testing_env!(ctx.clone());
+        account_manager.withdraw_token_credit(ACCOUNT, storage_balance.available);
+
+        // Assert
+        let account = account_manager.registered_account_near_data(ACCOUNT);
+        assert_eq!(
+            account.near_balance(),
+            account_manager.storage_balance_bounds().min
+        );
+        assert_eq!(
+            StorageTokenCredit::load(ACCOUNT),
+            account_manager.storage_balance_bounds().min
+        );
+
+        // Act - unregistering the account does not refund the token credit as NEAR
+        testing_env!(ctx.clone());
+        assert!(account_manager.storage_unregister(None));
+        assert_eq!(transfers(), vec![(ACCOUNT.to_string(), 1)]);
+        assert_eq!(StorageTokenCredit::load(ACCOUNT), YoctoNear::ZERO);
+    }
+
+    #[test]
+    fn deposit_is_capped_by_max_storage_balance() {
+        // Arrange
+        let ctx = new_context(ACCOUNT);
+        testing_env!(ctx);
+        let min = AccountManager::measure_storage_usage(());
+        let mut account_manager = deploy(Some((min.value() * 2).into()));
+        let max = account_manager.storage_balance_bounds().max.unwrap();
+
+        // Act
+        let credit = account_manager.deposit_token_credit(ACCOUNT, YOCTO.into());
+        assert_eq!(credit, max);
+        let credit = account_manager.deposit_token_credit(ACCOUNT, YOCTO.into());
+
+        // Assert
+        assert_eq!(credit, YoctoNear::ZERO);
+        assert_eq!(StorageTokenCredit::load(ACCOUNT), max);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INSUFFICIENT_STORAGE_BALANCE]")]
+    fn registration_requires_min_storage_balance() {
+        let ctx = new_context(ACCOUNT);
+        testing_env!(ctx);
+        let mut account_manager = deploy(None);
+        let min = account_manager.storage_balance_bounds().min;
+        account_manager.deposit_token_credit(ACCOUNT, min - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INSUFFICIENT_STORAGE_BALANCE]")]
+    fn withdraw_more_than_token_credit() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy(None);
+        account_manager.deposit_token_credit(ACCOUNT, YOCTO.into());
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act
+        account_manager.withdraw_token_credit(ACCOUNT, (YOCTO + 1).into());
+    }
+}
+
+#[cfg(test)]
+mod test_storage_usage_by_component {
+    use super::*;
+    use oysterpack_smart_near::near_sdk;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    const ACCOUNT: &str = "alice";
+    const DELEGATE: &str = "bot";
+
+    fn deploy() -> AccountManager {
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(StorageUsageBounds {
+                min: AccountManager::measure_storage_usage(()),
+                max: None,
+            }),
+            admin_account: to_valid_account_id("admin"),
+            component_account_storage_mins: None,
+        });
+        AccountManager::new(Default::default())
+    }
+
+    fn total(breakdown: &BTreeMap<String, StorageUsage>) -> StorageUsage {
+        breakdown
+            .values()
+            .map(|storage_usage| storage_usage.value())
+            .sum::<u64>()
+            .into()
+    }
+
+    #[test]
+    fn registered_account() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        let mut actions = HashMap::new();
+        actions.insert("stake".to_string(), None);
+        account_manager.ops_delegation_grant(to_valid_account_id(DELEGATE), actions, None);
+
+        // Assert
+        let breakdown = account_manager
+            .ops_storage_usage_by_component(to_valid_account_id(ACCOUNT))
+            .unwrap();
+        assert_eq!(
+            breakdown.keys().cloned().collect::<Vec<_>>(),
+            vec![
+                COMPONENT_ID_ACCOUNT.to_string(),
+                COMPONENT_ID_ACCOUNT_DELEGATIONS.to_string(),
+                COMPONENT_ID_ACCOUNT_REGISTRY.to_string(),
+            ]
+        );
+        for component_storage_usage in breakdown.values() {
+            assert_eq!(
+                component_storage_usage.cost,
+                component_storage_usage.storage_usage.cost()
+            );
+        }
+        assert_eq!(
+            total(&AccountStorageUsageBreakdown::load(ACCOUNT)),
+            account_manager
+                .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                .unwrap()
+        );
+
+        let account_metrics = AccountManager::account_metrics();
+        assert_eq!(
+            total(&account_metrics.storage_usage_by_component),
+            account_metrics.total_storage_usage
+        );
+        assert_eq!(
+            account_metrics.storage_usage_by_component[COMPONENT_ID_ACCOUNT_DELEGATIONS.name()],
+            breakdown[COMPONENT_ID_ACCOUNT_DELEGATIONS.name()].storage_usage
+        );
+
+        assert!(account_manager
+            .ops_storage_usage_by_component(to_valid_account_id(DELEGATE))
+            .is_none());
+    }
+
+    #[test]
+    fn unregistered_account() {
+        // Arrange
+        let mut ctx = new_context(ACCOUNT);
+        testing_env!(ctx.clone());
+        let mut account_manager = deploy();
+        let account_metrics = AccountManager::account_metrics();
+
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        account_manager.storage_deposit(None, None);
+
+        // Act
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        assert!(account_manager.storage_unregister(None));
+
+        // Assert - the account storage usage breakdown is deleted along with the account
+        assert!(account_manager
+            .ops_storage_usage_by_component(to_valid_account_id(ACCOUNT))
+            .is_none());
+        assert!(AccountStorageUsageBreakdown::load(ACCOUNT).is_empty());
+        let account_metrics_after_unregister = AccountManager::account_metrics();
+        assert_eq!(
+            account_metrics_after_unregister.total_storage_usage,
+            account_metrics.total_storage_usage
+        );
+        assert_eq!(
+            account_metrics_after_unregister.storage_usage_by_component,
+            account_metrics.storage_usage_by_component
+        );
     }
 }
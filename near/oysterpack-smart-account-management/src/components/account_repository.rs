@@ -19,8 +19,11 @@ where
     ) -> Account<T> {
         ERR_ACCOUNT_ALREADY_REGISTERED.assert(|| !AccountNearDataObject::exists(account_id));
 
-        let near_data = AccountNearDataObject::new(account_id, near_balance);
-        near_data.save();
+        AccountNearDataObject::new(account_id, near_balance).save();
+        AccountRegistry::add(account_id);
+        // the account storage usage is updated by the storage event handler - thus the account
+        // needs to be reloaded
+        let near_data = AccountNearDataObject::registered_account(account_id);
 
         match data {
             Some(data) => {
@@ -73,6 +76,19 @@ where
 
     fn delete_account(&mut self, account_id: &str) {
         if let Some((near_data, data)) = self.load_account(account_id) {
+            if near_data.permission_expirations().is_some() {
+                ExpiringPermissionsAccounts::remove(account_id);
+            }
+            AccountDelegations::delete(account_id);
+            AccountMigration::delete(account_id);
+            if let Some(sponsors) = near_data.sponsors() {
+                for sponsor in sponsors.keys() {
+                    SponsoredAccounts::remove(sponsor, account_id);
+                }
+            }
+            StorageTokenCredit::delete(account_id);
+            GrandfatheredStorageBalance::delete(account_id);
+            AccountRegistry::remove(account_id);
             near_data.delete();
             if let Some(data) = data {
                 data.delete();
@@ -1,8 +1,16 @@
 use oysterpack_smart_near::domain::StorageUsage;
 use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
 
-use crate::{AccountNearDataObject, AccountStorageUsage, StorageUsageBounds};
+use crate::components::account_management::{
+    ERR_INSUFFICIENT_STORAGE_BALANCE, ERR_STORAGE_BALANCE_BELOW_MIN, ERR_STORAGE_USAGE_MAX_EXCEEDED,
+};
+use crate::{
+    AccountIdHash, AccountNearDataObject, AccountStorageUsage, AccountStorageUsageBreakdown,
+    ComponentStorageUsage, GrandfatheredStorageBalance, StorageBalanceBounds, StorageUsageBounds,
+};
 use oysterpack_smart_near::component::{Component, Deploy};
+use oysterpack_smart_near::domain::YoctoNear;
+use std::collections::BTreeMap;
 
 #[derive(Default)]
 pub(crate) struct AccountStorageUsageComponent;
@@ -16,6 +24,79 @@ impl AccountStorageUsage for AccountStorageUsageComponent {
         AccountNearDataObject::load(account_id.as_ref().as_str())
             .map(|account| account.storage_usage())
     }
+
+    fn ops_storage_usage_by_component(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<String, ComponentStorageUsage>> {
+        if !AccountNearDataObject::exists(account_id.as_ref().as_str()) {
+            return None;
+        }
+        Some(
+            AccountStorageUsageBreakdown::load(account_id.as_ref())
+                .into_iter()
+                .map(|(component, storage_usage)| (component, storage_usage.into()))
+                .collect(),
+        )
+    }
+}
+
+impl AccountStorageUsageComponent {
+    pub(crate) fn set_storage_usage_bounds(storage_usage_bounds: StorageUsageBounds) {
+        let mut state = Self::load_state().expect("requires deployment");
+        *state = storage_usage_bounds;
+        state.save();
+    }
+
+    /// Returns the min storage balance that the account is held to, which is the contract min
+    /// storage balance unless the account was grandfathered at a lower balance
+    pub(crate) fn storage_balance_min(account_id_hash: AccountIdHash) -> YoctoNear {
+        let storage_balance_bounds: StorageBalanceBounds = AccountStorageUsageComponent
+            .ops_storage_usage_bounds()
+            .into();
+        GrandfatheredStorageBalance::load_by_hash(account_id_hash)
+            .map_or(storage_balance_bounds.min, |min| {
+                min.min(storage_balance_bounds.min)
+            })
+    }
+
+    /// Asserts that the account is within the storage usage bounds and that its NEAR balance covers
+    /// its storage usage cost and the min storage balance.
+    ///
+    /// ## Panics
+    /// - [`ERR_STORAGE_USAGE_MAX_EXCEEDED`]
+    /// - [`ERR_INSUFFICIENT_STORAGE_BALANCE`]
+    /// - [`ERR_STORAGE_BALANCE_BELOW_MIN`]
+    pub(crate) fn assert_storage_balance_coverage(account: &AccountNearDataObject) {
+        if let Some(max) = AccountStorageUsageComponent.ops_storage_usage_bounds().max {
+            ERR_STORAGE_USAGE_MAX_EXCEEDED.assert_with_message(
+                || account.storage_usage() <= max,
+                || format!("storage usage = {}, max = {}", account.storage_usage(), max),
+            );
+        }
+        ERR_INSUFFICIENT_STORAGE_BALANCE.assert_with_message(
+            || account.near_balance() >= account.storage_usage_cost(),
+            || {
+                format!(
+                    "storage usage = {}, storage usage cost = {}, storage balance = {}",
+                    account.storage_usage(),
+                    account.storage_usage_cost(),
+                    account.near_balance()
+                )
+            },
+        );
+        let min = Self::storage_balance_min(account.key().account_id_hash());
+        ERR_STORAGE_BALANCE_BELOW_MIN.assert_with_message(
+            || account.near_balance() >= min,
+            || {
+                format!(
+                    "storage balance = {}, min storage balance = {}",
+                    account.near_balance(),
+                    min
+                )
+            },
+        );
+    }
 }
 
 impl Component for AccountStorageUsageComponent {
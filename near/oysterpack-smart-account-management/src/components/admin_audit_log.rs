@@ -0,0 +1,22 @@
+//! [`AdminAuditLogComponent`]
+
+use crate::{
+    AdminAuditLog, AuditLog, AuditLogEntry, AUDIT_LOG_DEFAULT_PAGE_SIZE, AUDIT_LOG_MAX_PAGE_SIZE,
+};
+use oysterpack_smart_near::near_sdk::json_types::U64;
+
+pub struct AdminAuditLogComponent;
+
+impl AdminAuditLog for AdminAuditLogComponent {
+    fn ops_audit_log(&self, from_id: Option<U64>, limit: Option<u16>) -> Vec<AuditLogEntry> {
+        let from_id = from_id.map_or_else(AuditLog::oldest_id, |id| id.0);
+        let limit = limit
+            .unwrap_or(AUDIT_LOG_DEFAULT_PAGE_SIZE)
+            .min(AUDIT_LOG_MAX_PAGE_SIZE);
+        AuditLog::entries(from_id, limit as u64)
+    }
+
+    fn ops_audit_log_count(&self) -> U64 {
+        AuditLog::count().into()
+    }
+}
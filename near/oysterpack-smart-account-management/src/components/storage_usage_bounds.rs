@@ -0,0 +1,407 @@
+//! [`StorageUsageBoundsComponent`]
+//! - constructor: [`StorageUsageBoundsComponent::new`]
+//!   - [`AccountManagementComponent`]
+//!   - min account storage functions provided by contract components
+
+use crate::components::account_management::AccountManagementComponent;
+use crate::components::account_storage_usage::AccountStorageUsageComponent;
+use crate::*;
+use oysterpack_smart_near::domain::{StorageUsage, YoctoNear};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    json_types::{ValidAccountId, U64},
+    serde_json::json,
+};
+use std::fmt::Debug;
+
+/// Implements [`StorageUsageBoundsManagement`]
+///
+/// The component must be constructed with the same min account storage functions that the
+/// contract components register when the [`AccountManagementComponent`] is deployed - see
+/// [`crate::components::account_management::AccountManagementComponentConfig::component_account_storage_mins`]
+pub struct StorageUsageBoundsComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    account_manager: AccountManagementComponent<T>,
+    component_account_storage_mins: Vec<fn() -> StorageUsage>,
+}
+
+impl<T> StorageUsageBoundsComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub fn new(
+        account_manager: AccountManagementComponent<T>,
+        component_account_storage_mins: Vec<fn() -> StorageUsage>,
+    ) -> Self {
+        Self {
+            account_manager,
+            component_account_storage_mins,
+        }
+    }
+
+    fn storage_balance_min() -> YoctoNear {
+        let storage_balance_bounds: StorageBalanceBounds = AccountStorageUsageComponent
+            .ops_storage_usage_bounds()
+            .into();
+        storage_balance_bounds.min
+    }
+}
+
+impl<T> StorageUsageBoundsManagement for StorageUsageBoundsComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    fn ops_storage_usage_bounds_remeasure(&mut self) -> StorageUsageBoundsChange {
+        self.account_manager
+            .assert_operator("ops_storage_usage_bounds_remeasure");
+
+        let previous = AccountStorageUsageComponent.ops_storage_usage_bounds();
+        let min = AccountManagementComponent::<T>::measure_account_storage_min(
+            &self.component_account_storage_mins,
+        );
+        if let Some(max) = previous.max {
+            ERR_STORAGE_USAGE_MIN_EXCEEDS_MAX
+                .assert_with_message(|| min <= max, || format!("min = {}, max = {}", min, max));
+        }
+        let current = StorageUsageBounds {
+            min,
+            max: previous.max,
+        };
+        if current != previous {
+            AccountStorageUsageComponent::set_storage_usage_bounds(current);
+            LOG_EVENT_STORAGE_USAGE_BOUNDS_CHANGED
+                .log(format!("min: {} -> {}", previous.min, current.min));
+            AuditLog::record(
+                "ops_storage_usage_bounds_remeasure",
+                None,
+                Some(json!({"previous_min": previous.min, "min": current.min}).to_string()),
+            );
+        }
+        StorageUsageBoundsChange { previous, current }
+    }
+
+    fn ops_storage_balance_shortfalls(
+        &self,
+        from_index: Option<U64>,
+        limit: Option<u32>,
+    ) -> StorageBalanceShortfalls {
+        let from_index = from_index.map_or(0, |index| index.0);
+        let limit = limit.unwrap_or(STORAGE_BALANCE_SHORTFALLS_DEFAULT_LIMIT) as u64;
+        let account_ids = AccountRegistry::accounts(from_index, limit);
+        let next_index = from_index + account_ids.len() as u64;
+
+        let accounts = account_ids
+            .into_iter()
+            .filter_map(|account_id| {
+                let storage_balance = self
+                    .account_manager
+                    .registered_account_near_data(&account_id)
+                    .near_balance();
+                let min = AccountManagementComponent::<T>::account_storage_balance_min(&account_id);
+                if storage_balance < min {
+                    Some(StorageBalanceShortfall {
+                        account_id,
+                        storage_balance,
+                        shortfall: min - storage_balance,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        StorageBalanceShortfalls {
+            accounts,
+            next_index: if next_index < AccountRegistry::count() {
+                Some(next_index.into())
+            } else {
+                None
+            },
+        }
+    }
+
+    fn ops_storage_balance_grandfather(&mut self, account_id: ValidAccountId) -> YoctoNear {
+        self.account_manager
+            .assert_operator("ops_storage_balance_grandfather");
+
+        let storage_balance = self
+            .account_manager
+            .registered_account_near_data(account_id.as_ref())
+            .near_balance();
+        let min = Self::storage_balance_min();
+        ERR_STORAGE_BALANCE_NOT_SHORT.assert_with_message(
+            || storage_balance < min,
+            || format!("storage balance = {}, min = {}", storage_balance, min),
+        );
+        GrandfatheredStorageBalance::save(account_id.as_ref(), storage_balance);
+
+        LOG_EVENT_STORAGE_BALANCE_GRANDFATHERED.log(format!(
+            "{}: {}",
+            account_id.as_ref(),
+            storage_balance
+        ));
+        AuditLog::record(
+            "ops_storage_balance_grandfather",
+            Some(account_id.as_ref()),
+            Some(json!({ "storage_balance": storage_balance }).to_string()),
+        );
+        storage_balance
+    }
+
+    fn ops_storage_balance_grandfather_revoke(&mut self, account_id: ValidAccountId) -> bool {
+        self.account_manager
+            .assert_operator("ops_storage_balance_grandfather_revoke");
+        let revoked = GrandfatheredStorageBalance::delete(account_id.as_ref());
+        if revoked {
+            AuditLog::record(
+                "ops_storage_balance_grandfather_revoke",
+                Some(account_id.as_ref()),
+                None,
+            );
+        }
+        revoked
+    }
+
+    fn ops_storage_balance_grandfathered(&self, account_id: ValidAccountId) -> Option<YoctoNear> {
+        GrandfatheredStorageBalance::load(account_id.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::account_management::AccountManagementComponentConfig;
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::near_sdk::{self, env, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+    use std::collections::HashMap;
+
+    type AccountManager = AccountManagementComponent<()>;
+    type StorageBounds = StorageUsageBoundsComponent<()>;
+
+    const ADMIN: &str = "admin";
+    const ACCOUNT: &str = "alice";
+
+    /// simulates a component that was added by a contract upgrade
+    fn upgrade_account_storage_min() -> StorageUsage {
+        100.into()
+    }
+
+    fn deploy() -> (VMContext, StorageBounds) {
+        let ctx = new_context(ADMIN);
+        testing_env!(ctx.clone());
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(StorageUsageBounds {
+                min: AccountManager::measure_storage_usage(()),
+                max: None,
+            }),
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: None,
+        });
+        let mut storage_bounds = StorageBounds::new(
+            AccountManager::new(Default::default()),
+            vec![upgrade_account_storage_min],
+        );
+
+        // register the account with the min storage balance before the upgrade
+        let mut ctx = ctx;
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        storage_bounds
+            .account_manager
+            .storage_deposit(None, Some(true));
+
+        (ctx, storage_bounds)
+    }
+
+    fn remeasure(
+        ctx: &mut VMContext,
+        storage_bounds: &mut StorageBounds,
+    ) -> StorageUsageBoundsChange {
+        ctx.predecessor_account_id = ADMIN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        storage_bounds.ops_storage_usage_bounds_remeasure()
+    }
+
+    fn grant_delegation(ctx: &mut VMContext, storage_bounds: &mut StorageBounds) {
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        let mut actions = HashMap::new();
+        actions.insert("stake".to_string(), None);
+        storage_bounds.account_manager.ops_delegation_grant(
+            to_valid_account_id("bot"),
+            actions,
+            None,
+        );
+    }
+
+    fn min_storage_balance() -> YoctoNear {
+        StorageBounds::storage_balance_min()
+    }
+
+    #[test]
+    fn remeasure_and_top_up() {
+        // Arrange
+        let (mut ctx, mut storage_bounds) = deploy();
+        let initial_min_storage_balance = min_storage_balance();
+        assert!(storage_bounds
+            .ops_storage_balance_shortfalls(None, None)
+            .accounts
+            .is_empty());
+
+        // Act
+        let change = remeasure(&mut ctx, &mut storage_bounds);
+
+        // Assert
+        assert_eq!(change.current.min, change.previous.min + 100.into());
+        assert_eq!(change.current.max, change.previous.max);
+        assert_eq!(
+            storage_bounds.account_manager.ops_storage_usage_bounds(),
+            change.current
+        );
+        let expected_shortfall: YoctoNear = (100 * env::storage_byte_cost()).into();
+        assert_eq!(
+            min_storage_balance(),
+            initial_min_storage_balance + expected_shortfall
+        );
+        let shortfalls = storage_bounds.ops_storage_balance_shortfalls(None, None);
+        assert_eq!(shortfalls.next_index, None);
+        assert_eq!(
+            shortfalls
+                .accounts
+                .iter()
+                .map(|shortfall| shortfall.account_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![ADMIN, ACCOUNT]
+        );
+        let shortfall = shortfalls.accounts[1].clone();
+        assert_eq!(shortfall.storage_balance, initial_min_storage_balance);
+        assert_eq!(shortfall.shortfall, expected_shortfall);
+
+        // Act - re-measuring again is a no-op
+        let change = remeasure(&mut ctx, &mut storage_bounds);
+        assert_eq!(change.previous, change.current);
+
+        // Act - top up
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = *shortfall.shortfall;
+        testing_env!(ctx.clone());
+        storage_bounds.account_manager.storage_deposit(None, None);
+
+        // Assert
+        let shortfalls = storage_bounds.ops_storage_balance_shortfalls(None, None);
+        assert_eq!(shortfalls.accounts.len(), 1);
+        assert_eq!(shortfalls.accounts[0].account_id, ADMIN);
+        grant_delegation(&mut ctx, &mut storage_bounds);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [STORAGE_BALANCE_BELOW_MIN]")]
+    fn storage_growth_below_min() {
+        let (mut ctx, mut storage_bounds) = deploy();
+        remeasure(&mut ctx, &mut storage_bounds);
+        grant_delegation(&mut ctx, &mut storage_bounds);
+    }
+
+    #[test]
+    fn grandfathered_account() {
+        // Arrange
+        let (mut ctx, mut storage_bounds) = deploy();
+        let initial_min_storage_balance = min_storage_balance();
+        remeasure(&mut ctx, &mut storage_bounds);
+
+        // Act
+        let grandfathered =
+            storage_bounds.ops_storage_balance_grandfather(to_valid_account_id(ACCOUNT));
+
+        // Assert
+        assert_eq!(grandfathered, initial_min_storage_balance);
+        assert_eq!(
+            storage_bounds.ops_storage_balance_grandfathered(to_valid_account_id(ACCOUNT)),
+            Some(grandfathered)
+        );
+        assert_eq!(
+            AccountManager::account_storage_balance_min(ACCOUNT),
+            grandfathered
+        );
+        let shortfalls = storage_bounds.ops_storage_balance_shortfalls(None, None);
+        assert_eq!(shortfalls.accounts.len(), 1);
+        assert_eq!(shortfalls.accounts[0].account_id, ADMIN);
+        // grandfathered accounts may grow their storage usage
+        grant_delegation(&mut ctx, &mut storage_bounds);
+
+        // Act - revoke
+        ctx.predecessor_account_id = ADMIN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        assert!(storage_bounds.ops_storage_balance_grandfather_revoke(to_valid_account_id(ACCOUNT)));
+        assert!(
+            !storage_bounds.ops_storage_balance_grandfather_revoke(to_valid_account_id(ACCOUNT))
+        );
+
+        // Assert
+        assert!(storage_bounds
+            .ops_storage_balance_grandfathered(to_valid_account_id(ACCOUNT))
+            .is_none());
+        assert_eq!(
+            storage_bounds
+                .ops_storage_balance_shortfalls(None, None)
+                .accounts
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn grandfathered_account_unregistered() {
+        let (mut ctx, mut storage_bounds) = deploy();
+        remeasure(&mut ctx, &mut storage_bounds);
+        storage_bounds.ops_storage_balance_grandfather(to_valid_account_id(ACCOUNT));
+
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        assert!(storage_bounds.account_manager.storage_unregister(None));
+
+        assert!(GrandfatheredStorageBalance::load(ACCOUNT).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [STORAGE_BALANCE_NOT_SHORT]")]
+    fn grandfather_account_not_short() {
+        let (mut ctx, mut storage_bounds) = deploy();
+        ctx.predecessor_account_id = ADMIN.to_string();
+        testing_env!(ctx.clone());
+        storage_bounds.ops_storage_balance_grandfather(to_valid_account_id(ACCOUNT));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
+    fn remeasure_not_operator() {
+        let (_ctx, mut storage_bounds) = deploy();
+        storage_bounds.ops_storage_usage_bounds_remeasure();
+    }
+
+    #[test]
+    fn shortfalls_paging() {
+        let (mut ctx, mut storage_bounds) = deploy();
+        remeasure(&mut ctx, &mut storage_bounds);
+
+        let shortfalls = storage_bounds.ops_storage_balance_shortfalls(None, Some(1));
+        assert_eq!(shortfalls.accounts.len(), 1);
+        assert_eq!(shortfalls.accounts[0].account_id, ADMIN);
+        assert_eq!(shortfalls.next_index, Some(1.into()));
+
+        let shortfalls =
+            storage_bounds.ops_storage_balance_shortfalls(shortfalls.next_index, Some(1));
+        assert_eq!(shortfalls.accounts.len(), 1);
+        assert_eq!(shortfalls.accounts[0].account_id, ACCOUNT);
+        assert_eq!(shortfalls.next_index, None);
+    }
+}
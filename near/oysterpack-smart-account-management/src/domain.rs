@@ -1,22 +1,50 @@
 pub use account_data::*;
+pub use account_delegations::*;
+pub use account_freeze::*;
+pub use account_gc_settings::*;
+pub use account_kv_store::*;
 pub use account_metrics::*;
+pub use account_migration::*;
 pub use account_near_data::*;
+pub use account_registry::*;
 pub use account_storage_event::*;
+pub use account_storage_usage_breakdown::*;
+pub use admin_audit_log::*;
+pub use approval_required_commands::*;
 pub use contract_permissions::*;
+pub use expiring_permissions::*;
+pub use grandfathered_storage_balance::*;
 pub use oysterpack_smart_near::domain::AccountIdHash;
 pub use permissions::*;
+pub use sponsored_accounts::*;
 pub use storage_balance::*;
 pub use storage_balance_bounds::*;
+pub use storage_ledger::*;
 pub use storage_management_event::*;
+pub use storage_token_credit::*;
 pub use storage_usage_bounds::*;
 
 mod account_data;
+mod account_delegations;
+mod account_freeze;
+mod account_gc_settings;
+mod account_kv_store;
 mod account_metrics;
+mod account_migration;
 mod account_near_data;
+mod account_registry;
 mod account_storage_event;
+mod account_storage_usage_breakdown;
+mod admin_audit_log;
+mod approval_required_commands;
 mod contract_permissions;
+mod expiring_permissions;
+mod grandfathered_storage_balance;
 mod permissions;
+mod sponsored_accounts;
 mod storage_balance;
 mod storage_balance_bounds;
+mod storage_ledger;
 mod storage_management_event;
+mod storage_token_credit;
 mod storage_usage_bounds;
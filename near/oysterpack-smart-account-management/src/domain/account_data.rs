@@ -6,7 +6,7 @@ use oysterpack_smart_near::near_sdk::{
     env,
 };
 
-use crate::{AccountIdHash, AccountStorageEvent};
+use crate::{AccountIdHash, AccountStorageEvent, COMPONENT_ID_ACCOUNT_DATA};
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
 
@@ -59,12 +59,14 @@ where
             AccountStorageEvent::StorageUsageChanged(
                 self.key().clone(),
                 storage_usage_change.into(),
+                COMPONENT_ID_ACCOUNT_DATA,
             )
         } else {
             let storage_usage_change = storage_usage_before_save - storage_usage_after_save;
             AccountStorageEvent::StorageUsageChanged(
                 self.key().clone(),
                 (storage_usage_change as i64 * -1).into(),
+                COMPONENT_ID_ACCOUNT_DATA,
             )
         };
         eventbus::post(&event);
@@ -80,6 +82,7 @@ where
             eventbus::post(&AccountStorageEvent::StorageUsageChanged(
                 key,
                 (storage_usage_deleted as i64 * -1).into(),
+                COMPONENT_ID_ACCOUNT_DATA,
             ))
         }
         result
@@ -0,0 +1,197 @@
+use crate::{
+    AccountIdHash, AccountStorageEvent, COMPONENT_ID_ACCOUNT_DELEGATIONS,
+    ERR_DELEGATION_ALLOWANCE_EXCEEDED, ERR_DELEGATION_EXPIRED, ERR_DELEGATION_NOT_AUTHORIZED,
+};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{data::Object, domain::Expiration, eventbus, Hash};
+use std::collections::HashMap;
+
+/// Authorizes a delegate account to perform a scoped set of actions on behalf of the account that
+/// granted the delegation.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct Delegation {
+    /// maps action names to the remaining allowance
+    /// - None means the allowance is unlimited
+    /// - the allowance unit is defined by the action, e.g., yoctoNEAR for staking actions and token
+    ///   amount for FT transfers
+    pub actions: HashMap<String, Option<U128>>,
+    pub expiration: Option<Expiration>,
+}
+
+impl Delegation {
+    pub fn expired(&self) -> bool {
+        self.expiration
+            .map_or(false, |expiration| expiration.expired())
+    }
+
+    /// Returns true if the action is delegated and the remaining allowance covers the amount
+    pub fn allows(&self, action: &str, amount: u128) -> bool {
+        !self.expired()
+            && self.actions.get(action).map_or(false, |allowance| {
+                allowance.map_or(true, |allowance| allowance.0 >= amount)
+            })
+    }
+}
+
+const ACCOUNT_DELEGATIONS_KEY: u128 = 1957684311920735640914337269043916513;
+
+type DAO = Object<(u128, AccountIdHash), HashMap<AccountId, Delegation>>;
+
+/// Account delegations are keyed by the delegate account ID.
+///
+/// ## Notes
+/// - delegations are stored separately from the account data, but the storage is charged to the
+///   account that granted the delegations, i.e., [`AccountStorageEvent::StorageUsageChanged`]
+///   events are posted when delegations are saved or deleted
+pub struct AccountDelegations;
+
+impl AccountDelegations {
+    pub fn load(account_id: &str) -> HashMap<AccountId, Delegation> {
+        DAO::load(&Self::key(account_id))
+            .map_or_else(HashMap::new, |delegations| (*delegations).clone())
+    }
+
+    pub fn delegation(account_id: &str, delegate: &str) -> Option<Delegation> {
+        Self::load(account_id).remove(delegate)
+    }
+
+    /// Empty delegations are deleted from storage
+    pub fn save(account_id: &str, delegations: HashMap<AccountId, Delegation>) {
+        let key = Self::key(account_id);
+        Self::track_storage_usage(&key.1, || {
+            if delegations.is_empty() {
+                DAO::delete_by_key(&key);
+            } else {
+                DAO::new(key, delegations).save();
+            }
+        });
+    }
+
+    pub fn delete(account_id: &str) {
+        let key = Self::key(account_id);
+        if DAO::exists(&key) {
+            Self::track_storage_usage(&key.1, || {
+                DAO::delete_by_key(&key);
+            });
+        }
+    }
+
+    /// Debits the delegate's allowance for the action on behalf of the account.
+    ///
+    /// ## Panics
+    /// - [`ERR_DELEGATION_NOT_AUTHORIZED`] - if the action has not been delegated to the delegate
+    /// - [`ERR_DELEGATION_EXPIRED`]
+    /// - [`ERR_DELEGATION_ALLOWANCE_EXCEEDED`]
+    pub fn consume(account_id: &str, delegate: &str, action: &str, amount: u128) {
+        let mut delegations = Self::load(account_id);
+        let delegation = delegations.get_mut(delegate);
+        ERR_DELEGATION_NOT_AUTHORIZED.assert(|| {
+            delegation
+                .as_ref()
+                .map_or(false, |delegation| delegation.actions.contains_key(action))
+        });
+        let delegation = delegation.unwrap();
+        ERR_DELEGATION_EXPIRED.assert(|| !delegation.expired());
+        let allowance = delegation.actions.get_mut(action).unwrap();
+        if let Some(remaining) = allowance {
+            ERR_DELEGATION_ALLOWANCE_EXCEEDED.assert(|| remaining.0 >= amount);
+            remaining.0 -= amount;
+            Self::save(account_id, delegations);
+        }
+    }
+
+    fn key(account_id: &str) -> (u128, AccountIdHash) {
+        (
+            ACCOUNT_DELEGATIONS_KEY,
+            AccountIdHash(Hash::from(account_id)),
+        )
+    }
+
+    fn track_storage_usage<F: FnOnce()>(account_id_hash: &AccountIdHash, f: F) {
+        let storage_usage_before = env::storage_usage();
+        f();
+        let storage_usage_after = env::storage_usage();
+        if storage_usage_after != storage_usage_before {
+            let change = storage_usage_after as i64 - storage_usage_before as i64;
+            eventbus::post(&AccountStorageEvent::StorageUsageChanged(
+                *account_id_hash,
+                change.into(),
+                COMPONENT_ID_ACCOUNT_DELEGATIONS,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oysterpack_smart_near_test::*;
+
+    #[test]
+    fn consume() {
+        let ctx = new_context("bob");
+        testing_env!(ctx);
+
+        let mut delegations = HashMap::new();
+        let mut actions = HashMap::new();
+        actions.insert("stake".to_string(), None);
+        actions.insert("unstake".to_string(), Some(100.into()));
+        delegations.insert(
+            "bot".to_string(),
+            Delegation {
+                actions,
+                expiration: None,
+            },
+        );
+        AccountDelegations::save("alice", delegations);
+
+        AccountDelegations::consume("alice", "bot", "stake", 1000);
+        AccountDelegations::consume("alice", "bot", "unstake", 60);
+        let delegation = AccountDelegations::delegation("alice", "bot").unwrap();
+        assert_eq!(delegation.actions["stake"], None);
+        assert_eq!(delegation.actions["unstake"], Some(40.into()));
+        assert!(delegation.allows("unstake", 40));
+        assert!(!delegation.allows("unstake", 41));
+        assert!(!delegation.allows("withdraw", 0));
+
+        AccountDelegations::delete("alice");
+        assert!(AccountDelegations::load("alice").is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [DELEGATION_ALLOWANCE_EXCEEDED]")]
+    fn consume_allowance_exceeded() {
+        let ctx = new_context("bob");
+        testing_env!(ctx);
+
+        let mut delegations = HashMap::new();
+        let mut actions = HashMap::new();
+        actions.insert("unstake".to_string(), Some(100.into()));
+        delegations.insert(
+            "bot".to_string(),
+            Delegation {
+                actions,
+                expiration: None,
+            },
+        );
+        AccountDelegations::save("alice", delegations);
+
+        AccountDelegations::consume("alice", "bot", "unstake", 101);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [DELEGATION_NOT_AUTHORIZED]")]
+    fn consume_not_delegated() {
+        let ctx = new_context("bob");
+        testing_env!(ctx);
+
+        AccountDelegations::consume("alice", "bot", "unstake", 1);
+    }
+}
@@ -0,0 +1,22 @@
+use oysterpack_smart_near::domain::Expiration;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+/// Admin imposed freeze, which blocks the account from moving funds out of the contract, e.g.,
+/// transferring tokens, withdrawing its storage balance, or unregistering.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct AccountFreeze {
+    pub reason: String,
+    /// if not set, then the account stays frozen until it is unfrozen by an admin
+    pub expiration: Option<Expiration>,
+}
+
+impl AccountFreeze {
+    pub fn expired(&self) -> bool {
+        self.expiration
+            .map_or(false, |expiration| expiration.expired())
+    }
+}
@@ -0,0 +1,42 @@
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+};
+
+const ACCOUNT_GC_SETTINGS_KEY: u128 = 1958118923405169236170289064812559183;
+
+type DAO = Object<u128, AccountGcSettings>;
+
+/// Inactive account garbage collection settings
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct AccountGcSettings {
+    /// accounts that have been idle for more than the specified number of epochs are eligible for
+    /// garbage collection - garbage collection is disabled if not set
+    pub idle_epochs: Option<U64>,
+    /// [`crate::AccountRegistry`] position where the next garbage collection scan starts
+    pub cursor: U64,
+}
+
+impl Default for AccountGcSettings {
+    fn default() -> Self {
+        Self {
+            idle_epochs: None,
+            cursor: 0.into(),
+        }
+    }
+}
+
+impl AccountGcSettings {
+    pub fn load() -> Self {
+        DAO::load(&ACCOUNT_GC_SETTINGS_KEY).map_or_else(Self::default, |settings| *settings)
+    }
+
+    pub(crate) fn save(&self) {
+        DAO::new(ACCOUNT_GC_SETTINGS_KEY, *self).save();
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    AccountIdHash, AccountStorageEvent, StorageManagementEvent, COMPONENT_ID_ACCOUNT_KV_STORE,
+};
+use lazy_static::lazy_static;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+};
+use oysterpack_smart_near::{data::Object, domain::StorageUsage, eventbus, Hash};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+const ACCOUNT_KV_INDEX_KEY: u128 = 1958203417731546280950467384622839477;
+const ACCOUNT_KV_ENTRY_KEY: u128 = 1958203452580133419426127035118412269;
+
+type IndexDAO = Object<(u128, AccountIdHash), AccountKvIndex>;
+type EntryDAO = Object<(u128, AccountIdHash, String), String>;
+
+lazy_static! {
+    static ref STORAGE_MANAGEMENT_EVENT_HANDLER_REGISTERED: Mutex<bool> = Mutex::new(false);
+}
+
+/// Tracks the account's keys and the storage used by the account's key-value entries
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Default)]
+struct AccountKvIndex {
+    keys: BTreeSet<String>,
+    storage_usage: StorageUsage,
+}
+
+/// Per account key-value store.
+///
+/// The entries are charged to the account's storage usage, i.e.,
+/// [`AccountStorageEvent::StorageUsageChanged`] events are posted.
+pub struct AccountKvStore;
+
+impl AccountKvStore {
+    pub fn get(account_id: &str, key: &str) -> Option<String> {
+        EntryDAO::load(&Self::entry_key(&Self::account_id_hash(account_id), key))
+            .map(|value| (*value).clone())
+    }
+
+    /// returns the account's keys in sorted order
+    pub fn keys(account_id: &str) -> Vec<String> {
+        Self::load_index(&Self::account_id_hash(account_id))
+            .keys
+            .into_iter()
+            .collect()
+    }
+
+    /// storage used by the account's key-value entries, which includes the account's key index
+    pub fn storage_usage(account_id: &str) -> StorageUsage {
+        Self::load_index(&Self::account_id_hash(account_id)).storage_usage
+    }
+
+    /// Returns the previous value
+    pub(crate) fn set(account_id: &str, key: &str, value: &str) -> Option<String> {
+        let mut previous_value = None;
+        Self::update(account_id, |account_id_hash, index| {
+            let entry_key = Self::entry_key(account_id_hash, key);
+            previous_value = EntryDAO::load(&entry_key).map(|value| (*value).clone());
+            EntryDAO::new(entry_key, value.to_string()).save();
+            index.keys.insert(key.to_string());
+        });
+        previous_value
+    }
+
+    /// Returns false if the key did not exist
+    pub(crate) fn delete(account_id: &str, key: &str) -> bool {
+        let mut deleted = false;
+        Self::update(account_id, |account_id_hash, index| {
+            if index.keys.remove(key) {
+                deleted = EntryDAO::delete_by_key(&Self::entry_key(account_id_hash, key));
+            }
+        });
+        deleted
+    }
+
+    pub(crate) fn delete_all(account_id: &str) {
+        Self::update(account_id, |account_id_hash, index| {
+            for key in index.keys.iter() {
+                EntryDAO::delete_by_key(&Self::entry_key(account_id_hash, key));
+            }
+            index.keys.clear();
+        });
+    }
+
+    /// moves the account's entries to the new account
+    pub(crate) fn migrate(account_id: &str, new_account_id: &str) {
+        let entries: Vec<(String, String)> = Self::keys(account_id)
+            .into_iter()
+            .filter_map(|key| Self::get(account_id, &key).map(|value| (key, value)))
+            .collect();
+        if entries.is_empty() {
+            return;
+        }
+        Self::delete_all(account_id);
+        Self::update(new_account_id, |account_id_hash, index| {
+            for (key, value) in entries {
+                EntryDAO::new(Self::entry_key(account_id_hash, &key), value).save();
+                index.keys.insert(key);
+            }
+        });
+    }
+
+    /// Used to register event handler hooks to handle account unregistrations and migrations
+    ///
+    /// can be safely called multiple times and will only register the event handlers once
+    pub fn register_storage_management_event_handler() {
+        let mut registered = STORAGE_MANAGEMENT_EVENT_HANDLER_REGISTERED.lock().unwrap();
+        if !*registered {
+            eventbus::register(Self::on_storage_management_event);
+            *registered = true;
+        }
+    }
+
+    /// - [`StorageManagementEvent::PreUnregister`] - the account's entries are deleted
+    /// - [`StorageManagementEvent::PreMigrate`] - the account's entries are moved to the new account
+    fn on_storage_management_event(event: &StorageManagementEvent) {
+        match event {
+            StorageManagementEvent::PreUnregister { account_id, .. } => {
+                Self::delete_all(account_id)
+            }
+            StorageManagementEvent::PreMigrate {
+                account_id,
+                new_account_id,
+            } => Self::migrate(account_id, new_account_id),
+            _ => {}
+        }
+    }
+
+    /// Applies the change to the account's entries and key index, and then tracks the storage usage
+    /// change against the account. The key index is deleted once the account has no keys.
+    fn update<F>(account_id: &str, f: F)
+    where
+        F: FnOnce(&AccountIdHash, &mut AccountKvIndex),
+    {
+        let account_id_hash = Self::account_id_hash(account_id);
+        let index_key = (ACCOUNT_KV_INDEX_KEY, account_id_hash);
+        let mut index = Self::load_index(&account_id_hash);
+        let storage_usage_before = env::storage_usage();
+        f(&account_id_hash, &mut index);
+        if index.keys.is_empty() {
+            IndexDAO::delete_by_key(&index_key);
+        } else {
+            IndexDAO::new(index_key, index.clone()).save();
+        }
+        let change = env::storage_usage() as i64 - storage_usage_before as i64;
+        if change == 0 {
+            return;
+        }
+        if !index.keys.is_empty() {
+            // the storage usage is a fixed size field - thus updating it does not change the storage usage
+            index.storage_usage = ((index.storage_usage.value() as i64 + change) as u64).into();
+            IndexDAO::new(index_key, index).save();
+        }
+        eventbus::post(&AccountStorageEvent::StorageUsageChanged(
+            account_id_hash,
+            change.into(),
+            COMPONENT_ID_ACCOUNT_KV_STORE,
+        ));
+    }
+
+    fn load_index(account_id_hash: &AccountIdHash) -> AccountKvIndex {
+        IndexDAO::load(&(ACCOUNT_KV_INDEX_KEY, *account_id_hash))
+            .map_or_else(AccountKvIndex::default, |index| (*index).clone())
+    }
+
+    fn entry_key(account_id_hash: &AccountIdHash, key: &str) -> (u128, AccountIdHash, String) {
+        (ACCOUNT_KV_ENTRY_KEY, *account_id_hash, key.to_string())
+    }
+
+    fn account_id_hash(account_id: &str) -> AccountIdHash {
+        AccountIdHash(Hash::from(account_id))
+    }
+}
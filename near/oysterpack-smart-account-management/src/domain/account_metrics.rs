@@ -1,27 +1,37 @@
-use crate::AccountStorageEvent;
+use crate::{
+    AccountStorageEvent, AccountStorageUsageBreakdown, ComponentId, StorageLedger,
+    COMPONENT_ID_ACCOUNT,
+};
 use oysterpack_smart_near::near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     serde::{Deserialize, Serialize},
 };
 
+use crate::components::account_storage_usage::AccountStorageUsageComponent;
 use crate::AccountNearDataObject;
 use lazy_static::lazy_static;
 use oysterpack_smart_near::{
+    component::Component,
     data::{numbers::U128, Object},
-    domain::{StorageUsage, YoctoNear},
+    domain::{StorageUsage, StorageUsageChange, YoctoNear},
     eventbus,
 };
+use std::collections::BTreeMap;
 use std::sync::Mutex;
 
 /// Account metrics
 #[derive(
-    BorshSerialize, BorshDeserialize, Deserialize, Serialize, Copy, Clone, Debug, PartialEq, Default,
+    BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Debug, PartialEq, Default,
 )]
 #[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
 pub struct AccountMetrics {
     pub total_registered_accounts: U128,
     pub total_near_balance: YoctoNear,
     pub total_storage_usage: StorageUsage,
+    /// [`AccountMetrics::total_storage_usage`] broken down by [`ComponentId`]
+    /// - persisted separately in order to keep the stored metrics layout stable
+    #[borsh_skip]
+    pub storage_usage_by_component: BTreeMap<String, StorageUsage>,
 }
 
 lazy_static! {
@@ -31,15 +41,53 @@ lazy_static! {
 const ACCOUNT_METRICS_KEY: u128 = 1952364736129901845182088441739779955;
 type DAO = Object<u128, AccountMetrics>;
 
+const STORAGE_USAGE_BY_COMPONENT_KEY: u128 = 1260256237047456432036585472814379121;
+type StorageUsageByComponentDAO = Object<u128, BTreeMap<String, StorageUsage>>;
+
 impl AccountMetrics {
     pub fn load() -> AccountMetrics {
         let stats = DAO::load(&ACCOUNT_METRICS_KEY)
             .unwrap_or_else(|| DAO::new(ACCOUNT_METRICS_KEY, AccountMetrics::default()));
-        *stats
+        let mut stats = (*stats).clone();
+        if let Some(storage_usage_by_component) =
+            StorageUsageByComponentDAO::load(&STORAGE_USAGE_BY_COMPONENT_KEY)
+        {
+            stats.storage_usage_by_component = (*storage_usage_by_component).clone();
+        }
+        stats
     }
 
     fn save(&self) {
-        DAO::new(ACCOUNT_METRICS_KEY, *self).save();
+        DAO::new(ACCOUNT_METRICS_KEY, self.clone()).save();
+        StorageUsageByComponentDAO::new(
+            STORAGE_USAGE_BY_COMPONENT_KEY,
+            self.storage_usage_by_component.clone(),
+        )
+        .save();
+    }
+
+    fn apply_storage_usage_change(&mut self, component: ComponentId, change: StorageUsageChange) {
+        if change.value() == 0 {
+            return;
+        }
+        if change.is_positive() {
+            self.total_storage_usage = self
+                .total_storage_usage
+                .checked_add(change.value() as u64)
+                .expect("total_storage_usage overflow")
+                .into();
+        } else {
+            self.total_storage_usage = self
+                .total_storage_usage
+                .checked_sub(change.value().abs() as u64)
+                .expect("total_storage_usage overflow")
+                .into();
+        }
+        AccountStorageUsageBreakdown::apply_change(
+            &mut self.storage_usage_by_component,
+            component,
+            change,
+        );
     }
 
     #[cfg(test)]
@@ -48,6 +96,7 @@ impl AccountMetrics {
         stats.total_storage_usage = 0.into();
         stats.total_near_balance = 0.into();
         stats.total_registered_accounts = 0.into();
+        stats.storage_usage_by_component.clear();
         stats.save();
     }
 
@@ -94,29 +143,35 @@ impl AccountMetrics {
                     .expect("total_near_balance overflow")
                     .into();
             }
-            AccountStorageEvent::StorageUsageChanged(account_id_hash, change) => {
+            AccountStorageEvent::StorageUsageChanged(account_id_hash, change, component) => {
                 if change.value() != 0 {
-                    if change.is_positive() {
-                        stats.total_storage_usage = stats
-                            .total_storage_usage
-                            .checked_add(change.value() as u64)
-                            .expect("total_storage_usage overflow")
-                            .into();
-                    } else {
-                        stats.total_storage_usage = stats
-                            .total_storage_usage
-                            .checked_sub(change.value().abs() as u64)
-                            .expect("total_storage_usage overflow")
-                            .into();
-                    }
+                    stats.apply_storage_usage_change(component, change);
 
                     if let Some(mut account) = AccountNearDataObject::load(account_id_hash) {
-                        if change.is_positive() {
-                            account.incr_storage_usage((change.value() as u64).into())
-                        } else {
-                            account.decr_storage_usage((change.value().abs() as u64).into())
+                        // the breakdown is saved directly instead of posting another event because
+                        // events cannot be posted from within an event handler
+                        let breakdown_change =
+                            AccountStorageUsageBreakdown::apply(account_id_hash, component, change);
+                        stats.apply_storage_usage_change(COMPONENT_ID_ACCOUNT, breakdown_change);
+
+                        let change = change.value() + breakdown_change.value();
+                        if change > 0 {
+                            account.incr_storage_usage((change as u64).into());
+                        } else if change < 0 {
+                            account.decr_storage_usage((change.abs() as u64).into())
                         }
                         account.save();
+                        // storage usage growth must be covered by the account's storage balance
+                        // - the check applies once the account storage usage bounds are deployed
+                        if change > 0 {
+                            if StorageLedger::is_deferred() {
+                                StorageLedger::record(account_id_hash);
+                            } else if AccountStorageUsageComponent::load_state().is_some() {
+                                AccountStorageUsageComponent::assert_storage_balance_coverage(
+                                    &account,
+                                );
+                            }
+                        }
                     }
                 }
             }
@@ -143,7 +198,7 @@ impl AccountMetrics {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::StorageBalance;
+    use crate::{StorageBalance, COMPONENT_ID_ACCOUNT_DATA};
     use near_sdk::test_utils;
     use oysterpack_smart_near::domain::StorageUsageChange;
     use oysterpack_smart_near::*;
@@ -200,31 +255,42 @@ mod test {
         assert_eq!(stats.total_storage_usage, account.storage_usage());
         let initial_account_storage_usage = account.storage_usage();
 
-        // Act - storage usage increase
+        // Act - storage usage increase while the storage balance coverage checks are deferred
+        StorageLedger::begin_deferral();
         eventbus::post(&AccountStorageEvent::StorageUsageChanged(
             account.key().account_id_hash(),
             1000_u64.into(),
+            COMPONENT_ID_ACCOUNT_DATA,
         ));
+        StorageLedger::end_deferral();
 
         // Assert
         let stats = AccountMetrics::load();
         assert_eq!(stats.total_registered_accounts, 1.into());
         assert_eq!(stats.total_near_balance, YOCTO.into());
+        let account = AccountNearDataObject::load(account_id).unwrap();
+        assert_eq!(stats.total_storage_usage, account.storage_usage());
         assert_eq!(
-            stats.total_storage_usage,
-            account.storage_usage() + 1000.into()
+            stats.storage_usage_by_component[COMPONENT_ID_ACCOUNT_DATA.name()],
+            1000.into()
         );
+        // the account is also charged for the new component entry in its storage usage breakdown
+        assert!(account.storage_usage() > initial_account_storage_usage + 1000.into());
+        assert!(StorageLedger::contains(account.key().account_id_hash()));
 
-        let account = AccountNearDataObject::load(account_id).unwrap();
+        let breakdown = AccountStorageUsageBreakdown::load(account_id);
+        assert_eq!(breakdown[COMPONENT_ID_ACCOUNT_DATA.name()], 1000.into());
         assert_eq!(
-            account.storage_usage(),
-            initial_account_storage_usage + 1000.into()
+            breakdown.values().map(|usage| usage.value()).sum::<u64>(),
+            account.storage_usage().value()
         );
+        assert_eq!(breakdown, stats.storage_usage_by_component);
 
         // Act - storage usage decrease
         eventbus::post(&AccountStorageEvent::StorageUsageChanged(
             account.key().account_id_hash(),
             StorageUsageChange(-1000),
+            COMPONENT_ID_ACCOUNT_DATA,
         ));
         let account = AccountNearDataObject::load(account_id).unwrap();
 
@@ -236,6 +302,8 @@ mod test {
 
         let account = AccountNearDataObject::load(account_id).unwrap();
         assert_eq!(account.storage_usage(), initial_account_storage_usage);
+        assert!(!AccountStorageUsageBreakdown::load(account_id)
+            .contains_key(COMPONENT_ID_ACCOUNT_DATA.name()));
 
         // Act - account unregistered
         eventbus::post(&AccountStorageEvent::Unregistered(YOCTO.into()));
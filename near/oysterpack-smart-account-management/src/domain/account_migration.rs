@@ -0,0 +1,63 @@
+use crate::{AccountIdHash, AccountStorageEvent, COMPONENT_ID_ACCOUNT_MIGRATION};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{data::Object, eventbus, Hash};
+
+/// Pending account migration, which was initiated by the account and is waiting to be accepted by
+/// the new account.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct AccountMigration {
+    pub account_id: AccountId,
+    pub new_account_id: AccountId,
+}
+
+const ACCOUNT_MIGRATION_KEY: u128 = 1957942138203563714309186538914276061;
+
+type DAO = Object<(u128, AccountIdHash), AccountMigration>;
+
+impl AccountMigration {
+    /// Returns the pending migration that was initiated by the account
+    pub fn load(account_id: &str) -> Option<Self> {
+        DAO::load(&Self::key(account_id)).map(|migration| (*migration).clone())
+    }
+
+    /// The storage is charged to the account that initiated the migration, i.e.,
+    /// [`AccountStorageEvent::StorageUsageChanged`] events are posted
+    /// - replaces any pending migration for the account
+    pub(crate) fn save(&self) {
+        let key = Self::key(&self.account_id);
+        Self::track_storage_usage(&key.1, || DAO::new(key, self.clone()).save());
+    }
+
+    pub(crate) fn delete(account_id: &str) {
+        let key = Self::key(account_id);
+        if DAO::exists(&key) {
+            Self::track_storage_usage(&key.1, || {
+                DAO::delete_by_key(&key);
+            });
+        }
+    }
+
+    fn key(account_id: &str) -> (u128, AccountIdHash) {
+        (ACCOUNT_MIGRATION_KEY, AccountIdHash(Hash::from(account_id)))
+    }
+
+    fn track_storage_usage<F: FnOnce()>(account_id_hash: &AccountIdHash, f: F) {
+        let storage_usage_before = env::storage_usage();
+        f();
+        let storage_usage_after = env::storage_usage();
+        if storage_usage_after != storage_usage_before {
+            let change = storage_usage_after as i64 - storage_usage_before as i64;
+            eventbus::post(&AccountStorageEvent::StorageUsageChanged(
+                *account_id_hash,
+                change.into(),
+                COMPONENT_ID_ACCOUNT_MIGRATION,
+            ));
+        }
+    }
+}
@@ -1,15 +1,17 @@
 use crate::{
-    AccountIdHash, AccountStorageEvent, Permissions, StorageBalance, ERR_ACCOUNT_NOT_REGISTERED,
+    AccountFreeze, AccountIdHash, AccountStorageEvent, AccountStorageUsageBreakdown, Permissions,
+    StorageBalance, COMPONENT_ID_ACCOUNT, ERR_ACCOUNT_NOT_REGISTERED,
 };
 use oysterpack_smart_near::near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    env,
+    env, AccountId,
 };
 use oysterpack_smart_near::{
     data::Object,
-    domain::{StorageUsage, YoctoNear},
+    domain::{BlockTime, Expiration, StorageUsage, YoctoNear},
     eventbus, Hash,
 };
+use std::collections::{BTreeMap, HashMap};
 use std::ops::{Deref, DerefMut};
 
 type DAO = Object<AccountNearDataHash, AccountNearData>;
@@ -66,31 +68,49 @@ impl AccountNearDataObject {
         }
         let event = if storage_usage_after_save > storage_usage_before_save {
             let storage_usage_change = storage_usage_after_save - storage_usage_before_save;
-            AccountStorageEvent::StorageUsageChanged(self.key().0, storage_usage_change.into())
+            AccountStorageEvent::StorageUsageChanged(
+                self.key().0,
+                storage_usage_change.into(),
+                COMPONENT_ID_ACCOUNT,
+            )
         } else {
             let storage_usage_change = storage_usage_before_save - storage_usage_after_save;
             AccountStorageEvent::StorageUsageChanged(
                 self.key().0,
                 (storage_usage_change as i64 * -1).into(),
+                COMPONENT_ID_ACCOUNT,
             )
         };
         eventbus::post(&event);
     }
 
     /// tracks storage usage - emits [`AccountStorageEvent::StorageUsageChanged`]
+    /// - the account's [`AccountStorageUsageBreakdown`] is deleted along with it
     pub fn delete(self) -> bool {
         let key = self.key().0;
         let storage_usage_before_save = env::storage_usage();
+        AccountStorageUsageBreakdown::delete(key);
         let result = self.0.delete();
         let storage_usage_deleted = storage_usage_before_save - env::storage_usage();
         if storage_usage_deleted > 0 {
             eventbus::post(&AccountStorageEvent::StorageUsageChanged(
                 key,
                 (storage_usage_deleted as i64 * -1).into(),
+                COMPONENT_ID_ACCOUNT,
             ))
         }
         result
     }
+
+    /// Returns an in memory copy of the account data keyed by the new account ID.
+    /// - the storage usage is reset to zero because it is tracked when the new object is saved
+    /// - accepting the migration counts as activity for the new account
+    pub(crate) fn migrate(&self, new_account_id: &str) -> Self {
+        let mut account = (***self).clone();
+        account.storage_usage = 0.into();
+        account.record_activity();
+        Self(DAO::new(new_account_id.into(), account))
+    }
 }
 
 impl Deref for AccountNearDataObject {
@@ -115,20 +135,119 @@ impl DerefMut for AccountNearDataObject {
 ///   for the contract. The last 2 permission bits are reserved by:
 ///   - [`crate::Permissions::ADMIN`]
 ///   - [`crate::Permissions::OPERATOR`]
-#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+/// - [`AccountNearData::permission_expirations`] - permissions can be granted for a limited time.
+///   Expired permissions are treated as if they were never granted.
+/// - [`AccountNearData::sponsors`] - tracks storage deposits made by other accounts on behalf of
+///   the account, which are refunded to the sponsors when the funds are withdrawn
+/// - [`AccountNearData::account_freeze`] - admins can freeze the account, which blocks the account
+///   from moving funds out of the contract
+/// - [`AccountNearData::last_active`] - used to identify inactive accounts that are eligible for
+///   garbage collection
+///
+/// ## Storage Layout
+/// Fields are only ever appended. Accounts that were stored in the baseline [`AccountNearDataV1`]
+/// layout are converted when they are loaded, and are persisted in the current layout the next time
+/// they are saved - see [`AccountNearData::migrate_v1`].
+#[derive(BorshSerialize, Clone, Debug, PartialEq)]
 pub struct AccountNearData {
     near_balance: YoctoNear,
     storage_usage: StorageUsage,
     permissions: Option<Permissions>,
+    /// permission bit -> expiration
+    permission_expirations: Option<HashMap<u8, Expiration>>,
+    /// sponsor account ID -> sponsored NEAR balance
+    sponsors: Option<BTreeMap<AccountId, YoctoNear>>,
+    account_freeze: Option<AccountFreeze>,
+    last_active: BlockTime,
+}
+
+/// Baseline [`AccountNearData`] storage layout
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct AccountNearDataV1 {
+    pub near_balance: YoctoNear,
+    pub storage_usage: StorageUsage,
+    pub permissions: Option<Permissions>,
+}
+
+/// fields that were appended to the [`AccountNearDataV1`] layout
+#[derive(BorshDeserialize)]
+struct AccountNearDataV2Fields {
+    permission_expirations: Option<HashMap<u8, Expiration>>,
+    sponsors: Option<BTreeMap<AccountId, YoctoNear>>,
+    account_freeze: Option<AccountFreeze>,
+    last_active: BlockTime,
+}
+
+impl BorshDeserialize for AccountNearData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let AccountNearDataV1 {
+            near_balance,
+            storage_usage,
+            permissions,
+        } = AccountNearDataV1::deserialize(buf)?;
+        if buf.is_empty() {
+            return Ok(Self {
+                near_balance,
+                storage_usage,
+                permissions,
+                permission_expirations: None,
+                sponsors: None,
+                account_freeze: None,
+                last_active: AccountNearData::v1_last_active(),
+            });
+        }
+        let AccountNearDataV2Fields {
+            permission_expirations,
+            sponsors,
+            account_freeze,
+            last_active,
+        } = AccountNearDataV2Fields::deserialize(buf)?;
+        Ok(Self {
+            near_balance,
+            storage_usage,
+            permissions,
+            permission_expirations,
+            sponsors,
+            account_freeze,
+            last_active,
+        })
+    }
 }
 
+/// when the contract was upgraded from the [`AccountNearDataV1`] layout
+type V1MigrationBlockTime = Object<u128, BlockTime>;
+
 impl AccountNearData {
+    const V1_MIGRATION_BLOCK_TIME_KEY: u128 = 1953035509579102406775126588391115274;
+
+    /// Must be invoked by the contract `migrate` function when upgrading from a contract version that
+    /// stored accounts in the [`AccountNearDataV1`] layout. It is idempotent.
+    ///
+    /// V1 accounts did not track activity. Records the upgrade block time, which is used as the
+    /// [`AccountNearData::last_active`] baseline for V1 accounts. Otherwise, they would appear to be
+    /// active on each load and could never become eligible for garbage collection.
+    pub fn migrate_v1() {
+        if !V1MigrationBlockTime::exists(&Self::V1_MIGRATION_BLOCK_TIME_KEY) {
+            V1MigrationBlockTime::new(Self::V1_MIGRATION_BLOCK_TIME_KEY, BlockTime::from_env())
+                .save();
+        }
+    }
+
+    fn v1_last_active() -> BlockTime {
+        V1MigrationBlockTime::load(&Self::V1_MIGRATION_BLOCK_TIME_KEY)
+            .map_or_else(BlockTime::from_env, |block_time| *block_time)
+    }
+
     /// constructor
     pub fn new(near_balance: YoctoNear, storage_usage: StorageUsage) -> Self {
         Self {
             near_balance,
             storage_usage,
             permissions: None,
+            permission_expirations: None,
+            sponsors: None,
+            account_freeze: None,
+            last_active: BlockTime::from_env(),
         }
     }
 
@@ -140,17 +259,78 @@ impl AccountNearData {
         self.storage_usage
     }
 
+    /// returns the account's active permissions, i.e., expired permissions are excluded
     pub fn permissions(&self) -> Option<Permissions> {
-        self.permissions
+        self.permissions.and_then(|mut permissions| {
+            permissions.revoke(self.expired_permissions());
+            if permissions.has_permissions() {
+                Some(permissions)
+            } else {
+                None
+            }
+        })
     }
 
+    /// returns expirations for permissions that were granted with an expiration
+    /// - permission bit -> expiration
+    /// - expired permissions are included until they are purged
+    pub fn permission_expirations(&self) -> Option<&HashMap<u8, Expiration>> {
+        self.permission_expirations.as_ref()
+    }
+
+    /// returns the expiration for the specified permission bit
+    /// - returns None if the permission was not granted with an expiration
+    pub fn permission_expiration(&self, perm_bit: u8) -> Option<Expiration> {
+        self.permission_expirations
+            .as_ref()
+            .and_then(|expirations| expirations.get(&perm_bit).cloned())
+    }
+
+    /// returns the permission bits that have expired
+    pub fn expired_permissions(&self) -> Permissions {
+        self.permission_expirations
+            .as_ref()
+            .map_or_else(Default::default, |expirations| {
+                expirations
+                    .iter()
+                    .filter(|(_, expiration)| expiration.expired())
+                    .fold(0_u64, |permissions, (perm_bit, _)| {
+                        permissions | 1 << *perm_bit
+                    })
+                    .into()
+            })
+    }
+
+    /// removes permissions that have expired
+    ///
+    /// Returns the permissions that were purged.
+    pub fn purge_expired_permissions(&mut self) -> Permissions {
+        let expired_permissions = self.expired_permissions();
+        if expired_permissions.has_permissions() {
+            self.revoke(expired_permissions);
+        }
+        expired_permissions
+    }
+
+    /// The locked storage balance is the greater of the required min storage balance and the cost
+    /// of the account's actual storage usage - the rest is available.
     pub fn storage_balance(&self, required_min_storage_balance: YoctoNear) -> StorageBalance {
+        let locked_balance = required_min_storage_balance.max(self.storage_usage_cost());
         StorageBalance {
             total: self.near_balance,
-            available: (self.near_balance.value() - required_min_storage_balance.value()).into(),
+            available: self
+                .near_balance
+                .value()
+                .saturating_sub(locked_balance.value())
+                .into(),
         }
     }
 
+    /// the NEAR balance required to pay for the account's storage usage at the current storage byte cost
+    pub fn storage_usage_cost(&self) -> YoctoNear {
+        (env::storage_byte_cost() * self.storage_usage.value() as u128).into()
+    }
+
     /// Posts [`AccountStorageEvent::Deposit`] event
     ///
     /// ## Panics
@@ -186,6 +366,113 @@ impl AccountNearData {
         *self.near_balance = amount.value();
     }
 
+    /// returns the account freeze if it is in effect, i.e., expired freezes are excluded
+    pub fn account_freeze(&self) -> Option<&AccountFreeze> {
+        self.account_freeze
+            .as_ref()
+            .filter(|account_freeze| !account_freeze.expired())
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.account_freeze().is_some()
+    }
+
+    /// replaces any existing freeze
+    pub fn freeze(&mut self, account_freeze: AccountFreeze) {
+        self.account_freeze = Some(account_freeze);
+    }
+
+    /// Returns false if the account was not frozen, which includes expired freezes
+    pub fn unfreeze(&mut self) -> bool {
+        let frozen = self.is_frozen();
+        self.account_freeze = None;
+        frozen
+    }
+
+    /// when the account was registered or last initiated activity, e.g., moved funds
+    pub fn last_active(&self) -> BlockTime {
+        self.last_active
+    }
+
+    /// updates [`AccountNearData::last_active`] to the current block
+    pub fn record_activity(&mut self) {
+        self.last_active = BlockTime::from_env();
+    }
+
+    /// number of epochs that have passed since the account was last active
+    pub fn idle_epochs(&self) -> u64 {
+        env::epoch_height().saturating_sub(self.last_active.epoch.value())
+    }
+
+    /// sponsor account ID -> sponsored NEAR balance
+    pub fn sponsors(&self) -> Option<&BTreeMap<AccountId, YoctoNear>> {
+        self.sponsors.as_ref()
+    }
+
+    /// Returns the portion of the NEAR balance that was deposited by sponsors.
+    ///
+    /// Sponsor contributions are capped at the account NEAR balance because the NEAR balance may be
+    /// debited by other means, e.g., staking the account's available storage balance.
+    pub fn sponsored_balance(&self) -> YoctoNear {
+        self.sponsors
+            .as_ref()
+            .map_or(YoctoNear::ZERO, |sponsors| {
+                sponsors
+                    .values()
+                    .fold(YoctoNear::ZERO, |total, amount| total + *amount)
+            })
+            .min(self.near_balance)
+    }
+
+    /// NOTE: the NEAR balance is not changed - the deposit is expected to be credited separately
+    pub(crate) fn credit_sponsor(&mut self, sponsor: &str, amount: YoctoNear) {
+        if amount == YoctoNear::ZERO {
+            return;
+        }
+        let mut sponsors = self.sponsors.take().unwrap_or_default();
+        let balance = sponsors
+            .entry(sponsor.to_string())
+            .or_insert(YoctoNear::ZERO);
+        *balance += amount;
+        self.sponsors = Some(sponsors);
+    }
+
+    /// Debits the specified amount from the sponsor balances, in sponsor account ID order.
+    ///
+    /// Returns the amounts debited per sponsor, which are expected to be refunded to the sponsors.
+    /// - the NEAR balance is not changed - the refunds are expected to be debited separately
+    pub(crate) fn debit_sponsors(&mut self, amount: YoctoNear) -> Vec<(AccountId, YoctoNear)> {
+        let mut refunds = vec![];
+        if let Some(mut sponsors) = self.sponsors.take() {
+            let mut remaining = amount;
+            for (sponsor, balance) in sponsors.iter_mut() {
+                if remaining == YoctoNear::ZERO {
+                    break;
+                }
+                let refund = remaining.min(*balance);
+                *balance -= refund;
+                remaining -= refund;
+                refunds.push((sponsor.clone(), refund));
+            }
+            sponsors.retain(|_, balance| *balance > YoctoNear::ZERO);
+            if !sponsors.is_empty() {
+                self.sponsors = Some(sponsors);
+            }
+        }
+        refunds
+    }
+
+    /// transfers the sponsored balance to the sponsor's new account ID
+    pub(crate) fn replace_sponsor(&mut self, sponsor: &str, new_sponsor: &str) {
+        if let Some(sponsors) = self.sponsors.as_mut() {
+            if let Some(balance) = sponsors.remove(sponsor) {
+                *sponsors
+                    .entry(new_sponsor.to_string())
+                    .or_insert(YoctoNear::ZERO) += balance;
+            }
+        }
+    }
+
     /// ## Panics
     /// if overflow occurs
     pub(crate) fn incr_storage_usage(&mut self, amount: StorageUsage) {
@@ -203,7 +490,7 @@ impl AccountNearData {
     }
 
     pub fn is_admin(&self) -> bool {
-        self.permissions.map_or(false, |permissions| {
+        self.permissions().map_or(false, |permissions| {
             permissions.contains(Permissions::ADMIN)
         })
     }
@@ -212,6 +499,7 @@ impl AccountNearData {
         let mut permissions = self.permissions.take().unwrap_or_else(Default::default);
         permissions.grant(Permissions::ADMIN);
         self.permissions = Some(permissions);
+        self.set_permissions_expiration(Permissions::ADMIN.into(), None);
     }
 
     pub fn revoke_admin(&mut self) {
@@ -219,18 +507,26 @@ impl AccountNearData {
             permissions.revoke(Permissions::ADMIN);
             self.permissions = Some(permissions);
         }
+        self.set_permissions_expiration(Permissions::ADMIN.into(), None);
     }
 
     pub fn is_operator(&self) -> bool {
-        self.permissions.map_or(false, |permissions| {
+        self.permissions().map_or(false, |permissions| {
             permissions.contains(Permissions::OPERATOR) || permissions.contains(Permissions::ADMIN)
         })
     }
 
     pub fn grant_operator(&mut self) {
+        self.grant_operator_with_expiration(None);
+    }
+
+    /// if expiration is specified, then the operator permission will be automatically revoked when
+    /// it expires
+    pub fn grant_operator_with_expiration(&mut self, expiration: Option<Expiration>) {
         let mut permissions = self.permissions.take().unwrap_or_else(Default::default);
         permissions.grant(Permissions::OPERATOR);
         self.permissions = Some(permissions);
+        self.set_permissions_expiration(Permissions::OPERATOR.into(), expiration);
     }
 
     pub fn revoke_operator(&mut self) {
@@ -238,12 +534,21 @@ impl AccountNearData {
             permissions.revoke(Permissions::OPERATOR);
             self.permissions = Some(permissions);
         }
+        self.set_permissions_expiration(Permissions::OPERATOR.into(), None);
     }
 
     pub fn grant(&mut self, access: Permissions) {
+        self.grant_with_expiration(access, None);
+    }
+
+    /// if expiration is specified, then the permissions will be automatically revoked when they
+    /// expire
+    /// - re-granting permissions replaces any previous expiration
+    pub fn grant_with_expiration(&mut self, access: Permissions, expiration: Option<Expiration>) {
         let mut permissions = self.permissions.take().unwrap_or_else(Default::default);
         permissions.grant(access);
         self.permissions = Some(permissions);
+        self.set_permissions_expiration(access, expiration);
     }
 
     pub fn revoke(&mut self, access: Permissions) {
@@ -255,18 +560,39 @@ impl AccountNearData {
                 self.permissions = None;
             }
         }
+        self.set_permissions_expiration(access, None);
     }
 
     pub fn revoke_all(&mut self) {
         self.permissions = None;
+        self.permission_expirations = None;
     }
 
     /// returns true if the account has all of the specified permissions
+    /// - expired permissions are treated as absent
     pub fn contains_permissions(&self, permissions: Permissions) -> bool {
-        self.permissions.map_or(false, |perms| {
+        self.permissions().map_or(false, |perms| {
             perms.contains(permissions) || perms.contains(Permissions::ADMIN)
         })
     }
+
+    /// if `expiration` is None, then any expirations for the specified permission bits are cleared
+    fn set_permissions_expiration(
+        &mut self,
+        permissions: Permissions,
+        expiration: Option<Expiration>,
+    ) {
+        let mut expirations = self.permission_expirations.take().unwrap_or_default();
+        for perm_bit in permissions.permission_bits() {
+            match expiration {
+                Some(expiration) => expirations.insert(perm_bit, expiration),
+                None => expirations.remove(&perm_bit),
+            };
+        }
+        if !expirations.is_empty() {
+            self.permission_expirations = Some(expirations);
+        }
+    }
 }
 
 type AccountNearDataKey = u128;
@@ -302,8 +628,52 @@ mod tests {
     use oysterpack_smart_near::YOCTO;
     use oysterpack_smart_near_test::*;
 
+    #[test]
+    fn load_v1_account() {
+        let mut ctx = new_context("bob");
+        ctx.epoch_height = 10;
+        testing_env!(ctx.clone());
+        let account_id: AccountNearDataHash = "bob".into();
+        Object::<AccountNearDataHash, AccountNearDataV1>::new(
+            account_id,
+            AccountNearDataV1 {
+                near_balance: YOCTO.into(),
+                storage_usage: 100.into(),
+                permissions: Some(Permissions::OPERATOR.into()),
+            },
+        )
+        .save();
+
+        ctx.epoch_height = 20;
+        testing_env!(ctx.clone());
+        AccountNearData::migrate_v1();
+        let migrated_on = BlockTime::from_env();
+
+        ctx.epoch_height = 30;
+        testing_env!(ctx.clone());
+        // migrating is idempotent
+        AccountNearData::migrate_v1();
+        let mut account = AccountNearDataObject::load("bob").unwrap();
+        assert_eq!(account.near_balance(), YOCTO.into());
+        assert_eq!(account.storage_usage(), 100.into());
+        assert!(account.is_operator());
+        assert!(account.permission_expirations().is_none());
+        assert!(account.sponsors().is_none());
+        assert!(!account.is_frozen());
+        assert_eq!(account.last_active(), migrated_on);
+        assert_eq!(account.idle_epochs(), 10);
+
+        // once saved, the account is stored in the current layout
+        account.record_activity();
+        account.save();
+        let account = AccountNearDataObject::load("bob").unwrap();
+        assert_eq!(account.last_active(), BlockTime::from_env());
+        assert!(account.is_operator());
+    }
+
     #[test]
     fn bitflags() {
+        testing_env!(new_context("bob"));
         let mut account = AccountNearData::new(YoctoNear::ZERO, 0.into());
 
         assert!(!account.is_admin());
@@ -319,6 +689,43 @@ mod tests {
         assert!(!account.is_operator());
     }
 
+    #[test]
+    fn expiring_permissions() {
+        let mut ctx = new_context("bob");
+        ctx.block_index = 100;
+        testing_env!(ctx.clone());
+
+        let mut account = AccountNearData::new(YoctoNear::ZERO, 0.into());
+        account.grant_operator_with_expiration(Some(Expiration::Block(100.into())));
+        account.grant_with_expiration((1 << 5).into(), Some(Expiration::Block(200.into())));
+        assert!(account.is_operator());
+        assert!(account.contains_permissions((1 << 5).into()));
+        assert!(!account.expired_permissions().has_permissions());
+
+        // operator permission expires
+        ctx.block_index = 101;
+        testing_env!(ctx.clone());
+        assert!(!account.is_operator());
+        assert!(account.contains_permissions((1 << 5).into()));
+        assert_eq!(account.expired_permissions(), Permissions::OPERATOR.into());
+        assert_eq!(account.permissions().unwrap(), (1 << 5).into());
+
+        assert_eq!(
+            account.purge_expired_permissions(),
+            Permissions::OPERATOR.into()
+        );
+        assert!(account.permission_expiration(62).is_none());
+        assert_eq!(account.permission_expirations().unwrap().len(), 1);
+
+        // granting without an expiration clears the expiration
+        account.grant((1 << 5).into());
+        assert!(account.permission_expirations().is_none());
+
+        ctx.block_index = 201;
+        testing_env!(ctx.clone());
+        assert!(account.contains_permissions((1 << 5).into()));
+    }
+
     #[test]
     fn update_near_balance() {
         // Arrange
@@ -0,0 +1,155 @@
+use crate::{AccountIdHash, AccountStorageEvent, COMPONENT_ID_ACCOUNT_REGISTRY};
+use oysterpack_smart_near::near_sdk::{env, AccountId};
+use oysterpack_smart_near::{data::Object, eventbus, Hash};
+
+const ACCOUNT_REGISTRY_COUNT_KEY: u128 = 1958118370514937162830582145935734131;
+const ACCOUNT_REGISTRY_ENTRY_KEY: u128 = 1958118412848357019645167231264385405;
+const ACCOUNT_REGISTRY_INDEX_KEY: u128 = 1958118447015625467936232779546838249;
+
+type CountDAO = Object<u128, u64>;
+type EntryDAO = Object<(u128, u64), AccountId>;
+type IndexDAO = Object<(u128, AccountIdHash), u64>;
+
+/// Indexes registered accounts by position, which enables the registered accounts to be scanned in
+/// pages, e.g., for garbage collection.
+///
+/// Entries are kept contiguous - when an account is removed, the last entry is moved into the removed
+/// account's position. Thus, account positions are not stable.
+///
+/// The index entries are charged to the account's storage usage, i.e.,
+/// [`AccountStorageEvent::StorageUsageChanged`] events are posted.
+pub struct AccountRegistry;
+
+impl AccountRegistry {
+    /// number of registered accounts
+    pub fn count() -> u64 {
+        CountDAO::load(&ACCOUNT_REGISTRY_COUNT_KEY).map_or(0, |count| *count)
+    }
+
+    /// returns the account at the specified position
+    pub fn get(index: u64) -> Option<AccountId> {
+        EntryDAO::load(&(ACCOUNT_REGISTRY_ENTRY_KEY, index)).map(|account_id| (*account_id).clone())
+    }
+
+    /// returns the account's position in the registry
+    pub fn index_of(account_id: &str) -> Option<u64> {
+        IndexDAO::load(&Self::index_key(account_id)).map(|index| *index)
+    }
+
+    /// Returns accounts in position order starting with the specified position
+    pub fn accounts(from_index: u64, limit: u64) -> Vec<AccountId> {
+        let to_index = Self::count().min(from_index.saturating_add(limit));
+        (from_index..to_index).filter_map(Self::get).collect()
+    }
+
+    /// NOTE: the account is expected to be registered, i.e., its [`crate::AccountNearDataObject`]
+    /// has been saved, because the index storage is charged to the account
+    pub(crate) fn add(account_id: &str) {
+        let key = Self::index_key(account_id);
+        if IndexDAO::exists(&key) {
+            return;
+        }
+        let index = Self::count();
+        Self::track_storage_usage(&key.1, || {
+            EntryDAO::new((ACCOUNT_REGISTRY_ENTRY_KEY, index), account_id.to_string()).save();
+            IndexDAO::new(key, index).save();
+        });
+        CountDAO::new(ACCOUNT_REGISTRY_COUNT_KEY, index + 1).save();
+    }
+
+    /// NOTE: the account is expected to still be registered, i.e., this should be invoked before
+    /// its [`crate::AccountNearDataObject`] is deleted
+    pub(crate) fn remove(account_id: &str) {
+        let key = Self::index_key(account_id);
+        let index = match IndexDAO::load(&key) {
+            Some(index) => *index,
+            None => return,
+        };
+        let last_index = Self::count() - 1;
+        // the moved entry's storage usage does not change because the account ID value is simply
+        // moved to a different key of the same size - thus the storage that is freed belongs to the
+        // removed account
+        Self::track_storage_usage(&key.1, || {
+            IndexDAO::delete_by_key(&key);
+            if index != last_index {
+                let last_account_id = Self::get(last_index).unwrap();
+                EntryDAO::new((ACCOUNT_REGISTRY_ENTRY_KEY, index), last_account_id.clone()).save();
+                IndexDAO::new(Self::index_key(&last_account_id), index).save();
+            }
+            EntryDAO::delete_by_key(&(ACCOUNT_REGISTRY_ENTRY_KEY, last_index));
+        });
+        CountDAO::new(ACCOUNT_REGISTRY_COUNT_KEY, last_index).save();
+    }
+
+    fn index_key(account_id: &str) -> (u128, AccountIdHash) {
+        (
+            ACCOUNT_REGISTRY_INDEX_KEY,
+            AccountIdHash(Hash::from(account_id)),
+        )
+    }
+
+    fn track_storage_usage<F: FnOnce()>(account_id_hash: &AccountIdHash, f: F) {
+        let storage_usage_before = env::storage_usage();
+        f();
+        let storage_usage_after = env::storage_usage();
+        if storage_usage_after != storage_usage_before {
+            let change = storage_usage_after as i64 - storage_usage_before as i64;
+            eventbus::post(&AccountStorageEvent::StorageUsageChanged(
+                *account_id_hash,
+                change.into(),
+                COMPONENT_ID_ACCOUNT_REGISTRY,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oysterpack_smart_near_test::*;
+
+    #[test]
+    fn add_and_remove() {
+        let ctx = new_context("admin");
+        testing_env!(ctx);
+
+        assert_eq!(AccountRegistry::count(), 0);
+        assert!(AccountRegistry::accounts(0, 10).is_empty());
+
+        for account_id in ["alice", "bob", "charlie", "dave"].iter() {
+            AccountRegistry::add(account_id);
+        }
+        // adding a registered account is a no-op
+        AccountRegistry::add("bob");
+        assert_eq!(AccountRegistry::count(), 4);
+        assert_eq!(
+            AccountRegistry::accounts(0, 10),
+            vec!["alice", "bob", "charlie", "dave"]
+        );
+        assert_eq!(AccountRegistry::accounts(1, 2), vec!["bob", "charlie"]);
+        assert_eq!(AccountRegistry::index_of("charlie"), Some(2));
+
+        // the last account is moved into the removed account's position
+        AccountRegistry::remove("bob");
+        assert_eq!(AccountRegistry::count(), 3);
+        assert_eq!(
+            AccountRegistry::accounts(0, 10),
+            vec!["alice", "dave", "charlie"]
+        );
+        assert_eq!(AccountRegistry::index_of("dave"), Some(1));
+        assert!(AccountRegistry::index_of("bob").is_none());
+
+        // removing the last account
+        AccountRegistry::remove("charlie");
+        assert_eq!(AccountRegistry::accounts(0, 10), vec!["alice", "dave"]);
+
+        // removing an account that is not registered is a no-op
+        AccountRegistry::remove("bob");
+        assert_eq!(AccountRegistry::count(), 2);
+
+        AccountRegistry::remove("alice");
+        AccountRegistry::remove("dave");
+        assert_eq!(AccountRegistry::count(), 0);
+        assert!(AccountRegistry::get(0).is_none());
+    }
+}
@@ -14,8 +14,8 @@ pub enum AccountStorageEvent {
     Deposit(YoctoNear),
     /// an account made a withdrawal from its storage available balance
     Withdrawal(YoctoNear),
-    /// account storage usage changed
-    StorageUsageChanged(AccountIdHash, StorageUsageChange),
+    /// account storage usage changed for the specified component
+    StorageUsageChanged(AccountIdHash, StorageUsageChange, ComponentId),
     /// an account was unregistered
     /// - its NEAR balance was refunded
     Unregistered(YoctoNear),
@@ -24,12 +24,45 @@ pub enum AccountStorageEvent {
 impl Display for AccountStorageEvent {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            AccountStorageEvent::StorageUsageChanged(_, change) => write!(f, "{:?}", change),
+            AccountStorageEvent::StorageUsageChanged(_, change, _) => write!(f, "{:?}", change),
             _ => write!(f, "{:?}", self),
         }
     }
 }
 
+/// Identifies the component that owns the account storage referenced by
+/// [`AccountStorageEvent::StorageUsageChanged`] events, which is used to break down storage usage
+/// by component - see [`crate::AccountStorageUsageBreakdown`]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ComponentId(pub &'static str);
+
+impl ComponentId {
+    pub fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Display for ComponentId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// [`crate::AccountNearDataObject`] and [`crate::AccountStorageUsageBreakdown`] storage
+pub const COMPONENT_ID_ACCOUNT: ComponentId = ComponentId("account");
+/// [`crate::AccountDataObject`] storage
+pub const COMPONENT_ID_ACCOUNT_DATA: ComponentId = ComponentId("account_data");
+/// [`crate::AccountRegistry`] storage
+pub const COMPONENT_ID_ACCOUNT_REGISTRY: ComponentId = ComponentId("account_registry");
+/// [`crate::AccountDelegations`] storage
+pub const COMPONENT_ID_ACCOUNT_DELEGATIONS: ComponentId = ComponentId("account_delegations");
+/// [`crate::AccountMigration`] storage
+pub const COMPONENT_ID_ACCOUNT_MIGRATION: ComponentId = ComponentId("account_migration");
+/// [`crate::AccountKvStore`] storage
+pub const COMPONENT_ID_ACCOUNT_KV_STORE: ComponentId = ComponentId("account_kv_store");
+/// [`crate::StorageTokenCredit`] storage
+pub const COMPONENT_ID_STORAGE_TOKEN_CREDIT: ComponentId = ComponentId("storage_token_credit");
+
 /// log event for [`AccountStorageEvent`]
 pub const LOG_EVENT_ACCOUNT_STORAGE_CHANGED: LogEvent =
     LogEvent(Level::INFO, "ACCOUNT_STORAGE_CHANGED");
@@ -0,0 +1,119 @@
+use crate::{AccountIdHash, ComponentId, COMPONENT_ID_ACCOUNT};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    serde::{Deserialize, Serialize},
+};
+use oysterpack_smart_near::{
+    data::Object,
+    domain::{StorageUsage, StorageUsageChange, YoctoNear},
+};
+use std::collections::BTreeMap;
+
+const ACCOUNT_STORAGE_USAGE_BREAKDOWN_KEY: u128 = 1904470472417316215068518653462076245;
+
+type DAO = Object<(u128, AccountIdHash), BTreeMap<String, StorageUsage>>;
+
+/// Account storage usage broken down by [`ComponentId`]
+///
+/// ## Notes
+/// - maintained by the [`crate::AccountMetrics`] account storage event handler, i.e., it is only
+///   tracked for registered accounts
+/// - the breakdown's own storage usage is charged to the account under [`COMPONENT_ID_ACCOUNT`],
+///   thus the component storage usages add up to the account's storage usage
+pub struct AccountStorageUsageBreakdown;
+
+impl AccountStorageUsageBreakdown {
+    pub fn load(account_id: &str) -> BTreeMap<String, StorageUsage> {
+        Self::load_by_hash(account_id.into())
+    }
+
+    pub(crate) fn load_by_hash(account_id_hash: AccountIdHash) -> BTreeMap<String, StorageUsage> {
+        DAO::load(&(ACCOUNT_STORAGE_USAGE_BREAKDOWN_KEY, account_id_hash))
+            .map_or_else(BTreeMap::new, |breakdown| (*breakdown).clone())
+    }
+
+    /// applies the component storage usage change to the account's breakdown
+    ///
+    /// Returns the storage usage change for persisting the breakdown itself, which has been charged
+    /// to [`COMPONENT_ID_ACCOUNT`]
+    pub(crate) fn apply(
+        account_id_hash: AccountIdHash,
+        component: ComponentId,
+        change: StorageUsageChange,
+    ) -> StorageUsageChange {
+        let mut breakdown = Self::load_by_hash(account_id_hash);
+        Self::apply_change(&mut breakdown, component, change);
+        // components that no longer use any storage are removed from the account's breakdown
+        if breakdown
+            .get(component.name())
+            .map_or(false, |storage_usage| storage_usage.value() == 0)
+        {
+            breakdown.remove(component.name());
+        }
+        // the account entry is always present, which means that charging the breakdown's own
+        // storage usage to it below does not change the breakdown's size
+        breakdown
+            .entry(COMPONENT_ID_ACCOUNT.to_string())
+            .or_default();
+
+        let key = (ACCOUNT_STORAGE_USAGE_BREAKDOWN_KEY, account_id_hash);
+        let storage_usage_before_save = env::storage_usage();
+        DAO::new(key, breakdown.clone()).save();
+        let self_change: StorageUsageChange =
+            (env::storage_usage() as i64 - storage_usage_before_save as i64).into();
+        if self_change.value() != 0 {
+            Self::apply_change(&mut breakdown, COMPONENT_ID_ACCOUNT, self_change);
+            DAO::new(key, breakdown).save();
+        }
+        self_change
+    }
+
+    /// Returns the amount of storage that was freed
+    /// - storage usage is not tracked, i.e., the caller is responsible for tracking it
+    pub(crate) fn delete(account_id_hash: AccountIdHash) -> StorageUsage {
+        let storage_usage_before_delete = env::storage_usage();
+        DAO::delete_by_key(&(ACCOUNT_STORAGE_USAGE_BREAKDOWN_KEY, account_id_hash));
+        (storage_usage_before_delete - env::storage_usage()).into()
+    }
+
+    pub(crate) fn apply_change(
+        breakdown: &mut BTreeMap<String, StorageUsage>,
+        component: ComponentId,
+        change: StorageUsageChange,
+    ) {
+        if change.value() == 0 {
+            return;
+        }
+        let storage_usage = breakdown.entry(component.to_string()).or_default();
+        *storage_usage = if change.is_positive() {
+            storage_usage
+                .checked_add(change.value() as u64)
+                .expect("component storage usage overflow")
+                .into()
+        } else {
+            storage_usage
+                .saturating_sub(change.value().abs() as u64)
+                .into()
+        };
+    }
+}
+
+/// Component storage usage and its storage staking cost
+#[derive(
+    BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Default,
+)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ComponentStorageUsage {
+    pub storage_usage: StorageUsage,
+    pub cost: YoctoNear,
+}
+
+impl From<StorageUsage> for ComponentStorageUsage {
+    fn from(storage_usage: StorageUsage) -> Self {
+        Self {
+            storage_usage,
+            cost: storage_usage.cost(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{data::Object, domain::BlockTime};
+
+/// Records a privileged call that changed contract state
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct AuditLogEntry {
+    /// sequence number - starts at 0 and is incremented for each entry that is recorded
+    pub id: U64,
+    /// predecessor account that invoked the call
+    pub actor: AccountId,
+    /// contract function name
+    pub action: String,
+    /// account that the action was applied to
+    pub target: Option<AccountId>,
+    /// JSON encoded args
+    pub args: Option<String>,
+    pub block_time: BlockTime,
+}
+
+const AUDIT_LOG_COUNT_KEY: u128 = 1957541230582611716449322937434578944;
+const AUDIT_LOG_ENTRY_KEY: u128 = 1957541287714101582378069722163411527;
+
+type CountDAO = Object<u128, u64>;
+type EntryDAO = Object<(u128, u64), AuditLogEntry>;
+
+/// Bounded ring buffer audit log that is stored in contract state.
+///
+/// Once the log is at capacity, the oldest entry is overwritten by each new entry. Each entry is
+/// stored in its own slot, which keeps the storage cost of recording an entry constant.
+pub struct AuditLog;
+
+impl AuditLog {
+    /// max number of entries that are retained
+    pub const CAPACITY: u64 = 100;
+
+    /// Records the entry, where the predecessor account is the actor
+    pub fn record(action: &str, target: Option<&str>, args: Option<String>) {
+        let id = Self::count();
+        let entry = AuditLogEntry {
+            id: id.into(),
+            actor: env::predecessor_account_id(),
+            action: action.to_string(),
+            target: target.map(str::to_string),
+            args,
+            block_time: BlockTime::from_env(),
+        };
+        EntryDAO::new((AUDIT_LOG_ENTRY_KEY, id % Self::CAPACITY), entry).save();
+        CountDAO::new(AUDIT_LOG_COUNT_KEY, id + 1).save();
+    }
+
+    /// total number of entries that have been recorded, which includes entries that have been
+    /// overwritten
+    pub fn count() -> u64 {
+        CountDAO::load(&AUDIT_LOG_COUNT_KEY).map_or(0, |count| *count)
+    }
+
+    /// ID of the oldest entry that is retained
+    pub fn oldest_id() -> u64 {
+        Self::count().saturating_sub(Self::CAPACITY)
+    }
+
+    /// Returns entries in ascending ID order starting with the specified ID.
+    /// - if the entry for `from_id` has been overwritten, then entries are returned starting with
+    ///   the oldest retained entry
+    pub fn entries(from_id: u64, limit: u64) -> Vec<AuditLogEntry> {
+        let from_id = from_id.max(Self::oldest_id());
+        let to_id = Self::count().min(from_id.saturating_add(limit));
+        (from_id..to_id)
+            .filter_map(|id| EntryDAO::load(&(AUDIT_LOG_ENTRY_KEY, id % Self::CAPACITY)))
+            .map(|entry| (*entry).clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oysterpack_smart_near_test::*;
+
+    #[test]
+    fn ring_buffer() {
+        let ctx = new_context("admin");
+        testing_env!(ctx);
+
+        assert_eq!(AuditLog::count(), 0);
+        assert!(AuditLog::entries(0, 10).is_empty());
+
+        AuditLog::record("ops_permissions_grant_admin", Some("bob"), None);
+        let entries = AuditLog::entries(0, 10);
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.id, 0.into());
+        assert_eq!(entry.actor, "admin");
+        assert_eq!(entry.action, "ops_permissions_grant_admin");
+        assert_eq!(entry.target, Some("bob".to_string()));
+        assert!(entry.args.is_none());
+
+        for i in 1..(AuditLog::CAPACITY + 10) {
+            AuditLog::record("action", None, Some(i.to_string()));
+        }
+        assert_eq!(AuditLog::count(), AuditLog::CAPACITY + 10);
+        assert_eq!(AuditLog::oldest_id(), 10);
+
+        // overwritten entries are skipped
+        let entries = AuditLog::entries(0, 5);
+        assert_eq!(
+            entries.iter().map(|entry| entry.id.0).collect::<Vec<_>>(),
+            vec![10, 11, 12, 13, 14]
+        );
+        assert_eq!(entries[0].args, Some("10".to_string()));
+
+        let entries = AuditLog::entries(AuditLog::count() - 2, 5);
+        assert_eq!(
+            entries.iter().map(|entry| entry.id.0).collect::<Vec<_>>(),
+            vec![AuditLog::CAPACITY + 8, AuditLog::CAPACITY + 9]
+        );
+        assert!(AuditLog::entries(AuditLog::count(), 5).is_empty());
+    }
+}
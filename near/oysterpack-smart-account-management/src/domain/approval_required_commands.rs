@@ -0,0 +1,95 @@
+use crate::ERR_APPROVAL_REQUIRED;
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    serde::{Deserialize, Serialize},
+};
+
+const APPROVAL_REQUIRED_COMMANDS_KEY: u128 = 1959302117838616426531907232215917539;
+
+type DAO = Object<u128, ApprovalRequiredCommands>;
+
+/// Owner, admin, and operator commands that must be submitted through an approved proposal, e.g., a
+/// multisig proposal. Approved proposals are executed as function calls against the contract itself,
+/// i.e., the contract account is the predecessor.
+///
+/// Commands are identified by the contract method name. The check is applied by the shared
+/// permission assertions - see [`crate::AccountRepository::assert_admin`] and
+/// [`crate::AccountRepository::assert_operator`] - and by the contract owner access assertion for
+/// owner commands that can be submitted as proposals.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub enum ApprovalRequiredCommands {
+    /// every owner, admin, and operator command requires approval
+    All,
+    /// only the listed commands require approval
+    Commands(Vec<String>),
+}
+
+impl ApprovalRequiredCommands {
+    pub fn load() -> Option<Self> {
+        DAO::load(&APPROVAL_REQUIRED_COMMANDS_KEY).map(|commands| (*commands).clone())
+    }
+
+    pub fn save(self) {
+        DAO::new(APPROVAL_REQUIRED_COMMANDS_KEY, self).save();
+    }
+
+    pub fn clear() {
+        DAO::delete_by_key(&APPROVAL_REQUIRED_COMMANDS_KEY);
+    }
+
+    pub fn requires_approval(&self, command: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Commands(commands) => commands.iter().any(|cmd| cmd == command),
+        }
+    }
+
+    /// ## Panics
+    /// [`ERR_APPROVAL_REQUIRED`] - if the command requires approval and the predecessor account is
+    /// not the contract account
+    pub fn assert_approved(command: &str) {
+        if Self::load().map_or(false, |commands| commands.requires_approval(command)) {
+            ERR_APPROVAL_REQUIRED.assert_with_message(
+                || env::predecessor_account_id() == env::current_account_id(),
+                || command,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oysterpack_smart_near_test::*;
+
+    #[test]
+    fn assert_approved() {
+        let mut ctx = new_context("admin");
+        ctx.current_account_id = "contract".to_string();
+        testing_env!(ctx.clone());
+        ApprovalRequiredCommands::assert_approved("ops_permissions_grant");
+
+        ApprovalRequiredCommands::Commands(vec!["ops_permissions_grant".to_string()]).save();
+        ApprovalRequiredCommands::assert_approved("ops_permissions_revoke");
+
+        ctx.predecessor_account_id = "contract".to_string();
+        testing_env!(ctx.clone());
+        ApprovalRequiredCommands::assert_approved("ops_permissions_grant");
+
+        ApprovalRequiredCommands::clear();
+        assert!(ApprovalRequiredCommands::load().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [APPROVAL_REQUIRED] ops_permissions_revoke")]
+    fn all_commands_require_approval() {
+        let mut ctx = new_context("admin");
+        ctx.current_account_id = "contract".to_string();
+        testing_env!(ctx.clone());
+        ApprovalRequiredCommands::All.save();
+        ApprovalRequiredCommands::assert_approved("ops_permissions_revoke");
+    }
+}
@@ -1,10 +1,11 @@
 use crate::Permissions;
 use oysterpack_smart_near::asserts::ERR_INVALID;
-use oysterpack_smart_near::Error;
+use oysterpack_smart_near::near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use oysterpack_smart_near::{data::Object, Error};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Default, PartialEq)]
-pub struct ContractPermissions(pub HashMap<u8, &'static str>);
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct ContractPermissions(pub HashMap<u8, String>);
 
 impl ContractPermissions {
     /// retains permission bits that are in the range 0-61
@@ -14,7 +15,7 @@ impl ContractPermissions {
     /// ## Panics
     /// - if permission names are not unique
     /// - if permission bits >= 62 were specified
-    pub fn new(permissions: HashMap<u8, &'static str>) -> Self {
+    pub fn new(permissions: HashMap<u8, String>) -> Self {
         let invalid_permissions: Vec<u8> = permissions
             .iter()
             .filter_map(|(k, _)| if *k >= 62_u8 { Some(*k) } else { None })
@@ -115,6 +116,94 @@ impl ContractPermissions {
         });
         Ok(permissions.into())
     }
+
+    /// ## Panics
+    /// - if the permission bit is not in the range 0-61
+    /// - if the permission bit is already defined
+    /// - if the permission name is not unique
+    pub fn add(&mut self, perm_bit: u8, name: &str) {
+        ERR_INVALID.assert(
+            || perm_bit < 62,
+            || "invalid permission bit - valid range is [0-61]",
+        );
+        ERR_INVALID.assert(
+            || !self.0.contains_key(&perm_bit),
+            || "permission bit is already defined",
+        );
+        self.assert_name_is_unique(name);
+        self.0.insert(perm_bit, name.to_string());
+    }
+
+    /// ## Panics
+    /// - if the permission bit is not defined
+    /// - if the permission name is not unique
+    pub fn rename(&mut self, perm_bit: u8, name: &str) {
+        self.assert_defined(perm_bit);
+        self.assert_name_is_unique(name);
+        self.0.insert(perm_bit, name.to_string());
+    }
+
+    /// ## Panics
+    /// if the permission bit is not defined
+    pub fn remove(&mut self, perm_bit: u8) {
+        self.assert_defined(perm_bit);
+        self.0.remove(&perm_bit);
+    }
+
+    fn assert_defined(&self, perm_bit: u8) {
+        ERR_INVALID.assert(
+            || self.0.contains_key(&perm_bit),
+            || "permission bit is not defined",
+        );
+    }
+
+    fn assert_name_is_unique(&self, name: &str) {
+        ERR_INVALID.assert(
+            || self.0.values().all(|value| value != name),
+            || "permission names must be unique",
+        );
+    }
+}
+
+const CONTRACT_PERMISSIONS_KEY: u128 = 1957612093862385839528432061924816302;
+
+type DAO = Object<u128, ContractPermissionsState>;
+
+/// Contract permission definitions that are managed at runtime.
+///
+/// Once the contract permissions are changed at runtime, the persisted definitions take precedence
+/// over the [`ContractPermissions`] that the contract was constructed with.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct ContractPermissionsState {
+    pub permissions: ContractPermissions,
+    /// retired permission bits can no longer be granted and are not reused. They are revoked from
+    /// accounts lazily, i.e., the next time the account's permissions are updated.
+    pub retired: Permissions,
+}
+
+impl ContractPermissionsState {
+    pub fn load() -> Option<Self> {
+        DAO::load(&CONTRACT_PERMISSIONS_KEY).map(|state| (*state).clone())
+    }
+
+    pub fn save(&self) {
+        DAO::new(CONTRACT_PERMISSIONS_KEY, self.clone()).save();
+    }
+
+    /// ## Panics
+    /// if the permission bit has been retired
+    pub fn add(&mut self, perm_bit: u8, name: &str) {
+        ERR_INVALID.assert(
+            || !self.retired.contains(1_u64 << perm_bit),
+            || "permission bit has been retired and cannot be reused",
+        );
+        self.permissions.add(perm_bit, name);
+    }
+
+    pub fn retire(&mut self, perm_bit: u8) {
+        self.permissions.remove(perm_bit);
+        self.retired.grant(1_u64 << perm_bit);
+    }
 }
 
 /// [`ContractPermissions`] can be constructed by specifying (permission_bit, permission_label) mappings:
@@ -130,8 +219,8 @@ impl ContractPermissions {
 ///
 /// ## Panics
 /// - if any permission bits >= 62
-impl From<Vec<(u8, &'static str)>> for ContractPermissions {
-    fn from(values: Vec<(u8, &'static str)>) -> Self {
+impl From<Vec<(u8, &str)>> for ContractPermissions {
+    fn from(values: Vec<(u8, &str)>) -> Self {
         let mut permissions = values
             .iter()
             .fold(HashMap::new(), |mut permissions, entry| {
@@ -139,7 +228,7 @@ impl From<Vec<(u8, &'static str)>> for ContractPermissions {
                     || entry.0 < 62,
                     || "invalid permission bit - valid range is [0-61]",
                 );
-                permissions.insert(entry.0, entry.1);
+                permissions.insert(entry.0, entry.1.to_string());
                 permissions
             });
         permissions.shrink_to_fit();
@@ -169,8 +258,8 @@ mod test_contract_permissions {
         const MINTER: Permission = 1 << 10;
         const BURNER: Permission = 1 << 20;
         let mut perms = HashMap::new();
-        perms.insert(10, "minter");
-        perms.insert(20, "burner");
+        perms.insert(10, "minter".to_string());
+        perms.insert(20, "burner".to_string());
         let contract_permissions = ContractPermissions(perms);
 
         assert!(!contract_permissions.is_supported((1 << 15).into()));
@@ -239,6 +328,28 @@ mod test_contract_permissions {
         );
     }
 
+    #[test]
+    fn add_rename_remove() {
+        test_utils::test_env::setup();
+        let mut contract_permissions: ContractPermissions = vec![(1, "1")].into();
+        contract_permissions.add(2, "2");
+        contract_permissions.rename(1, "one");
+        assert_eq!(
+            contract_permissions.permission_names(((1 << 1) | (1 << 2)).into()),
+            vec!["2".to_string(), "one".to_string()]
+        );
+        contract_permissions.remove(2);
+        assert!(!contract_permissions.is_supported((1 << 2).into()));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID] permission names must be unique")]
+    fn rename_with_duplicate_name() {
+        test_utils::test_env::setup();
+        let mut contract_permissions: ContractPermissions = vec![(1, "1"), (2, "2")].into();
+        contract_permissions.rename(1, "2");
+    }
+
     #[test]
     fn fold_permissions_with_no_contract_perms() {
         test_utils::test_env::setup();
@@ -0,0 +1,123 @@
+use crate::{AccountIdHash, AccountNearData};
+use oysterpack_smart_near::near_sdk::{
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{data::Object, domain::Expiration, Hash};
+
+/// Permission that was granted with an expiration
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ExpiringPermission {
+    pub account_id: AccountId,
+    /// permission bit
+    pub permission: u8,
+    /// permission name
+    pub name: String,
+    pub expiration: Expiration,
+}
+
+/// Page of expiring permissions
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ExpiringPermissions {
+    /// sorted by expiration
+    pub permissions: Vec<ExpiringPermission>,
+    /// index where the next scan starts - None when all accounts with expiring permissions have
+    /// been scanned
+    pub next_index: Option<U64>,
+}
+
+const EXPIRING_PERMISSIONS_ACCOUNTS_COUNT_KEY: u128 = 1957382250390176431146513370298162719;
+const EXPIRING_PERMISSIONS_ACCOUNTS_ENTRY_KEY: u128 = 1957382287165102939237318867010743287;
+const EXPIRING_PERMISSIONS_ACCOUNTS_INDEX_KEY: u128 = 1957382319846213957410267330119815503;
+
+type CountDAO = Object<u128, u64>;
+type EntryDAO = Object<(u128, u64), AccountId>;
+type IndexDAO = Object<(u128, AccountIdHash), u64>;
+
+/// Tracks which accounts have been granted permissions with an expiration.
+/// - the account expirations are stored on [`AccountNearData`] - this only serves as an index
+///   used to look up the accounts
+/// - accounts are indexed by position, which enables the index to be scanned in pages - entries
+///   are kept contiguous, i.e., when an account is removed, the last entry is moved into the removed
+///   account's position
+pub(crate) struct ExpiringPermissionsAccounts;
+
+impl ExpiringPermissionsAccounts {
+    pub(crate) fn count() -> u64 {
+        CountDAO::load(&EXPIRING_PERMISSIONS_ACCOUNTS_COUNT_KEY).map_or(0, |count| *count)
+    }
+
+    pub(crate) fn contains(account_id: &str) -> bool {
+        IndexDAO::exists(&Self::index_key(account_id))
+    }
+
+    /// Returns accounts in position order starting with the specified position
+    pub(crate) fn accounts(from_index: u64, limit: u64) -> Vec<AccountId> {
+        let to_index = Self::count().min(from_index.saturating_add(limit));
+        (from_index..to_index)
+            .filter_map(|index| {
+                EntryDAO::load(&(EXPIRING_PERMISSIONS_ACCOUNTS_ENTRY_KEY, index))
+                    .map(|account_id| (*account_id).clone())
+            })
+            .collect()
+    }
+
+    /// adds or removes the account depending on whether the account has any permission expirations
+    pub(crate) fn update(account_id: &str, account: &AccountNearData) {
+        if account.permission_expirations().is_some() {
+            Self::add(account_id);
+        } else {
+            Self::remove(account_id);
+        }
+    }
+
+    fn add(account_id: &str) {
+        if Self::contains(account_id) {
+            return;
+        }
+        let key = Self::index_key(account_id);
+        let index = Self::count();
+        EntryDAO::new(
+            (EXPIRING_PERMISSIONS_ACCOUNTS_ENTRY_KEY, index),
+            account_id.to_string(),
+        )
+        .save();
+        IndexDAO::new(key, index).save();
+        CountDAO::new(EXPIRING_PERMISSIONS_ACCOUNTS_COUNT_KEY, index + 1).save();
+    }
+
+    pub(crate) fn remove(account_id: &str) {
+        let key = Self::index_key(account_id);
+        let index = match IndexDAO::load(&key) {
+            Some(index) => *index,
+            None => return,
+        };
+        let last_index = Self::count() - 1;
+        IndexDAO::delete_by_key(&key);
+        if index != last_index {
+            let last_account_id = Self::accounts(last_index, 1).pop().unwrap();
+            EntryDAO::new(
+                (EXPIRING_PERMISSIONS_ACCOUNTS_ENTRY_KEY, index),
+                last_account_id.clone(),
+            )
+            .save();
+            IndexDAO::new(Self::index_key(&last_account_id), index).save();
+        }
+        EntryDAO::delete_by_key(&(EXPIRING_PERMISSIONS_ACCOUNTS_ENTRY_KEY, last_index));
+        if last_index == 0 {
+            CountDAO::delete_by_key(&EXPIRING_PERMISSIONS_ACCOUNTS_COUNT_KEY);
+        } else {
+            CountDAO::new(EXPIRING_PERMISSIONS_ACCOUNTS_COUNT_KEY, last_index).save();
+        }
+    }
+
+    fn index_key(account_id: &str) -> (u128, AccountIdHash) {
+        (
+            EXPIRING_PERMISSIONS_ACCOUNTS_INDEX_KEY,
+            AccountIdHash(Hash::from(account_id)),
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use crate::AccountIdHash;
+use oysterpack_smart_near::{data::Object, domain::YoctoNear, Hash};
+
+const GRANDFATHERED_STORAGE_BALANCE_KEY: u128 = 1077826381935911224480410561762164694;
+
+type DAO = Object<(u128, AccountIdHash), YoctoNear>;
+
+/// Min storage balance that an account was grandfathered at when the contract's account storage
+/// min was raised - see [`crate::StorageUsageBoundsManagement`]
+///
+/// Grandfathered accounts are held to the lower of the grandfathered balance and the contract min.
+///
+/// The entry is granted by operators to accounts whose balance is below the contract min, thus its
+/// storage is paid for by the contract instead of being charged to the account.
+pub struct GrandfatheredStorageBalance;
+
+impl GrandfatheredStorageBalance {
+    pub fn load(account_id: &str) -> Option<YoctoNear> {
+        Self::load_by_hash(account_id.into())
+    }
+
+    pub(crate) fn load_by_hash(account_id_hash: AccountIdHash) -> Option<YoctoNear> {
+        DAO::load(&(GRANDFATHERED_STORAGE_BALANCE_KEY, account_id_hash)).map(|balance| *balance)
+    }
+
+    pub(crate) fn save(account_id: &str, min: YoctoNear) {
+        DAO::new(Self::key(account_id), min).save();
+    }
+
+    pub(crate) fn delete(account_id: &str) -> bool {
+        DAO::delete_by_key(&Self::key(account_id))
+    }
+
+    pub(crate) fn migrate(account_id: &str, new_account_id: &str) {
+        if let Some(min) = Self::load(account_id) {
+            Self::delete(account_id);
+            Self::save(new_account_id, min);
+        }
+    }
+
+    fn key(account_id: &str) -> (u128, AccountIdHash) {
+        (
+            GRANDFATHERED_STORAGE_BALANCE_KEY,
+            AccountIdHash(Hash::from(account_id)),
+        )
+    }
+}
@@ -92,6 +92,13 @@ impl Permissions {
     pub fn has_permissions(&self) -> bool {
         *self.0 != 0
     }
+
+    /// returns the permission bits that are set
+    pub fn permission_bits(&self) -> Vec<u8> {
+        (0..64_u8)
+            .filter(|perm_bit| self.contains(1_u64 << *perm_bit))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -126,7 +133,10 @@ mod tests {
         assert!(perms.contains(1 << 20));
         assert!(!perms.contains(1 << 50));
 
+        assert_eq!(perms.permission_bits(), vec![15, 20]);
+
         perms.revoke_all();
         assert!(!perms.has_permissions());
+        assert!(perms.permission_bits().is_empty());
     }
 }
@@ -0,0 +1,40 @@
+use crate::AccountIdHash;
+use oysterpack_smart_near::near_sdk::AccountId;
+use oysterpack_smart_near::{data::Object, Hash};
+use std::collections::HashSet;
+
+const SPONSORED_ACCOUNTS_KEY: u128 = 1957763386414519108536212618410458321;
+type DAO = Object<(u128, AccountIdHash), HashSet<AccountId>>;
+
+/// Indexes the accounts that a sponsor has made storage deposits for.
+/// - the sponsored balances are stored on [`crate::AccountNearData`] - this only serves as an index
+///   used to look up the accounts
+pub struct SponsoredAccounts;
+
+impl SponsoredAccounts {
+    pub fn load(sponsor: &str) -> HashSet<AccountId> {
+        DAO::load(&Self::key(sponsor)).map_or_else(HashSet::new, |accounts| (*accounts).clone())
+    }
+
+    pub(crate) fn add(sponsor: &str, account_id: &str) {
+        let mut accounts = Self::load(sponsor);
+        if accounts.insert(account_id.to_string()) {
+            DAO::new(Self::key(sponsor), accounts).save();
+        }
+    }
+
+    pub(crate) fn remove(sponsor: &str, account_id: &str) {
+        let mut accounts = Self::load(sponsor);
+        if accounts.remove(account_id) {
+            if accounts.is_empty() {
+                DAO::delete_by_key(&Self::key(sponsor));
+            } else {
+                DAO::new(Self::key(sponsor), accounts).save();
+            }
+        }
+    }
+
+    fn key(sponsor: &str) -> (u128, AccountIdHash) {
+        (SPONSORED_ACCOUNTS_KEY, AccountIdHash(Hash::from(sponsor)))
+    }
+}
@@ -0,0 +1,96 @@
+use crate::AccountIdHash;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+
+thread_local! {
+    static LEDGER: RefCell<HashSet<AccountIdHash>> = RefCell::new(HashSet::new());
+    static DEFERRALS: Cell<u32> = Cell::new(0);
+}
+
+/// In-memory ledger that tracks the accounts whose storage balance coverage needs to be checked
+/// before the current contract call completes, i.e., accounts whose storage usage has grown or whose
+/// NEAR balance has been debited.
+///
+/// Storage balance coverage is enforced by the account storage event handler as soon as an account's
+/// storage usage grows, unless the check is deferred - in which case the accounts are recorded on
+/// the ledger and settled when the outermost deferral completes.
+///
+/// The ledger lives for the duration of the contract function call - it is never persisted.
+/// The entries are settled by [`crate::components::account_management::AccountManagementComponent::defer_storage_balance_coverage`]
+pub struct StorageLedger;
+
+impl StorageLedger {
+    pub(crate) fn record(account_id_hash: AccountIdHash) {
+        LEDGER.with(|ledger| {
+            ledger.borrow_mut().insert(account_id_hash);
+        });
+    }
+
+    pub fn contains(account_id_hash: AccountIdHash) -> bool {
+        LEDGER.with(|ledger| ledger.borrow().contains(&account_id_hash))
+    }
+
+    pub fn is_empty() -> bool {
+        LEDGER.with(|ledger| ledger.borrow().is_empty())
+    }
+
+    /// clears the ledger and returns the accounts that were recorded
+    pub(crate) fn take() -> HashSet<AccountIdHash> {
+        LEDGER.with(|ledger| ledger.replace(HashSet::new()))
+    }
+
+    /// returns true if storage balance coverage checks are currently deferred
+    pub fn is_deferred() -> bool {
+        DEFERRALS.with(|deferrals| deferrals.get() > 0)
+    }
+
+    pub(crate) fn begin_deferral() {
+        DEFERRALS.with(|deferrals| deferrals.set(deferrals.get() + 1));
+    }
+
+    /// returns true if the outermost deferral completed, i.e., the ledger needs to be settled
+    pub(crate) fn end_deferral() -> bool {
+        DEFERRALS.with(|deferrals| {
+            let count = deferrals.get().saturating_sub(1);
+            deferrals.set(count);
+            count == 0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oysterpack_smart_near::{near_sdk, Hash};
+    use oysterpack_smart_near_test::*;
+
+    #[test]
+    fn record_and_take() {
+        testing_env!(new_context("bob"));
+
+        assert!(StorageLedger::is_empty());
+        let alice = AccountIdHash(Hash::from("alice"));
+        StorageLedger::record(alice);
+        StorageLedger::record(alice);
+        assert!(StorageLedger::contains(alice));
+
+        let accounts = StorageLedger::take();
+        assert_eq!(accounts.len(), 1);
+        assert!(accounts.contains(&alice));
+        assert!(StorageLedger::is_empty());
+    }
+
+    #[test]
+    fn nested_deferrals() {
+        testing_env!(new_context("bob"));
+
+        assert!(!StorageLedger::is_deferred());
+        StorageLedger::begin_deferral();
+        StorageLedger::begin_deferral();
+        assert!(StorageLedger::is_deferred());
+        assert!(!StorageLedger::end_deferral());
+        assert!(StorageLedger::is_deferred());
+        assert!(StorageLedger::end_deferral());
+        assert!(!StorageLedger::is_deferred());
+    }
+}
@@ -28,11 +28,28 @@ pub enum StorageManagementEvent {
     /// - deleting [`crate::AccountNearDataObject`] and [`crate::AccountDataObject`] objects from contract storage
     ///
     /// ## NOTES
-    ///- the predecessor account is being unregistered
+    /// - the account being unregistered is specified by `account_id`, which is not necessarily the
+    ///   predecessor account, e.g., inactive accounts are unregistered by operators via garbage collection
     /// - hooks should use [`crate::ERR_CODE_UNREGISTER_FAILURE`] for failures
     ///
     /// [1]: crate::components::account_management::AccountManagementComponent
     PreUnregister { account_id: AccountId, force: bool },
+    /// Invoked when an account migration is accepted, before the account is deleted. It provides a
+    /// hook for other components to move their account scoped data to the new account ID.
+    ///
+    /// The hook is responsible for moving any account data outside of the [`crate::AccountNearDataObject`]
+    /// and [`crate::AccountDataObject`] objects, which are moved by the [AccountManagementComponent][1].
+    ///
+    /// ## NOTES
+    /// - the new account is registered before the hooks are run, thus storage usage changes for the
+    ///   new account ID are charged to the new account
+    /// - the predecessor account is the new account
+    ///
+    /// [1]: crate::components::account_management::AccountManagementComponent
+    PreMigrate {
+        account_id: AccountId,
+        new_account_id: AccountId,
+    },
 }
 
 impl Display for StorageManagementEvent {
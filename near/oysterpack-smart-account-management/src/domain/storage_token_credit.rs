@@ -0,0 +1,81 @@
+use crate::{AccountIdHash, AccountStorageEvent, COMPONENT_ID_STORAGE_TOKEN_CREDIT};
+use oysterpack_smart_near::near_sdk::env;
+use oysterpack_smart_near::{data::Object, domain::YoctoNear, eventbus, Hash};
+
+const STORAGE_TOKEN_CREDIT_KEY: u128 = 1958290172834106519736014502967310457;
+
+type DAO = Object<(u128, AccountIdHash), YoctoNear>;
+
+/// Tracks the portion of the account's NEAR balance that was credited in exchange for fungible
+/// tokens that were deposited to pay for storage.
+///
+/// The token credit is not withdrawable as NEAR - it is paid back in the original tokens by the
+/// component that accepted the token deposits.
+///
+/// The credit entry is charged to the account's storage usage, i.e.,
+/// [`AccountStorageEvent::StorageUsageChanged`] events are posted.
+pub struct StorageTokenCredit;
+
+impl StorageTokenCredit {
+    pub fn load(account_id: &str) -> YoctoNear {
+        DAO::load(&Self::key(account_id)).map_or(YoctoNear::ZERO, |credit| *credit)
+    }
+
+    pub(crate) fn credit(account_id: &str, amount: YoctoNear) {
+        if amount == YoctoNear::ZERO {
+            return;
+        }
+        let credit = Self::load(account_id) + amount;
+        Self::save(account_id, credit);
+    }
+
+    /// ## Panics
+    /// if the amount is greater than the account's token credit
+    pub(crate) fn debit(account_id: &str, amount: YoctoNear) {
+        let credit = Self::load(account_id);
+        assert!(
+            amount <= credit,
+            "token credit debit amount exceeds the credit"
+        );
+        Self::save(account_id, credit - amount);
+    }
+
+    pub(crate) fn delete(account_id: &str) {
+        Self::save(account_id, YoctoNear::ZERO);
+    }
+
+    /// moves the account's token credit to the new account
+    pub(crate) fn migrate(account_id: &str, new_account_id: &str) {
+        let credit = Self::load(account_id);
+        if credit > YoctoNear::ZERO {
+            Self::delete(account_id);
+            Self::save(new_account_id, credit);
+        }
+    }
+
+    /// zero credit entries are deleted
+    fn save(account_id: &str, credit: YoctoNear) {
+        let key = Self::key(account_id);
+        let storage_usage_before = env::storage_usage();
+        if credit == YoctoNear::ZERO {
+            DAO::delete_by_key(&key);
+        } else {
+            DAO::new(key, credit).save();
+        }
+        let change = env::storage_usage() as i64 - storage_usage_before as i64;
+        if change != 0 {
+            eventbus::post(&AccountStorageEvent::StorageUsageChanged(
+                key.1,
+                change.into(),
+                COMPONENT_ID_STORAGE_TOKEN_CREDIT,
+            ));
+        }
+    }
+
+    fn key(account_id: &str) -> (u128, AccountIdHash) {
+        (
+            STORAGE_TOKEN_CREDIT_KEY,
+            AccountIdHash(Hash::from(account_id)),
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use crate::AccountNearDataObject;
-use crate::{AccountDataObject, ERR_NOT_AUTHORIZED};
+use crate::{AccountDataObject, ApprovalRequiredCommands, ERR_NOT_AUTHORIZED};
 use oysterpack_smart_near::near_sdk::{
     borsh::{BorshDeserialize, BorshSerialize},
     env,
@@ -61,17 +61,21 @@ where
     /// - tracks storage usage - emits [`crate::AccountStorageEvent::StorageUsageChanged`]
     fn delete_account(&mut self, account_id: &str);
 
-    /// asserts that the predecessor account ID is registered and has operator permission
-    fn assert_operator(&self) -> AccountNearDataObject {
+    /// asserts that the predecessor account ID is registered and has operator permission, and that
+    /// the command was approved if it requires approval - see [`ApprovalRequiredCommands`]
+    fn assert_operator(&self, command: &str) -> AccountNearDataObject {
         let account = self.registered_account_near_data(env::predecessor_account_id().as_str());
         ERR_NOT_AUTHORIZED.assert(|| account.is_operator());
+        ApprovalRequiredCommands::assert_approved(command);
         account
     }
 
-    /// asserts that the predecessor account ID is registered and has admin permission
-    fn assert_admin(&self) -> AccountNearDataObject {
+    /// asserts that the predecessor account ID is registered and has admin permission, and that
+    /// the command was approved if it requires approval - see [`ApprovalRequiredCommands`]
+    fn assert_admin(&self, command: &str) -> AccountNearDataObject {
         let account = self.registered_account_near_data(env::predecessor_account_id().as_str());
         ERR_NOT_AUTHORIZED.assert(|| account.is_admin());
+        ApprovalRequiredCommands::assert_approved(command);
         account
     }
 }
@@ -1,9 +1,25 @@
 //! Contract Interfaces
 
 pub use access_control::*;
+pub use account_delegation::*;
+pub use account_freeze::*;
+pub use account_garbage_collection::*;
+pub use account_key_value_store::*;
+pub use account_migration::*;
 pub use account_storage_usage::*;
+pub use admin_audit_log::*;
 pub use storage_management::*;
+pub use storage_sponsorship::*;
+pub use storage_usage_bounds_management::*;
 
 mod access_control;
+mod account_delegation;
+mod account_freeze;
+mod account_garbage_collection;
+mod account_key_value_store;
+mod account_migration;
 mod account_storage_usage;
+mod admin_audit_log;
 mod storage_management;
+mod storage_sponsorship;
+mod storage_usage_bounds_management;
@@ -1,5 +1,6 @@
-use crate::Permissions;
-use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+use crate::{ExpiringPermissions, Permissions};
+use oysterpack_smart_near::domain::{ExpirationDuration, ExpirationSetting};
+use oysterpack_smart_near::near_sdk::json_types::{ValidAccountId, U64};
 use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
 use std::collections::HashMap;
 
@@ -7,6 +8,8 @@ use std::collections::HashMap;
 ///
 /// ## Notes
 /// - admins have full access
+/// - permissions can be granted with an expiration - expired permissions are treated as if they
+///   were never granted
 pub trait PermissionsManagement {
     fn ops_permissions_is_admin(&self, account_id: ValidAccountId) -> bool;
 
@@ -29,10 +32,18 @@ pub trait PermissionsManagement {
 
     /// Is restricted to admins
     ///
+    /// If `expiration` is specified, then the operator permission is automatically revoked once it
+    /// expires. Re-granting the permission replaces the previous expiration.
+    ///
     /// ## Panics
     /// - if predecessor account is not owner or admin
     /// - if `account_id` is not registered
-    fn ops_permissions_grant_operator(&mut self, account_id: ValidAccountId);
+    /// - if `expiration` is already expired
+    fn ops_permissions_grant_operator(
+        &mut self,
+        account_id: ValidAccountId,
+        expiration: Option<ExpirationSetting>,
+    );
 
     /// Is restricted to admins
     ///
@@ -46,11 +57,20 @@ pub trait PermissionsManagement {
     /// [`crate::Permissions::ADMIN`] and [`crate::Permissions::OPERATOR`] can not be granted - explicit grant functions
     /// must be used.
     ///
+    /// If `expiration` is specified, then the permissions are automatically revoked once they
+    /// expire. Re-granting permissions replaces the previous expiration.
+    ///
     /// ## Panics
     /// - if predecessor account is not owner or admin
     /// - if `account_id` is not registered
     /// - if permissions are not supported by the contract
-    fn ops_permissions_grant(&mut self, account_id: ValidAccountId, permissions: Permissions);
+    /// - if `expiration` is already expired
+    fn ops_permissions_grant(
+        &mut self,
+        account_id: ValidAccountId,
+        permissions: Permissions,
+        expiration: Option<ExpirationSetting>,
+    );
 
     /// Is restricted to admins.
     ///
@@ -59,15 +79,18 @@ pub trait PermissionsManagement {
     ///
     /// ## Args
     /// - permissions - array of permission bits
+    /// - expiration - see [`PermissionsManagement::ops_permissions_grant`]
     ///
     /// ## Panics
     /// - if predecessor account is not owner or admin
     /// - if `account_id` is not registered
     /// - if permissions are not supported by the contract
+    /// - if `expiration` is already expired
     fn ops_permissions_grant_permissions(
         &mut self,
         account_id: ValidAccountId,
         permissions: Vec<u8>,
+        expiration: Option<ExpirationSetting>,
     );
 
     /// Is restricted to admins
@@ -112,13 +135,84 @@ pub trait PermissionsManagement {
 
     /// lists the permission bits that are supported by the contract with a human friendly name
     /// - [`crate::Permissions::ADMIN`] and [`crate::Permissions::OPERATOR`] are excluded
+    /// - reflects any changes that were applied at runtime
     fn ops_permissions_contract_permissions(&self) -> Option<HashMap<u8, String>>;
+
+    /// Defines a new contract permission. Is restricted to admins.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE`]
+    ///
+    /// ## Panics
+    /// - if predecessor account is not admin
+    /// - if the permission bit is not in the range 0-61, or is already defined, or has been retired
+    /// - if the permission name is not unique
+    fn ops_permissions_contract_permission_add(&mut self, permission: u8, name: String);
+
+    /// Is restricted to admins.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE`]
+    ///
+    /// ## Panics
+    /// - if predecessor account is not admin
+    /// - if the permission bit is not defined
+    /// - if the permission name is not unique
+    fn ops_permissions_contract_permission_rename(&mut self, permission: u8, name: String);
+
+    /// Retires the permission bit, which means it can no longer be granted and the bit cannot be
+    /// reused. Accounts that were granted the permission are cleaned up lazily, i.e., the permission
+    /// is revoked from the account the next time the account's permissions are updated. In the
+    /// meantime, the permission is excluded from account permission queries.
+    ///
+    /// Is restricted to admins.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE`]
+    ///
+    /// ## Panics
+    /// - if predecessor account is not admin
+    /// - if the permission bit is not defined
+    fn ops_permissions_contract_permission_retire(&mut self, permission: u8);
+
+    /// returns the permission bits that have been retired
+    fn ops_permissions_contract_permissions_retired(&self) -> Option<Vec<u8>>;
+
+    /// Scans up to `limit` accounts that were granted permissions with an expiration, starting at
+    /// `from_index`, and lists the permissions that have not yet expired. Each page is sorted by
+    /// expiration.
+    ///
+    /// If `within` is specified, then only permissions that will expire within the specified
+    /// duration are returned. Expirations are only comparable when they are specified using the
+    /// same unit, i.e., if `within` is specified in epochs, then only expirations that are set on
+    /// epochs will be returned.
+    ///
+    /// `limit` defaults to [`EXPIRING_PERMISSIONS_DEFAULT_LIMIT`]
+    fn ops_permissions_expiring(
+        &self,
+        within: Option<ExpirationDuration>,
+        from_index: Option<U64>,
+        limit: Option<u32>,
+    ) -> ExpiringPermissions;
 }
 
+/// default number of accounts scanned per [`PermissionsManagement::ops_permissions_expiring`] page
+pub const EXPIRING_PERMISSIONS_DEFAULT_LIMIT: u32 = 100;
+
 pub const ERR_NOT_AUTHORIZED: ErrorConst = ErrorConst(
     ErrCode("NOT_AUTHORIZED"),
     "account is not authorized to perform the requested action",
 );
 
+/// Indicates the command must be submitted through an approved proposal - see
+/// [`crate::ApprovalRequiredCommands`]
+pub const ERR_APPROVAL_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("APPROVAL_REQUIRED"),
+    "command must be submitted through an approved proposal",
+);
+
 pub const LOG_EVENT_PERMISSIONS_GRANT: LogEvent = LogEvent(Level::INFO, "PERMISSIONS_GRANT");
 pub const LOG_EVENT_PERMISSIONS_REVOKE: LogEvent = LogEvent(Level::INFO, "PERMISSIONS_REVOKE");
+
+pub const LOG_EVENT_CONTRACT_PERMISSIONS_CHANGE: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_PERMISSIONS_CHANGE");
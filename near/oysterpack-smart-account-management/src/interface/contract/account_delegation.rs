@@ -0,0 +1,82 @@
+use crate::Delegation;
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::{
+    json_types::{ValidAccountId, U128},
+    AccountId,
+};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+use std::collections::HashMap;
+
+/// # **Contract Interface**: Account Delegation API
+///
+/// Enables an account to authorize another account, e.g., a bot or custodial service, to perform
+/// a scoped set of actions on its behalf without handing over its keys.
+///
+/// Actions are identified by name and are defined by the contract components that support
+/// delegation, e.g., `stake`, `unstake`, `withdraw`, `ft_transfer`. Each delegated action can be
+/// capped by an allowance, which is debited each time the delegate performs the action.
+///
+/// ## Notes
+/// - the delegations are stored as part of the account's storage usage
+pub trait AccountDelegation {
+    /// Authorizes the delegate to perform the specified actions on behalf of the predecessor account.
+    /// - `actions` maps action names to an optional allowance - None means the allowance is unlimited
+    /// - re-granting replaces the delegate's previous delegation
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_DELEGATION_GRANT`]
+    ///
+    /// ## Panics
+    /// - if 1 yoctoNEAR is not attached
+    /// - if the predecessor account is not registered
+    /// - if the delegate is the predecessor account
+    /// - if no actions are specified
+    /// - if `expiration` is already expired
+    fn ops_delegation_grant(
+        &mut self,
+        delegate: ValidAccountId,
+        actions: HashMap<String, Option<U128>>,
+        expiration: Option<ExpirationSetting>,
+    ) -> Delegation;
+
+    /// Revokes the delegate's delegation from the predecessor account.
+    ///
+    /// Returns false if no delegation was granted to the delegate.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_DELEGATION_REVOKE`]
+    fn ops_delegation_revoke(&mut self, delegate: ValidAccountId) -> bool;
+
+    /// Returns delegations granted by the account keyed by delegate account ID, which includes
+    /// expired delegations that have not been revoked
+    ///
+    /// Returns None if the account has not granted any delegations
+    fn ops_delegations(&self, account_id: ValidAccountId)
+        -> Option<HashMap<AccountId, Delegation>>;
+
+    fn ops_delegation(
+        &self,
+        account_id: ValidAccountId,
+        delegate: ValidAccountId,
+    ) -> Option<Delegation>;
+}
+
+pub const LOG_EVENT_DELEGATION_GRANT: LogEvent = LogEvent(Level::INFO, "DELEGATION_GRANT");
+pub const LOG_EVENT_DELEGATION_REVOKE: LogEvent = LogEvent(Level::INFO, "DELEGATION_REVOKE");
+
+pub const ERR_DELEGATION_NOT_AUTHORIZED: ErrorConst = ErrorConst(
+    ErrCode("DELEGATION_NOT_AUTHORIZED"),
+    "action has not been delegated to the predecessor account",
+);
+
+pub const ERR_DELEGATION_EXPIRED: ErrorConst =
+    ErrorConst(ErrCode("DELEGATION_EXPIRED"), "delegation is expired");
+
+pub const ERR_DELEGATION_ALLOWANCE_EXCEEDED: ErrorConst = ErrorConst(
+    ErrCode("DELEGATION_ALLOWANCE_EXCEEDED"),
+    "amount exceeds the delegated action's remaining allowance",
+);
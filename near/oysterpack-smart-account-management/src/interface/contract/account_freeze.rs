@@ -0,0 +1,55 @@
+use crate::AccountFreeze;
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Account Freeze API
+///
+/// Enables admins to respond to compromised accounts and legal requests by freezing accounts.
+/// While an account is frozen, it is blocked from moving funds out of the contract:
+/// - [`crate::StorageManagement::storage_withdraw`]
+/// - [`crate::StorageManagement::storage_unregister`]
+/// - contract components that transfer or withdraw account funds, e.g., token transfers and unstaking,
+///   are expected to check the account freeze via [`ERR_ACCOUNT_FROZEN`]
+///
+/// Freezing and unfreezing accounts are recorded in the admin audit log.
+pub trait AccountFreezeManagement {
+    /// Freezes the account. If the account is already frozen, then the freeze is replaced.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_ACCOUNT_FREEZE`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if the account is not registered
+    /// - if the predecessor account is the account being frozen
+    /// - if the reason is blank or longer than [`ACCOUNT_FREEZE_REASON_MAX_LEN`]
+    /// - if `expiration` is already expired
+    fn ops_account_freeze(
+        &mut self,
+        account_id: ValidAccountId,
+        reason: String,
+        expiration: Option<ExpirationSetting>,
+    ) -> AccountFreeze;
+
+    /// Returns false if the account was not frozen.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_ACCOUNT_UNFREEZE`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if the account is not registered
+    fn ops_account_unfreeze(&mut self, account_id: ValidAccountId) -> bool;
+
+    /// Returns None if the account is not registered or is not frozen
+    fn ops_account_freeze_status(&self, account_id: ValidAccountId) -> Option<AccountFreeze>;
+}
+
+pub const ACCOUNT_FREEZE_REASON_MAX_LEN: usize = 256;
+
+pub const LOG_EVENT_ACCOUNT_FREEZE: LogEvent = LogEvent(Level::WARN, "ACCOUNT_FREEZE");
+pub const LOG_EVENT_ACCOUNT_UNFREEZE: LogEvent = LogEvent(Level::INFO, "ACCOUNT_UNFREEZE");
+
+pub const ERR_ACCOUNT_FROZEN: ErrorConst =
+    ErrorConst(ErrCode("ACCOUNT_FROZEN"), "account is frozen");
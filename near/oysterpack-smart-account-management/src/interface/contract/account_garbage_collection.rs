@@ -0,0 +1,84 @@
+use crate::AccountGcSettings;
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Inactive Account Garbage Collection API
+///
+/// Dust accounts that are no longer used keep contract storage locked. Operators can garbage collect
+/// accounts that have been idle for longer than the configured number of epochs - see
+/// [`crate::AccountNearData::last_active`]. Garbage collected accounts are unregistered on their
+/// behalf and their storage balances are refunded, i.e., sponsored balances are refunded to the
+/// sponsors and the rest is transferred to the account.
+///
+/// An account is eligible for garbage collection only if it holds no value in the contract besides
+/// its storage balance. Accounts are skipped if they:
+/// - have permissions, e.g., admins and operators
+/// - are frozen
+/// - have a pending account migration
+/// - sponsor other accounts
+/// - have non-default account data
+/// - are retained by contract components, e.g., accounts with token balances
+///
+/// [`crate::StorageManagementEvent::PreUnregister`] is posted for each account with `force=false`
+/// before the account is unregistered.
+pub trait AccountGarbageCollection {
+    /// Scans up to `limit` registered accounts, resuming where the previous scan left off, and
+    /// unregisters the accounts that are eligible for garbage collection.
+    ///
+    /// The scan stops early when there is not enough gas left to process the next account.
+    ///
+    /// `limit` defaults to [`ACCOUNT_GC_DEFAULT_LIMIT`]
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_ACCOUNTS_GC`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an operator
+    /// - [`ERR_ACCOUNT_GC_DISABLED`] - if the idle epochs setting is not configured
+    fn ops_accounts_gc(&mut self, limit: Option<u32>) -> AccountGcReport;
+
+    /// Sets the number of epochs that an account must be idle for before it is eligible for garbage
+    /// collection. Setting it to None disables garbage collection.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if `idle_epochs` is zero
+    fn ops_accounts_gc_set_idle_epochs(&mut self, idle_epochs: Option<U64>);
+
+    fn ops_accounts_gc_settings(&self) -> AccountGcSettings;
+}
+
+pub const ACCOUNT_GC_DEFAULT_LIMIT: u32 = 50;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct AccountGcReport {
+    /// number of accounts that were checked for garbage collection
+    pub scanned: u32,
+    /// accounts that were unregistered
+    pub unregistered: Vec<GarbageCollectedAccount>,
+    /// [`crate::AccountRegistry`] position where the next scan starts
+    pub cursor: U64,
+    /// number of registered accounts after garbage collection
+    pub registered_accounts: U64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct GarbageCollectedAccount {
+    pub account_id: AccountId,
+    /// amount that was refunded to the account - sponsor refunds are excluded
+    pub refund: YoctoNear,
+}
+
+pub const LOG_EVENT_ACCOUNTS_GC: LogEvent = LogEvent(Level::INFO, "ACCOUNTS_GC");
+
+pub const ERR_ACCOUNT_GC_DISABLED: ErrorConst = ErrorConst(
+    ErrCode("ACCOUNT_GC_DISABLED"),
+    "account garbage collection is disabled because idle epochs is not configured",
+);
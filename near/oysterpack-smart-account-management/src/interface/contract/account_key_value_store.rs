@@ -0,0 +1,67 @@
+use oysterpack_smart_near::domain::StorageUsage;
+use oysterpack_smart_near::near_sdk::{
+    json_types::ValidAccountId,
+    serde::{Deserialize, Serialize},
+};
+use oysterpack_smart_near::{ErrCode, ErrorConst};
+
+/// # **Contract Interface**: Account Key-Value Store API
+///
+/// Provides registered accounts with a generic key-value data store, which enables products built
+/// on the contract to store account scoped data, e.g., profiles, preferences, referral codes, without
+/// requiring their own contract component.
+///
+/// The entries are charged to the account's storage usage, which must be covered by the account's
+/// storage balance. Key and value sizes, and the total storage used per account, are bounded by
+/// [`AccountKeyValueStoreLimits`].
+///
+/// The account's entries are deleted when the account is unregistered, and moved to the new account
+/// when the account is migrated.
+pub trait AccountKeyValueStore {
+    /// Sets the value for the specified key and returns the previous value.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Panics
+    /// - if 1 yoctoNEAR is not attached
+    /// - if the predecessor account is not registered
+    /// - if the key is blank or the key or value exceeds the max length
+    /// - [`ERR_KV_QUOTA_EXCEEDED`] - if the account's key-value storage usage would exceed the max
+    /// - if the account's storage balance does not cover the storage usage
+    fn ops_kv_set(&mut self, key: String, value: String) -> Option<String>;
+
+    /// Returns false if the key does not exist.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Panics
+    /// - if 1 yoctoNEAR is not attached
+    /// - if the predecessor account is not registered
+    fn ops_kv_delete(&mut self, key: String) -> bool;
+
+    fn ops_kv_get(&self, account_id: ValidAccountId, key: String) -> Option<String>;
+
+    /// returns the account's keys in sorted order
+    fn ops_kv_keys(&self, account_id: ValidAccountId) -> Vec<String>;
+
+    /// returns the storage used by the account's key-value entries
+    fn ops_kv_storage_usage(&self, account_id: ValidAccountId) -> StorageUsage;
+
+    fn ops_kv_limits(&self) -> AccountKeyValueStoreLimits;
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct AccountKeyValueStoreLimits {
+    /// max key length in bytes
+    pub max_key_len: u16,
+    /// max value length in bytes
+    pub max_value_len: u32,
+    /// max storage that can be used per account for key-value entries
+    pub max_storage_usage: StorageUsage,
+}
+
+pub const ERR_KV_QUOTA_EXCEEDED: ErrorConst = ErrorConst(
+    ErrCode("KV_QUOTA_EXCEEDED"),
+    "account key-value store storage usage exceeds the max allowed storage usage",
+);
@@ -0,0 +1,74 @@
+use crate::AccountMigration;
+use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Account Migration API
+///
+/// Enables an account to move all of its contract state to a new account ID, e.g., when rotating to
+/// a new NEAR account. Migration is a 2-step process:
+/// 1. the account initiates the migration to the new account ID
+/// 2. the new account accepts the migration, which moves the account state to the new account ID
+///    and deletes the old account
+///
+/// All account scoped state is moved, including the storage balance, permissions, delegations
+/// granted by the account, sponsorships, and any component account data, e.g., token balances and
+/// unstaked balances. Components move their account data via
+/// [`crate::StorageManagementEvent::PreMigrate`] event hooks.
+///
+/// ## Notes
+/// - the pending migration is stored as part of the account's storage usage
+/// - delegations granted to the account by other accounts are not migrated
+pub trait AccountMigrationManagement {
+    /// Initiates migrating the predecessor account to the new account ID.
+    /// - replaces any pending migration for the predecessor account
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_ACCOUNT_MIGRATE`]
+    ///
+    /// ## Panics
+    /// - if 1 yoctoNEAR is not attached
+    /// - if the predecessor account is not registered
+    /// - if the predecessor account is frozen
+    /// - if the new account ID is the predecessor account
+    /// - if the new account ID is already registered
+    fn ops_account_migrate(&mut self, new_account_id: ValidAccountId) -> AccountMigration;
+
+    /// Accepts the pending migration from the specified account to the predecessor account.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_ACCOUNT_MIGRATED`]
+    ///
+    /// ## Panics
+    /// - if 1 yoctoNEAR is not attached
+    /// - [`ERR_ACCOUNT_MIGRATION_NOT_FOUND`] - if the account has no pending migration to the predecessor account
+    /// - if the predecessor account is already registered
+    /// - if the account is frozen
+    fn ops_account_migrate_accept(&mut self, account_id: ValidAccountId);
+
+    /// Cancels the predecessor account's pending migration.
+    ///
+    /// Returns false if there was no pending migration.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_ACCOUNT_MIGRATE_CANCEL`]
+    fn ops_account_migrate_cancel(&mut self) -> bool;
+
+    /// Returns the account's pending migration
+    fn ops_account_migration(&self, account_id: ValidAccountId) -> Option<AccountMigration>;
+}
+
+pub const LOG_EVENT_ACCOUNT_MIGRATE: LogEvent = LogEvent(Level::INFO, "ACCOUNT_MIGRATE");
+pub const LOG_EVENT_ACCOUNT_MIGRATED: LogEvent = LogEvent(Level::INFO, "ACCOUNT_MIGRATED");
+pub const LOG_EVENT_ACCOUNT_MIGRATE_CANCEL: LogEvent =
+    LogEvent(Level::INFO, "ACCOUNT_MIGRATE_CANCEL");
+
+pub const ERR_ACCOUNT_MIGRATION_NOT_FOUND: ErrorConst = ErrorConst(
+    ErrCode("ACCOUNT_MIGRATION_NOT_FOUND"),
+    "account has no pending migration to the predecessor account",
+);
@@ -1,6 +1,7 @@
-use crate::StorageUsageBounds;
+use crate::{ComponentStorageUsage, StorageUsageBounds};
 use oysterpack_smart_near::domain::StorageUsage;
 use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+use std::collections::BTreeMap;
 
 /// # **Contract Interface**: Account Storage Usage API
 ///
@@ -15,4 +16,13 @@ pub trait AccountStorageUsage {
     ///
     /// Returns None if the account is not registered
     fn ops_storage_usage(&self, account_id: ValidAccountId) -> Option<StorageUsage>;
+
+    /// Returns the account storage usage broken down by component, including the storage staking
+    /// cost per component
+    ///
+    /// Returns None if the account is not registered
+    fn ops_storage_usage_by_component(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<String, ComponentStorageUsage>>;
 }
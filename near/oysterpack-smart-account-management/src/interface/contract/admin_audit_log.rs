@@ -0,0 +1,21 @@
+use crate::AuditLogEntry;
+use oysterpack_smart_near::near_sdk::json_types::U64;
+
+/// # **Contract Interface**: Admin Audit Log API
+///
+/// Privileged calls that change permissions, operator settings, or contract ownership are recorded
+/// in a bounded audit log - see [`crate::AuditLog`]
+pub trait AdminAuditLog {
+    /// Returns audit log entries in ascending ID order
+    /// - `from_id` defaults to the oldest entry that is retained
+    /// - `limit` defaults to [`AUDIT_LOG_DEFAULT_PAGE_SIZE`] and is capped at [`AUDIT_LOG_MAX_PAGE_SIZE`]
+    fn ops_audit_log(&self, from_id: Option<U64>, limit: Option<u16>) -> Vec<AuditLogEntry>;
+
+    /// returns the total number of entries that have been recorded, which can be used to page
+    /// through the latest entries
+    fn ops_audit_log_count(&self) -> U64;
+}
+
+pub const AUDIT_LOG_DEFAULT_PAGE_SIZE: u16 = 20;
+
+pub const AUDIT_LOG_MAX_PAGE_SIZE: u16 = 100;
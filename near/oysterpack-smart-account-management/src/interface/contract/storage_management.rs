@@ -17,6 +17,8 @@ use oysterpack_smart_near::ErrCode;
 ///    account. The initial deposit for the account must be at least the minimum amount required by the contract.
 /// 3. Account storage total and available balance can be looked up. The amount required to pay for the account's storage usage
 ///    will be locked up in the contract. Any storage balance above storage staking costs is available for withdrawal.
+///    The locked balance is the greater of the minimum required balance and the account's actual storage usage cost.
+///    Contract calls that grow the account's storage usage beyond what its storage balance covers will fail.
 ///
 /// [1]: https://docs.near.org/docs/concepts/storage#how-much-does-it-cost
 /// [2]: https://docs.near.org/docs/concepts/storage#the-million-cheap-data-additions-attack
@@ -36,6 +38,8 @@ pub trait StorageManagement {
     /// - If `registration_only=true`, contract MUST refund above the minimum balance if the account
     ///   wasn't registered and refund full deposit if already registered.
     ///  - Any attached deposit in excess of `storage_balance_bounds.max` must be refunded to predecessor account.
+    ///  - If the deposit is made on behalf of another account, then the predecessor account is tracked
+    ///    as a sponsor for the credited amount - see [`crate::StorageSponsorship`]
     ///
     /// ## Example Use Cases
     ///  1. In order for the account to hold tokens, the account must first have enough NEAR funds
@@ -56,6 +60,7 @@ pub trait StorageManagement {
     /// ## Panics
     /// - If the attached deposit is less than the minimum required account storage fee on the initial deposit.
     /// - If `account_id` is not a valid NEAR account ID
+    /// - If a sponsor deposit does not satisfy the [`crate::StorageSponsorship`] limits
     ///
     /// `#[payable]`
     fn storage_deposit(
@@ -72,6 +77,8 @@ pub trait StorageManagement {
     ///   restricted function-call access-key call.
     /// - If the withdrawal amount is zero, then the 1 yoctoNEAR attached deposit is not refunded
     ///   because it would cost more to send the refund
+    /// - The account's own funds are withdrawn first - any sponsored portion of the withdrawal is
+    ///   refunded to the sponsors
     ///
     /// ## Arguments
     /// - `amount` - the amount to withdraw from the account's storage available balance expressed in yoctoNEAR
@@ -97,6 +104,8 @@ pub trait StorageManagement {
     /// **NOTE**: function requires exactly 1 yoctoNEAR attached balance to prevent restricted function-call
     /// access-key call (UX wallet security)
     ///
+    /// Any sponsored storage balance is refunded to the sponsors.
+    ///
     /// ## Returns
     /// - true if the account was successfully unregistered
     /// - false indicates that the account is not registered with the contract
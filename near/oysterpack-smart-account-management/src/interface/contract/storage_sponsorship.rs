@@ -0,0 +1,50 @@
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{json_types::ValidAccountId, AccountId};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+use std::collections::BTreeMap;
+
+/// # **Contract Interface**: Storage Sponsorship API
+///
+/// When [`crate::StorageManagement::storage_deposit`] is invoked on behalf of another account, the
+/// predecessor account is tracked as a sponsor for the deposit amount that was credited to the
+/// account's storage balance. Sponsored funds are routed back to the sponsor:
+/// - when the account withdraws from its storage available balance, the account's own funds are
+///   withdrawn first - any remaining amount is refunded to the sponsors
+/// - when the account unregisters, the sponsored balance is refunded to the sponsors
+///
+/// Tracking a sponsor adds storage that is charged to the sponsored account. Thus, a deposit from a
+/// new sponsor must at least cover the storage cost of tracking the sponsor, and the number of
+/// sponsors per account is capped at [`MAX_ACCOUNT_SPONSORS`]. In order to prevent the sponsor slots
+/// from being taken up with dust deposits, a deposit from a new sponsor must also be at least the
+/// min account storage balance - see [`crate::StorageManagement::storage_balance_bounds`].
+pub trait StorageSponsorship {
+    /// Returns the accounts that the sponsor has sponsored, which still hold a sponsored balance
+    fn ops_storage_sponsored_accounts(&self, sponsor: ValidAccountId) -> Vec<AccountId>;
+
+    /// Returns the account's sponsors mapped to their sponsored balances
+    ///
+    /// Returns None if the account is not registered or has no sponsors
+    fn ops_storage_sponsors(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<AccountId, YoctoNear>>;
+}
+
+pub const LOG_EVENT_STORAGE_SPONSOR_DEPOSIT: LogEvent =
+    LogEvent(Level::INFO, "STORAGE_SPONSOR_DEPOSIT");
+
+pub const LOG_EVENT_STORAGE_SPONSOR_REFUND: LogEvent =
+    LogEvent(Level::INFO, "STORAGE_SPONSOR_REFUND");
+
+/// max number of sponsors that are tracked per account
+pub const MAX_ACCOUNT_SPONSORS: usize = 10;
+
+pub const ERR_SPONSOR_DEPOSIT_TOO_LOW: ErrorConst = ErrorConst(
+    ErrCode("SPONSOR_DEPOSIT_TOO_LOW"),
+    "sponsor deposit is too low",
+);
+
+pub const ERR_MAX_ACCOUNT_SPONSORS_EXCEEDED: ErrorConst = ErrorConst(
+    ErrCode("MAX_ACCOUNT_SPONSORS_EXCEEDED"),
+    "account has reached the max number of sponsors",
+);
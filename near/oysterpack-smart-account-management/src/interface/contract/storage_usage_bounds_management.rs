@@ -0,0 +1,110 @@
+use crate::StorageUsageBounds;
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{
+    json_types::{ValidAccountId, U64},
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Storage Usage Bounds Management API
+///
+/// The account storage min is measured when the contract is deployed. When a contract upgrade adds
+/// components that require account storage, or grows the account data, the deployed min becomes
+/// stale. Operators re-measure the storage usage bounds after the upgrade, which raises the min
+/// storage balance for all accounts.
+///
+/// Accounts whose storage balance falls below the new min storage balance must top up their storage
+/// balance via [`crate::StorageManagement::storage_deposit`] before their storage usage can grow.
+/// Operators may grandfather such accounts instead, which holds the account to the storage balance
+/// it had when it was grandfathered - see [`crate::GrandfatheredStorageBalance`].
+pub trait StorageUsageBoundsManagement {
+    /// Re-measures the account storage min using the min account storage functions registered by
+    /// the contract components. The max storage usage is left as is.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_STORAGE_USAGE_BOUNDS_CHANGED`] - if the min changed
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an operator
+    /// - [`ERR_STORAGE_USAGE_MIN_EXCEEDS_MAX`]
+    fn ops_storage_usage_bounds_remeasure(&mut self) -> StorageUsageBoundsChange;
+
+    /// Scans up to `limit` registered accounts starting at `from_index`, and returns the accounts
+    /// whose storage balance is below the min storage balance they are held to.
+    ///
+    /// `limit` defaults to [`STORAGE_BALANCE_SHORTFALLS_DEFAULT_LIMIT`]
+    fn ops_storage_balance_shortfalls(
+        &self,
+        from_index: Option<U64>,
+        limit: Option<u32>,
+    ) -> StorageBalanceShortfalls;
+
+    /// Grandfathers the account at its current storage balance, i.e., the account will not be
+    /// required to top up its storage balance to the contract min storage balance.
+    ///
+    /// Returns the grandfathered min storage balance.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_STORAGE_BALANCE_GRANDFATHERED`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an operator
+    /// - if the account is not registered
+    /// - [`ERR_STORAGE_BALANCE_NOT_SHORT`] - if the account's storage balance is not below the min
+    fn ops_storage_balance_grandfather(&mut self, account_id: ValidAccountId) -> YoctoNear;
+
+    /// Revokes the account's grandfathered min storage balance.
+    ///
+    /// Returns false if the account was not grandfathered.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an operator
+    fn ops_storage_balance_grandfather_revoke(&mut self, account_id: ValidAccountId) -> bool;
+
+    /// Returns None if the account is not grandfathered
+    fn ops_storage_balance_grandfathered(&self, account_id: ValidAccountId) -> Option<YoctoNear>;
+}
+
+pub const STORAGE_BALANCE_SHORTFALLS_DEFAULT_LIMIT: u32 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct StorageUsageBoundsChange {
+    pub previous: StorageUsageBounds,
+    pub current: StorageUsageBounds,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct StorageBalanceShortfalls {
+    pub accounts: Vec<StorageBalanceShortfall>,
+    /// [`crate::AccountRegistry`] index where the next scan starts - None when all registered accounts
+    /// have been scanned
+    pub next_index: Option<U64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct StorageBalanceShortfall {
+    pub account_id: AccountId,
+    pub storage_balance: YoctoNear,
+    /// amount that the account must deposit to top up its storage balance to the min
+    pub shortfall: YoctoNear,
+}
+
+pub const LOG_EVENT_STORAGE_USAGE_BOUNDS_CHANGED: LogEvent =
+    LogEvent(Level::INFO, "STORAGE_USAGE_BOUNDS_CHANGED");
+
+pub const LOG_EVENT_STORAGE_BALANCE_GRANDFATHERED: LogEvent =
+    LogEvent(Level::INFO, "STORAGE_BALANCE_GRANDFATHERED");
+
+pub const ERR_STORAGE_USAGE_MIN_EXCEEDS_MAX: ErrorConst = ErrorConst(
+    ErrCode("STORAGE_USAGE_MIN_EXCEEDS_MAX"),
+    "re-measured account storage min exceeds the max storage usage",
+);
+
+pub const ERR_STORAGE_BALANCE_NOT_SHORT: ErrorConst = ErrorConst(
+    ErrCode("STORAGE_BALANCE_NOT_SHORT"),
+    "account storage balance is not below the min storage balance",
+);
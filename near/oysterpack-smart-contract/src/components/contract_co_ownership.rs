@@ -0,0 +1,484 @@
+//! [`ContractCoOwnershipComponent`]
+//! - [`ContractCoOwnershipComponent::owner_payees`] should be used by contracts to pay out owner
+//!   earnings in order for the earnings to be shared by the co-owners
+
+use crate::components::contract_multisig::ContractMultiSigComponent;
+use crate::{
+    CoOwnership, CoOwnershipConfig, ContractCoOwnership, ContractOwnerObject,
+    ContractOwnershipAccountIdsObject, Proposal, ProposalCommand, ERR_CO_OWNERSHIP_NOT_CONFIGURED,
+    ERR_CO_OWNER_ACCESS_REQUIRED, ERR_CO_OWNER_QUORUM_REQUIRED, ERR_INVALID_CO_OWNERSHIP_CONFIG,
+    ERR_PROPOSAL_ALREADY_APPROVED, ERR_PROPOSAL_EXPIRED, ERR_PROPOSAL_NOT_FOUND,
+    LOG_EVENT_CO_OWNERSHIP_CONFIG_CHANGE, LOG_EVENT_CO_OWNER_PROPOSAL_APPROVED,
+    LOG_EVENT_CO_OWNER_PROPOSAL_CANCELLED, LOG_EVENT_CO_OWNER_PROPOSAL_CREATED,
+    LOG_EVENT_CO_OWNER_PROPOSAL_EXECUTED,
+};
+use oysterpack_smart_near::asserts::ERR_CODE_BAD_REQUEST;
+use oysterpack_smart_near::domain::{Expiration, ExpirationSetting, YoctoNear};
+use oysterpack_smart_near::near_sdk::{env, json_types::U64, AccountId, Promise};
+
+pub struct ContractCoOwnershipComponent;
+
+impl ContractCoOwnershipComponent {
+    pub fn is_enabled() -> bool {
+        CoOwnership::exists()
+    }
+
+    /// Returns the accounts that owner earnings are paid out to along with their share of the amount:
+    /// - if the contract is co-owned, then the amount is split across the co-owners pro rata
+    /// - otherwise, the full amount is paid to the owner account
+    pub fn owner_payees(amount: u128) -> Vec<(AccountId, u128)> {
+        match CoOwnership::load() {
+            Some(co_ownership) => co_ownership.config.pro_rata(amount),
+            None => vec![(
+                ContractOwnershipAccountIdsObject::load().owner.clone(),
+                amount,
+            )],
+        }
+    }
+
+    /// returns true if the account is a co-owner, or is the owner account if the contract is not
+    /// co-owned
+    pub fn is_owner_payee(account_id: &str) -> bool {
+        match CoOwnership::load() {
+            Some(co_ownership) => co_ownership.config.is_co_owner(account_id),
+            None => ContractOwnershipAccountIdsObject::load().owner == account_id,
+        }
+    }
+
+    /// Transfers owner funds that were requested by the specified owner account. If the account is
+    /// the current owner account, then the funds are paid out to the owner payees.
+    pub(crate) fn transfer_owner_funds(owner_account_id: &str, amount: YoctoNear) {
+        if ContractOwnershipAccountIdsObject::load().owner != owner_account_id {
+            Promise::new(owner_account_id.to_string()).transfer(amount.value());
+            return;
+        }
+        for (account_id, amount) in Self::owner_payees(amount.value()) {
+            Promise::new(account_id).transfer(amount);
+        }
+    }
+
+    /// co-ownership ends when the contract changes owners
+    pub(crate) fn dissolve(reason: &str) {
+        if CoOwnership::delete() {
+            LOG_EVENT_CO_OWNERSHIP_CONFIG_CHANGE.log(format!("dissolved: {}", reason));
+        }
+    }
+
+    fn load_co_ownership() -> CoOwnership {
+        match CoOwnership::load() {
+            Some(co_ownership) => co_ownership,
+            None => {
+                ERR_CO_OWNERSHIP_NOT_CONFIGURED.panic();
+                unreachable!()
+            }
+        }
+    }
+
+    fn assert_co_owner(co_ownership: &CoOwnership) -> AccountId {
+        let account_id = env::predecessor_account_id();
+        ERR_CO_OWNER_ACCESS_REQUIRED.assert(|| co_ownership.config.is_co_owner(&account_id));
+        account_id
+    }
+
+    fn proposal_index(co_ownership: &CoOwnership, proposal_id: U64) -> usize {
+        match co_ownership
+            .proposals
+            .iter()
+            .position(|proposal| proposal.id == proposal_id)
+        {
+            Some(index) => index,
+            None => {
+                ERR_PROPOSAL_NOT_FOUND.panic();
+                unreachable!()
+            }
+        }
+    }
+
+    /// if the proposal approval weight meets the quorum, then the proposal command is executed and
+    /// the proposal is removed from the pending proposals
+    fn execute_if_approved(co_ownership: &mut CoOwnership, index: usize) {
+        let approval_weight = co_ownership
+            .config
+            .approval_weight(&co_ownership.proposals[index]);
+        if approval_weight >= co_ownership.config.quorum {
+            let proposal = co_ownership.proposals.remove(index);
+            LOG_EVENT_CO_OWNER_PROPOSAL_EXECUTED.log(format!(
+                "id: {} | method: {}",
+                proposal.id.0, proposal.command.method_name
+            ));
+            proposal.command.execute();
+        }
+    }
+}
+
+impl ContractCoOwnership for ContractCoOwnershipComponent {
+    fn ops_co_ownership(&self) -> Option<CoOwnershipConfig> {
+        CoOwnership::load().map(|co_ownership| co_ownership.config)
+    }
+
+    fn ops_co_ownership_configure(&mut self, config: Option<CoOwnershipConfig>) {
+        let current = CoOwnership::load();
+        match current.as_ref() {
+            None => {
+                ContractOwnerObject::assert_owner_command_access("ops_co_ownership_configure");
+            }
+            Some(_) => ERR_CO_OWNER_QUORUM_REQUIRED
+                .assert(|| env::predecessor_account_id() == env::current_account_id()),
+        }
+
+        match config {
+            None => Self::dissolve("co-ownership was dissolved by the co-owners"),
+            Some(config) => {
+                config.validate();
+                ERR_INVALID_CO_OWNERSHIP_CONFIG.assert_with_message(
+                    || !ContractMultiSigComponent::<()>::is_enabled(),
+                    || "contract cannot be co-owned while multisig is configured",
+                );
+                CoOwnership {
+                    config: config.clone(),
+                    next_proposal_id: current.map_or(1, |current| current.next_proposal_id),
+                    proposals: vec![],
+                }
+                .save();
+                LOG_EVENT_CO_OWNERSHIP_CONFIG_CHANGE.log(format!(
+                    "quorum: {} | co-owners: {:?}",
+                    config.quorum,
+                    config
+                        .co_owners
+                        .iter()
+                        .map(|co_owner| (co_owner.account_id.as_str(), co_owner.weight))
+                        .collect::<Vec<_>>()
+                ));
+            }
+        }
+    }
+
+    fn ops_co_owner_propose(
+        &mut self,
+        command: ProposalCommand,
+        expiration: Option<ExpirationSetting>,
+    ) -> Proposal {
+        let mut co_ownership = Self::load_co_ownership();
+        let proposer = Self::assert_co_owner(&co_ownership);
+        command.assert_allowed();
+
+        let expiration: Expiration = expiration
+            .map(Into::into)
+            .unwrap_or_else(|| co_ownership.config.proposal_expiration.into());
+        ERR_CODE_BAD_REQUEST.assert(
+            || !expiration.expired(),
+            || "expiration cannot be set to expired",
+        );
+
+        let proposal = Proposal {
+            id: co_ownership.next_proposal_id.into(),
+            proposer: proposer.clone(),
+            command,
+            approvals: vec![proposer],
+            expiration,
+        };
+        co_ownership.next_proposal_id += 1;
+        co_ownership.proposals.push(proposal.clone());
+        LOG_EVENT_CO_OWNER_PROPOSAL_CREATED.log(format!(
+            "id: {} | method: {} | expiration: {}",
+            proposal.id.0, proposal.command.method_name, proposal.expiration
+        ));
+
+        let index = co_ownership.proposals.len() - 1;
+        Self::execute_if_approved(&mut co_ownership, index);
+        co_ownership.save();
+        proposal
+    }
+
+    fn ops_co_owner_approve(&mut self, proposal_id: U64) -> Proposal {
+        let mut co_ownership = Self::load_co_ownership();
+        let approver = Self::assert_co_owner(&co_ownership);
+        let index = Self::proposal_index(&co_ownership, proposal_id);
+        {
+            let proposal = &mut co_ownership.proposals[index];
+            ERR_PROPOSAL_EXPIRED.assert(|| !proposal.expired());
+            ERR_PROPOSAL_ALREADY_APPROVED.assert(|| !proposal.approved_by(&approver));
+            proposal.approvals.push(approver);
+        }
+        let proposal = co_ownership.proposals[index].clone();
+        LOG_EVENT_CO_OWNER_PROPOSAL_APPROVED.log(format!(
+            "id: {} | approval weight: {}/{}",
+            proposal.id.0,
+            co_ownership.config.approval_weight(&proposal),
+            co_ownership.config.quorum
+        ));
+
+        Self::execute_if_approved(&mut co_ownership, index);
+        co_ownership.save();
+        proposal
+    }
+
+    fn ops_co_owner_cancel(&mut self, proposal_id: U64) {
+        let mut co_ownership = Self::load_co_ownership();
+        let index = Self::proposal_index(&co_ownership, proposal_id);
+        ERR_CO_OWNER_ACCESS_REQUIRED.assert(|| {
+            let account_id = env::predecessor_account_id();
+            let proposal = &co_ownership.proposals[index];
+            proposal.proposer == account_id
+                || (proposal.expired() && co_ownership.config.is_co_owner(&account_id))
+        });
+        let proposal = co_ownership.proposals.remove(index);
+        co_ownership.save();
+        LOG_EVENT_CO_OWNER_PROPOSAL_CANCELLED.log(format!("id: {}", proposal.id.0));
+    }
+
+    fn ops_co_owner_proposals(&self) -> Vec<Proposal> {
+        CoOwnership::load().map_or_else(Vec::new, |co_ownership| co_ownership.proposals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use crate::{CoOwner, ContractOwnership};
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::domain::ExpirationDuration;
+    use oysterpack_smart_near::near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+    const CONTRACT: &str = "contract";
+
+    fn deploy() -> VMContext {
+        let mut ctx = new_context(OWNER);
+        ctx.current_account_id = CONTRACT.to_string();
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ProposalCommand::register_methods(&["ops_owner_withdraw_balance"]);
+        ctx
+    }
+
+    fn config() -> CoOwnershipConfig {
+        CoOwnershipConfig {
+            co_owners: vec![
+                CoOwner {
+                    account_id: "alice".to_string(),
+                    weight: 50,
+                },
+                CoOwner {
+                    account_id: "bob".to_string(),
+                    weight: 30,
+                },
+                CoOwner {
+                    account_id: "charlie".to_string(),
+                    weight: 20,
+                },
+            ],
+            quorum: 51,
+            proposal_expiration: ExpirationDuration::Epochs(3),
+        }
+    }
+
+    fn command() -> ProposalCommand {
+        ProposalCommand {
+            method_name: "ops_owner_withdraw_balance".to_string(),
+            args: r#"{"amount":"1000"}"#.to_string(),
+            deposit: 1.into(),
+            gas: 10.into(),
+        }
+    }
+
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(transfer) => Some((receipt.receiver_id.clone(), transfer.deposit)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn co_ownership_workflow() {
+        // Arrange
+        let mut ctx = deploy();
+        let mut co_ownership = ContractCoOwnershipComponent;
+        assert!(!ContractCoOwnershipComponent::is_enabled());
+        assert_eq!(
+            ContractCoOwnershipComponent::owner_payees(100),
+            vec![(OWNER.to_string(), 100)]
+        );
+
+        // Act - owner configures co-ownership
+        co_ownership.ops_co_ownership_configure(Some(config()));
+
+        // Assert
+        assert!(ContractCoOwnershipComponent::is_enabled());
+        assert_eq!(co_ownership.ops_co_ownership().unwrap(), config());
+        assert_eq!(ContractOwnershipComponent.ops_owner(), OWNER);
+        assert!(ContractCoOwnershipComponent::is_owner_payee("bob"));
+        assert!(!ContractCoOwnershipComponent::is_owner_payee(OWNER));
+
+        // Act - alice proposes, which does not meet the quorum on its own
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal = co_ownership.ops_co_owner_propose(command(), None);
+
+        // Assert
+        assert_eq!(proposal.id, 1.into());
+        assert!(deserialize_receipts().is_empty());
+        assert_eq!(
+            co_ownership.ops_co_owner_proposals(),
+            vec![proposal.clone()]
+        );
+
+        // Act - charlie approves, which reaches the quorum
+        ctx.predecessor_account_id = "charlie".to_string();
+        testing_env!(ctx.clone());
+        co_ownership.ops_co_owner_approve(proposal.id);
+
+        // Assert
+        assert!(co_ownership.ops_co_owner_proposals().is_empty());
+        let receipts = deserialize_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, CONTRACT);
+        match &receipts[0].actions[0] {
+            Action::FunctionCall(action) => {
+                assert_eq!(action.method_name, "ops_owner_withdraw_balance");
+            }
+            _ => panic!("expected FunctionCall"),
+        }
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [CO_OWNER_PROPOSAL_EXECUTED]")));
+
+        // Act - the approved command is executed by the contract
+        ctx.predecessor_account_id = CONTRACT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some(1000.into()));
+
+        // Assert - the withdrawal is distributed pro rata, and the remainder goes to the first co-owner
+        assert_eq!(
+            transfers(),
+            vec![
+                ("alice".to_string(), 501),
+                ("bob".to_string(), 300),
+                ("charlie".to_string(), 200)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CO_OWNER_QUORUM_REQUIRED]")]
+    fn owner_action_requires_quorum() {
+        let mut ctx = deploy();
+        ContractCoOwnershipComponent.ops_co_ownership_configure(Some(config()));
+
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_withdraw_balance(None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CO_OWNER_QUORUM_REQUIRED]")]
+    fn reconfigure_requires_quorum() {
+        deploy();
+        let mut co_ownership = ContractCoOwnershipComponent;
+        co_ownership.ops_co_ownership_configure(Some(config()));
+        co_ownership.ops_co_ownership_configure(None);
+    }
+
+    #[test]
+    fn dissolved_when_ownership_is_transferred() {
+        // Arrange
+        let mut ctx = deploy();
+        ContractCoOwnershipComponent.ops_co_ownership_configure(Some(config()));
+
+        // Act
+        ctx.predecessor_account_id = CONTRACT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id("dave"));
+        ctx.predecessor_account_id = "dave".to_string();
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_finalize_transfer();
+
+        // Assert
+        assert!(ContractCoOwnershipComponent.ops_co_ownership().is_none());
+        assert_eq!(ContractOwnershipComponent.ops_owner(), "dave");
+        assert!(test_utils::get_logs().contains(
+            &LOG_EVENT_CO_OWNERSHIP_CONFIG_CHANGE
+                .message("dissolved: contract ownership was transferred")
+        ));
+    }
+
+    #[test]
+    fn proposal_cancelled_by_proposer() {
+        let mut ctx = deploy();
+        let mut co_ownership = ContractCoOwnershipComponent;
+        co_ownership.ops_co_ownership_configure(Some(config()));
+
+        ctx.predecessor_account_id = "bob".to_string();
+        testing_env!(ctx.clone());
+        let proposal = co_ownership.ops_co_owner_propose(command(), None);
+        co_ownership.ops_co_owner_cancel(proposal.id);
+
+        assert!(co_ownership.ops_co_owner_proposals().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CO_OWNER_ACCESS_REQUIRED]")]
+    fn propose_as_non_co_owner() {
+        deploy();
+        let mut co_ownership = ContractCoOwnershipComponent;
+        co_ownership.ops_co_ownership_configure(Some(config()));
+        co_ownership.ops_co_owner_propose(command(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_COMMAND_NOT_ALLOWED]")]
+    fn propose_unregistered_method() {
+        let mut ctx = deploy();
+        let mut co_ownership = ContractCoOwnershipComponent;
+        co_ownership.ops_co_ownership_configure(Some(config()));
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx);
+        let mut command = command();
+        command.method_name = "ops_owner_transfer".to_string();
+        co_ownership.ops_co_owner_propose(command, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_COMMAND_NOT_ALLOWED]")]
+    fn propose_callback() {
+        let mut ctx = deploy();
+        let mut co_ownership = ContractCoOwnershipComponent;
+        co_ownership.ops_co_ownership_configure(Some(config()));
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx);
+        let mut command = command();
+        command.method_name = "ops_contract_sale_resolve_token_transfer".to_string();
+        co_ownership.ops_co_owner_propose(command, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID_CO_OWNERSHIP_CONFIG]")]
+    fn quorum_must_be_majority() {
+        deploy();
+        let mut config = config();
+        config.quorum = 50;
+        ContractCoOwnershipComponent.ops_co_ownership_configure(Some(config));
+    }
+
+    #[test]
+    fn pro_rata() {
+        let config = config();
+        assert_eq!(
+            config.pro_rata(9),
+            vec![
+                ("alice".to_string(), 6),
+                ("bob".to_string(), 2),
+                ("charlie".to_string(), 1)
+            ]
+        );
+        // zero shares are excluded
+        assert_eq!(config.pro_rata(1), vec![("alice".to_string(), 1)]);
+    }
+}
@@ -0,0 +1,192 @@
+//! [`ContractHealthCheckComponent`]
+//! - contracts extend the report returned by [`ContractHealthCheck::ops_health_check`] with their
+//!   own component checks
+
+use crate::components::contract_owner_withdrawals::ContractOwnerWithdrawalsComponent;
+use crate::{
+    ContractBid, ContractHealthCheck, ContractNearBalances, ContractOwnerObject,
+    ContractSaleTokens, HealthCheckReport, NearBalances, HEALTH_CHECK_ACCOUNT_NEAR_BALANCES,
+    HEALTH_CHECK_CONTRACT_BID_BALANCE, HEALTH_CHECK_CONTRACT_NEAR_BALANCES,
+    HEALTH_CHECK_OWNER_BALANCE, HEALTH_CHECK_STORAGE_STAKING, HEALTH_CHECK_STORAGE_USAGE,
+};
+use oysterpack_smart_account_management::AccountMetrics;
+use oysterpack_smart_near::domain::{StorageUsage, YoctoNear};
+use oysterpack_smart_near::near_sdk::env;
+
+pub struct ContractHealthCheckComponent;
+
+impl ContractHealthCheckComponent {
+    /// formats the balances sorted by balance ID - registered balance IDs are formatted by name
+    fn format_near_balances(near_balances: &NearBalances) -> String {
+        let mut balances: Vec<_> = near_balances.iter().collect();
+        balances.sort_by_key(|(id, _)| id.0);
+        balances
+            .iter()
+            .map(|(id, balance)| {
+                let id = ContractNearBalances::balance_id_label(**id)
+                    .map_or_else(|| id.0.to_string(), |label| label.name);
+                format!("{}={}", id, balance)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl ContractHealthCheck for ContractHealthCheckComponent {
+    fn ops_health_check(&self) -> HealthCheckReport {
+        let mut report = HealthCheckReport::new();
+        let account_metrics = AccountMetrics::load();
+        let liquid = env::account_balance();
+        let total = liquid + env::account_locked_balance();
+        let accounts = account_metrics.total_near_balance.value();
+        let near_balances = ContractNearBalances::load_near_balances();
+        let balances: u128 = near_balances.values().map(|balance| balance.value()).sum();
+
+        let owner = total
+            .checked_sub(accounts)
+            .and_then(|balance| balance.checked_sub(balances));
+        report.check(HEALTH_CHECK_CONTRACT_NEAR_BALANCES, owner.is_some(), || {
+            format!(
+                "total: {} < accounts: {} + balances: {} [{}]",
+                total,
+                accounts,
+                balances,
+                Self::format_near_balances(&near_balances)
+            )
+        });
+        report.check(
+            HEALTH_CHECK_ACCOUNT_NEAR_BALANCES,
+            liquid >= accounts,
+            || format!("account_balance: {} < accounts: {}", liquid, accounts),
+        );
+
+        let storage_usage = env::storage_usage();
+        let account_storage_usage = account_metrics.total_storage_usage.value();
+        report.check(
+            HEALTH_CHECK_STORAGE_USAGE,
+            account_storage_usage <= storage_usage,
+            || {
+                format!(
+                    "account storage usage: {} > contract storage usage: {}",
+                    account_storage_usage, storage_usage
+                )
+            },
+        );
+        let storage_cost = StorageUsage(storage_usage).cost();
+        report.check(
+            HEALTH_CHECK_STORAGE_STAKING,
+            liquid >= storage_cost.value(),
+            || {
+                format!(
+                    "account_balance: {} < storage cost: {}",
+                    liquid, storage_cost
+                )
+            },
+        );
+
+        // the owner balance can only be checked if it could be derived
+        if let Some(owner) = owner {
+            let pending_withdrawals =
+                ContractOwnerWithdrawalsComponent::pending_withdrawals_total();
+            report.check(
+                HEALTH_CHECK_OWNER_BALANCE,
+                owner >= pending_withdrawals.value(),
+                || {
+                    format!(
+                        "owner: {} < pending withdrawals: {}",
+                        YoctoNear::from(owner),
+                        pending_withdrawals
+                    )
+                },
+            );
+        }
+
+        let near_bid = match ContractOwnerObject::load().bid() {
+            Some((_, bid)) if ContractSaleTokens::load().bid_token().is_none() => bid.amount.into(),
+            _ => YoctoNear::ZERO,
+        };
+        let bid_balance = ContractBid::near_balance();
+        report.check(
+            HEALTH_CHECK_CONTRACT_BID_BALANCE,
+            bid_balance == near_bid,
+            || format!("bid balance: {} != NEAR bid: {}", bid_balance, near_bid),
+        );
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::contract_metrics::ContractMetricsComponent;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use crate::{BalanceId, ContractMetrics};
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+
+    fn deploy() {
+        let ctx = new_context(OWNER);
+        testing_env!(ctx);
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+    }
+
+    #[test]
+    fn healthy() {
+        deploy();
+        ContractNearBalances::incr_balance(BalanceId(1), YOCTO.into());
+
+        let report = ContractHealthCheckComponent.ops_health_check();
+
+        assert!(report.is_healthy(), "{:?}", report);
+        assert_eq!(
+            report.checks,
+            vec![
+                HEALTH_CHECK_CONTRACT_NEAR_BALANCES,
+                HEALTH_CHECK_ACCOUNT_NEAR_BALANCES,
+                HEALTH_CHECK_STORAGE_USAGE,
+                HEALTH_CHECK_STORAGE_STAKING,
+                HEALTH_CHECK_OWNER_BALANCE,
+                HEALTH_CHECK_CONTRACT_BID_BALANCE,
+            ]
+        );
+    }
+
+    #[test]
+    fn balances_exceed_contract_balance() {
+        deploy();
+        ContractNearBalances::set_balance(
+            BalanceId(1),
+            (env::account_balance() + env::account_locked_balance() + 1).into(),
+        );
+
+        let report = ContractHealthCheckComponent.ops_health_check();
+
+        assert!(!report.is_healthy());
+        assert!(report.violated(HEALTH_CHECK_CONTRACT_NEAR_BALANCES));
+        // the owner balance cannot be derived
+        assert!(!report
+            .checks
+            .contains(&HEALTH_CHECK_OWNER_BALANCE.to_string()));
+        // metrics views do not panic when the owner balance cannot be derived
+        let near_balances = ContractMetricsComponent.ops_metrics_near_balances();
+        assert_eq!(near_balances.owner(), YoctoNear::ZERO);
+    }
+
+    #[test]
+    fn contract_bid_balance_drift() {
+        deploy();
+        ContractBid::incr_near_balance(YOCTO.into());
+
+        let report = ContractHealthCheckComponent.ops_health_check();
+
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(
+            report.violations[0].check,
+            HEALTH_CHECK_CONTRACT_BID_BALANCE
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! [`ContractMetricsComponent`]
 
 use crate::{ContractMetrics, ContractMetricsSnapshot};
-use crate::{ContractNearBalances, ContractStorageUsage, ContractStorageUsageCosts};
+use crate::{
+    ContractNearBalances, ContractStorageUsage, ContractStorageUsageCosts, RegisteredBalanceId,
+};
 use oysterpack_smart_account_management::AccountMetrics;
 use oysterpack_smart_near::data::numbers::U128;
 use oysterpack_smart_near::domain::BlockTime;
@@ -35,29 +37,43 @@ impl ContractMetrics for ContractMetricsComponent {
 
     fn ops_metrics(&self) -> ContractMetricsSnapshot {
         let storage_usage = self.ops_metrics_contract_storage_usage();
+        let storage_usage_by_component = self
+            .ops_metrics_accounts()
+            .storage_usage_by_component
+            .into_iter()
+            .map(|(component, storage_usage)| (component, storage_usage.into()))
+            .collect();
         ContractMetricsSnapshot {
             block_time: BlockTime::from_env(),
             total_registered_accounts: self.ops_metrics_total_registered_accounts(),
             storage_usage,
             near_balances: self.ops_metrics_near_balances(),
             storage_usage_costs: storage_usage.into(),
+            storage_usage_by_component,
         }
     }
 
     fn ops_metrics_accounts(&self) -> AccountMetrics {
         AccountMetrics::load()
     }
+
+    fn ops_metrics_balance_ids(&self) -> Vec<RegisteredBalanceId> {
+        ContractNearBalances::registered_balance_ids()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use crate::{ContractBid, CONTRACT_LOCKED_STORAGE_BALANCE};
     use near_sdk::env;
     use oysterpack_smart_account_management::components::account_management::{
         AccountManagementComponent, AccountManagementComponentConfig,
     };
     use oysterpack_smart_account_management::{
-        AccountRepository, StorageManagement, StorageUsageBounds,
+        AccountRepository, StorageManagement, StorageUsageBounds, COMPONENT_ID_ACCOUNT,
+        COMPONENT_ID_ACCOUNT_REGISTRY,
     };
     use oysterpack_smart_near::component::*;
     use oysterpack_smart_near::domain::YoctoNear;
@@ -203,6 +219,46 @@ mod tests {
             assert_eq!(metrics.block_time.timestamp.value(), 1);
             assert_eq!(metrics.block_time.height.value(), 2);
             assert_eq!(metrics.block_time.epoch.value(), 3);
+
+            let storage_usage_by_component = &metrics.storage_usage_by_component;
+            assert_eq!(
+                storage_usage_by_component
+                    .values()
+                    .map(|usage| usage.storage_usage.value())
+                    .sum::<u64>(),
+                metrics.storage_usage.accounts().value()
+            );
+            assert_eq!(
+                storage_usage_by_component
+                    .values()
+                    .map(|usage| usage.cost.value())
+                    .sum::<u128>(),
+                metrics.storage_usage_costs.accounts().value()
+            );
+            assert!(storage_usage_by_component.contains_key(COMPONENT_ID_ACCOUNT.name()));
+            assert!(storage_usage_by_component.contains_key(COMPONENT_ID_ACCOUNT_REGISTRY.name()));
+        });
+    }
+
+    #[test]
+    fn balance_ids_are_registered_on_deployment() {
+        run_test(|_ctx, _account_manager| {
+            ContractOwnershipComponent::deploy(to_valid_account_id(ADMIN));
+            ContractBid::incr_near_balance(YOCTO.into());
+
+            let balance_ids = ContractMetricsComponent.ops_metrics_balance_ids();
+            assert_eq!(balance_ids.len(), 3);
+            assert!(balance_ids.iter().any(|balance_id| balance_id.id
+                == CONTRACT_LOCKED_STORAGE_BALANCE
+                && balance_id.label.component == "ContractOperatorComponent"));
+
+            let near_balances = ContractMetricsComponent.ops_metrics_near_balances();
+            let labeled_balances = near_balances.labeled_balances().unwrap();
+            assert_eq!(labeled_balances.len(), 1);
+            assert_eq!(
+                labeled_balances[0].label.as_ref().unwrap().name,
+                "contract_bid"
+            );
         });
     }
 }
@@ -0,0 +1,638 @@
+//! [`ContractMultiSigComponent`]
+
+use crate::{
+    CoOwnership, ContractMultiSig, ContractOwnerObject, MultiSigConfig, Proposal, ProposalCommand,
+    ERR_INVALID_MULTISIG_CONFIG, ERR_MULTISIG_APPROVAL_REQUIRED,
+    ERR_MULTISIG_APPROVER_ACCESS_REQUIRED, ERR_MULTISIG_NOT_CONFIGURED,
+    ERR_PROPOSAL_ALREADY_APPROVED, ERR_PROPOSAL_EXPIRED, ERR_PROPOSAL_NOT_FOUND,
+    LOG_EVENT_MULTISIG_CONFIG_CHANGE, LOG_EVENT_MULTISIG_PROPOSAL_APPROVED,
+    LOG_EVENT_MULTISIG_PROPOSAL_CANCELLED, LOG_EVENT_MULTISIG_PROPOSAL_CREATED,
+    LOG_EVENT_MULTISIG_PROPOSAL_EXECUTED,
+};
+use oysterpack_smart_account_management::components::account_management::AccountManagementComponent;
+use oysterpack_smart_near::asserts::ERR_CODE_BAD_REQUEST;
+use oysterpack_smart_near::component::{Component, ComponentState};
+use oysterpack_smart_near::domain::{Expiration, ExpirationSetting};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    json_types::U64,
+};
+use std::fmt::Debug;
+
+pub struct ContractMultiSigComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    _account_manager: AccountManagementComponent<T>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MultiSigState {
+    pub config: MultiSigConfig,
+    pub next_proposal_id: u64,
+    pub proposals: Vec<Proposal>,
+}
+
+impl<T> Component for ContractMultiSigComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    type State = MultiSigState;
+
+    const STATE_KEY: u128 = 1957451097164870528436719474309364611;
+}
+
+impl<T> ContractMultiSigComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub fn new(account_manager: AccountManagementComponent<T>) -> Self {
+        Self {
+            _account_manager: account_manager,
+        }
+    }
+
+    pub fn is_enabled() -> bool {
+        ComponentState::<MultiSigState>::exists(&Self::STATE_KEY)
+    }
+
+    /// returns true if the function call was submitted by an approved proposal, i.e., the contract
+    /// is the predecessor account
+    pub fn is_approved_call() -> bool {
+        env::predecessor_account_id() == env::current_account_id()
+    }
+
+    /// Used to plug multisig approval into commands. If multisig is configured, then the command
+    /// must be submitted through an approved proposal.
+    ///
+    /// ## Panics
+    /// if multisig is configured and the predecessor account is not the contract account
+    pub fn assert_approved_if_enabled() {
+        if Self::is_enabled() {
+            ERR_MULTISIG_APPROVAL_REQUIRED.assert(Self::is_approved_call);
+        }
+    }
+
+    fn load_multisig_state() -> ComponentState<MultiSigState> {
+        match Self::load_state() {
+            Some(state) => state,
+            None => {
+                ERR_MULTISIG_NOT_CONFIGURED.panic();
+                unreachable!()
+            }
+        }
+    }
+
+    fn assert_approver(state: &MultiSigState) -> String {
+        let account_id = env::predecessor_account_id();
+        ERR_MULTISIG_APPROVER_ACCESS_REQUIRED.assert(|| state.config.is_approver(&account_id));
+        account_id
+    }
+
+    fn proposal_index(state: &MultiSigState, proposal_id: U64) -> usize {
+        match state
+            .proposals
+            .iter()
+            .position(|proposal| proposal.id == proposal_id)
+        {
+            Some(index) => index,
+            None => {
+                ERR_PROPOSAL_NOT_FOUND.panic();
+                unreachable!()
+            }
+        }
+    }
+
+    /// if the proposal is approved, then the proposal command is executed and the proposal is
+    /// removed from the pending proposals
+    fn execute_if_approved(state: &mut MultiSigState, index: usize) {
+        if state.proposals[index].approved(state.config.threshold) {
+            let proposal = state.proposals.remove(index);
+            LOG_EVENT_MULTISIG_PROPOSAL_EXECUTED.log(format!(
+                "id: {} | method: {}",
+                proposal.id.0, proposal.command.method_name
+            ));
+            proposal.command.execute();
+        }
+    }
+
+    /// grants the contract account admin permission in order for approved proposal commands to
+    /// pass the account permission checks
+    fn grant_contract_admin() {
+        let mut account =
+            AccountManagementComponent::<T>::get_or_register_account(&env::current_account_id());
+        if !account.is_admin() {
+            account.grant_admin();
+            account.save();
+        }
+    }
+}
+
+impl<T> ContractMultiSig for ContractMultiSigComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    fn ops_multisig_config(&self) -> Option<MultiSigConfig> {
+        Self::load_state().map(|state| state.config.clone())
+    }
+
+    fn ops_multisig_configure(&mut self, config: MultiSigConfig) {
+        match Self::load_state() {
+            None => {
+                ContractOwnerObject::assert_owner_access();
+            }
+            Some(_) => ERR_MULTISIG_APPROVAL_REQUIRED.assert(Self::is_approved_call),
+        }
+        config.validate();
+        ERR_INVALID_MULTISIG_CONFIG.assert_with_message(
+            || !CoOwnership::exists(),
+            || "multisig cannot be configured while the contract is co-owned",
+        );
+
+        let next_proposal_id = Self::load_state().map_or(1, |state| state.next_proposal_id);
+        let state = Self::new_state(MultiSigState {
+            config: config.clone(),
+            next_proposal_id,
+            proposals: vec![],
+        });
+        state.save();
+        config.approval_required_commands().save();
+        Self::grant_contract_admin();
+        LOG_EVENT_MULTISIG_CONFIG_CHANGE.log(format!(
+            "threshold: {} | approvers: {:?}",
+            config.threshold, config.approvers
+        ));
+    }
+
+    fn ops_multisig_propose(
+        &mut self,
+        command: ProposalCommand,
+        expiration: Option<ExpirationSetting>,
+    ) -> Proposal {
+        let mut state = Self::load_multisig_state();
+        let proposer = Self::assert_approver(&state);
+        command.assert_allowed();
+
+        let expiration: Expiration = expiration
+            .map(Into::into)
+            .unwrap_or_else(|| state.config.proposal_expiration.into());
+        ERR_CODE_BAD_REQUEST.assert(
+            || !expiration.expired(),
+            || "expiration cannot be set to expired",
+        );
+
+        let proposal = Proposal {
+            id: state.next_proposal_id.into(),
+            proposer: proposer.clone(),
+            command,
+            approvals: vec![proposer],
+            expiration,
+        };
+        state.next_proposal_id += 1;
+        state.proposals.push(proposal.clone());
+        LOG_EVENT_MULTISIG_PROPOSAL_CREATED.log(format!(
+            "id: {} | method: {} | expiration: {}",
+            proposal.id.0, proposal.command.method_name, proposal.expiration
+        ));
+
+        let index = state.proposals.len() - 1;
+        Self::execute_if_approved(&mut state, index);
+        state.save();
+        proposal
+    }
+
+    fn ops_multisig_approve(&mut self, proposal_id: U64) -> Proposal {
+        let mut state = Self::load_multisig_state();
+        let approver = Self::assert_approver(&state);
+        let index = Self::proposal_index(&state, proposal_id);
+        {
+            let proposal = &mut state.proposals[index];
+            ERR_PROPOSAL_EXPIRED.assert(|| !proposal.expired());
+            ERR_PROPOSAL_ALREADY_APPROVED.assert(|| !proposal.approved_by(&approver));
+            proposal.approvals.push(approver);
+        }
+        let proposal = state.proposals[index].clone();
+        LOG_EVENT_MULTISIG_PROPOSAL_APPROVED.log(format!(
+            "id: {} | approvals: {}/{}",
+            proposal.id.0,
+            proposal.approvals.len(),
+            state.config.threshold
+        ));
+
+        Self::execute_if_approved(&mut state, index);
+        state.save();
+        proposal
+    }
+
+    fn ops_multisig_cancel(&mut self, proposal_id: U64) {
+        let mut state = Self::load_multisig_state();
+        let index = Self::proposal_index(&state, proposal_id);
+        ERR_MULTISIG_APPROVER_ACCESS_REQUIRED.assert(|| {
+            let account_id = env::predecessor_account_id();
+            let proposal = &state.proposals[index];
+            proposal.proposer == account_id
+                || (proposal.expired() && state.config.is_approver(&account_id))
+        });
+        let proposal = state.proposals.remove(index);
+        state.save();
+        LOG_EVENT_MULTISIG_PROPOSAL_CANCELLED.log(format!("id: {}", proposal.id.0));
+    }
+
+    fn ops_multisig_proposal(&self, proposal_id: U64) -> Option<Proposal> {
+        Self::load_state().and_then(|state| {
+            state
+                .proposals
+                .iter()
+                .find(|proposal| proposal.id == proposal_id)
+                .cloned()
+        })
+    }
+
+    fn ops_multisig_proposals(&self) -> Vec<Proposal> {
+        Self::load_state().map_or_else(Vec::new, |state| state.proposals.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use crate::ContractOwnership;
+    use oysterpack_smart_account_management::components::account_management::AccountManagementComponentConfig;
+    use oysterpack_smart_account_management::PermissionsManagement;
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::domain::ExpirationDuration;
+    use oysterpack_smart_near::near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+    type MultiSig = ContractMultiSigComponent<()>;
+
+    const OWNER: &str = "owner";
+    const CONTRACT: &str = "contract";
+
+    fn deploy() -> VMContext {
+        let mut ctx = new_context(OWNER);
+        ctx.current_account_id = CONTRACT.to_string();
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            component_account_storage_mins: None,
+            admin_account: to_valid_account_id(OWNER),
+        });
+        ProposalCommand::register_methods(&[
+            "ops_permissions_revoke_all",
+            "ops_multisig_configure",
+            "ops_owner_transfer",
+        ]);
+        ctx
+    }
+
+    fn config() -> MultiSigConfig {
+        MultiSigConfig {
+            approvers: vec![
+                "alice".to_string(),
+                "bob".to_string(),
+                "charlie".to_string(),
+            ],
+            threshold: 2,
+            proposal_expiration: ExpirationDuration::Epochs(3),
+            commands: None,
+        }
+    }
+
+    fn command() -> ProposalCommand {
+        ProposalCommand {
+            method_name: "ops_permissions_revoke_all".to_string(),
+            args: r#"{"account_id":"mallory"}"#.to_string(),
+            deposit: 0.into(),
+            gas: 10.into(),
+        }
+    }
+
+    #[test]
+    fn multisig_workflow() {
+        // Arrange
+        let ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        assert!(!MultiSig::is_enabled());
+        assert!(multisig.ops_multisig_config().is_none());
+        assert!(multisig.ops_multisig_proposals().is_empty());
+
+        // Act - owner configures multisig
+        multisig.ops_multisig_configure(config());
+
+        // Assert
+        assert!(MultiSig::is_enabled());
+        assert_eq!(multisig.ops_multisig_config().unwrap(), config());
+        assert!(AccountManager::default().ops_permissions_is_admin(to_valid_account_id(CONTRACT)));
+
+        // Act - alice proposes
+        let mut ctx = ctx.clone();
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal = multisig.ops_multisig_propose(command(), None);
+
+        // Assert
+        assert_eq!(proposal.id, 1.into());
+        assert_eq!(proposal.approvals, vec!["alice".to_string()]);
+        assert_eq!(
+            proposal.expiration,
+            Expiration::Epoch((ctx.epoch_height + 3).into())
+        );
+        assert_eq!(
+            multisig.ops_multisig_proposal(proposal.id).unwrap(),
+            proposal
+        );
+        assert!(deserialize_receipts().is_empty());
+
+        // Act - bob approves, which reaches the threshold
+        ctx.predecessor_account_id = "bob".to_string();
+        testing_env!(ctx.clone());
+        let proposal = multisig.ops_multisig_approve(proposal.id);
+
+        // Assert
+        assert_eq!(
+            proposal.approvals,
+            vec!["alice".to_string(), "bob".to_string()]
+        );
+        assert!(multisig.ops_multisig_proposal(proposal.id).is_none());
+        let receipts = deserialize_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, CONTRACT);
+        match &receipts[0].actions[0] {
+            Action::FunctionCall(action) => {
+                assert_eq!(action.method_name, "ops_permissions_revoke_all");
+                assert_eq!(action.args, r#"{"account_id":"mallory"}"#);
+            }
+            _ => panic!("expected FunctionCall"),
+        }
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [MULTISIG_PROPOSAL_EXECUTED]")));
+    }
+
+    #[test]
+    fn assert_approved_if_enabled() {
+        let mut ctx = deploy();
+        MultiSig::assert_approved_if_enabled();
+
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = CONTRACT.to_string();
+        testing_env!(ctx.clone());
+        MultiSig::assert_approved_if_enabled();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MULTISIG_APPROVAL_REQUIRED]")]
+    fn assert_approved_if_enabled_not_approved() {
+        deploy();
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config());
+        MultiSig::assert_approved_if_enabled();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [APPROVAL_REQUIRED] ops_permissions_revoke_all")]
+    fn admin_commands_require_approval() {
+        deploy();
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config());
+        AccountManager::default().ops_permissions_revoke_all(to_valid_account_id(CONTRACT));
+    }
+
+    #[test]
+    fn configured_commands_require_approval() {
+        let mut ctx = deploy();
+        let mut config = config();
+        config.commands = Some(vec!["ops_permissions_revoke_all".to_string()]);
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config);
+
+        let mut account_manager = AccountManager::default();
+        // commands that are not configured do not require approval
+        account_manager.ops_permissions_revoke_operator(to_valid_account_id(CONTRACT));
+
+        ctx.predecessor_account_id = CONTRACT.to_string();
+        testing_env!(ctx.clone());
+        account_manager.ops_permissions_revoke_all(to_valid_account_id(OWNER));
+        assert!(!account_manager.ops_permissions_is_admin(to_valid_account_id(OWNER)));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [APPROVAL_REQUIRED] ops_owner_transfer")]
+    fn owner_commands_require_approval() {
+        let mut ctx = deploy();
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config());
+
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id("bob"));
+    }
+
+    #[test]
+    fn owner_commands_via_approved_proposal() {
+        let mut ctx = deploy();
+        let mut commands_config = config();
+        commands_config.commands = Some(vec!["ops_permissions_revoke_all".to_string()]);
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(commands_config);
+
+        // owner commands that are not configured do not require approval
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id("bob"));
+        assert_eq!(
+            ContractOwnershipComponent.ops_owner_prospective(),
+            Some("bob".to_string())
+        );
+
+        // approved proposals act on behalf of the owner
+        ctx.predecessor_account_id = CONTRACT.to_string();
+        testing_env!(ctx.clone());
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config());
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id("charlie"));
+        assert_eq!(
+            ContractOwnershipComponent.ops_owner_prospective(),
+            Some("charlie".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MULTISIG_APPROVAL_REQUIRED]")]
+    fn reconfigure_requires_approval() {
+        deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+        multisig.ops_multisig_configure(config());
+    }
+
+    #[test]
+    fn reconfigure_via_approved_proposal_cancels_pending_proposals() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        multisig.ops_multisig_propose(command(), None);
+
+        ctx.predecessor_account_id = CONTRACT.to_string();
+        testing_env!(ctx.clone());
+        let mut config = config();
+        config.threshold = 3;
+        multisig.ops_multisig_configure(config.clone());
+        assert_eq!(multisig.ops_multisig_config().unwrap(), config);
+        assert!(multisig.ops_multisig_proposals().is_empty());
+
+        // proposal IDs are not reused
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal = multisig.ops_multisig_propose(command(), None);
+        assert_eq!(proposal.id, 2.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_ACCESS_REQUIRED]")]
+    fn configure_requires_owner() {
+        let mut ctx = deploy();
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID_MULTISIG_CONFIG]")]
+    fn configure_with_threshold_greater_than_approvers() {
+        deploy();
+        let mut config = config();
+        config.threshold = 4;
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID_MULTISIG_CONFIG]")]
+    fn configure_with_duplicate_approvers() {
+        deploy();
+        let mut config = config();
+        config.approvers.push("alice".to_string());
+        MultiSig::new(AccountManager::default()).ops_multisig_configure(config);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MULTISIG_APPROVER_ACCESS_REQUIRED]")]
+    fn propose_not_approver() {
+        deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+        multisig.ops_multisig_propose(command(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_COMMAND_NOT_ALLOWED]")]
+    fn propose_unregistered_method() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx);
+        let mut command = command();
+        command.method_name = "ops_permissions_grant_admin".to_string();
+        multisig.ops_multisig_propose(command, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_COMMAND_NOT_ALLOWED]")]
+    fn register_callback() {
+        deploy();
+        ProposalCommand::register_methods(&["ops_permissions_revoke_all", "ft_on_transfer"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MULTISIG_NOT_CONFIGURED]")]
+    fn propose_not_configured() {
+        deploy();
+        MultiSig::new(AccountManager::default()).ops_multisig_propose(command(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_ALREADY_APPROVED]")]
+    fn approve_twice() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal = multisig.ops_multisig_propose(command(), None);
+        multisig.ops_multisig_approve(proposal.id);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_EXPIRED]")]
+    fn approve_expired_proposal() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal = multisig.ops_multisig_propose(command(), None);
+
+        ctx.predecessor_account_id = "bob".to_string();
+        ctx.epoch_height += 4;
+        testing_env!(ctx.clone());
+        multisig.ops_multisig_approve(proposal.id);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [PROPOSAL_NOT_FOUND]")]
+    fn approve_unknown_proposal() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        multisig.ops_multisig_approve(1.into());
+    }
+
+    #[test]
+    fn cancel() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal_1 = multisig.ops_multisig_propose(command(), None);
+        let proposal_2 = multisig.ops_multisig_propose(command(), None);
+        assert_eq!(multisig.ops_multisig_proposals().len(), 2);
+
+        // proposer can cancel
+        multisig.ops_multisig_cancel(proposal_1.id);
+        assert_eq!(multisig.ops_multisig_proposals(), vec![proposal_2.clone()]);
+
+        // any approver can cancel expired proposals
+        ctx.predecessor_account_id = "bob".to_string();
+        ctx.epoch_height += 4;
+        testing_env!(ctx.clone());
+        multisig.ops_multisig_cancel(proposal_2.id);
+        assert!(multisig.ops_multisig_proposals().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MULTISIG_APPROVER_ACCESS_REQUIRED]")]
+    fn cancel_unexpired_proposal_not_proposer() {
+        let mut ctx = deploy();
+        let mut multisig = MultiSig::new(AccountManager::default());
+        multisig.ops_multisig_configure(config());
+
+        ctx.predecessor_account_id = "alice".to_string();
+        testing_env!(ctx.clone());
+        let proposal = multisig.ops_multisig_propose(command(), None);
+
+        ctx.predecessor_account_id = "bob".to_string();
+        testing_env!(ctx.clone());
+        multisig.ops_multisig_cancel(proposal.id);
+    }
+}
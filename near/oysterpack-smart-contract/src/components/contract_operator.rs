@@ -4,12 +4,13 @@ use crate::contract::contract_operator::CONTRACT_LOCKED_STORAGE_BALANCE;
 use crate::interface::contract::contract_operator::ContractOperator;
 use crate::{ContractNearBalances, ContractOwnerObject, ContractOwnershipAccountIdsObject};
 use oysterpack_smart_account_management::components::account_management::AccountManagementComponent;
-use oysterpack_smart_account_management::AccountRepository;
+use oysterpack_smart_account_management::{AccountRepository, AuditLog};
 use oysterpack_smart_near::{
     domain::StorageUsage,
     near_sdk::{
         borsh::{BorshDeserialize, BorshSerialize},
         env,
+        serde_json::json,
     },
 };
 use std::fmt::Debug;
@@ -35,19 +36,26 @@ where
     T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
 {
     fn ops_operator_lock_storage_balance(&mut self, storage_usage: StorageUsage) {
-        self.account_manager.assert_operator();
+        self.account_manager
+            .assert_operator("ops_operator_lock_storage_balance");
         let storage_use_cost = env::storage_byte_cost() * *storage_usage as u128;
         ContractNearBalances::set_balance(CONTRACT_LOCKED_STORAGE_BALANCE, storage_use_cost.into());
+        AuditLog::record(
+            "ops_operator_lock_storage_balance",
+            None,
+            Some(json!({ "storage_usage": storage_usage }).to_string()),
+        );
     }
 
     fn ops_owner_grant_admin(&mut self) {
-        ContractOwnerObject::assert_owner_access();
+        ContractOwnerObject::assert_owner_command_access("ops_owner_grant_admin");
         let account_ids = ContractOwnershipAccountIdsObject::load();
 
         let mut account =
             AccountManagementComponent::<T>::get_or_register_account(&account_ids.owner);
         account.grant_admin();
         account.save();
+        AuditLog::record("ops_owner_grant_admin", Some(&account_ids.owner), None);
     }
 }
 
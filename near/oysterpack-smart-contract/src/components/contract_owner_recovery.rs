@@ -0,0 +1,517 @@
+//! [`ContractOwnerRecoveryComponent`]
+
+use crate::components::contract_co_ownership::ContractCoOwnershipComponent;
+use crate::components::contract_owner_withdrawals::ContractOwnerWithdrawalsComponent;
+use crate::components::contract_ownership::ContractOwnershipComponent;
+use crate::{
+    ContractAuction, ContractOwnerObject, ContractOwnerRecovery, ContractOwnershipAccountIdsObject,
+    OwnerRecovery, OwnerRecoveryConfig, ERR_CONTRACT_AUCTION_IN_PROGRESS,
+    ERR_GUARDIAN_ACCESS_REQUIRED, ERR_OWNER_RECOVERY_IN_PROGRESS,
+    ERR_OWNER_RECOVERY_NOT_CONFIGURED, ERR_OWNER_RECOVERY_NOT_FOUND, ERR_OWNER_RECOVERY_TIMELOCKED,
+    ERR_PROSPECTIVE_OWNER_ACCESS_REQUIRED, LOG_EVENT_CONTRACT_TRANSFER_CANCELLED,
+    LOG_EVENT_OWNER_RECOVERY_APPROVED, LOG_EVENT_OWNER_RECOVERY_CANCELLED,
+    LOG_EVENT_OWNER_RECOVERY_CONFIG_CHANGE, LOG_EVENT_OWNER_RECOVERY_FINALIZED,
+    LOG_EVENT_OWNER_RECOVERY_INITIATED, LOG_EVENT_OWNER_RECOVERY_VETOED,
+};
+use oysterpack_smart_account_management::AuditLog;
+use oysterpack_smart_near::asserts::{assert_request, assert_yocto_near_attached};
+use oysterpack_smart_near::component::{Component, ComponentState};
+use oysterpack_smart_near::domain::BlockTime;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    json_types::ValidAccountId,
+    serde_json::json,
+    AccountId,
+};
+
+pub struct ContractOwnerRecoveryComponent;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Default)]
+pub struct OwnerRecoveryState {
+    pub config: Option<OwnerRecoveryConfig>,
+    pub recovery: Option<OwnerRecovery>,
+}
+
+impl Component for ContractOwnerRecoveryComponent {
+    type State = OwnerRecoveryState;
+
+    const STATE_KEY: u128 = 1393768310592946327712690238476150037;
+}
+
+impl ContractOwnerRecoveryComponent {
+    fn load_recovery_state() -> ComponentState<OwnerRecoveryState> {
+        Self::load_state().unwrap_or_else(|| Self::new_state(OwnerRecoveryState::default()))
+    }
+
+    /// Guardians are designated by the owner. Thus, the recovery config and any recovery in
+    /// progress are cleared when the contract changes owners.
+    pub(crate) fn reset(reason: &str) {
+        if let Some(state) = Self::load_state() {
+            if state.recovery.is_some() {
+                LOG_EVENT_OWNER_RECOVERY_CANCELLED.log(reason);
+            }
+            if state.config.is_some() {
+                LOG_EVENT_OWNER_RECOVERY_CONFIG_CHANGE.log(format!("cleared: {}", reason));
+            }
+            state.delete();
+        }
+    }
+
+    fn assert_guardian(config: Option<&OwnerRecoveryConfig>) -> AccountId {
+        let config = match config {
+            Some(config) => config,
+            None => {
+                ERR_OWNER_RECOVERY_NOT_CONFIGURED.panic();
+                unreachable!()
+            }
+        };
+        let account_id = env::predecessor_account_id();
+        ERR_GUARDIAN_ACCESS_REQUIRED.assert(|| config.is_guardian(&account_id));
+        account_id
+    }
+
+    fn recovery_mut(state: &mut OwnerRecoveryState) -> &mut OwnerRecovery {
+        match state.recovery.as_mut() {
+            Some(recovery) => recovery,
+            None => {
+                ERR_OWNER_RECOVERY_NOT_FOUND.panic();
+                unreachable!()
+            }
+        }
+    }
+
+    /// starts the time-lock if the approvals reach the threshold
+    fn approve(config: &OwnerRecoveryConfig, recovery: &mut OwnerRecovery, guardian: AccountId) {
+        recovery.approvals.push(guardian.clone());
+        if recovery.timelock.is_none() && recovery.approvals.len() >= config.threshold as usize {
+            recovery.timelock = Some(config.delay.into());
+        }
+        LOG_EVENT_OWNER_RECOVERY_APPROVED.log(format!(
+            "guardian: {} | approvals: {}/{} | timelock: {:?}",
+            guardian,
+            recovery.approvals.len(),
+            config.threshold,
+            recovery.timelock
+        ));
+    }
+}
+
+impl ContractOwnerRecovery for ContractOwnerRecoveryComponent {
+    fn ops_owner_recovery_config(&self) -> Option<OwnerRecoveryConfig> {
+        Self::load_state().and_then(|state| state.config.clone())
+    }
+
+    fn ops_owner_recovery_configure(&mut self, config: Option<OwnerRecoveryConfig>) {
+        assert_yocto_near_attached();
+        ContractOwnerObject::assert_owner_command_access("ops_owner_recovery_configure");
+        if let Some(config) = config.as_ref() {
+            config.validate(&ContractOwnershipAccountIdsObject::load().owner);
+        }
+
+        let mut state = Self::load_recovery_state();
+        if state.recovery.take().is_some() {
+            LOG_EVENT_OWNER_RECOVERY_CANCELLED.log("owner recovery config changed");
+        }
+        state.config = config.clone();
+        state.save();
+        LOG_EVENT_OWNER_RECOVERY_CONFIG_CHANGE.log(format!("{:?}", config));
+        AuditLog::record(
+            "ops_owner_recovery_configure",
+            None,
+            Some(json!({ "config": config }).to_string()),
+        );
+    }
+
+    fn ops_owner_recovery(&self) -> Option<OwnerRecovery> {
+        Self::load_state().and_then(|state| state.recovery.clone())
+    }
+
+    fn ops_owner_recovery_initiate(&mut self, new_owner: ValidAccountId) -> OwnerRecovery {
+        assert_yocto_near_attached();
+        let mut state = Self::load_recovery_state();
+        let guardian = Self::assert_guardian(state.config.as_ref());
+        ERR_OWNER_RECOVERY_IN_PROGRESS.assert(|| state.recovery.is_none());
+        assert_request(
+            || new_owner.as_ref() != &ContractOwnershipAccountIdsObject::load().owner,
+            || "new owner must not be the current owner",
+        );
+
+        let config = state.config.clone().unwrap();
+        let mut recovery = OwnerRecovery {
+            new_owner: new_owner.as_ref().to_string(),
+            approvals: vec![],
+            initiated_on: BlockTime::from_env(),
+            timelock: None,
+        };
+        LOG_EVENT_OWNER_RECOVERY_INITIATED
+            .log(format!("guardian: {} | new owner: {}", guardian, new_owner));
+        Self::approve(&config, &mut recovery, guardian);
+        state.recovery = Some(recovery.clone());
+        state.save();
+        AuditLog::record(
+            "ops_owner_recovery_initiate",
+            Some(new_owner.as_ref()),
+            None,
+        );
+        recovery
+    }
+
+    fn ops_owner_recovery_approve(&mut self) -> OwnerRecovery {
+        assert_yocto_near_attached();
+        let mut state = Self::load_recovery_state();
+        let guardian = Self::assert_guardian(state.config.as_ref());
+        let config = state.config.clone().unwrap();
+        let recovery = Self::recovery_mut(&mut state);
+        assert_request(
+            || !recovery.approved_by(&guardian),
+            || "guardian already approved the owner recovery",
+        );
+
+        Self::approve(&config, recovery, guardian);
+        let recovery = recovery.clone();
+        state.save();
+        recovery
+    }
+
+    fn ops_owner_recovery_cancel(&mut self) {
+        assert_yocto_near_attached();
+        let mut state = Self::load_recovery_state();
+        let recovery = Self::recovery_mut(&mut state);
+        ERR_GUARDIAN_ACCESS_REQUIRED
+            .assert(|| recovery.initiated_by() == env::predecessor_account_id().as_str());
+
+        state.recovery = None;
+        state.save();
+        LOG_EVENT_OWNER_RECOVERY_CANCELLED.log("cancelled by the initiating guardian");
+        AuditLog::record("ops_owner_recovery_cancel", None, None);
+    }
+
+    fn ops_owner_recovery_veto(&mut self) -> bool {
+        assert_yocto_near_attached();
+        ContractOwnerObject::assert_owner_command_access("ops_owner_recovery_veto");
+
+        let mut state = Self::load_recovery_state();
+        match state.recovery.take() {
+            None => false,
+            Some(recovery) => {
+                state.save();
+                LOG_EVENT_OWNER_RECOVERY_VETOED.log(format!("new owner: {}", recovery.new_owner));
+                AuditLog::record("ops_owner_recovery_veto", Some(&recovery.new_owner), None);
+                true
+            }
+        }
+    }
+
+    fn ops_owner_recovery_finalize(&mut self) {
+        assert_yocto_near_attached();
+        let mut state = Self::load_recovery_state();
+        let recovery = Self::recovery_mut(&mut state).clone();
+        ERR_PROSPECTIVE_OWNER_ACCESS_REQUIRED
+            .assert(|| recovery.new_owner == env::predecessor_account_id());
+        ERR_OWNER_RECOVERY_TIMELOCKED.assert(|| recovery.finalizable());
+        // binding auction bids must not be silently cancelled - the auction must be settled first
+        ERR_CONTRACT_AUCTION_IN_PROGRESS.assert_with_message(
+            || ContractAuction::load().map_or(true, |auction| !auction.has_bids()),
+            || "ownership cannot be recovered after auction bids have been placed",
+        );
+
+        let reason = "contract ownership was recovered";
+        let mut owner = ContractOwnerObject::load();
+        ContractOwnershipComponent::cancel_contract_sale(&mut owner, reason);
+        let mut account_ids = ContractOwnershipAccountIdsObject::load();
+        if owner.prospective_owner_account_id_hash.take().is_some() {
+            account_ids.prospective_owner.take();
+            LOG_EVENT_CONTRACT_TRANSFER_CANCELLED.log(reason);
+        }
+        let prior_owner = account_ids.owner.clone();
+        owner.account_id_hash = recovery.new_owner.as_str().into();
+        account_ids.owner = recovery.new_owner.clone();
+        owner.save();
+        account_ids.save();
+
+        ContractOwnerWithdrawalsComponent::reset(reason);
+        ContractCoOwnershipComponent::dissolve(reason);
+        state.delete();
+
+        LOG_EVENT_OWNER_RECOVERY_FINALIZED.log(format!(
+            "prior owner: {} | new owner: {}",
+            prior_owner, recovery.new_owner
+        ));
+        AuditLog::record("ops_owner_recovery_finalize", Some(&prior_owner), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::contract_sale::ContractSaleComponent;
+    use crate::{
+        ContractAuctionSettings, ContractOwnerWithdrawals, ContractOwnership, ContractSaleAuction,
+        OwnerWithdrawalTimelockConfig, SealedBid,
+    };
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::domain::{Expiration, ExpirationDuration};
+    use oysterpack_smart_near::near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+    const NEW_OWNER: &str = "new_owner";
+    const GUARDIAN_1: &str = "guardian_1";
+    const GUARDIAN_2: &str = "guardian_2";
+    const GUARDIAN_3: &str = "guardian_3";
+    const BUYER: &str = "buyer";
+
+    fn deploy() -> VMContext {
+        let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        ctx.epoch_height = 100;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ctx
+    }
+
+    fn config() -> OwnerRecoveryConfig {
+        OwnerRecoveryConfig {
+            guardians: vec![
+                GUARDIAN_1.to_string(),
+                GUARDIAN_2.to_string(),
+                GUARDIAN_3.to_string(),
+            ],
+            threshold: 2,
+            delay: ExpirationDuration::Epochs(2),
+        }
+    }
+
+    fn configure() -> VMContext {
+        let ctx = deploy();
+        ContractOwnerRecoveryComponent.ops_owner_recovery_configure(Some(config()));
+        ctx
+    }
+
+    fn call_as(ctx: &mut VMContext, account_id: &str) {
+        ctx.predecessor_account_id = account_id.to_string();
+        testing_env!(ctx.clone());
+    }
+
+    #[test]
+    fn recovery_workflow() {
+        // Arrange - the owner has a pending withdrawal when the owner key is lost
+        let mut ctx = configure();
+        let mut recovery = ContractOwnerRecoveryComponent;
+        assert_eq!(recovery.ops_owner_recovery_config(), Some(config()));
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(Some(
+            OwnerWithdrawalTimelockConfig {
+                threshold: YOCTO.into(),
+                delay: ExpirationDuration::Epochs(10),
+                guardian: None,
+            },
+        ));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        assert_eq!(
+            ContractOwnerWithdrawalsComponent
+                .ops_owner_pending_withdrawals()
+                .len(),
+            1
+        );
+
+        // Act - guardian initiates the recovery
+        call_as(&mut ctx, GUARDIAN_1);
+        let state = recovery.ops_owner_recovery_initiate(to_valid_account_id(NEW_OWNER));
+
+        // Assert - the threshold is not yet reached
+        assert_eq!(state.new_owner, NEW_OWNER);
+        assert_eq!(state.initiated_by(), GUARDIAN_1);
+        assert!(state.timelock.is_none());
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[WARN] [OWNER_RECOVERY_INITIATED]")));
+
+        // Act - the second approval reaches the threshold
+        call_as(&mut ctx, GUARDIAN_2);
+        let state = recovery.ops_owner_recovery_approve();
+
+        // Assert - the time-lock is started
+        assert_eq!(state.approvals, vec![GUARDIAN_1, GUARDIAN_2]);
+        assert_eq!(state.timelock, Some(Expiration::Epoch(102.into())));
+        assert_eq!(recovery.ops_owner_recovery(), Some(state));
+
+        // Act - the new owner finalizes the recovery after the time-lock expires
+        ctx.epoch_height = 103;
+        call_as(&mut ctx, NEW_OWNER);
+        recovery.ops_owner_recovery_finalize();
+
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), NEW_OWNER);
+        assert!(ContractOwnerObject::is_owner());
+        assert!(recovery.ops_owner_recovery().is_none());
+        assert!(recovery.ops_owner_recovery_config().is_none());
+        assert!(ContractOwnerWithdrawalsComponent
+            .ops_owner_pending_withdrawals()
+            .is_empty());
+        assert!(ContractOwnerWithdrawalsComponent
+            .ops_owner_withdrawal_timelock()
+            .is_none());
+        let logs = test_utils::get_logs();
+        assert!(logs
+            .iter()
+            .any(|log| log.starts_with("[INFO] [OWNER_WITHDRAWAL_CANCELLED]")));
+        assert!(logs
+            .iter()
+            .any(|log| log.starts_with("[WARN] [OWNER_RECOVERY_FINALIZED]")));
+    }
+
+    /// the owner starts a sealed bid auction and a buyer commits a bid before the recovery is
+    /// initiated - returns when the recovery time-lock has expired
+    fn recovery_with_sealed_bid(ctx: &mut VMContext) {
+        ctx.block_index = 100;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_auction_start(ContractAuctionSettings::Sealed {
+            reserve_price: YOCTO.into(),
+            commit_duration: ExpirationDuration::Blocks(100),
+            reveal_duration: ExpirationDuration::Blocks(50),
+        });
+        ctx.attached_deposit = 2 * YOCTO;
+        call_as(ctx, BUYER);
+        ContractSaleComponent.ops_contract_sealed_bid_commit(SealedBid::commitment(
+            BUYER,
+            (2 * YOCTO).into(),
+            "salt",
+        ));
+
+        let mut recovery = ContractOwnerRecoveryComponent;
+        ctx.attached_deposit = 1;
+        call_as(ctx, GUARDIAN_1);
+        recovery.ops_owner_recovery_initiate(to_valid_account_id(NEW_OWNER));
+        call_as(ctx, GUARDIAN_2);
+        recovery.ops_owner_recovery_approve();
+        ctx.epoch_height = 103;
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn finalize_with_auction_bids() {
+        let mut ctx = configure();
+        recovery_with_sealed_bid(&mut ctx);
+
+        call_as(&mut ctx, NEW_OWNER);
+        ContractOwnerRecoveryComponent.ops_owner_recovery_finalize();
+    }
+
+    #[test]
+    fn finalize_after_auction_settled() {
+        // Arrange - the auction ends without any revealed bids
+        let mut ctx = configure();
+        recovery_with_sealed_bid(&mut ctx);
+        ctx.block_index = 251;
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        assert!(!ContractSaleComponent.ops_contract_auction_settle());
+
+        // Act
+        ctx.attached_deposit = 1;
+        call_as(&mut ctx, NEW_OWNER);
+        ContractOwnerRecoveryComponent.ops_owner_recovery_finalize();
+
+        // Assert - the sealed bid deposit is still withdrawable
+        assert_eq!(ContractOwnershipComponent.ops_owner(), NEW_OWNER);
+        call_as(&mut ctx, BUYER);
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sealed_bid_withdraw(),
+            (2 * YOCTO).into()
+        );
+        assert_eq!(SealedBid::deposits_near_balance(), 0.into());
+    }
+
+    #[test]
+    fn owner_veto() {
+        let mut ctx = configure();
+        let mut recovery = ContractOwnerRecoveryComponent;
+        assert!(!recovery.ops_owner_recovery_veto());
+
+        call_as(&mut ctx, GUARDIAN_1);
+        recovery.ops_owner_recovery_initiate(to_valid_account_id(NEW_OWNER));
+        call_as(&mut ctx, GUARDIAN_2);
+        recovery.ops_owner_recovery_approve();
+
+        call_as(&mut ctx, OWNER);
+        assert!(recovery.ops_owner_recovery_veto());
+
+        assert!(recovery.ops_owner_recovery().is_none());
+        assert_eq!(recovery.ops_owner_recovery_config(), Some(config()));
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [OWNER_RECOVERY_VETOED]")));
+    }
+
+    #[test]
+    fn initiating_guardian_cancels_recovery() {
+        let mut ctx = configure();
+        let mut recovery = ContractOwnerRecoveryComponent;
+        call_as(&mut ctx, GUARDIAN_1);
+        recovery.ops_owner_recovery_initiate(to_valid_account_id(NEW_OWNER));
+
+        recovery.ops_owner_recovery_cancel();
+
+        assert!(recovery.ops_owner_recovery().is_none());
+    }
+
+    #[test]
+    fn ownership_transfer_clears_recovery_config() {
+        let mut ctx = configure();
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(NEW_OWNER));
+        call_as(&mut ctx, NEW_OWNER);
+        ContractOwnershipComponent.ops_owner_finalize_transfer();
+
+        assert!(ContractOwnerRecoveryComponent
+            .ops_owner_recovery_config()
+            .is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_RECOVERY_TIMELOCKED]")]
+    fn finalize_before_timelock_expires() {
+        let mut ctx = configure();
+        let mut recovery = ContractOwnerRecoveryComponent;
+        call_as(&mut ctx, GUARDIAN_1);
+        recovery.ops_owner_recovery_initiate(to_valid_account_id(NEW_OWNER));
+        call_as(&mut ctx, GUARDIAN_2);
+        recovery.ops_owner_recovery_approve();
+
+        ctx.epoch_height = 101;
+        call_as(&mut ctx, NEW_OWNER);
+        recovery.ops_owner_recovery_finalize();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_RECOVERY_TIMELOCKED]")]
+    fn finalize_below_threshold() {
+        let mut ctx = configure();
+        let mut recovery = ContractOwnerRecoveryComponent;
+        call_as(&mut ctx, GUARDIAN_1);
+        recovery.ops_owner_recovery_initiate(to_valid_account_id(NEW_OWNER));
+
+        ctx.epoch_height = 200;
+        call_as(&mut ctx, NEW_OWNER);
+        recovery.ops_owner_recovery_finalize();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [GUARDIAN_ACCESS_REQUIRED]")]
+    fn initiate_as_non_guardian() {
+        let mut ctx = configure();
+        call_as(&mut ctx, "attacker");
+        ContractOwnerRecoveryComponent.ops_owner_recovery_initiate(to_valid_account_id("attacker"));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID_OWNER_RECOVERY_CONFIG]")]
+    fn configure_with_invalid_threshold() {
+        deploy();
+        ContractOwnerRecoveryComponent.ops_owner_recovery_configure(Some(OwnerRecoveryConfig {
+            threshold: 4,
+            ..config()
+        }));
+    }
+}
@@ -0,0 +1,650 @@
+//! [`ContractOwnerWithdrawalsComponent`]
+
+use crate::components::contract_co_ownership::ContractCoOwnershipComponent;
+use crate::components::contract_metrics::ContractMetricsComponent;
+use crate::{
+    ContractMetrics, ContractOwnerObject, ContractOwnerWithdrawals, OwnerWithdrawalTimelockConfig,
+    OwnerWithdrawalTimelockConfigChange, PendingOwnerWithdrawal, RecentOwnerWithdrawal,
+    ERR_OWNER_BALANCE_OVERDRAW, ERR_OWNER_OR_GUARDIAN_ACCESS_REQUIRED,
+    ERR_OWNER_WITHDRAWAL_NOT_FOUND, ERR_OWNER_WITHDRAWAL_TIMELOCKED,
+    LOG_EVENT_OWNER_WITHDRAWAL_CANCELLED, LOG_EVENT_OWNER_WITHDRAWAL_EXECUTED,
+    LOG_EVENT_OWNER_WITHDRAWAL_PENDING, LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE,
+};
+use oysterpack_smart_account_management::AuditLog;
+use oysterpack_smart_near::asserts::assert_yocto_near_attached;
+use oysterpack_smart_near::component::{Component, ComponentState};
+use oysterpack_smart_near::domain::{BlockTime, YoctoNear};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    json_types::U64,
+    serde_json::json,
+};
+
+pub struct ContractOwnerWithdrawalsComponent;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Default)]
+pub struct OwnerWithdrawalsState {
+    pub config: Option<OwnerWithdrawalTimelockConfig>,
+    pub config_change: Option<OwnerWithdrawalTimelockConfigChange>,
+    pub next_withdrawal_id: u64,
+    pub pending_withdrawals: Vec<PendingOwnerWithdrawal>,
+    /// withdrawals that were transferred immediately within the rolling time-lock delay window
+    pub recent_withdrawals: Vec<RecentOwnerWithdrawal>,
+}
+
+impl OwnerWithdrawalsState {
+    /// applies the scheduled config change if its time-lock has expired
+    fn apply_config_change(&mut self) {
+        if self
+            .config_change
+            .as_ref()
+            .map_or(false, |change| change.timelock.expired())
+        {
+            self.config = self.config_change.take().unwrap().config;
+        }
+    }
+
+    /// total amount that was transferred immediately within the rolling time-lock delay window
+    pub fn recent_withdrawals_total(&self) -> YoctoNear {
+        self.recent_withdrawals
+            .iter()
+            .filter(|withdrawal| !withdrawal.expiration.expired())
+            .map(|withdrawal| withdrawal.amount)
+            .sum()
+    }
+
+    pub fn pending_withdrawals_total(&self) -> YoctoNear {
+        self.pending_withdrawals
+            .iter()
+            .map(|withdrawal| withdrawal.amount)
+            .sum()
+    }
+}
+
+impl Component for ContractOwnerWithdrawalsComponent {
+    type State = OwnerWithdrawalsState;
+
+    const STATE_KEY: u128 = 1617839284730163286120437810299547309;
+}
+
+impl ContractOwnerWithdrawalsComponent {
+    /// the state is lazily created, and scheduled config changes are lazily applied
+    fn load_withdrawals_state() -> ComponentState<OwnerWithdrawalsState> {
+        let mut state = Self::load_state().unwrap_or_else(|| {
+            Self::new_state(OwnerWithdrawalsState {
+                next_withdrawal_id: 1,
+                ..OwnerWithdrawalsState::default()
+            })
+        });
+        state.apply_config_change();
+        state
+    }
+
+    /// total amount that is reserved for pending owner withdrawals
+    pub fn pending_withdrawals_total() -> YoctoNear {
+        Self::load_state().map_or(YoctoNear::ZERO, |state| state.pending_withdrawals_total())
+    }
+
+    /// If the amount would take the total amount withdrawn within the rolling time-lock delay window
+    /// above the configured threshold, then the withdrawal is queued.
+    ///
+    /// Returns None if the withdrawal is not time-locked, i.e., the caller is expected to transfer
+    /// the funds immediately.
+    pub(crate) fn request_withdrawal(
+        amount: YoctoNear,
+        account_id: &str,
+    ) -> Option<PendingOwnerWithdrawal> {
+        let mut state = Self::load_withdrawals_state();
+        let config = state.config.clone()?;
+        state
+            .recent_withdrawals
+            .retain(|withdrawal| !withdrawal.expiration.expired());
+        if state.recent_withdrawals_total() + amount <= config.threshold {
+            state.recent_withdrawals.push(RecentOwnerWithdrawal {
+                amount,
+                expiration: config.delay.into(),
+            });
+            state.save();
+            return None;
+        }
+
+        let withdrawal = PendingOwnerWithdrawal {
+            id: state.next_withdrawal_id.into(),
+            account_id: account_id.to_string(),
+            amount,
+            requested_on: BlockTime::from_env(),
+            timelock: config.delay.into(),
+        };
+        state.next_withdrawal_id += 1;
+        state.pending_withdrawals.push(withdrawal.clone());
+        state.save();
+
+        LOG_EVENT_OWNER_WITHDRAWAL_PENDING.log(format!(
+            "id: {} | amount: {} | timelock: {}",
+            withdrawal.id.0, withdrawal.amount, withdrawal.timelock
+        ));
+        Some(withdrawal)
+    }
+
+    /// The time-lock config and guardian are designated by the owner, and pending withdrawals pay
+    /// out to the owner that requested them. Thus, the withdrawal state is cleared when the contract
+    /// changes owners - withdrawal IDs are not reused.
+    pub(crate) fn reset(reason: &str) {
+        if let Some(mut state) = Self::load_state() {
+            for withdrawal in state.pending_withdrawals.iter() {
+                LOG_EVENT_OWNER_WITHDRAWAL_CANCELLED.log(format!(
+                    "id: {} | amount: {} | {}",
+                    withdrawal.id.0, withdrawal.amount, reason
+                ));
+            }
+            if state.config.is_some() || state.config_change.is_some() {
+                LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE.log(format!("cleared: {}", reason));
+            }
+            *state = OwnerWithdrawalsState {
+                next_withdrawal_id: state.next_withdrawal_id,
+                ..OwnerWithdrawalsState::default()
+            };
+            state.save();
+        }
+    }
+
+    /// owner access is checked against the current owner, i.e., the owner that holds the balance
+    fn assert_owner_or_guardian(state: &OwnerWithdrawalsState) {
+        let account_id = env::predecessor_account_id();
+        ERR_OWNER_OR_GUARDIAN_ACCESS_REQUIRED.assert(|| {
+            ContractOwnerObject::is_owner()
+                || state
+                    .config
+                    .as_ref()
+                    .map_or(false, |config| config.is_guardian(&account_id))
+        });
+    }
+
+    fn pending_withdrawal_index(state: &OwnerWithdrawalsState, id: U64) -> usize {
+        match state
+            .pending_withdrawals
+            .iter()
+            .position(|withdrawal| withdrawal.id == id)
+        {
+            Some(index) => index,
+            None => {
+                ERR_OWNER_WITHDRAWAL_NOT_FOUND.panic();
+                unreachable!()
+            }
+        }
+    }
+}
+
+impl ContractOwnerWithdrawals for ContractOwnerWithdrawalsComponent {
+    fn ops_owner_withdrawal_timelock(&self) -> Option<OwnerWithdrawalTimelockConfig> {
+        Self::load_withdrawals_state().config.clone()
+    }
+
+    fn ops_owner_withdrawal_timelock_change(&self) -> Option<OwnerWithdrawalTimelockConfigChange> {
+        Self::load_withdrawals_state().config_change.clone()
+    }
+
+    fn ops_owner_withdrawal_timelock_configure(
+        &mut self,
+        config: Option<OwnerWithdrawalTimelockConfig>,
+    ) -> Option<OwnerWithdrawalTimelockConfigChange> {
+        assert_yocto_near_attached();
+        ContractOwnerObject::assert_owner_command_access("ops_owner_withdrawal_timelock_configure");
+
+        let mut state = Self::load_withdrawals_state();
+        let change = match state.config.as_ref() {
+            None => {
+                state.config = config.clone();
+                state.config_change = None;
+                LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE.log(format!("applied: {:?}", config));
+                None
+            }
+            Some(current_config) => {
+                let change = OwnerWithdrawalTimelockConfigChange {
+                    config: config.clone(),
+                    timelock: current_config.delay.into(),
+                };
+                LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE.log(format!(
+                    "scheduled: {:?} | timelock: {}",
+                    config, change.timelock
+                ));
+                state.config_change = Some(change.clone());
+                Some(change)
+            }
+        };
+        state.save();
+        AuditLog::record(
+            "ops_owner_withdrawal_timelock_configure",
+            None,
+            Some(json!({ "config": config }).to_string()),
+        );
+        change
+    }
+
+    fn ops_owner_withdrawal_timelock_cancel_change(&mut self) -> bool {
+        assert_yocto_near_attached();
+        let mut state = Self::load_withdrawals_state();
+        Self::assert_owner_or_guardian(&state);
+
+        match state.config_change.take() {
+            None => false,
+            Some(change) => {
+                state.save();
+                LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE
+                    .log(format!("cancelled: {:?}", change.config));
+                AuditLog::record("ops_owner_withdrawal_timelock_cancel_change", None, None);
+                true
+            }
+        }
+    }
+
+    fn ops_owner_pending_withdrawals(&self) -> Vec<PendingOwnerWithdrawal> {
+        Self::load_state().map_or_else(Vec::new, |state| state.pending_withdrawals.clone())
+    }
+
+    fn ops_owner_withdrawal_execute(&mut self, id: U64) {
+        let mut state = Self::load_withdrawals_state();
+        let index = Self::pending_withdrawal_index(&state, id);
+        ERR_OWNER_WITHDRAWAL_TIMELOCKED
+            .assert(|| state.pending_withdrawals[index].timelock.expired());
+
+        // the owner balance must cover its storage costs and all pending withdrawals
+        let near_balances = ContractMetricsComponent.ops_metrics_near_balances();
+        let storage_usage_costs = ContractMetricsComponent.ops_metrics_storage_usage_costs();
+        ERR_OWNER_BALANCE_OVERDRAW.assert(|| {
+            near_balances.owner() >= storage_usage_costs.owner() + state.pending_withdrawals_total()
+        });
+
+        let withdrawal = state.pending_withdrawals.remove(index);
+        state.save();
+        ContractCoOwnershipComponent::transfer_owner_funds(
+            &withdrawal.account_id,
+            withdrawal.amount,
+        );
+        LOG_EVENT_OWNER_WITHDRAWAL_EXECUTED.log(format!(
+            "id: {} | amount: {}",
+            withdrawal.id.0, withdrawal.amount
+        ));
+    }
+
+    fn ops_owner_withdrawal_cancel(&mut self, id: U64) {
+        assert_yocto_near_attached();
+        let mut state = Self::load_withdrawals_state();
+        let index = Self::pending_withdrawal_index(&state, id);
+        Self::assert_owner_or_guardian(&state);
+
+        let withdrawal = state.pending_withdrawals.remove(index);
+        state.save();
+        LOG_EVENT_OWNER_WITHDRAWAL_CANCELLED.log(format!(
+            "id: {} | amount: {}",
+            withdrawal.id.0, withdrawal.amount
+        ));
+        AuditLog::record(
+            "ops_owner_withdrawal_cancel",
+            Some(&withdrawal.account_id),
+            Some(json!({ "id": withdrawal.id, "amount": withdrawal.amount }).to_string()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use crate::components::contract_sale::ContractSaleComponent;
+    use crate::{CoOwner, CoOwnershipConfig, ContractCoOwnership, ContractOwnership, ContractSale};
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::domain::{Expiration, ExpirationDuration};
+    use oysterpack_smart_near::near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+    const GUARDIAN: &str = "guardian";
+
+    fn deploy() -> VMContext {
+        let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        ctx.epoch_height = 100;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ctx
+    }
+
+    fn config() -> OwnerWithdrawalTimelockConfig {
+        OwnerWithdrawalTimelockConfig {
+            threshold: YOCTO.into(),
+            delay: ExpirationDuration::Epochs(2),
+            guardian: Some(GUARDIAN.to_string()),
+        }
+    }
+
+    #[test]
+    fn timelocked_withdrawal_workflow() {
+        // Arrange
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        assert!(withdrawals.ops_owner_withdrawal_timelock().is_none());
+        assert!(withdrawals
+            .ops_owner_withdrawal_timelock_configure(Some(config()))
+            .is_none());
+        assert_eq!(withdrawals.ops_owner_withdrawal_timelock(), Some(config()));
+        let initial_owner_balance = ContractOwnershipComponent.ops_owner_balance();
+        assert_eq!(initial_owner_balance.pending_withdrawals, YoctoNear::ZERO);
+
+        // Act - withdrawal above the threshold
+        let owner_balance =
+            ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+
+        // Assert - the withdrawal is pending and reserved from the owner's available balance
+        assert!(deserialize_receipts().is_empty());
+        assert_eq!(owner_balance.pending_withdrawals, (10 * YOCTO).into());
+        assert!(owner_balance.available < initial_owner_balance.available - (10 * YOCTO));
+        let pending_withdrawals = withdrawals.ops_owner_pending_withdrawals();
+        assert_eq!(pending_withdrawals.len(), 1);
+        let withdrawal = &pending_withdrawals[0];
+        assert_eq!(withdrawal.id, 1.into());
+        assert_eq!(withdrawal.account_id, OWNER);
+        assert_eq!(withdrawal.amount, (10 * YOCTO).into());
+        assert_eq!(withdrawal.timelock, Expiration::Epoch(102.into()));
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [OWNER_WITHDRAWAL_PENDING]")));
+
+        // Act - anyone can execute the withdrawal after the time-lock expires
+        ctx.predecessor_account_id = "anyone".to_string();
+        ctx.attached_deposit = 0;
+        ctx.epoch_height = 103;
+        testing_env!(ctx.clone());
+        withdrawals.ops_owner_withdrawal_execute(withdrawal.id);
+
+        // Assert
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+        assert_eq!(
+            ContractOwnershipComponent
+                .ops_owner_balance()
+                .pending_withdrawals,
+            YoctoNear::ZERO
+        );
+        let receipts = deserialize_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, OWNER);
+        match &receipts[0].actions[0] {
+            Action::Transfer(action) => assert_eq!(action.deposit, 10 * YOCTO),
+            _ => panic!("expected TransferAction"),
+        }
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [OWNER_WITHDRAWAL_EXECUTED]")));
+    }
+
+    #[test]
+    fn withdrawal_within_threshold_is_transferred_immediately() {
+        deploy();
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(Some(config()));
+
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some(YOCTO.into()));
+
+        assert!(ContractOwnerWithdrawalsComponent
+            .ops_owner_pending_withdrawals()
+            .is_empty());
+        let receipts = deserialize_receipts();
+        assert_eq!(receipts.len(), 1);
+        match &receipts[0].actions[0] {
+            Action::Transfer(action) => assert_eq!(action.deposit, YOCTO + 1),
+            _ => panic!("expected TransferAction"),
+        }
+    }
+
+    #[test]
+    fn withdrawals_within_delay_window_are_cumulative() {
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        withdrawals.ops_owner_withdrawal_timelock_configure(Some(config()));
+
+        // Act - the first withdrawal is within the threshold
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((YOCTO / 2).into()));
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+        assert_eq!(deserialize_receipts().len(), 1);
+
+        // Act - the second withdrawal takes the window total above the threshold
+        ctx.block_index += 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((YOCTO / 2 + 1).into()));
+
+        // Assert
+        assert!(deserialize_receipts().is_empty());
+        let pending_withdrawals = withdrawals.ops_owner_pending_withdrawals();
+        assert_eq!(pending_withdrawals.len(), 1);
+        assert_eq!(pending_withdrawals[0].amount, (YOCTO / 2 + 1).into());
+
+        // Act - the first withdrawal drops out of the window after the delay
+        ctx.epoch_height = 103;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((YOCTO / 2 + 1).into()));
+
+        // Assert
+        assert_eq!(withdrawals.ops_owner_pending_withdrawals().len(), 1);
+        assert_eq!(deserialize_receipts().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_WITHDRAWAL_TIMELOCKED]")]
+    fn execute_before_timelock_expires() {
+        let mut ctx = deploy();
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+
+        ctx.epoch_height = 102;
+        testing_env!(ctx.clone());
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_execute(1.into());
+    }
+
+    #[test]
+    fn guardian_cancels_pending_withdrawal() {
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        withdrawals.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+
+        ctx.predecessor_account_id = GUARDIAN.to_string();
+        testing_env!(ctx.clone());
+        withdrawals.ops_owner_withdrawal_cancel(1.into());
+
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+        assert_eq!(
+            ContractOwnershipComponent
+                .ops_owner_balance()
+                .pending_withdrawals,
+            YoctoNear::ZERO
+        );
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [OWNER_WITHDRAWAL_CANCELLED]")));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_OR_GUARDIAN_ACCESS_REQUIRED]")]
+    fn cancel_requires_owner_or_guardian_access() {
+        let mut ctx = deploy();
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+
+        ctx.predecessor_account_id = "mallory".to_string();
+        testing_env!(ctx.clone());
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_cancel(1.into());
+    }
+
+    #[test]
+    fn co_owners_cancel_pending_withdrawal() {
+        // Arrange
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        withdrawals.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        ContractCoOwnershipComponent.ops_co_ownership_configure(Some(CoOwnershipConfig {
+            co_owners: vec![
+                CoOwner {
+                    account_id: OWNER.to_string(),
+                    weight: 50,
+                },
+                CoOwner {
+                    account_id: "alice".to_string(),
+                    weight: 50,
+                },
+            ],
+            quorum: 51,
+            proposal_expiration: ExpirationDuration::Epochs(3),
+        }));
+
+        // Act - the withdrawal is cancelled through an approved co-owner proposal
+        ctx.predecessor_account_id = env::current_account_id();
+        testing_env!(ctx.clone());
+        withdrawals.ops_owner_withdrawal_cancel(1.into());
+
+        // Assert
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_OR_GUARDIAN_ACCESS_REQUIRED]")]
+    fn cancel_requires_current_owner_access() {
+        deploy();
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        ContractCoOwnershipComponent.ops_co_ownership_configure(Some(CoOwnershipConfig {
+            co_owners: vec![
+                CoOwner {
+                    account_id: OWNER.to_string(),
+                    weight: 50,
+                },
+                CoOwner {
+                    account_id: "alice".to_string(),
+                    weight: 50,
+                },
+            ],
+            quorum: 51,
+            proposal_expiration: ExpirationDuration::Epochs(3),
+        }));
+
+        // the owner account that requested the withdrawal no longer has owner access on its own
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_cancel(1.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_WITHDRAWAL_NOT_FOUND]")]
+    fn cancel_withdrawal_not_found() {
+        deploy();
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_cancel(1.into());
+    }
+
+    #[test]
+    fn ownership_transfer_resets_withdrawals() {
+        // Arrange
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        withdrawals.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id("bob"));
+
+        // Act
+        ctx.predecessor_account_id = "bob".to_string();
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_finalize_transfer();
+
+        // Assert - the prior owner's withdrawals, time-lock, and guardian are cleared
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+        assert!(withdrawals.ops_owner_withdrawal_timelock().is_none());
+        assert_eq!(
+            ContractOwnershipComponent
+                .ops_owner_balance()
+                .pending_withdrawals,
+            YoctoNear::ZERO
+        );
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [OWNER_WITHDRAWAL_CANCELLED]")));
+
+        // Assert - withdrawal IDs are not reused
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        assert_eq!(withdrawals.ops_owner_pending_withdrawals()[0].id, 2.into());
+    }
+
+    #[test]
+    fn contract_sale_resets_withdrawals() {
+        // Arrange
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        withdrawals.ops_owner_withdrawal_timelock_configure(Some(config()));
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        let seller_balance = ContractOwnershipComponent.ops_owner_balance();
+
+        // Act
+        ctx.predecessor_account_id = "buyer".to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), "buyer");
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+        assert!(withdrawals.ops_owner_withdrawal_timelock().is_none());
+        // the seller's pending withdrawal is paid out to the seller with the sale proceeds
+        let receipts = deserialize_receipts();
+        let seller_payout: u128 = receipts
+            .iter()
+            .filter(|receipt| receipt.receiver_id == OWNER)
+            .flat_map(|receipt| receipt.actions.iter())
+            .map(|action| match action {
+                Action::Transfer(action) => action.deposit,
+                _ => 0,
+            })
+            .sum();
+        assert!(seller_payout >= seller_balance.available.value() + 10 * YOCTO);
+    }
+
+    #[test]
+    fn timelock_config_change_is_delayed() {
+        let mut ctx = deploy();
+        let mut withdrawals = ContractOwnerWithdrawalsComponent;
+        withdrawals.ops_owner_withdrawal_timelock_configure(Some(config()));
+
+        // Act - disable the time-lock
+        let change = withdrawals
+            .ops_owner_withdrawal_timelock_configure(None)
+            .unwrap();
+
+        // Assert - the current config remains in effect until the change time-lock expires
+        assert_eq!(change.config, None);
+        assert_eq!(change.timelock, Expiration::Epoch(102.into()));
+        assert_eq!(withdrawals.ops_owner_withdrawal_timelock(), Some(config()));
+        assert_eq!(
+            withdrawals.ops_owner_withdrawal_timelock_change(),
+            Some(change)
+        );
+
+        // Act - the guardian cancels the change
+        ctx.predecessor_account_id = GUARDIAN.to_string();
+        testing_env!(ctx.clone());
+        assert!(withdrawals.ops_owner_withdrawal_timelock_cancel_change());
+        assert!(!withdrawals.ops_owner_withdrawal_timelock_cancel_change());
+        assert!(withdrawals.ops_owner_withdrawal_timelock_change().is_none());
+
+        // Act - schedule the change again and let it take effect
+        ctx.predecessor_account_id = OWNER.to_string();
+        testing_env!(ctx.clone());
+        withdrawals.ops_owner_withdrawal_timelock_configure(None);
+        ctx.epoch_height = 103;
+        testing_env!(ctx.clone());
+
+        // Assert
+        assert!(withdrawals.ops_owner_withdrawal_timelock().is_none());
+        assert!(withdrawals.ops_owner_withdrawal_timelock_change().is_none());
+        ContractOwnershipComponent.ops_owner_withdraw_balance(Some((10 * YOCTO).into()));
+        assert!(withdrawals.ops_owner_pending_withdrawals().is_empty());
+        assert_eq!(deserialize_receipts().len(), 1);
+    }
+}
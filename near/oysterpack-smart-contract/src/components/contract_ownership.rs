@@ -2,21 +2,28 @@
 //! - deployment: [`ContractOwnershipComponent::deploy`]
 //!   - config: `ValidAccountId` - owner account ID
 
+use crate::components::contract_co_ownership::ContractCoOwnershipComponent;
 use crate::components::contract_metrics::ContractMetricsComponent;
+use crate::components::contract_owner_recovery::ContractOwnerRecoveryComponent;
+use crate::components::contract_owner_withdrawals::ContractOwnerWithdrawalsComponent;
 use crate::components::contract_sale::ContractSaleComponent;
 use crate::{
-    ContractMetrics, ContractOwnerNearBalance, ContractOwnerObject, ContractOwnership,
-    ContractOwnershipAccountIdsObject, ERR_OWNER_BALANCE_OVERDRAW,
-    LOG_EVENT_CONTRACT_SALE_CANCELLED, LOG_EVENT_CONTRACT_TRANSFER_CANCELLED,
-    LOG_EVENT_CONTRACT_TRANSFER_FINALIZED, LOG_EVENT_CONTRACT_TRANSFER_INITIATED,
+    BalanceIdLabel, ContractAuction, ContractBid, ContractMetrics, ContractNearBalances,
+    ContractOwnerNearBalance, ContractOwnerObject, ContractOwnership,
+    ContractOwnershipAccountIdsObject, SealedBid, CONTRACT_LOCKED_STORAGE_BALANCE,
+    ERR_CONTRACT_AUCTION_IN_PROGRESS, ERR_OWNER_BALANCE_OVERDRAW,
+    LOG_EVENT_CONTRACT_AUCTION_CANCELLED, LOG_EVENT_CONTRACT_SALE_CANCELLED,
+    LOG_EVENT_CONTRACT_TRANSFER_CANCELLED, LOG_EVENT_CONTRACT_TRANSFER_FINALIZED,
+    LOG_EVENT_CONTRACT_TRANSFER_INITIATED,
 };
+use oysterpack_smart_account_management::AuditLog;
 use oysterpack_smart_near::asserts::{
     assert_request, assert_yocto_near_attached, ERR_CODE_BAD_REQUEST,
 };
 use oysterpack_smart_near::component::Deploy;
 use oysterpack_smart_near::domain::{AccountIdHash, YoctoNear};
 use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
-use oysterpack_smart_near::near_sdk::{env, AccountId, Promise};
+use oysterpack_smart_near::near_sdk::{env, serde_json::json, AccountId};
 
 pub struct ContractOwnershipComponent;
 
@@ -26,6 +33,57 @@ impl Deploy for ContractOwnershipComponent {
 
     fn deploy(owner: Self::Config) {
         ContractOwnerObject::initialize_contract(owner);
+        Self::register_balance_ids();
+    }
+}
+
+impl ContractOwnershipComponent {
+    /// Registers the contract balance IDs that are managed by the contract components.
+    ///
+    /// This is invoked on deployment, but should also be invoked by contracts on migration in order
+    /// to label the balances of contracts that were deployed before balance IDs were registered.
+    pub fn register_balance_ids() {
+        ContractNearBalances::register_balance_id(
+            ContractBid::CONTRACT_BID_BALANCE_ID,
+            BalanceIdLabel {
+                name: "contract_bid".to_string(),
+                description: "NEAR bid to buy the contract".to_string(),
+                component: "ContractSaleComponent".to_string(),
+            },
+        );
+        ContractNearBalances::register_balance_id(
+            SealedBid::DEPOSITS_BALANCE_ID,
+            BalanceIdLabel {
+                name: "sealed_bid_deposits".to_string(),
+                description: "sealed bid auction deposits".to_string(),
+                component: "ContractSaleComponent".to_string(),
+            },
+        );
+        ContractNearBalances::register_balance_id(
+            CONTRACT_LOCKED_STORAGE_BALANCE,
+            BalanceIdLabel {
+                name: "locked_storage_balance".to_string(),
+                description: "owner balance that is locked to pay for contract storage".to_string(),
+                component: "ContractOperatorComponent".to_string(),
+            },
+        );
+    }
+
+    /// Cancels the contract sale price, the current bid, and the contract auction.
+    ///
+    /// The caller is responsible for saving the owner object.
+    pub(crate) fn cancel_contract_sale(owner: &mut ContractOwnerObject, reason: &str) {
+        if owner.sale_price.take().is_some() {
+            LOG_EVENT_CONTRACT_SALE_CANCELLED.log(reason);
+        }
+        if owner.bid.is_some() {
+            let mut account_ids = ContractOwnershipAccountIdsObject::load();
+            ContractSaleComponent::cancel_bid(owner, &mut account_ids, reason);
+            account_ids.save();
+        }
+        if ContractAuction::delete() {
+            LOG_EVENT_CONTRACT_AUCTION_CANCELLED.log(reason);
+        }
     }
 }
 
@@ -38,13 +96,16 @@ impl ContractOwnership for ContractOwnershipComponent {
     fn ops_owner_balance(&self) -> ContractOwnerNearBalance {
         let near_balances = ContractMetricsComponent.ops_metrics_near_balances();
         let storage_usage_costs = ContractMetricsComponent.ops_metrics_storage_usage_costs();
+        let pending_withdrawals = ContractOwnerWithdrawalsComponent::pending_withdrawals_total();
         let available = near_balances
             .owner()
             .saturating_sub(storage_usage_costs.owner().value())
+            .saturating_sub(pending_withdrawals.value())
             .into();
         ContractOwnerNearBalance {
             total: near_balances.owner(),
             available,
+            pending_withdrawals,
         }
     }
 
@@ -56,11 +117,15 @@ impl ContractOwnership for ContractOwnershipComponent {
     fn ops_owner_transfer(&mut self, new_owner: ValidAccountId) {
         assert_yocto_near_attached();
 
-        let mut owner = ContractOwnerObject::assert_owner_access();
+        let mut owner = ContractOwnerObject::assert_owner_command_access("ops_owner_transfer");
         assert_request(
             || new_owner.as_ref() != env::predecessor_account_id().as_str(),
             || "you cannot transfer to yourself",
         );
+        ERR_CONTRACT_AUCTION_IN_PROGRESS.assert_with_message(
+            || ContractAuction::load().map_or(true, |auction| !auction.has_bids()),
+            || "ownership cannot be transferred after auction bids have been placed",
+        );
         let new_owner_account_id_hash: AccountIdHash = new_owner.as_ref().as_str().into();
         let current_prospective_owner_account_id_hash =
             owner.prospective_owner_account_id_hash.as_ref().cloned();
@@ -71,22 +136,14 @@ impl ContractOwnership for ContractOwnershipComponent {
             account_ids.save();
 
             owner.prospective_owner_account_id_hash = Some(new_owner_account_id_hash);
-            if owner.sale_price.take().is_some() {
-                LOG_EVENT_CONTRACT_SALE_CANCELLED
-                    .log("contract ownership transfer is being initiated");
-            }
-            if owner.bid.is_some() {
-                let mut account_ids = ContractOwnershipAccountIdsObject::load();
-                ContractSaleComponent::cancel_bid(
-                    &mut owner,
-                    &mut account_ids,
-                    "contract ownership transfer has been initiated",
-                );
-                account_ids.save();
-            }
+            ContractOwnershipComponent::cancel_contract_sale(
+                &mut owner,
+                "contract ownership transfer has been initiated",
+            );
 
             LOG_EVENT_CONTRACT_TRANSFER_INITIATED.log(new_owner.as_ref());
             owner.save();
+            AuditLog::record("ops_owner_transfer", Some(new_owner.as_ref()), None);
         };
 
         match current_prospective_owner_account_id_hash {
@@ -111,6 +168,7 @@ impl ContractOwnership for ContractOwnershipComponent {
             account_ids.save();
 
             LOG_EVENT_CONTRACT_TRANSFER_CANCELLED.log("");
+            AuditLog::record("ops_owner_cancel_transfer", None, None);
         }
     }
 
@@ -129,8 +187,12 @@ impl ContractOwnership for ContractOwnershipComponent {
 
         owner.save();
         account_ids.save();
+        ContractCoOwnershipComponent::dissolve("contract ownership was transferred");
+        ContractOwnerRecoveryComponent::reset("contract ownership was transferred");
+        ContractOwnerWithdrawalsComponent::reset("contract ownership was transferred");
 
         LOG_EVENT_CONTRACT_TRANSFER_FINALIZED.log("");
+        AuditLog::record("ops_owner_finalize_transfer", None, None);
     }
 
     fn ops_owner_withdraw_balance(
@@ -138,7 +200,7 @@ impl ContractOwnership for ContractOwnershipComponent {
         amount: Option<YoctoNear>,
     ) -> ContractOwnerNearBalance {
         assert_yocto_near_attached();
-        ContractOwnerObject::assert_owner_access();
+        ContractOwnerObject::assert_owner_command_access("ops_owner_withdraw_balance");
 
         let mut owner_balance = self.ops_owner_balance();
         let amount = match amount {
@@ -154,7 +216,23 @@ impl ContractOwnership for ContractOwnershipComponent {
         };
 
         let account_ids = ContractOwnershipAccountIdsObject::load();
-        Promise::new(account_ids.owner.clone()).transfer(amount.value() + 1);
+        if let Some(withdrawal) =
+            ContractOwnerWithdrawalsComponent::request_withdrawal(amount, &account_ids.owner)
+        {
+            AuditLog::record(
+                "ops_owner_withdraw_balance",
+                None,
+                Some(json!({ "amount": amount, "pending_withdrawal": withdrawal.id }).to_string()),
+            );
+            return self.ops_owner_balance();
+        }
+
+        ContractCoOwnershipComponent::transfer_owner_funds(&account_ids.owner, amount + 1);
+        AuditLog::record(
+            "ops_owner_withdraw_balance",
+            None,
+            Some(json!({ "amount": amount }).to_string()),
+        );
 
         owner_balance.total -= amount + 1;
         owner_balance.available -= amount;
@@ -1,7 +1,28 @@
 //! [`ContractSaleComponent`]
+//! - contract bids paid with tokens are accepted via [`ContractSaleComponent::bid_with_tokens`],
+//!   which is exposed as a `TransferReceiver` by the fungible token crate - the contract's
+//!   `ft_on_transfer` must route [`ContractSaleTransferMessage`] transfers to it
 
+use crate::components::contract_co_ownership::ContractCoOwnershipComponent;
+use crate::components::contract_owner_recovery::ContractOwnerRecoveryComponent;
+use crate::components::contract_owner_withdrawals::ContractOwnerWithdrawalsComponent;
 use crate::components::contract_ownership::ContractOwnershipComponent;
-use crate::{ContractBid, ContractSale};
+use crate::{
+    ContractAuction, ContractAuctionBid, ContractAuctionSettings, ContractSaleAuction,
+    DutchAuction, EnglishAuction, SealedBid, SealedBidAuction, ERR_CONTRACT_AUCTION_ENDED,
+    ERR_CONTRACT_AUCTION_IN_PROGRESS, ERR_CONTRACT_AUCTION_NOT_ENDED, ERR_NO_CONTRACT_AUCTION,
+    ERR_SEALED_BID_INVALID_REVEAL, ERR_SEALED_BID_NOT_FOUND, ERR_SEALED_BID_REVEAL_NOT_ALLOWED,
+    LOG_EVENT_CONTRACT_AUCTION_CANCELLED, LOG_EVENT_CONTRACT_AUCTION_ENDED,
+    LOG_EVENT_CONTRACT_AUCTION_EXTENDED, LOG_EVENT_CONTRACT_AUCTION_STARTED,
+    LOG_EVENT_CONTRACT_SEALED_BID_COMMITTED, LOG_EVENT_CONTRACT_SEALED_BID_REVEALED,
+    LOG_EVENT_CONTRACT_SEALED_BID_WITHDRAWN,
+};
+use crate::{
+    ContractBid, ContractSale, ContractSaleCallbacks, ContractSaleTokenClaims,
+    ContractSaleTokenPrice, ContractSaleTokens, ContractSaleTransferMessage,
+    ERR_CONTRACT_SALE_CURRENCY_MISMATCH, ERR_CONTRACT_SALE_TOKEN_NOT_WHITELISTED,
+    LOG_EVENT_CONTRACT_SALE_TOKEN_TRANSFER_FAILURE,
+};
 use crate::{
     ContractBuyerBid, ContractOwner, ContractOwnerObject, ContractOwnership,
     ContractOwnershipAccountIdsObject, ERR_ACCESS_DENIED_MUST_BE_BUYER, ERR_CONTRACT_BID_TOO_LOW,
@@ -11,22 +32,51 @@ use crate::{
     LOG_EVENT_CONTRACT_BID_PLACED, LOG_EVENT_CONTRACT_BID_RAISED, LOG_EVENT_CONTRACT_FOR_SALE,
     LOG_EVENT_CONTRACT_SALE_CANCELLED, LOG_EVENT_CONTRACT_SOLD,
 };
+use crate::{
+    ContractRoyalty, ContractSaleHistory, ContractSaleRecord, ContractSaleRecords,
+    ERR_CONTRACT_ROYALTY_INVALID, ERR_CONTRACT_ROYALTY_NOT_FOUND,
+    LOG_EVENT_CONTRACT_ROYALTY_CHANGE, LOG_EVENT_CONTRACT_ROYALTY_PAID,
+};
 use oysterpack_smart_near::asserts::{assert_near_attached, ERR_CODE_BAD_REQUEST};
-use oysterpack_smart_near::domain::ExpirationSetting;
-use oysterpack_smart_near::near_sdk::{env, Promise};
+use oysterpack_smart_near::data::numbers::{U128, U128_ZERO};
+use oysterpack_smart_near::domain::{BasisPoints, BlockTime, ExpirationSetting, Gas};
+use oysterpack_smart_near::near_sdk::{
+    env, is_promise_success,
+    json_types::{ValidAccountId, U64},
+    serde::{Deserialize, Serialize},
+    AccountId, Promise,
+};
 use oysterpack_smart_near::{
     asserts::assert_yocto_near_attached,
     domain::{Expiration, YoctoNear},
-    LogEvent,
+    json_function_call, json_function_callback, Hash, LogEvent, TERA,
 };
 
+const FT_TRANSFER_GAS: u64 = 10 * TERA;
+const RESOLVE_TOKEN_TRANSFER_GAS: u64 = 10 * TERA;
+
 pub struct ContractSaleComponent;
 
 impl ContractSale for ContractSaleComponent {
     fn ops_contract_sale_price(&self) -> Option<YoctoNear> {
+        if ContractSaleTokens::load().price_token().is_some() {
+            return None;
+        }
         ContractOwnerObject::load().contract_sale_price()
     }
 
+    fn ops_contract_sale_token_price(&self) -> Option<ContractSaleTokenPrice> {
+        let tokens = ContractSaleTokens::load();
+        tokens.price_token().and_then(|token_id| {
+            ContractOwnerObject::load()
+                .contract_sale_price()
+                .map(|price| ContractSaleTokenPrice {
+                    token_id: token_id.clone(),
+                    amount: price.value().into(),
+                })
+        })
+    }
+
     fn ops_contract_bid(&self) -> Option<ContractBuyerBid> {
         ContractOwnerObject::load()
             .bid()
@@ -40,39 +90,50 @@ impl ContractSale for ContractSaleComponent {
                         .cloned()
                         .expect("BUG: contract_bid(): expected buyer"),
                     bid,
+                    token_id: ContractSaleTokens::load().bid_token().cloned(),
                 }
             })
     }
 
-    fn ops_contract_sell(&mut self, price: YoctoNear) {
-        let mut contract_owner = Self::validate_sell_contract_request(price);
-        match contract_owner.bid() {
-            None => match contract_owner.sale_price {
-                Some(current_price) if price == current_price => return,
-                _ => {
-                    contract_owner.sale_price = Some(price);
-                    LOG_EVENT_CONTRACT_FOR_SALE.log(price);
-                }
-            },
-            Some((_buyer, bid)) => {
-                if bid.expired() {
-                    let mut account_ids = ContractOwnershipAccountIdsObject::load();
-                    Self::cancel_bid(&mut contract_owner, &mut account_ids, "bid expired");
-                    account_ids.save();
+    fn ops_contract_sale_tokens(&self) -> Vec<AccountId> {
+        ContractSaleTokens::load().whitelist()
+    }
 
-                    contract_owner.sale_price = Some(price);
-                    LOG_EVENT_CONTRACT_FOR_SALE.log(price);
-                } else if bid.amount >= price {
-                    let mut account_ids = ContractOwnershipAccountIdsObject::load();
-                    Self::execute_contract_sale(&mut contract_owner, &mut account_ids);
-                    account_ids.save();
-                } else {
-                    contract_owner.sale_price = Some(price);
-                    LOG_EVENT_CONTRACT_FOR_SALE.log(price);
-                }
-            }
+    fn ops_contract_sale_token_whitelist(&mut self, token_id: ValidAccountId) {
+        assert_yocto_near_attached();
+        ContractOwnerObject::assert_owner_access();
+        let mut tokens = ContractSaleTokens::load();
+        if tokens.add_to_whitelist(token_id.as_ref()) {
+            tokens.save();
         }
-        contract_owner.save();
+    }
+
+    fn ops_contract_sale_token_unwhitelist(&mut self, token_id: ValidAccountId) -> bool {
+        assert_yocto_near_attached();
+        let owner = ContractOwnerObject::assert_owner_access();
+        let mut tokens = ContractSaleTokens::load();
+        ERR_CONTRACT_SALE_CURRENCY_MISMATCH.assert_with_message(
+            || {
+                owner.sale_price.is_none()
+                    || tokens.price_token().map(String::as_str) != Some(token_id.as_ref().as_str())
+            },
+            || "the contract is listed for sale in the token",
+        );
+        let removed = tokens.remove_from_whitelist(token_id.as_ref());
+        if removed {
+            tokens.save();
+        }
+        removed
+    }
+
+    fn ops_contract_sell(&mut self, price: YoctoNear) {
+        Self::sell(price, None);
+    }
+
+    fn ops_contract_sell_for_token(&mut self, token_id: ValidAccountId, price: U128) {
+        ERR_CONTRACT_SALE_TOKEN_NOT_WHITELISTED
+            .assert(|| ContractSaleTokens::load().is_whitelisted(token_id.as_ref()));
+        Self::sell(price.value().into(), Some(token_id.into()));
     }
 
     fn ops_contract_cancel_sale(&mut self) {
@@ -80,76 +141,29 @@ impl ContractSale for ContractSaleComponent {
         let mut contract_owner = ContractOwnerObject::assert_owner_access();
         if contract_owner.sale_price.take().is_some() {
             contract_owner.save();
+            Self::set_price_token(None);
             LOG_EVENT_CONTRACT_SALE_CANCELLED.log("");
         }
     }
 
     fn ops_contract_buy(&mut self, expiration: Option<ExpirationSetting>) {
         assert_near_attached("contract bid cannot be zero");
-        let expiration = Self::assert_not_expired(expiration);
-
-        let mut account_ids = ContractOwnershipAccountIdsObject::load();
-        ERR_OWNER_CANNOT_BUY_CONTRACT.assert(|| env::predecessor_account_id() != account_ids.owner);
-        let mut owner = ContractOwnerObject::load();
-        ERR_CONTRACT_SALE_NOT_ALLOWED.assert(
-            || !owner.transfer_initiated(),
-            || "bid cannot be placed while contract ownership is being transferred",
+        Self::buy(
+            env::predecessor_account_id(),
+            env::attached_deposit().into(),
+            None,
+            expiration,
         );
-
-        let bid = YoctoNear(env::attached_deposit());
-        match owner.bid.map(|(_, bid)| bid) {
-            None => Self::place_bid(&mut owner, &mut account_ids, bid, expiration),
-            Some(current_bid) => {
-                ERR_CONTRACT_BID_TOO_LOW
-                    .assert(|| bid > current_bid.amount || current_bid.expired());
-                Self::cancel_bid(&mut owner, &mut account_ids, "higher bid has been placed");
-                Self::place_bid(&mut owner, &mut account_ids, bid, expiration);
-            }
-        }
-
-        owner.save();
-        account_ids.save();
     }
 
     fn ops_contract_raise_bid(&mut self, expiration: Option<ExpirationSetting>) -> ContractBid {
         assert_near_attached("bid raise cannot be zero");
-        Self::assert_not_expired(expiration);
-
-        let mut owner = ContractOwnerObject::load();
-        let bid = match owner.bid {
-            None => {
-                ERR_NO_ACTIVE_BID.panic();
-                unreachable!()
-            }
-            Some((buyer_account_id_hash, mut bid)) => {
-                ERR_ACCESS_DENIED_MUST_BE_BUYER
-                    .assert(|| buyer_account_id_hash == env::predecessor_account_id().into());
-                bid.assert_not_expired();
-
-                let amount = env::attached_deposit().into();
-                ContractBid::incr_near_balance(amount);
-
-                bid.amount += amount;
-                bid.update_expiration(expiration);
-                owner.bid = Some((buyer_account_id_hash, bid));
-
-                if let Some(contract_sale_price) = owner.sale_price {
-                    if bid.amount >= contract_sale_price {
-                        let mut account_ids = ContractOwnershipAccountIdsObject::load();
-                        Self::execute_contract_sale(&mut owner, &mut account_ids);
-                        account_ids.save();
-                    } else {
-                        Self::log_bid_raised(bid);
-                    }
-                } else {
-                    Self::log_bid_raised(bid);
-                }
-                bid
-            }
-        };
-
-        owner.save();
-        bid
+        Self::raise_bid(
+            env::predecessor_account_id(),
+            env::attached_deposit().into(),
+            None,
+            expiration,
+        )
     }
 
     fn ops_contract_lower_bid(
@@ -160,6 +174,7 @@ impl ContractSale for ContractSaleComponent {
         assert_yocto_near_attached();
         Self::assert_not_expired(expiration);
         ERR_CODE_BAD_REQUEST.assert(|| amount > YoctoNear::ZERO, || "amount cannot be zero");
+        Self::assert_no_english_auction();
 
         let mut owner = ContractOwnerObject::load();
         let bid = match owner.bid {
@@ -173,25 +188,37 @@ impl ContractSale for ContractSaleComponent {
                     .assert(|| buyer_account_id_hash == env::predecessor_account_id().into());
 
                 bid.amount = bid.amount.saturating_sub(amount.value()).into();
-                ERR_CODE_BAD_REQUEST
-                    .assert(|| bid.amount > YoctoNear::ZERO, || "bid cannot be zero");
+                ERR_CODE_BAD_REQUEST.assert(|| bid.amount.value() > 0, || "bid cannot be zero");
                 bid.update_expiration(expiration);
 
-                ContractBid::decr_near_balance(amount);
-                Self::log_bid_lowered(bid);
-
                 owner.bid = Some((buyer_account_id_hash, bid));
                 bid
             }
         };
 
         owner.save();
-        Promise::new(env::predecessor_account_id()).transfer(amount.value() + 1);
+        match ContractSaleTokens::load().bid_token() {
+            None => {
+                ContractBid::decr_near_balance(amount);
+                Self::log_bid_lowered(bid);
+                Promise::new(env::predecessor_account_id()).transfer(amount.value() + 1);
+            }
+            Some(token_id) => {
+                Self::log_bid_lowered(bid);
+                Self::transfer_tokens(
+                    &env::predecessor_account_id(),
+                    token_id,
+                    amount.value().into(),
+                    "contract bid lowered",
+                );
+            }
+        }
         bid
     }
 
     fn ops_contract_update_bid_expiration(&mut self, expiration: ExpirationSetting) {
         assert_yocto_near_attached();
+        Self::assert_no_english_auction();
         let expiration: Expiration = expiration.into();
         ERR_CODE_BAD_REQUEST.assert(
             || !expiration.expired(),
@@ -240,6 +267,7 @@ impl ContractSale for ContractSaleComponent {
         if owner.bid.is_none() {
             return;
         }
+        Self::assert_no_english_auction();
 
         let mut account_ids = ContractOwnershipAccountIdsObject::load();
         ERR_ACCESS_DENIED_MUST_BE_BUYER
@@ -250,140 +278,1030 @@ impl ContractSale for ContractSaleComponent {
         owner.save();
         account_ids.save();
     }
-}
 
-impl ContractSaleComponent {
-    fn assert_not_expired(expiration: Option<ExpirationSetting>) -> Option<Expiration> {
-        expiration.map(|expiration| {
-            let expiration: Expiration = expiration.into();
-            ERR_CODE_BAD_REQUEST.assert(
-                || !expiration.expired(),
-                || "expiration cannot be set to expired",
+    fn ops_contract_sale_unclaimed_tokens(
+        &self,
+        account_id: ValidAccountId,
+        token_id: ValidAccountId,
+    ) -> U128 {
+        ContractSaleTokenClaims::get(account_id.as_ref(), token_id.as_ref())
+    }
+
+    fn ops_contract_sale_claim_tokens(&mut self, token_id: ValidAccountId) -> U128 {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        let amount = ContractSaleTokenClaims::take(&account_id, token_id.as_ref());
+        if amount.value() > 0 {
+            Self::transfer_tokens(
+                &account_id,
+                token_id.as_ref(),
+                amount,
+                "contract sale token claim",
             );
-            expiration
+        }
+        amount
+    }
+}
+
+impl ContractSaleAuction for ContractSaleComponent {
+    fn ops_contract_auction(&self) -> Option<ContractAuction> {
+        ContractAuction::load()
+    }
+
+    fn ops_contract_auction_price(&self) -> Option<YoctoNear> {
+        ContractAuction::load().map(|auction| match auction {
+            ContractAuction::English(auction) => auction.min_bid(),
+            ContractAuction::Dutch(auction) => auction.price(),
+            ContractAuction::Sealed(auction) => auction.reserve_price,
         })
     }
 
-    fn log_bid_event(event: LogEvent, bid: ContractBid) {
-        match bid.expiration {
-            None => event.log(format!("bid: {}", bid.amount)),
-            Some(expiration) => LOG_EVENT_CONTRACT_BID_PLACED
-                .log(format!("bid: {} | expiration: {}", bid.amount, expiration)),
+    fn ops_contract_auction_bids(&self) -> Vec<ContractAuctionBid> {
+        match ContractAuction::load() {
+            Some(ContractAuction::English(auction)) => auction.bids,
+            Some(ContractAuction::Sealed(auction)) => auction.bids,
+            _ => vec![],
         }
     }
 
-    fn log_bid_placed(bid: ContractBid) {
-        Self::log_bid_event(LOG_EVENT_CONTRACT_BID_PLACED, bid);
-    }
+    fn ops_contract_auction_start(&mut self, settings: ContractAuctionSettings) -> ContractAuction {
+        assert_yocto_near_attached();
+        let mut owner = ContractOwnerObject::assert_owner_access();
+        ERR_CONTRACT_SALE_NOT_ALLOWED.assert(
+            || !owner.transfer_initiated(),
+            || "contract cannot be auctioned after transfer process has been started",
+        );
+        ERR_CONTRACT_AUCTION_IN_PROGRESS.assert(|| ContractAuction::load().is_none());
+        let auction = settings.start();
 
-    fn log_bid_raised(bid: ContractBid) {
-        Self::log_bid_event(LOG_EVENT_CONTRACT_BID_RAISED, bid);
+        if owner.sale_price.take().is_some() {
+            Self::set_price_token(None);
+            LOG_EVENT_CONTRACT_SALE_CANCELLED.log("contract auction started");
+        }
+        if owner.bid.is_some() {
+            let mut account_ids = ContractOwnershipAccountIdsObject::load();
+            Self::cancel_bid(&mut owner, &mut account_ids, "contract auction started");
+            account_ids.save();
+        }
+        owner.save();
+        auction.save();
+
+        match &auction {
+            ContractAuction::English(auction) => LOG_EVENT_CONTRACT_AUCTION_STARTED.log(format!(
+                "English: reserve_price={}, min_increment={}, end={}",
+                auction.reserve_price, auction.min_increment, auction.end
+            )),
+            ContractAuction::Dutch(auction) => LOG_EVENT_CONTRACT_AUCTION_STARTED.log(format!(
+                "Dutch: start_price={}, floor_price={}, price_decrement={}",
+                auction.start_price, auction.floor_price, auction.price_decrement
+            )),
+            ContractAuction::Sealed(auction) => LOG_EVENT_CONTRACT_AUCTION_STARTED.log(format!(
+                "Sealed: reserve_price={}, commit_end={}, reveal_end={}",
+                auction.reserve_price, auction.commit_end, auction.reveal_end
+            )),
+        }
+        auction
     }
 
-    fn log_bid_lowered(bid: ContractBid) {
-        Self::log_bid_event(LOG_EVENT_CONTRACT_BID_LOWERED, bid);
+    fn ops_contract_auction_cancel(&mut self) -> bool {
+        assert_yocto_near_attached();
+        ContractOwnerObject::assert_owner_access();
+        match ContractAuction::load() {
+            None => false,
+            Some(auction) => {
+                ERR_CONTRACT_AUCTION_IN_PROGRESS.assert_with_message(
+                    || !auction.has_bids(),
+                    || "auction cannot be cancelled after bids have been placed",
+                );
+                ContractAuction::delete();
+                LOG_EVENT_CONTRACT_AUCTION_CANCELLED.log("");
+                true
+            }
+        }
     }
 
-    /// 1. clears the current bid
-    /// 2. refunds the bid amount back to the buyer
-    pub(crate) fn cancel_bid(
-        owner: &mut ContractOwnerObject,
-        account_ids: &mut ContractOwnershipAccountIdsObject,
-        msg: &str,
-    ) -> ContractBid {
-        ContractBid::clear_near_balance();
-        let (_, bid) = owner.bid.take().expect("BUG: cancel_bid(): expected bid");
-        let buyer = account_ids
-            .buyer
-            .take()
-            .expect("BUG: cancel_bid(): expected buyer");
-        Promise::new(buyer).transfer(bid.amount.value());
-        LOG_EVENT_CONTRACT_BID_CANCELLED.log(msg);
-        bid
+    fn ops_contract_auction_settle(&mut self) -> bool {
+        match ContractAuction::load() {
+            Some(ContractAuction::English(auction)) => Self::settle_english_auction(auction),
+            Some(ContractAuction::Sealed(auction)) => Self::settle_sealed_bid_auction(auction),
+            _ => {
+                ERR_NO_CONTRACT_AUCTION.panic();
+                unreachable!()
+            }
+        }
     }
 
-    // fn cancel_losing_bid(
-    //     owner: &mut ContractOwnerObject,
-    //     account_ids: &mut ContractOwnershipAccountIdsObject,
-    // ) {
-    //     let bid = Self::cancel_bid(owner, account_ids);
-    //     if bid.expired() {
-    //         LOG_EVENT_CONTRACT_BID_EXPIRED.log("bid expired");
-    //     } else {
-    //         LOG_EVENT_CONTRACT_BID_LOST.log("higher bid was placed");
-    //     }
-    // }
+    fn ops_contract_sealed_bid(&self, account_id: ValidAccountId) -> Option<SealedBid> {
+        SealedBid::load(account_id.as_ref())
+    }
 
-    fn place_bid(
-        owner: &mut ContractOwnerObject,
-        account_ids: &mut ContractOwnershipAccountIdsObject,
+    fn ops_contract_sealed_bid_commitment(
+        &self,
+        account_id: ValidAccountId,
         amount: YoctoNear,
-        expiration: Option<Expiration>,
-    ) {
-        account_ids.buyer = Some(env::predecessor_account_id());
-        let bid = ContractBid { amount, expiration };
-        owner.bid = Some((env::predecessor_account_id().into(), bid));
-        ContractBid::set_near_balance(amount);
+        salt: String,
+    ) -> Hash {
+        SealedBid::commitment(account_id.as_ref(), amount, &salt)
+    }
 
-        if let Some(sale_price) = owner.sale_price {
-            if amount >= sale_price {
-                Self::execute_contract_sale(owner, account_ids);
-                return;
+    fn ops_contract_sealed_bid_commit(&mut self, commitment: Hash) -> SealedBid {
+        assert_near_attached("sealed bid deposit is required");
+        let mut auction = Self::load_sealed_bid_auction();
+        ERR_CONTRACT_AUCTION_ENDED.assert_with_message(
+            || auction.commit_window_open(),
+            || "sealed bid commit window has ended",
+        );
+        let account_id = env::predecessor_account_id();
+        ERR_OWNER_CANNOT_BUY_CONTRACT
+            .assert(|| account_id != ContractOwnershipAccountIdsObject::load().owner);
+
+        let deposit = YoctoNear(env::attached_deposit());
+        let bid = match SealedBid::load(&account_id) {
+            Some(mut bid) if bid.auction_id == auction.id => {
+                bid.commitment = commitment;
+                bid.deposit += deposit;
+                bid
             }
-        }
+            prior_bid => {
+                ERR_CONTRACT_BID_TOO_LOW.assert_with_message(
+                    || deposit >= auction.reserve_price,
+                    || "sealed bid deposit must cover the auction reserve price",
+                );
+                if let Some(prior_bid) = prior_bid {
+                    SealedBid::decr_deposits_near_balance(prior_bid.deposit);
+                    Promise::new(account_id.clone()).transfer(prior_bid.deposit.value());
+                }
+                auction.commitments += 1;
+                ContractAuction::Sealed(auction.clone()).save();
+                SealedBid {
+                    auction_id: auction.id,
+                    commitment,
+                    deposit,
+                    revealed: None,
+                }
+            }
+        };
+        SealedBid::incr_deposits_near_balance(deposit);
+        bid.save(&account_id);
+        LOG_EVENT_CONTRACT_SEALED_BID_COMMITTED.log(format!("deposit: {}", bid.deposit));
+        bid
+    }
 
-        Self::log_bid_placed(bid);
+    fn ops_contract_sealed_bid_reveal(&mut self, amount: YoctoNear, salt: String) -> SealedBid {
+        assert_yocto_near_attached();
+        let mut auction = Self::load_sealed_bid_auction();
+        ERR_SEALED_BID_REVEAL_NOT_ALLOWED.assert(|| auction.reveal_window_open());
+
+        let account_id = env::predecessor_account_id();
+        let mut bid = match SealedBid::load(&account_id) {
+            Some(bid) if bid.auction_id == auction.id => bid,
+            _ => {
+                ERR_SEALED_BID_NOT_FOUND.panic();
+                unreachable!()
+            }
+        };
+        ERR_SEALED_BID_INVALID_REVEAL.assert_with_message(
+            || bid.revealed.is_none(),
+            || "bid has already been revealed",
+        );
+        ERR_SEALED_BID_INVALID_REVEAL.assert_with_message(
+            || bid.commitment == SealedBid::commitment(&account_id, amount, &salt),
+            || "bid does not match the commitment",
+        );
+        ERR_SEALED_BID_INVALID_REVEAL.assert_with_message(
+            || amount > YoctoNear::ZERO && amount <= bid.deposit,
+            || "bid must not be zero and must be covered by the deposit",
+        );
+
+        bid.revealed = Some(amount);
+        bid.save(&account_id);
+        auction.bids.push(ContractAuctionBid {
+            buyer: account_id,
+            amount,
+            placed_on: BlockTime::from_env(),
+        });
+        ContractAuction::Sealed(auction).save();
+        LOG_EVENT_CONTRACT_SEALED_BID_REVEALED.log(format!("bid: {}", amount));
+        bid
     }
 
-    fn validate_sell_contract_request(price: YoctoNear) -> ContractOwnerObject {
+    fn ops_contract_sealed_bid_withdraw(&mut self) -> YoctoNear {
         assert_yocto_near_attached();
-        let contract_owner = ContractOwnerObject::assert_owner_access();
-        ERR_CONTRACT_SALE_NOT_ALLOWED.assert(
-            || !contract_owner.transfer_initiated(),
-            || "contract cannot be sold after transfer process has been started",
+        let account_id = env::predecessor_account_id();
+        let bid = match SealedBid::load(&account_id) {
+            Some(bid) => bid,
+            None => {
+                ERR_SEALED_BID_NOT_FOUND.panic();
+                unreachable!()
+            }
+        };
+        ERR_CONTRACT_AUCTION_IN_PROGRESS.assert_with_message(
+            || match ContractAuction::load() {
+                Some(ContractAuction::Sealed(auction)) => auction.id != bid.auction_id,
+                _ => true,
+            },
+            || "sealed bid deposits can be withdrawn after the auction is settled",
         );
-        ERR_CONTRACT_SALE_PRICE_MUST_NOT_BE_ZERO.assert(|| price > YoctoNear::ZERO);
-        contract_owner
+
+        SealedBid::delete(&account_id);
+        SealedBid::decr_deposits_near_balance(bid.deposit);
+        Promise::new(account_id).transfer(bid.deposit.value() + 1);
+        LOG_EVENT_CONTRACT_SEALED_BID_WITHDRAWN.log(bid.deposit);
+        bid.deposit
     }
+}
 
-    /// 1. clear the NEAR bid balance, which effectively transfers the bid balance to the owner balance
-    /// 2. transfer the owner's NEAR funds out to the owner's account
-    /// 3. update the `contract_owner` object
-    ///    - set the new owner
-    ///    - clear the bid
-    ///    - clear the sale price
-    /// 4. update the ['ContractOwnershipAccountIds`] object
-    ///    - set the new owner account ID
-    ///    - clear the buyer account ID
-    /// 5. log event: LOG_EVENT_CONTRACT_SOLD
-    fn execute_contract_sale(
-        owner: &mut ContractOwner,
-        account_ids: &mut ContractOwnershipAccountIdsObject,
-    ) {
-        ContractBid::clear_near_balance();
+impl ContractSaleHistory for ContractSaleComponent {
+    fn ops_contract_sale_count(&self) -> U64 {
+        ContractSaleRecords::count()
+    }
 
-        // transfer the owner's NEAR funds out to the owner's account
-        let owner_balance = ContractOwnershipComponent.ops_owner_balance();
-        Promise::new(account_ids.owner.clone()).transfer(owner_balance.available.value());
+    fn ops_contract_sale_history(
+        &self,
+        from_index: U64,
+        limit: Option<u32>,
+    ) -> Vec<ContractSaleRecord> {
+        let limit = limit.unwrap_or(100) as u64;
+        (from_index.0..ContractSaleRecords::count().0)
+            .take(limit as usize)
+            .filter_map(ContractSaleRecords::get)
+            .collect()
+    }
 
-        // update the contract owner
-        let (buyer_account_id_hash, bid) = owner
-            .bid
-            .take()
-            .expect("BUG: execute_contract_sale(): expected bid");
-        owner.account_id_hash = buyer_account_id_hash;
-        owner.sale_price.take();
-        account_ids.owner = account_ids
-            .buyer
-            .take()
-            .expect("BUG: execute_contract_sale(): expected buyer");
+    fn ops_contract_royalties(&self) -> Vec<ContractRoyalty> {
+        ContractRoyalty::load_all()
+    }
 
-        LOG_EVENT_CONTRACT_SOLD.log(format!(
-            "buyer={}, price={}",
-            &account_ids.owner, bid.amount
+    fn ops_contract_royalty_grant(
+        &mut self,
+        account_id: ValidAccountId,
+        basis_points: BasisPoints,
+    ) -> ContractRoyalty {
+        assert_yocto_near_attached();
+        let owner = ContractOwnerObject::assert_owner_access();
+        ERR_CONTRACT_ROYALTY_INVALID.assert_with_message(
+            || {
+                owner.sale_price.is_none()
+                    && owner.bid.is_none()
+                    && ContractAuction::load().is_none()
+            },
+            || "royalties cannot be granted while the contract is for sale, has a bid, or is being auctioned",
+        );
+
+        let mut royalties = ContractRoyalty::load_all();
+        let royalty = ContractRoyalty {
+            account_id: account_id.into(),
+            basis_points,
+        };
+        match royalties
+            .iter_mut()
+            .find(|current| current.account_id == royalty.account_id)
+        {
+            Some(current) => {
+                ERR_CONTRACT_ROYALTY_INVALID.assert_with_message(
+                    || basis_points > current.basis_points,
+                    || "royalty can only be raised by the owner",
+                );
+                current.basis_points = basis_points;
+            }
+            None => {
+                ERR_CONTRACT_ROYALTY_INVALID.assert_with_message(
+                    || basis_points > BasisPoints::ZERO,
+                    || "royalty must not be zero",
+                );
+                royalties.push(royalty.clone());
+            }
+        }
+        ERR_CONTRACT_ROYALTY_INVALID.assert_with_message(
+            || {
+                ContractRoyalty::total_basis_points(&royalties)
+                    <= ContractRoyalty::MAX_TOTAL_BASIS_POINTS
+            },
+            || {
+                format!(
+                    "total royalties must not exceed {} basis points",
+                    ContractRoyalty::MAX_TOTAL_BASIS_POINTS
+                )
+            },
+        );
+        ContractRoyalty::save_all(royalties);
+        LOG_EVENT_CONTRACT_ROYALTY_CHANGE.log(format!(
+            "account={}, basis_points={}",
+            royalty.account_id, basis_points
         ));
+        royalty
     }
-}
+
+    fn ops_contract_royalty_reduce(
+        &mut self,
+        basis_points: BasisPoints,
+    ) -> Option<ContractRoyalty> {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        let mut royalties = ContractRoyalty::load_all();
+        let index = match royalties
+            .iter()
+            .position(|royalty| royalty.account_id == account_id)
+        {
+            Some(index) => index,
+            None => {
+                ERR_CONTRACT_ROYALTY_NOT_FOUND.panic();
+                unreachable!()
+            }
+        };
+        ERR_CONTRACT_ROYALTY_INVALID.assert_with_message(
+            || basis_points <= royalties[index].basis_points,
+            || "royalty can only be reduced by the recipient",
+        );
+        let royalty = if basis_points == BasisPoints::ZERO {
+            royalties.remove(index);
+            None
+        } else {
+            royalties[index].basis_points = basis_points;
+            Some(royalties[index].clone())
+        };
+        ContractRoyalty::save_all(royalties);
+        LOG_EVENT_CONTRACT_ROYALTY_CHANGE.log(format!(
+            "account={}, basis_points={}",
+            account_id, basis_points
+        ));
+        royalty
+    }
+}
+
+impl ContractSaleCallbacks for ContractSaleComponent {
+    fn ops_contract_sale_resolve_token_transfer(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+
+        ContractSaleTokenClaims::credit(&account_id, &token_id, amount);
+        LOG_EVENT_CONTRACT_SALE_TOKEN_TRANSFER_FAILURE.log(format!(
+            "tokens can be claimed: account: {}, token: {}, amount: {}",
+            account_id, token_id, amount
+        ));
+        false
+    }
+}
+
+impl ContractSaleComponent {
+    /// Accepts contract bids paid with tokens, where the predecessor account is the token contract.
+    ///
+    /// The full token amount is always used - if the bid is rejected, then the call panics and the
+    /// tokens are refunded by the token contract.
+    ///
+    /// ## Panics
+    /// - [`ERR_CONTRACT_SALE_TOKEN_NOT_WHITELISTED`]
+    /// - if the bid is rejected - see [`ContractSale::ops_contract_buy`] and [`ContractSale::ops_contract_raise_bid`]
+    pub fn bid_with_tokens(
+        sender_id: ValidAccountId,
+        amount: U128,
+        msg: ContractSaleTransferMessage,
+    ) {
+        let token_id = env::predecessor_account_id();
+        ERR_CONTRACT_SALE_TOKEN_NOT_WHITELISTED
+            .assert(|| ContractSaleTokens::load().is_whitelisted(&token_id));
+        ERR_CODE_BAD_REQUEST.assert(|| amount.value() > 0, || "token amount cannot be zero");
+
+        let buyer = sender_id.into();
+        match msg {
+            ContractSaleTransferMessage::ContractBuy { expiration } => {
+                Self::buy(buyer, amount, Some(token_id), expiration)
+            }
+            ContractSaleTransferMessage::ContractRaiseBid { expiration } => {
+                Self::raise_bid(buyer, amount, Some(token_id), expiration);
+            }
+        }
+    }
+
+    fn sell(price: YoctoNear, token_id: Option<AccountId>) {
+        let mut contract_owner = Self::validate_sell_contract_request(price);
+        ERR_CONTRACT_AUCTION_IN_PROGRESS.assert(|| ContractAuction::load().is_none());
+        match contract_owner.bid() {
+            None => match contract_owner.sale_price {
+                Some(current_price)
+                    if price == current_price
+                        && ContractSaleTokens::load().price_token() == token_id.as_ref() =>
+                {
+                    return
+                }
+                _ => Self::set_sale_price(&mut contract_owner, price, token_id),
+            },
+            Some((_buyer, bid)) => {
+                let bid_token_id = ContractSaleTokens::load().bid_token().cloned();
+                if bid.expired() || bid_token_id != token_id {
+                    let mut account_ids = ContractOwnershipAccountIdsObject::load();
+                    let msg = if bid.expired() {
+                        "bid expired"
+                    } else {
+                        "contract sale currency changed"
+                    };
+                    Self::cancel_bid(&mut contract_owner, &mut account_ids, msg);
+                    account_ids.save();
+
+                    Self::set_sale_price(&mut contract_owner, price, token_id);
+                } else if bid.amount >= price.into() {
+                    let mut account_ids = ContractOwnershipAccountIdsObject::load();
+                    Self::execute_contract_sale(
+                        &mut contract_owner,
+                        &mut account_ids,
+                        YoctoNear::ZERO,
+                    );
+                    account_ids.save();
+                } else {
+                    Self::set_sale_price(&mut contract_owner, price, token_id);
+                }
+            }
+        }
+        contract_owner.save();
+    }
+
+    fn set_sale_price(owner: &mut ContractOwner, price: YoctoNear, token_id: Option<AccountId>) {
+        LOG_EVENT_CONTRACT_FOR_SALE.log(Self::format_amount(price.into(), token_id.as_ref()));
+        owner.sale_price = Some(price);
+        Self::set_price_token(token_id);
+    }
+
+    fn set_price_token(token_id: Option<AccountId>) {
+        let mut tokens = ContractSaleTokens::load();
+        if tokens.price_token() != token_id.as_ref() {
+            tokens.set_price_token(token_id);
+            tokens.save();
+        }
+    }
+
+    /// tokens amounts are suffixed with the token ID
+    fn format_amount(amount: U128, token_id: Option<&AccountId>) -> String {
+        match token_id {
+            None => amount.to_string(),
+            Some(token_id) => format!("{} {}", amount, token_id),
+        }
+    }
+
+    fn buy(
+        buyer: AccountId,
+        bid: U128,
+        token_id: Option<AccountId>,
+        expiration: Option<ExpirationSetting>,
+    ) {
+        let expiration = Self::assert_not_expired(expiration);
+
+        let mut account_ids = ContractOwnershipAccountIdsObject::load();
+        ERR_OWNER_CANNOT_BUY_CONTRACT.assert(|| buyer != account_ids.owner);
+        let mut owner = ContractOwnerObject::load();
+        ERR_CONTRACT_SALE_NOT_ALLOWED.assert(
+            || !owner.transfer_initiated(),
+            || "bid cannot be placed while contract ownership is being transferred",
+        );
+
+        if let Some(auction) = ContractAuction::load() {
+            ERR_CONTRACT_SALE_CURRENCY_MISMATCH
+                .assert_with_message(|| token_id.is_none(), || "auctions are paid for in NEAR");
+            ERR_CODE_BAD_REQUEST.assert(
+                || expiration.is_none(),
+                || "bid expiration is not supported in auctions",
+            );
+            match auction {
+                ContractAuction::English(auction) => Self::place_english_auction_bid(
+                    &mut owner,
+                    &mut account_ids,
+                    auction,
+                    buyer,
+                    bid.into(),
+                ),
+                ContractAuction::Dutch(auction) => Self::buy_dutch_auction(
+                    &mut owner,
+                    &mut account_ids,
+                    auction,
+                    buyer,
+                    bid.into(),
+                ),
+                ContractAuction::Sealed(_) => ERR_CONTRACT_AUCTION_IN_PROGRESS
+                    .panic_with_message("sealed bid auction bids must be committed"),
+            }
+            owner.save();
+            account_ids.save();
+            return;
+        }
+
+        let tokens = ContractSaleTokens::load();
+        if owner.sale_price.is_some() {
+            ERR_CONTRACT_SALE_CURRENCY_MISMATCH
+                .assert(|| tokens.price_token() == token_id.as_ref());
+        }
+
+        match owner.bid.map(|(_, bid)| bid) {
+            None => Self::place_bid(
+                &mut owner,
+                &mut account_ids,
+                buyer,
+                bid,
+                token_id,
+                expiration,
+            ),
+            Some(current_bid) => {
+                if !current_bid.expired() {
+                    ERR_CONTRACT_SALE_CURRENCY_MISMATCH
+                        .assert(|| tokens.bid_token() == token_id.as_ref());
+                    ERR_CONTRACT_BID_TOO_LOW.assert(|| bid > current_bid.amount);
+                }
+                Self::cancel_bid(&mut owner, &mut account_ids, "higher bid has been placed");
+                Self::place_bid(
+                    &mut owner,
+                    &mut account_ids,
+                    buyer,
+                    bid,
+                    token_id,
+                    expiration,
+                );
+            }
+        }
+
+        owner.save();
+        account_ids.save();
+    }
+
+    fn raise_bid(
+        buyer: AccountId,
+        amount: U128,
+        token_id: Option<AccountId>,
+        expiration: Option<ExpirationSetting>,
+    ) -> ContractBid {
+        Self::assert_not_expired(expiration);
+
+        let mut owner = ContractOwnerObject::load();
+        let bid = match owner.bid {
+            None => {
+                ERR_NO_ACTIVE_BID.panic();
+                unreachable!()
+            }
+            Some((buyer_account_id_hash, mut bid)) => {
+                ERR_ACCESS_DENIED_MUST_BE_BUYER
+                    .assert(|| buyer_account_id_hash == buyer.as_str().into());
+                bid.assert_not_expired();
+                ERR_CONTRACT_SALE_CURRENCY_MISMATCH
+                    .assert(|| ContractSaleTokens::load().bid_token() == token_id.as_ref());
+                let auction = match ContractAuction::load() {
+                    Some(ContractAuction::English(auction)) => {
+                        ERR_CONTRACT_AUCTION_ENDED.assert(|| !auction.end.expired());
+                        ERR_CONTRACT_BID_TOO_LOW
+                            .assert(|| YoctoNear::from(amount) >= auction.min_increment);
+                        ERR_CODE_BAD_REQUEST.assert(
+                            || expiration.is_none(),
+                            || "bid expiration is not supported in auctions",
+                        );
+                        Some(auction)
+                    }
+                    _ => None,
+                };
+
+                if token_id.is_none() {
+                    ContractBid::incr_near_balance(amount.into());
+                }
+
+                bid.amount += amount.value();
+                bid.update_expiration(expiration);
+                owner.bid = Some((buyer_account_id_hash, bid));
+                if let Some(auction) = auction {
+                    Self::record_english_auction_bid(auction, buyer, bid.amount.into());
+                }
+
+                if let Some(contract_sale_price) = owner.sale_price {
+                    if bid.amount >= contract_sale_price.into() {
+                        let mut account_ids = ContractOwnershipAccountIdsObject::load();
+                        Self::execute_contract_sale(&mut owner, &mut account_ids, YoctoNear::ZERO);
+                        account_ids.save();
+                    } else {
+                        Self::log_bid_raised(bid);
+                    }
+                } else {
+                    Self::log_bid_raised(bid);
+                }
+                bid
+            }
+        };
+
+        owner.save();
+        bid
+    }
+
+    fn settle_english_auction(auction: EnglishAuction) -> bool {
+        ERR_CONTRACT_AUCTION_NOT_ENDED.assert(|| auction.end.expired());
+        ContractAuction::delete();
+
+        let mut owner = ContractOwnerObject::load();
+        let mut account_ids = ContractOwnershipAccountIdsObject::load();
+        let sold = match owner.bid {
+            Some((_, bid)) if YoctoNear::from(bid.amount) >= auction.reserve_price => {
+                Self::execute_contract_sale(&mut owner, &mut account_ids, YoctoNear::ZERO);
+                true
+            }
+            Some(_) => {
+                Self::cancel_bid(&mut owner, &mut account_ids, "reserve price was not met");
+                LOG_EVENT_CONTRACT_AUCTION_ENDED.log("reserve price was not met");
+                false
+            }
+            None => {
+                LOG_EVENT_CONTRACT_AUCTION_ENDED.log("no bids were placed");
+                false
+            }
+        };
+        owner.save();
+        account_ids.save();
+        sold
+    }
+
+    /// the winning bid amount is paid from the buyer's deposit, and the rest of the deposit can be
+    /// withdrawn by the buyer
+    fn settle_sealed_bid_auction(auction: SealedBidAuction) -> bool {
+        ERR_CONTRACT_AUCTION_NOT_ENDED.assert(|| auction.reveal_end.expired());
+        ContractAuction::delete();
+
+        match auction.highest_bid() {
+            Some(bid) if bid.amount >= auction.reserve_price => {
+                let mut sealed_bid = SealedBid::load(&bid.buyer)
+                    .expect("BUG: settle_sealed_bid_auction(): expected sealed bid");
+                sealed_bid.deposit -= bid.amount;
+                if sealed_bid.deposit == YoctoNear::ZERO {
+                    SealedBid::delete(&bid.buyer);
+                } else {
+                    sealed_bid.save(&bid.buyer);
+                }
+                SealedBid::decr_deposits_near_balance(bid.amount);
+
+                let mut owner = ContractOwnerObject::load();
+                let mut account_ids = ContractOwnershipAccountIdsObject::load();
+                owner.bid = Some((
+                    bid.buyer.as_str().into(),
+                    ContractBid {
+                        amount: bid.amount.into(),
+                        expiration: None,
+                    },
+                ));
+                account_ids.buyer = Some(bid.buyer.clone());
+                Self::execute_contract_sale(&mut owner, &mut account_ids, YoctoNear::ZERO);
+                owner.save();
+                account_ids.save();
+                true
+            }
+            Some(_) => {
+                LOG_EVENT_CONTRACT_AUCTION_ENDED.log("reserve price was not met");
+                false
+            }
+            None => {
+                LOG_EVENT_CONTRACT_AUCTION_ENDED.log("no bids were revealed");
+                false
+            }
+        }
+    }
+
+    fn load_sealed_bid_auction() -> SealedBidAuction {
+        match ContractAuction::load() {
+            Some(ContractAuction::Sealed(auction)) => auction,
+            _ => {
+                ERR_NO_CONTRACT_AUCTION.panic();
+                unreachable!()
+            }
+        }
+    }
+
+    /// bids are binding while the English auction is in progress
+    fn assert_no_english_auction() {
+        ERR_CONTRACT_AUCTION_IN_PROGRESS.assert_with_message(
+            || !matches!(ContractAuction::load(), Some(ContractAuction::English(_))),
+            || "bids cannot be lowered, cancelled, or changed while the auction is in progress",
+        );
+    }
+
+    fn place_english_auction_bid(
+        owner: &mut ContractOwnerObject,
+        account_ids: &mut ContractOwnershipAccountIdsObject,
+        auction: EnglishAuction,
+        buyer: AccountId,
+        bid: YoctoNear,
+    ) {
+        ERR_CONTRACT_AUCTION_ENDED.assert(|| !auction.end.expired());
+        ERR_CONTRACT_BID_TOO_LOW.assert(|| bid >= auction.min_bid());
+        if owner.bid.is_some() {
+            Self::cancel_bid(owner, account_ids, "higher bid has been placed");
+        }
+        Self::place_bid(owner, account_ids, buyer.clone(), bid.into(), None, None);
+        Self::record_english_auction_bid(auction, buyer, bid);
+    }
+
+    fn record_english_auction_bid(mut auction: EnglishAuction, buyer: AccountId, bid: YoctoNear) {
+        if auction.record_bid(buyer, bid) {
+            LOG_EVENT_CONTRACT_AUCTION_EXTENDED.log(format!("end: {}", auction.end));
+        }
+        ContractAuction::English(auction).save();
+    }
+
+    /// the buyer pays the current auction price, and the rest of the deposit is refunded
+    fn buy_dutch_auction(
+        owner: &mut ContractOwnerObject,
+        account_ids: &mut ContractOwnershipAccountIdsObject,
+        auction: DutchAuction,
+        buyer: AccountId,
+        deposit: YoctoNear,
+    ) {
+        let price = auction.price();
+        ERR_CONTRACT_BID_TOO_LOW.assert(|| deposit >= price);
+        ContractAuction::delete();
+
+        owner.bid = Some((
+            buyer.as_str().into(),
+            ContractBid {
+                amount: price.into(),
+                expiration: None,
+            },
+        ));
+        account_ids.buyer = Some(buyer);
+        Self::execute_contract_sale(owner, account_ids, deposit - price);
+    }
+
+    fn assert_not_expired(expiration: Option<ExpirationSetting>) -> Option<Expiration> {
+        expiration.map(|expiration| {
+            let expiration: Expiration = expiration.into();
+            ERR_CODE_BAD_REQUEST.assert(
+                || !expiration.expired(),
+                || "expiration cannot be set to expired",
+            );
+            expiration
+        })
+    }
+
+    fn log_bid_event(event: LogEvent, bid: ContractBid) {
+        let amount = Self::format_amount(bid.amount, ContractSaleTokens::load().bid_token());
+        match bid.expiration {
+            None => event.log(format!("bid: {}", amount)),
+            Some(expiration) => LOG_EVENT_CONTRACT_BID_PLACED
+                .log(format!("bid: {} | expiration: {}", amount, expiration)),
+        }
+    }
+
+    fn log_bid_placed(bid: ContractBid) {
+        Self::log_bid_event(LOG_EVENT_CONTRACT_BID_PLACED, bid);
+    }
+
+    fn log_bid_raised(bid: ContractBid) {
+        Self::log_bid_event(LOG_EVENT_CONTRACT_BID_RAISED, bid);
+    }
+
+    fn log_bid_lowered(bid: ContractBid) {
+        Self::log_bid_event(LOG_EVENT_CONTRACT_BID_LOWERED, bid);
+    }
+
+    /// 1. clears the current bid
+    /// 2. refunds the bid amount back to the buyer in the bid currency
+    pub(crate) fn cancel_bid(
+        owner: &mut ContractOwnerObject,
+        account_ids: &mut ContractOwnershipAccountIdsObject,
+        msg: &str,
+    ) -> ContractBid {
+        let (_, bid) = owner.bid.take().expect("BUG: cancel_bid(): expected bid");
+        let buyer = account_ids
+            .buyer
+            .take()
+            .expect("BUG: cancel_bid(): expected buyer");
+        let mut tokens = ContractSaleTokens::load();
+        match tokens.bid_token().cloned() {
+            None => {
+                ContractBid::clear_near_balance();
+                Promise::new(buyer).transfer(bid.amount.value());
+            }
+            Some(token_id) => {
+                tokens.set_bid_token(None);
+                tokens.save();
+                Self::transfer_tokens(&buyer, &token_id, bid.amount, "contract bid refund");
+            }
+        }
+        LOG_EVENT_CONTRACT_BID_CANCELLED.log(msg);
+        bid
+    }
+
+    // fn cancel_losing_bid(
+    //     owner: &mut ContractOwnerObject,
+    //     account_ids: &mut ContractOwnershipAccountIdsObject,
+    // ) {
+    //     let bid = Self::cancel_bid(owner, account_ids);
+    //     if bid.expired() {
+    //         LOG_EVENT_CONTRACT_BID_EXPIRED.log("bid expired");
+    //     } else {
+    //         LOG_EVENT_CONTRACT_BID_LOST.log("higher bid was placed");
+    //     }
+    // }
+
+    fn place_bid(
+        owner: &mut ContractOwnerObject,
+        account_ids: &mut ContractOwnershipAccountIdsObject,
+        buyer: AccountId,
+        amount: U128,
+        token_id: Option<AccountId>,
+        expiration: Option<Expiration>,
+    ) {
+        let bid = ContractBid { amount, expiration };
+        owner.bid = Some((buyer.as_str().into(), bid));
+        account_ids.buyer = Some(buyer);
+        if token_id.is_none() {
+            ContractBid::set_near_balance(amount.into());
+        } else {
+            let mut tokens = ContractSaleTokens::load();
+            tokens.set_bid_token(token_id);
+            tokens.save();
+        }
+
+        if let Some(sale_price) = owner.sale_price {
+            if amount >= sale_price.into() {
+                Self::execute_contract_sale(owner, account_ids, YoctoNear::ZERO);
+                return;
+            }
+        }
+
+        Self::log_bid_placed(bid);
+    }
+
+    fn validate_sell_contract_request(price: YoctoNear) -> ContractOwnerObject {
+        assert_yocto_near_attached();
+        let contract_owner = ContractOwnerObject::assert_owner_access();
+        ERR_CONTRACT_SALE_NOT_ALLOWED.assert(
+            || !contract_owner.transfer_initiated(),
+            || "contract cannot be sold after transfer process has been started",
+        );
+        ERR_CONTRACT_SALE_PRICE_MUST_NOT_BE_ZERO.assert(|| price > YoctoNear::ZERO);
+        contract_owner
+    }
+
+    /// 1. clear the NEAR bid balance, which effectively transfers the bid balance to the owner balance
+    /// 2. transfer the owner's NEAR funds out to the owner's account
+    ///    - if the bid is in tokens, then the tokens are transferred to the owner's account
+    /// 3. update the `contract_owner` object
+    ///    - set the new owner
+    ///    - clear the bid
+    ///    - clear the sale price
+    /// 4. update the ['ContractOwnershipAccountIds`] object
+    ///    - set the new owner account ID
+    ///    - clear the buyer account ID
+    /// 5. log event: LOG_EVENT_CONTRACT_SOLD
+    ///
+    /// `buyer_refund` is the buyer's excess NEAR deposit, which is refunded to the buyer instead of
+    /// being transferred to the owner
+    fn execute_contract_sale(
+        owner: &mut ContractOwner,
+        account_ids: &mut ContractOwnershipAccountIdsObject,
+        buyer_refund: YoctoNear,
+    ) {
+        let mut tokens = ContractSaleTokens::load();
+        let bid_token_id = tokens.bid_token().cloned();
+        if bid_token_id.is_none() {
+            ContractBid::clear_near_balance();
+        }
+
+        let (buyer_account_id_hash, bid) = owner
+            .bid
+            .take()
+            .expect("BUG: execute_contract_sale(): expected bid");
+
+        // royalties are paid out of the sale price
+        let royalties: Vec<(AccountId, U128)> = ContractRoyalty::load_all()
+            .iter()
+            .map(|royalty| (royalty.account_id.clone(), royalty.amount(bid.amount)))
+            .filter(|(_, amount)| amount.value() > 0)
+            .collect();
+        let royalties_total = royalties
+            .iter()
+            .fold(U128_ZERO, |total, (_, amount)| total + amount.value());
+        for (account_id, amount) in royalties.iter() {
+            match bid_token_id.as_ref() {
+                None => {
+                    Promise::new(account_id.clone()).transfer(amount.value());
+                }
+                Some(token_id) => {
+                    Self::transfer_tokens(account_id, token_id, *amount, "contract sale royalty");
+                }
+            }
+            LOG_EVENT_CONTRACT_ROYALTY_PAID.log(format!(
+                "account={}, amount={}",
+                account_id,
+                Self::format_amount(*amount, bid_token_id.as_ref())
+            ));
+        }
+
+        // the seller's pending withdrawals are released, i.e., they are paid out to the seller with
+        // the rest of the owner's NEAR funds
+        ContractOwnerWithdrawalsComponent::reset("contract was sold");
+
+        // transfer the owner's NEAR funds out to the owner's account
+        let owner_balance = ContractOwnershipComponent.ops_owner_balance();
+        match bid_token_id.as_ref() {
+            None => {
+                ContractCoOwnershipComponent::transfer_owner_funds(
+                    &account_ids.owner,
+                    owner_balance
+                        .available
+                        .saturating_sub(buyer_refund.value())
+                        .saturating_sub(royalties_total.value())
+                        .into(),
+                );
+                if buyer_refund > YoctoNear::ZERO {
+                    let buyer = account_ids
+                        .buyer
+                        .as_ref()
+                        .expect("BUG: execute_contract_sale(): expected buyer");
+                    Promise::new(buyer.clone()).transfer(buyer_refund.value());
+                }
+            }
+            Some(token_id) => {
+                // 1 yoctoNEAR is kept to attach to the `ft_transfer` call
+                ContractCoOwnershipComponent::transfer_owner_funds(
+                    &account_ids.owner,
+                    owner_balance.available.saturating_sub(1).into(),
+                );
+                let proceeds = bid.amount - royalties_total.value();
+                for (account_id, amount) in
+                    ContractCoOwnershipComponent::owner_payees(proceeds.value())
+                {
+                    Self::transfer_tokens(&account_id, token_id, amount.into(), "contract sale");
+                }
+            }
+        }
+
+        // update the contract owner
+        let seller = account_ids.owner.clone();
+        owner.account_id_hash = buyer_account_id_hash;
+        owner.sale_price.take();
+        account_ids.owner = account_ids
+            .buyer
+            .take()
+            .expect("BUG: execute_contract_sale(): expected buyer");
+        if tokens.bid_token().is_some() || tokens.price_token().is_some() {
+            tokens.set_bid_token(None);
+            tokens.set_price_token(None);
+            tokens.save();
+        }
+
+        LOG_EVENT_CONTRACT_SOLD.log(format!(
+            "buyer={}, price={}",
+            &account_ids.owner,
+            Self::format_amount(bid.amount, bid_token_id.as_ref())
+        ));
+        ContractCoOwnershipComponent::dissolve("contract was sold");
+        ContractOwnerRecoveryComponent::reset("contract was sold");
+        ContractSaleRecords::append(ContractSaleRecord {
+            seller,
+            buyer: account_ids.owner.clone(),
+            price: bid.amount,
+            token_id: bid_token_id,
+            royalties,
+            sold_on: BlockTime::from_env(),
+        });
+    }
+
+    /// transfers the tokens to the account, and then resolves the transfer
+    fn transfer_tokens(account_id: &str, token_id: &str, amount: U128, memo: &str) -> Promise {
+        json_function_call(
+            token_id,
+            "ft_transfer",
+            Some(FtTransferArgs {
+                receiver_id: account_id.to_string(),
+                amount,
+                memo: Some(memo.to_string()),
+            }),
+            1.into(),
+            Gas(FT_TRANSFER_GAS),
+        )
+        .then(json_function_callback(
+            "ops_contract_sale_resolve_token_transfer",
+            Some(ResolveTokenTransferArgs {
+                account_id: account_id.to_string(),
+                token_id: token_id.to_string(),
+                amount,
+            }),
+            YoctoNear::ZERO,
+            Gas(RESOLVE_TOKEN_TRANSFER_GAS),
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+struct FtTransferArgs {
+    receiver_id: AccountId,
+    amount: U128,
+    memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+struct ResolveTokenTransferArgs {
+    account_id: AccountId,
+    token_id: AccountId,
+    amount: U128,
+}
 
 #[cfg(test)]
 mod tests {
@@ -427,7 +1345,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 1000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
 
         // Act - Bob raises the bid
@@ -439,7 +1357,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 2000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
 
         // Act - Bob raises the bid and updates expiration
@@ -451,7 +1369,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 3000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
 
         // Act - Bob sets an expiration
@@ -466,7 +1384,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 3000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert_eq!(
             bid.bid.expiration,
             Some(ExpirationSetting::Relative(ExpirationDuration::Epochs(10),).into())
@@ -481,7 +1399,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 3000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
 
         // Act - Bob lowers the bid
@@ -493,7 +1411,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 2000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
         let receipts = deserialize_receipts();
         let action = &receipts[0].actions[0];
@@ -518,7 +1436,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 2000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
 
         // Act - owner cancels sale
@@ -531,7 +1449,7 @@ mod tests {
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
         assert_eq!(bid.buyer.as_str(), bob);
         assert_eq!(bid.bid.amount.value(), 2000);
-        assert_eq!(ContractBid::near_balance(), bid.bid.amount);
+        assert_eq!(ContractBid::near_balance(), bid.bid.amount.into());
         assert!(bid.bid.expiration.is_none());
 
         // Act - buyer cancels bid
@@ -595,17 +1513,109 @@ mod tests_sell_contract {
     use oysterpack_smart_near_test::*;
 
     #[test]
-    fn new_sale_no_bid() {
+    fn new_sale_no_bid() {
+        // Arrange
+        let alfio = "alfio";
+
+        let mut ctx = new_context(alfio);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(alfio));
+
+        // Act
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        // Assert
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_price(),
+            Some(YOCTO.into())
+        );
+        let logs = test_utils::get_logs();
+
+        assert_eq!(
+            &logs[0],
+            LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
+        );
+    }
+
+    #[test]
+    fn update_sale_no_bid() {
+        // Arrange
+        let alfio = "alfio";
+
+        let mut ctx = new_context(alfio);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(alfio));
+
+        // Act
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        ContractSaleComponent.ops_contract_sell((2 * YOCTO).into());
+        // Assert
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_price(),
+            Some((2 * YOCTO).into())
+        );
+        let logs = test_utils::get_logs();
+
+        assert_eq!(
+            &logs[0],
+            LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
+        );
+        assert_eq!(
+            &logs[1],
+            LOG_EVENT_CONTRACT_FOR_SALE.message(2 * YOCTO).as_str()
+        );
+    }
+
+    #[test]
+    fn update_sale_with_same_price_no_bid() {
+        // Arrange
+        let alfio = "alfio";
+
+        let mut ctx = new_context(alfio);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(alfio));
+
+        // Act
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        // Assert
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_price(),
+            Some(YOCTO.into())
+        );
+        let logs = test_utils::get_logs();
+        assert_eq!(
+            &logs[0],
+            LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
+        );
+        assert_eq!(logs.len(), 1);
+    }
+
+    #[test]
+    fn new_sale_lower_bid() {
         // Arrange
-        let alfio = "alfio";
+        let owner = "alfio";
+        let buyer = "bob";
 
-        let mut ctx = new_context(alfio);
+        let mut ctx = new_context(owner);
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(alfio));
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+        ctx.attached_deposit = 100;
+        ctx.predecessor_account_id = buyer.to_string();
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
         // Act
+        ctx.attached_deposit = 1;
+        ctx.predecessor_account_id = owner.to_string();
+        testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
         // Assert
         assert_eq!(
@@ -621,65 +1631,81 @@ mod tests_sell_contract {
     }
 
     #[test]
-    fn update_sale_no_bid() {
+    fn new_sale_matching_bid() {
         // Arrange
-        let alfio = "alfio";
+        let owner = "alfio";
+        let buyer = "bob";
 
-        let mut ctx = new_context(alfio);
+        let mut ctx = new_context(owner);
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(alfio));
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+        ctx.attached_deposit = YOCTO;
+        ctx.predecessor_account_id = buyer.to_string();
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
         // Act
+        ctx.attached_deposit = 1;
+        ctx.predecessor_account_id = owner.to_string();
+        testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
-        ContractSaleComponent.ops_contract_sell((2 * YOCTO).into());
         // Assert
-        assert_eq!(
-            ContractSaleComponent.ops_contract_sale_price(),
-            Some((2 * YOCTO).into())
-        );
+        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert!(ContractOwnershipAccountIdsObject::load().buyer.is_none());
+        assert_eq!(ContractOwnershipComponent.ops_owner(), buyer.to_string());
+
         let logs = test_utils::get_logs();
 
         assert_eq!(
             &logs[0],
-            LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
-        );
-        assert_eq!(
-            &logs[1],
-            LOG_EVENT_CONTRACT_FOR_SALE.message(2 * YOCTO).as_str()
+            LOG_EVENT_CONTRACT_SOLD
+                .message(format!("buyer={}, price={}", buyer, YOCTO))
+                .as_str()
         );
     }
 
     #[test]
-    fn update_sale_with_same_price_no_bid() {
+    fn new_sale_higher_bid() {
         // Arrange
-        let alfio = "alfio";
+        let owner = "alfio";
+        let buyer = "bob";
 
-        let mut ctx = new_context(alfio);
+        let mut ctx = new_context(owner);
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(alfio));
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+        ctx.attached_deposit = 2 * YOCTO;
+        ctx.predecessor_account_id = buyer.to_string();
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
         // Act
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        ctx.attached_deposit = 1;
+        ctx.predecessor_account_id = owner.to_string();
+        testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
         // Assert
-        assert_eq!(
-            ContractSaleComponent.ops_contract_sale_price(),
-            Some(YOCTO.into())
-        );
+        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert!(ContractOwnershipAccountIdsObject::load().buyer.is_none());
+        assert_eq!(ContractOwnershipComponent.ops_owner(), buyer.to_string());
+
         let logs = test_utils::get_logs();
+
         assert_eq!(
             &logs[0],
-            LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
+            LOG_EVENT_CONTRACT_SOLD
+                .message(format!("buyer={}, price={}", buyer, 2 * YOCTO))
+                .as_str()
         );
-        assert_eq!(logs.len(), 1);
     }
 
     #[test]
-    fn new_sale_lower_bid() {
+    fn new_sale_expired_bid() {
         // Arrange
         let owner = "alfio";
         let buyer = "bob";
@@ -689,14 +1715,18 @@ mod tests_sell_contract {
         testing_env!(ctx.clone());
 
         ContractOwnershipComponent::deploy(to_valid_account_id(owner));
-        ctx.attached_deposit = 100;
+        ctx.attached_deposit = 2 * YOCTO;
         ctx.predecessor_account_id = buyer.to_string();
+        ctx.epoch_height = 100;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Absolute(
+            Expiration::Epoch(200.into()),
+        )));
 
         // Act
         ctx.attached_deposit = 1;
         ctx.predecessor_account_id = owner.to_string();
+        ctx.epoch_height = 201;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
         // Assert
@@ -704,16 +1734,26 @@ mod tests_sell_contract {
             ContractSaleComponent.ops_contract_sale_price(),
             Some(YOCTO.into())
         );
-        let logs = test_utils::get_logs();
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert!(ContractOwnershipAccountIdsObject::load().buyer.is_none());
+        assert_eq!(ContractOwnershipComponent.ops_owner(), owner.to_string());
 
+        let logs = test_utils::get_logs();
+        println!("{:#?}", logs);
         assert_eq!(
             &logs[0],
+            LOG_EVENT_CONTRACT_BID_CANCELLED
+                .message("bid expired")
+                .as_str()
+        );
+        assert_eq!(
+            &logs[1],
             LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
         );
     }
 
     #[test]
-    fn new_sale_matching_bid() {
+    fn updated_sale_matching_bid() {
         // Arrange
         let owner = "alfio";
         let buyer = "bob";
@@ -732,6 +1772,7 @@ mod tests_sell_contract {
         ctx.attached_deposit = 1;
         ctx.predecessor_account_id = owner.to_string();
         testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell((5 * YOCTO).into());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
         // Assert
         assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
@@ -743,6 +1784,10 @@ mod tests_sell_contract {
 
         assert_eq!(
             &logs[0],
+            LOG_EVENT_CONTRACT_FOR_SALE.message(5 * YOCTO).as_str()
+        );
+        assert_eq!(
+            &logs[1],
             LOG_EVENT_CONTRACT_SOLD
                 .message(format!("buyer={}, price={}", buyer, YOCTO))
                 .as_str()
@@ -750,7 +1795,7 @@ mod tests_sell_contract {
     }
 
     #[test]
-    fn new_sale_higher_bid() {
+    fn update_sale_higher_bid() {
         // Arrange
         let owner = "alfio";
         let buyer = "bob";
@@ -769,6 +1814,7 @@ mod tests_sell_contract {
         ctx.attached_deposit = 1;
         ctx.predecessor_account_id = owner.to_string();
         testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell((5 * YOCTO).into());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
         // Assert
         assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
@@ -780,6 +1826,10 @@ mod tests_sell_contract {
 
         assert_eq!(
             &logs[0],
+            LOG_EVENT_CONTRACT_FOR_SALE.message(5 * YOCTO).as_str()
+        );
+        assert_eq!(
+            &logs[1],
             LOG_EVENT_CONTRACT_SOLD
                 .message(format!("buyer={}, price={}", buyer, 2 * YOCTO))
                 .as_str()
@@ -787,7 +1837,10 @@ mod tests_sell_contract {
     }
 
     #[test]
-    fn new_sale_expired_bid() {
+    #[should_panic(
+        expected = "[ERR] [CONTRACT_SALE_NOT_ALLOWED] contract cannot be sold after transfer process has been started"
+    )]
+    fn transfer_ownership_initiated() {
         // Arrange
         let owner = "alfio";
         let buyer = "bob";
@@ -796,702 +1849,1047 @@ mod tests_sell_contract {
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
-        ctx.attached_deposit = 2 * YOCTO;
-        ctx.predecessor_account_id = buyer.to_string();
-        ctx.epoch_height = 100;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Absolute(
-            Expiration::Epoch(200.into()),
-        )));
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(buyer));
+
+        // Act
+        ctx.attached_deposit = 1;
+        ctx.predecessor_account_id = owner.to_string();
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_ACCESS_REQUIRED]")]
+    fn not_owner() {
+        // Arrange
+        let owner = "alfio";
+
+        let mut ctx = new_context(owner);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+
+        // Act
+        ctx.attached_deposit = 1;
+        ctx.predecessor_account_id = "bob".to_string();
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn zero_deposit() {
+        // Arrange
+        let owner = "alfio";
+
+        let mut ctx = new_context(owner);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+
+        // Act
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn two_deposit() {
+        // Arrange
+        let owner = "alfio";
+
+        let mut ctx = new_context(owner);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+
+        // Act
+        ctx.attached_deposit = 2;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_SALE_PRICE_MUST_NOT_BE_ZERO]")]
+    fn zero_sale_price() {
+        // Arrange
+        let owner = "alfio";
+
+        let mut ctx = new_context(owner);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+
+        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+
+        // Act
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(YoctoNear::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests_buy_contract {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near::component::*;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+    const BUYER_1: &str = "buyer1";
+    const BUYER_2: &str = "buyer2";
+
+    fn arrange(sale_price: Option<YoctoNear>, bid: Option<ContractBuyerBid>) -> VMContext {
+        let ctx = new_context(OWNER);
+        {
+            let mut ctx = ctx.clone();
+            ctx.attached_deposit = 1;
+            testing_env!(ctx.clone());
+
+            ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+            if let Some(sale_price) = sale_price {
+                ContractSaleComponent.ops_contract_sell(sale_price);
+            }
+
+            if let Some(bid) = bid {
+                ctx.predecessor_account_id = bid.buyer;
+                ctx.attached_deposit = bid.bid.amount.value();
+                testing_env!(ctx.clone());
+                ContractSaleComponent
+                    .ops_contract_buy(bid.bid.expiration.as_ref().cloned().map(Into::into));
+            }
+        }
+        assert_eq!(ContractOwnershipComponent.ops_owner(), OWNER.to_string());
+        ctx
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [NEAR_DEPOSIT_REQUIRED]")]
+    fn zero_yocto_near_attached() {
+        let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 0;
+        ctx.predecessor_account_id = BUYER_1.to_string();
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+    }
+
+    #[test]
+    fn higher_sale_price_and_lower_prior_bid_() {
+        let mut ctx = arrange(
+            None,
+            Some(ContractBuyerBid {
+                buyer: BUYER_2.to_string(),
+                bid: ContractBid {
+                    amount: 1000.into(),
+                    expiration: None,
+                },
+                token_id: None,
+            }),
+        );
 
-        // Act
         ctx.attached_deposit = 1;
-        ctx.predecessor_account_id = owner.to_string();
-        ctx.epoch_height = 201;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
-        // Assert
-        assert_eq!(
-            ContractSaleComponent.ops_contract_sale_price(),
-            Some(YOCTO.into())
-        );
-        assert!(ContractSaleComponent.ops_contract_bid().is_none());
-        assert!(ContractOwnershipAccountIdsObject::load().buyer.is_none());
-        assert_eq!(ContractOwnershipComponent.ops_owner(), owner.to_string());
+
+        ctx.predecessor_account_id = BUYER_1.to_string();
+        ctx.attached_deposit = 1001;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.buyer, ctx.predecessor_account_id);
+        assert_eq!(bid.bid.amount, ctx.attached_deposit.into());
 
         let logs = test_utils::get_logs();
         println!("{:#?}", logs);
         assert_eq!(
-            &logs[0],
-            LOG_EVENT_CONTRACT_BID_CANCELLED
-                .message("bid expired")
-                .as_str()
-        );
-        assert_eq!(
-            &logs[1],
-            LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO).as_str()
+            logs,
+            vec![
+                LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
+                LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 1001")
+            ]
         );
+
+        let receipts = deserialize_receipts();
+        assert_eq!(&receipts[0].receiver_id, BUYER_2);
+        match &receipts[0].actions[0] {
+            Action::Transfer(transfer) => assert_eq!(transfer.deposit, 1000),
+            _ => panic!("expected TransferAction"),
+        }
     }
 
     #[test]
-    fn updated_sale_matching_bid() {
-        // Arrange
-        let owner = "alfio";
-        let buyer = "bob";
+    #[should_panic(expected = "[ERR] [CONTRACT_SALE_NOT_ALLOWED]")]
+    fn with_contract_transfer_initiated() {
+        let mut ctx = arrange(None, None);
 
-        let mut ctx = new_context(owner);
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(BUYER_2));
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
-        ctx.attached_deposit = YOCTO;
-        ctx.predecessor_account_id = buyer.to_string();
+        ctx.predecessor_account_id = BUYER_1.to_string();
+        ctx.attached_deposit = 10000;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
+    }
 
-        // Act
-        ctx.attached_deposit = 1;
-        ctx.predecessor_account_id = owner.to_string();
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell((5 * YOCTO).into());
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
-        // Assert
-        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-        assert!(ContractSaleComponent.ops_contract_bid().is_none());
-        assert!(ContractOwnershipAccountIdsObject::load().buyer.is_none());
-        assert_eq!(ContractOwnershipComponent.ops_owner(), buyer.to_string());
+    #[cfg(test)]
+    mod no_sale_no_bid {
+        use super::*;
+        use oysterpack_smart_near::domain::ExpirationDuration;
 
-        let logs = test_utils::get_logs();
+        #[test]
+        fn no_expiration() {
+            let mut ctx = arrange(None, None);
 
-        assert_eq!(
-            &logs[0],
-            LOG_EVENT_CONTRACT_FOR_SALE.message(5 * YOCTO).as_str()
-        );
-        assert_eq!(
-            &logs[1],
-            LOG_EVENT_CONTRACT_SOLD
-                .message(format!("buyer={}, price={}", buyer, YOCTO))
-                .as_str()
-        );
-    }
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 100;
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
 
-    #[test]
-    fn update_sale_higher_bid() {
-        // Arrange
-        let owner = "alfio";
-        let buyer = "bob";
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+            assert_eq!(bid.buyer, BUYER_1.to_string());
+            assert_eq!(bid.bid.amount, 100.into());
+            assert!(bid.bid.expiration.is_none());
 
-        let mut ctx = new_context(owner);
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                &logs[0],
+                LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 100").as_str()
+            );
+        }
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
-        ctx.attached_deposit = 2 * YOCTO;
-        ctx.predecessor_account_id = buyer.to_string();
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        #[test]
+        fn with_future_expiration() {
+            let mut ctx = arrange(None, None);
 
-        // Act
-        ctx.attached_deposit = 1;
-        ctx.predecessor_account_id = owner.to_string();
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell((5 * YOCTO).into());
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
-        // Assert
-        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-        assert!(ContractSaleComponent.ops_contract_bid().is_none());
-        assert!(ContractOwnershipAccountIdsObject::load().buyer.is_none());
-        assert_eq!(ContractOwnershipComponent.ops_owner(), buyer.to_string());
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 100;
+            ctx.epoch_height = 10;
+            testing_env!(ctx.clone());
+            let expiration = Expiration::Epoch(20.into());
+            ContractSaleComponent.ops_contract_buy(Some(expiration.into()));
 
-        let logs = test_utils::get_logs();
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+            assert_eq!(bid.buyer, BUYER_1.to_string());
+            assert_eq!(bid.bid.amount, 100.into());
+            assert_eq!(bid.bid.expiration.unwrap(), expiration);
 
-        assert_eq!(
-            &logs[0],
-            LOG_EVENT_CONTRACT_FOR_SALE.message(5 * YOCTO).as_str()
-        );
-        assert_eq!(
-            &logs[1],
-            LOG_EVENT_CONTRACT_SOLD
-                .message(format!("buyer={}, price={}", buyer, 2 * YOCTO))
-                .as_str()
-        );
-    }
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                &logs[0],
+                LOG_EVENT_CONTRACT_BID_PLACED
+                    .message("bid: 100 | expiration: EpochHeight(20)")
+                    .as_str()
+            );
+        }
 
-    #[test]
-    #[should_panic(
-        expected = "[ERR] [CONTRACT_SALE_NOT_ALLOWED] contract cannot be sold after transfer process has been started"
-    )]
-    fn transfer_ownership_initiated() {
-        // Arrange
-        let owner = "alfio";
-        let buyer = "bob";
+        #[test]
+        fn with_future_relative_expiration() {
+            let mut ctx = arrange(None, None);
 
-        let mut ctx = new_context(owner);
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 100;
+            ctx.epoch_height = 10;
+            testing_env!(ctx.clone());
+            let expiration = Expiration::Epoch(20.into());
+            ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Relative(
+                ExpirationDuration::Epochs(10),
+            )));
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(buyer));
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+            assert_eq!(bid.buyer, BUYER_1.to_string());
+            assert_eq!(bid.bid.amount, 100.into());
+            assert_eq!(bid.bid.expiration.unwrap(), expiration);
 
-        // Act
-        ctx.attached_deposit = 1;
-        ctx.predecessor_account_id = owner.to_string();
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                &logs[0],
+                LOG_EVENT_CONTRACT_BID_PLACED
+                    .message("bid: 100 | expiration: EpochHeight(20)")
+                    .as_str()
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [BAD_REQUEST] expiration cannot be set to expired")]
+        fn with_expired_bid() {
+            let mut ctx = arrange(None, None);
+
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 100;
+            ctx.epoch_height = 10;
+            testing_env!(ctx.clone());
+            let expiration = Expiration::Epoch(5.into());
+            ContractSaleComponent.ops_contract_buy(Some(expiration.into()));
+        }
     }
 
-    #[test]
-    #[should_panic(expected = "[ERR] [OWNER_ACCESS_REQUIRED]")]
-    fn not_owner() {
-        // Arrange
-        let owner = "alfio";
+    #[cfg(test)]
+    mod with_sale_no_bid {
+        use super::*;
+        use oysterpack_smart_near::YOCTO;
+
+        #[test]
+        fn higher_sale_price() {
+            let mut ctx = arrange(Some(1000.into()), None);
+
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 100;
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+            assert_eq!(bid.buyer, BUYER_1.to_string());
+            assert_eq!(bid.bid.amount, 100.into());
+            assert!(bid.bid.expiration.is_none());
 
-        let mut ctx = new_context(owner);
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                &logs[0],
+                LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 100").as_str()
+            );
+        }
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+        #[test]
+        fn with_matching_sale_price() {
+            let mut ctx = arrange(Some((YOCTO * 1_000_000).into()), None);
 
-        // Act
-        ctx.attached_deposit = 1;
-        ctx.predecessor_account_id = "bob".to_string();
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
-    }
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = YOCTO * 1_000_000;
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                &logs[0],
+                LOG_EVENT_CONTRACT_SOLD
+                    .message("buyer=buyer1, price=1000000000000000000000000000000")
+                    .as_str()
+            );
+            assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1.to_string());
+            assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+            assert!(ContractSaleComponent.ops_contract_bid().is_none());
 
-    #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn zero_deposit() {
-        // Arrange
-        let owner = "alfio";
+            let receipts = deserialize_receipts();
+            assert_eq!(&receipts[0].receiver_id, OWNER);
+            match &receipts[0].actions[0] {
+                Action::Transfer(transfer) => assert!(transfer.deposit > ctx.attached_deposit),
+                _ => panic!("expected TransferAction"),
+            }
+        }
 
-        let mut ctx = new_context(owner);
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
+        #[test]
+        fn with_lower_sale_price() {
+            let mut ctx = arrange(Some(100.into()), None);
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = YOCTO * 1_000_000;
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                &logs[0],
+                LOG_EVENT_CONTRACT_SOLD
+                    .message("buyer=buyer1, price=1000000000000000000000000000000")
+                    .as_str()
+            );
+            assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1.to_string());
+            assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+            assert!(ContractSaleComponent.ops_contract_bid().is_none());
 
-        // Act
-        ctx.attached_deposit = 0;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+            let receipts = deserialize_receipts();
+            assert_eq!(&receipts[0].receiver_id, OWNER);
+            match &receipts[0].actions[0] {
+                Action::Transfer(transfer) => assert!(transfer.deposit > ctx.attached_deposit),
+                _ => panic!("expected TransferAction"),
+            }
+        }
     }
 
-    #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn two_deposit() {
-        // Arrange
-        let owner = "alfio";
+    #[cfg(test)]
+    mod no_sale_with_bid {
+        use super::*;
+        use oysterpack_smart_near::domain::ExpirationDuration;
 
-        let mut ctx = new_context(owner);
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
+        #[test]
+        #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
+        fn higher_prior_bid() {
+            let mut ctx = arrange(
+                None,
+                Some(ContractBuyerBid {
+                    buyer: BUYER_2.to_string(),
+                    bid: ContractBid {
+                        amount: 1000.into(),
+                        expiration: None,
+                    },
+                    token_id: None,
+                }),
+            );
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 999;
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+        }
 
-        // Act
-        ctx.attached_deposit = 2;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
-    }
+        #[test]
+        fn higher_prior_expired_bid() {
+            testing_env!(new_context(OWNER));
+            let mut ctx = arrange(
+                None,
+                Some(ContractBuyerBid {
+                    buyer: BUYER_2.to_string(),
+                    bid: ContractBid {
+                        amount: 1000.into(),
+                        expiration: Some(
+                            ExpirationSetting::Relative(ExpirationDuration::Epochs(10)).into(),
+                        ),
+                    },
+                    token_id: None,
+                }),
+            );
 
-    #[test]
-    #[should_panic(expected = "[ERR] [CONTRACT_SALE_PRICE_MUST_NOT_BE_ZERO]")]
-    fn zero_sale_price() {
-        // Arrange
-        let owner = "alfio";
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
 
-        let mut ctx = new_context(owner);
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 999;
+            if let Some(Expiration::Epoch(epoch)) = bid.bid.expiration {
+                ctx.epoch_height = epoch.value() + 1; // expires the bid
+            }
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+            assert_eq!(bid.buyer, ctx.predecessor_account_id);
+            assert_eq!(bid.bid.amount, ctx.attached_deposit.into());
 
-        ContractOwnershipComponent::deploy(to_valid_account_id(owner));
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                logs,
+                vec![
+                    LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
+                    LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 999")
+                ]
+            );
 
-        // Act
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(YoctoNear::ZERO);
-    }
-}
+            let receipts = deserialize_receipts();
+            assert_eq!(&receipts[0].receiver_id, BUYER_2);
+            match &receipts[0].actions[0] {
+                Action::Transfer(transfer) => assert_eq!(transfer.deposit, 1000),
+                _ => panic!("expected TransferAction"),
+            }
+        }
 
-#[cfg(test)]
-mod tests_buy_contract {
-    use super::*;
-    use crate::components::contract_ownership::ContractOwnershipComponent;
-    use near_sdk::{test_utils, VMContext};
-    use oysterpack_smart_near::component::*;
-    use oysterpack_smart_near::YOCTO;
-    use oysterpack_smart_near_test::*;
+        #[test]
+        #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
+        fn matching_prior_bid() {
+            let mut ctx = arrange(
+                None,
+                Some(ContractBuyerBid {
+                    buyer: BUYER_2.to_string(),
+                    bid: ContractBid {
+                        amount: 1000.into(),
+                        expiration: None,
+                    },
+                    token_id: None,
+                }),
+            );
 
-    const OWNER: &str = "owner";
-    const BUYER_1: &str = "buyer1";
-    const BUYER_2: &str = "buyer2";
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 999;
+            testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+        }
 
-    fn arrange(sale_price: Option<YoctoNear>, bid: Option<ContractBuyerBid>) -> VMContext {
-        let ctx = new_context(OWNER);
-        {
-            let mut ctx = ctx.clone();
-            ctx.attached_deposit = 1;
+        #[test]
+        fn lower_prior_bid() {
+            let mut ctx = arrange(
+                None,
+                Some(ContractBuyerBid {
+                    buyer: BUYER_2.to_string(),
+                    bid: ContractBid {
+                        amount: 1000.into(),
+                        expiration: None,
+                    },
+                    token_id: None,
+                }),
+            );
+
+            ctx.predecessor_account_id = BUYER_1.to_string();
+            ctx.attached_deposit = 1001;
             testing_env!(ctx.clone());
+            ContractSaleComponent.ops_contract_buy(None);
+            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+            assert_eq!(bid.buyer, ctx.predecessor_account_id);
+            assert_eq!(bid.bid.amount, ctx.attached_deposit.into());
 
-            ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
-            if let Some(sale_price) = sale_price {
-                ContractSaleComponent.ops_contract_sell(sale_price);
-            }
+            let logs = test_utils::get_logs();
+            println!("{:#?}", logs);
+            assert_eq!(
+                logs,
+                vec![
+                    LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
+                    LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 1001")
+                ]
+            );
 
-            if let Some(bid) = bid {
-                ctx.predecessor_account_id = bid.buyer;
-                ctx.attached_deposit = bid.bid.amount.value();
-                testing_env!(ctx.clone());
-                ContractSaleComponent
-                    .ops_contract_buy(bid.bid.expiration.as_ref().cloned().map(Into::into));
+            let receipts = deserialize_receipts();
+            assert_eq!(&receipts[0].receiver_id, BUYER_2);
+            match &receipts[0].actions[0] {
+                Action::Transfer(transfer) => assert_eq!(transfer.deposit, 1000),
+                _ => panic!("expected TransferAction"),
             }
         }
-        assert_eq!(ContractOwnershipComponent.ops_owner(), OWNER.to_string());
-        ctx
     }
+}
+
+#[cfg(test)]
+mod tests_cancel_contract_sale {
+    use super::*;
+    use near_sdk::test_utils;
+    use oysterpack_smart_near::component::*;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
 
     #[test]
-    #[should_panic(expected = "[ERR] [NEAR_DEPOSIT_REQUIRED]")]
-    fn zero_yocto_near_attached() {
+    fn cancel_prior_sale() {
+        // Arrange
         let mut ctx = new_context(OWNER);
-        ctx.attached_deposit = 0;
-        ctx.predecessor_account_id = BUYER_1.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
-    }
 
-    #[test]
-    fn higher_sale_price_and_lower_prior_bid_() {
-        let mut ctx = arrange(
-            None,
-            Some(ContractBuyerBid {
-                buyer: BUYER_2.to_string(),
-                bid: ContractBid {
-                    amount: 1000.into(),
-                    expiration: None,
-                },
-            }),
-        );
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_sell(YOCTO.into());
 
-        ctx.predecessor_account_id = BUYER_1.to_string();
-        ctx.attached_deposit = 1001;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
-        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert_eq!(bid.buyer, ctx.predecessor_account_id);
-        assert_eq!(bid.bid.amount, ctx.attached_deposit.into());
+        // Act
+        ContractSaleComponent.ops_contract_cancel_sale();
 
+        // Assert
+        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
         let logs = test_utils::get_logs();
         println!("{:#?}", logs);
         assert_eq!(
             logs,
             vec![
-                LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
-                LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 1001")
+                LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO),
+                LOG_EVENT_CONTRACT_SALE_CANCELLED.message("")
             ]
         );
-
-        let receipts = deserialize_receipts();
-        assert_eq!(&receipts[0].receiver_id, BUYER_2);
-        match &receipts[0].actions[0] {
-            Action::Transfer(transfer) => assert_eq!(transfer.deposit, 1000),
-            _ => panic!("expected TransferAction"),
-        }
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [CONTRACT_SALE_NOT_ALLOWED]")]
-    fn with_contract_transfer_initiated() {
-        let mut ctx = arrange(None, None);
-
+    fn no_prior_sale() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(BUYER_2));
-
-        ctx.predecessor_account_id = BUYER_1.to_string();
-        ctx.attached_deposit = 10000;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
-    }
-
-    #[cfg(test)]
-    mod no_sale_no_bid {
-        use super::*;
-        use oysterpack_smart_near::domain::ExpirationDuration;
-
-        #[test]
-        fn no_expiration() {
-            let mut ctx = arrange(None, None);
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 100;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-            assert_eq!(bid.buyer, BUYER_1.to_string());
-            assert_eq!(bid.bid.amount, 100.into());
-            assert!(bid.bid.expiration.is_none());
+        // Act
+        ContractSaleComponent.ops_contract_cancel_sale();
 
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                &logs[0],
-                LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 100").as_str()
-            );
-        }
+        // Assert
+        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+        let logs = test_utils::get_logs();
+        assert!(logs.is_empty());
+    }
+}
 
-        #[test]
-        fn with_future_expiration() {
-            let mut ctx = arrange(None, None);
+#[cfg(test)]
+mod tests_raise_contract_bid {
+    use super::*;
+    use oysterpack_smart_near::component::*;
+    use oysterpack_smart_near::domain::ExpirationDuration;
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 100;
-            ctx.epoch_height = 10;
-            testing_env!(ctx.clone());
-            let expiration = Expiration::Epoch(20.into());
-            ContractSaleComponent.ops_contract_buy(Some(expiration.into()));
+    const OWNER: &str = "owner";
+    const BUYER: &str = "buyer";
 
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-            assert_eq!(bid.buyer, BUYER_1.to_string());
-            assert_eq!(bid.bid.amount, 100.into());
-            assert_eq!(bid.bid.expiration.unwrap(), expiration);
+    #[test]
+    #[should_panic(expected = "[ERR] [NO_ACTIVE_BID]")]
+    fn no_prior_bid() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                &logs[0],
-                LOG_EVENT_CONTRACT_BID_PLACED
-                    .message("bid: 100 | expiration: EpochHeight(20)")
-                    .as_str()
-            );
-        }
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(None);
+    }
 
-        #[test]
-        fn with_future_relative_expiration() {
-            let mut ctx = arrange(None, None);
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
+    fn not_current_bidder() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 100;
-            ctx.epoch_height = 10;
-            testing_env!(ctx.clone());
-            let expiration = Expiration::Epoch(20.into());
-            ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Relative(
-                ExpirationDuration::Epochs(10),
-            )));
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-            assert_eq!(bid.buyer, BUYER_1.to_string());
-            assert_eq!(bid.bid.amount, 100.into());
-            assert_eq!(bid.bid.expiration.unwrap(), expiration);
+        ctx.predecessor_account_id = "BUYER2".to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(None);
+    }
 
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                &logs[0],
-                LOG_EVENT_CONTRACT_BID_PLACED
-                    .message("bid: 100 | expiration: EpochHeight(20)")
-                    .as_str()
-            );
-        }
+    #[test]
+    #[should_panic(
+        expected = "[ERR] [NEAR_DEPOSIT_REQUIRED] NEAR deposit is required - bid raise cannot be zero"
+    )]
+    fn with_zero_deposit_attached() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-        #[test]
-        #[should_panic(expected = "[ERR] [BAD_REQUEST] expiration cannot be set to expired")]
-        fn with_expired_bid() {
-            let mut ctx = arrange(None, None);
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 100;
-            ctx.epoch_height = 10;
-            testing_env!(ctx.clone());
-            let expiration = Expiration::Epoch(5.into());
-            ContractSaleComponent.ops_contract_buy(Some(expiration.into()));
-        }
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_raise_bid(None);
     }
 
-    #[cfg(test)]
-    mod with_sale_no_bid {
-        use super::*;
-        use oysterpack_smart_near::YOCTO;
-
-        #[test]
-        fn higher_sale_price() {
-            let mut ctx = arrange(Some(1000.into()), None);
-
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 100;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
+    #[test]
+    fn prexisting_bid_with_no_sale_price() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-            assert_eq!(bid.buyer, BUYER_1.to_string());
-            assert_eq!(bid.bid.amount, 100.into());
-            assert!(bid.bid.expiration.is_none());
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                &logs[0],
-                LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 100").as_str()
-            );
-        }
+        ctx.attached_deposit = 500;
+        testing_env!(ctx.clone());
+        // Act
+        let raised_bid = ContractSaleComponent.ops_contract_raise_bid(None);
 
-        #[test]
-        fn with_matching_sale_price() {
-            let mut ctx = arrange(Some((YOCTO * 1_000_000).into()), None);
+        // Assert
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.bid.amount, 1500.into());
+        assert_eq!(raised_bid, bid.bid);
+    }
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = YOCTO * 1_000_000;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                &logs[0],
-                LOG_EVENT_CONTRACT_SOLD
-                    .message("buyer=buyer1, price=1000000000000000000000000000000")
-                    .as_str()
-            );
-            assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1.to_string());
-            assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-            assert!(ContractSaleComponent.ops_contract_bid().is_none());
+    #[test]
+    fn prexisting_bid_and_raise_triggers_buy() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            let receipts = deserialize_receipts();
-            assert_eq!(&receipts[0].receiver_id, OWNER);
-            match &receipts[0].actions[0] {
-                Action::Transfer(transfer) => assert!(transfer.deposit > ctx.attached_deposit),
-                _ => panic!("expected TransferAction"),
-            }
-        }
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
-        #[test]
-        fn with_lower_sale_price() {
-            let mut ctx = arrange(Some(100.into()), None);
+        ctx.predecessor_account_id = OWNER.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(1500.into());
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = YOCTO * 1_000_000;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                &logs[0],
-                LOG_EVENT_CONTRACT_SOLD
-                    .message("buyer=buyer1, price=1000000000000000000000000000000")
-                    .as_str()
-            );
-            assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1.to_string());
-            assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-            assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 500;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(None);
 
-            let receipts = deserialize_receipts();
-            assert_eq!(&receipts[0].receiver_id, OWNER);
-            match &receipts[0].actions[0] {
-                Action::Transfer(transfer) => assert!(transfer.deposit > ctx.attached_deposit),
-                _ => panic!("expected TransferAction"),
-            }
-        }
+        // Assert
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+        assert_eq!(
+            ContractOwnershipComponent.ops_owner(),
+            ctx.predecessor_account_id
+        );
     }
 
-    #[cfg(test)]
-    mod no_sale_with_bid {
-        use super::*;
-        use oysterpack_smart_near::domain::ExpirationDuration;
+    #[test]
+    fn prexisting_bid_with_higher_sale_price() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-        #[test]
-        #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
-        fn higher_prior_bid() {
-            let mut ctx = arrange(
-                None,
-                Some(ContractBuyerBid {
-                    buyer: BUYER_2.to_string(),
-                    bid: ContractBid {
-                        amount: 1000.into(),
-                        expiration: None,
-                    },
-                }),
-            );
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 999;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
-        }
+        ctx.predecessor_account_id = OWNER.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_sell(2500.into());
 
-        #[test]
-        fn higher_prior_expired_bid() {
-            testing_env!(new_context(OWNER));
-            let mut ctx = arrange(
-                None,
-                Some(ContractBuyerBid {
-                    buyer: BUYER_2.to_string(),
-                    bid: ContractBid {
-                        amount: 1000.into(),
-                        expiration: Some(
-                            ExpirationSetting::Relative(ExpirationDuration::Epochs(10)).into(),
-                        ),
-                    },
-                }),
-            );
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 500;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(None);
 
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        // Assert
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.bid.amount, 1500.into());
+    }
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 999;
-            if let Some(Expiration::Epoch(epoch)) = bid.bid.expiration {
-                ctx.epoch_height = epoch.value() + 1; // expires the bid
-            }
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-            assert_eq!(bid.buyer, ctx.predecessor_account_id);
-            assert_eq!(bid.bid.amount, ctx.attached_deposit.into());
+    #[test]
+    fn prexisting_bid_with_expiration() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                logs,
-                vec![
-                    LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
-                    LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 999")
-                ]
-            );
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
-            let receipts = deserialize_receipts();
-            assert_eq!(&receipts[0].receiver_id, BUYER_2);
-            match &receipts[0].actions[0] {
-                Action::Transfer(transfer) => assert_eq!(transfer.deposit, 1000),
-                _ => panic!("expected TransferAction"),
-            }
+        ctx.attached_deposit = 500;
+        ctx.epoch_height = 10;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(Some(ExpirationSetting::Relative(
+            ExpirationDuration::Epochs(10),
+        )));
+
+        // Assert
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.bid.amount, 1500.into());
+        match bid.bid.expiration.unwrap() {
+            Expiration::Epoch(epoch) => assert_eq!(epoch, 20.into()),
+            _ => panic!("expected Expiration::Epoch"),
         }
+    }
 
-        #[test]
-        #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
-        fn matching_prior_bid() {
-            let mut ctx = arrange(
-                None,
-                Some(ContractBuyerBid {
-                    buyer: BUYER_2.to_string(),
-                    bid: ContractBid {
-                        amount: 1000.into(),
-                        expiration: None,
-                    },
-                }),
-            );
+    #[test]
+    fn prexisting_bid_with_prior_expiration_set() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 999;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
-        }
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        ctx.epoch_height = 10;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Relative(
+            ExpirationDuration::Epochs(10),
+        )));
 
-        #[test]
-        fn lower_prior_bid() {
-            let mut ctx = arrange(
-                None,
-                Some(ContractBuyerBid {
-                    buyer: BUYER_2.to_string(),
-                    bid: ContractBid {
-                        amount: 1000.into(),
-                        expiration: None,
-                    },
-                }),
-            );
+        ctx.attached_deposit = 500;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(None);
 
-            ctx.predecessor_account_id = BUYER_1.to_string();
-            ctx.attached_deposit = 1001;
-            testing_env!(ctx.clone());
-            ContractSaleComponent.ops_contract_buy(None);
-            let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-            assert_eq!(bid.buyer, ctx.predecessor_account_id);
-            assert_eq!(bid.bid.amount, ctx.attached_deposit.into());
+        // Assert
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.bid.amount, 1500.into());
+        match bid.bid.expiration.unwrap() {
+            Expiration::Epoch(epoch) => assert_eq!(epoch, 20.into()),
+            _ => panic!("expected Expiration::Epoch"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [BID_IS_EXPIRED]")]
+    fn bid_expired() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-            let logs = test_utils::get_logs();
-            println!("{:#?}", logs);
-            assert_eq!(
-                logs,
-                vec![
-                    LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
-                    LOG_EVENT_CONTRACT_BID_PLACED.message("bid: 1001")
-                ]
-            );
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1000;
+        ctx.epoch_height = 10;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Relative(
+            ExpirationDuration::Epochs(10),
+        )));
 
-            let receipts = deserialize_receipts();
-            assert_eq!(&receipts[0].receiver_id, BUYER_2);
-            match &receipts[0].actions[0] {
-                Action::Transfer(transfer) => assert_eq!(transfer.deposit, 1000),
-                _ => panic!("expected TransferAction"),
-            }
-        }
+        ctx.attached_deposit = 500;
+        ctx.epoch_height = 100;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_raise_bid(None);
     }
 }
 
 #[cfg(test)]
-mod tests_cancel_contract_sale {
+mod tests_lower_contract_bid {
     use super::*;
-    use near_sdk::test_utils;
     use oysterpack_smart_near::component::*;
+    use oysterpack_smart_near::domain::ExpirationDuration;
     use oysterpack_smart_near::YOCTO;
     use oysterpack_smart_near_test::*;
 
     const OWNER: &str = "owner";
+    const BUYER: &str = "buyer";
 
     #[test]
-    fn cancel_prior_sale() {
+    fn lowered() {
         // Arrange
         let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
+        // Act
+        let lowered_bid = ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
 
+        // Assert
+        assert_eq!(
+            ContractSaleComponent.ops_contract_bid().unwrap().bid.amount,
+            (9 * YOCTO).into()
+        );
+        assert_eq!(
+            lowered_bid,
+            ContractSaleComponent.ops_contract_bid().unwrap().bid
+        );
+    }
+
+    #[test]
+    fn lowered_with_new_expiration() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
-        ContractSaleComponent.ops_contract_sell(YOCTO.into());
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
 
+        ctx.attached_deposit = 1;
+        ctx.block_index = 10;
+        testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_cancel_sale();
+        ContractSaleComponent.ops_contract_lower_bid(
+            YOCTO.into(),
+            Some(ExpirationSetting::Relative(ExpirationDuration::Blocks(10))),
+        );
 
         // Assert
-        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-        let logs = test_utils::get_logs();
-        println!("{:#?}", logs);
         assert_eq!(
-            logs,
-            vec![
-                LOG_EVENT_CONTRACT_FOR_SALE.message(YOCTO),
-                LOG_EVENT_CONTRACT_SALE_CANCELLED.message("")
-            ]
+            ContractSaleComponent.ops_contract_bid().unwrap().bid.amount,
+            (9 * YOCTO).into()
         );
+        match ContractSaleComponent
+            .ops_contract_bid()
+            .unwrap()
+            .bid
+            .expiration
+            .unwrap()
+        {
+            Expiration::Block(block) => assert_eq!(block, 20.into()),
+            _ => panic!("expected expiration on block"),
+        }
     }
 
     #[test]
-    fn no_prior_sale() {
+    #[should_panic(expected = "[ERR] [NO_ACTIVE_BID]")]
+    fn no_bid() {
         // Arrange
         let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+    }
 
+    #[test]
+    #[should_panic(expected = "[ERR] [BID_IS_EXPIRED]")]
+    fn prior_bid_expired() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = YOCTO;
+        ctx.epoch_height = 5;
+        testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_cancel_sale();
+        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Absolute(
+            Expiration::Epoch(10.into()),
+        )));
 
-        // Assert
-        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-        let logs = test_utils::get_logs();
-        assert!(logs.is_empty());
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1;
+        ctx.epoch_height = 11;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid(100.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [BAD_REQUEST] expiration cannot be set to expired")]
+    fn with_expired_expiration() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = YOCTO;
+        ctx.epoch_height = 5;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1;
+        ctx.epoch_height = 11;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid(
+            100.into(),
+            Some(ExpirationSetting::Absolute(Expiration::Epoch(10.into()))),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
+    fn not_buyer() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.predecessor_account_id = "OTHER".to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn zero_deposit() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn two_deposit() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 2;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [BAD_REQUEST] bid cannot be zero")]
+    fn lower_to_zero() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid((10 * YOCTO).into(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [BAD_REQUEST] bid cannot be zero")]
+    fn overflow() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.predecessor_account_id = BUYER.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_lower_bid((11 * YOCTO).into(), None);
     }
 }
 
 #[cfg(test)]
-mod tests_raise_contract_bid {
+mod tests_contract_bid_expiration {
     use super::*;
     use oysterpack_smart_near::component::*;
     use oysterpack_smart_near::domain::ExpirationDuration;
@@ -1502,676 +2900,1151 @@ mod tests_raise_contract_bid {
     const BUYER: &str = "buyer";
 
     #[test]
-    #[should_panic(expected = "[ERR] [NO_ACTIVE_BID]")]
-    fn no_prior_bid() {
+    fn update() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = YOCTO;
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_buy(None);
+
+        ctx.attached_deposit = 1;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_raise_bid(None);
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
+
+        // Assert
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.bid.expiration, Some(expiration.into()));
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
-    fn not_current_bidder() {
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn update_zero_deposit() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = YOCTO;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
 
-        ctx.predecessor_account_id = "BUYER2".to_string();
-        ctx.attached_deposit = YOCTO;
+        ctx.attached_deposit = 0;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_raise_bid(None);
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
     }
 
     #[test]
-    #[should_panic(
-        expected = "[ERR] [NEAR_DEPOSIT_REQUIRED] NEAR deposit is required - bid raise cannot be zero"
-    )]
-    fn with_zero_deposit_attached() {
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn update_two_deposit() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1000;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
 
-        ctx.attached_deposit = 0;
+        ctx.attached_deposit = 2;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_raise_bid(None);
+        // Act
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
     }
 
     #[test]
-    fn prexisting_bid_with_no_sale_price() {
+    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
+    fn update_not_buyer() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1000;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
 
-        ctx.attached_deposit = 500;
+        ctx.predecessor_account_id = "OTHER".to_string();
+        ctx.attached_deposit = 1;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
         // Act
-        let raised_bid = ContractSaleComponent.ops_contract_raise_bid(None);
-
-        // Assert
-        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert_eq!(bid.bid.amount, 1500.into());
-        assert_eq!(raised_bid, bid.bid);
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
     }
 
     #[test]
-    fn prexisting_bid_and_raise_triggers_buy() {
+    #[should_panic(expected = "[ERR] [BAD_REQUEST] expiration cannot be set to expired")]
+    fn update_with_expired_setting() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1000;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
 
-        ctx.predecessor_account_id = OWNER.to_string();
+        ctx.predecessor_account_id = "OTHER".to_string();
         ctx.attached_deposit = 1;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(1500.into());
+        // Act
+        let expiration = ExpirationSetting::Absolute(Expiration::Timestamp(60.into()));
+        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
+    }
+
+    #[test]
+    fn clear() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 500;
+        ctx.attached_deposit = 10 * YOCTO;
+        testing_env!(ctx.clone());
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_buy(Some(expiration));
+
+        ctx.attached_deposit = 1;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_raise_bid(None);
+
+        ContractSaleComponent.ops_contract_clear_bid_expiration();
 
         // Assert
-        assert!(ContractSaleComponent.ops_contract_bid().is_none());
-        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
-        assert_eq!(
-            ContractOwnershipComponent.ops_owner(),
-            ctx.predecessor_account_id
-        );
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert!(bid.bid.expiration.is_none());
     }
 
     #[test]
-    fn prexisting_bid_with_higher_sale_price() {
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn clear_zero_deposit() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1000;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_buy(Some(expiration));
 
-        ctx.predecessor_account_id = OWNER.to_string();
-        ctx.attached_deposit = 1;
+        ctx.attached_deposit = 0;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_sell(2500.into());
+        // Act
+        ContractSaleComponent.ops_contract_clear_bid_expiration();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
+    fn clear_two_deposit() {
+        // Arrange
+        let mut ctx = new_context(OWNER);
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 500;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_raise_bid(None);
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_buy(Some(expiration));
 
-        // Assert
-        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert_eq!(bid.bid.amount, 1500.into());
+        ctx.attached_deposit = 2;
+        ctx.block_timestamp = 100;
+        testing_env!(ctx.clone());
+        // Act
+        ContractSaleComponent.ops_contract_clear_bid_expiration();
     }
 
     #[test]
-    fn prexisting_bid_with_expiration() {
+    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
+    fn clear_not_buyer() {
         // Arrange
         let mut ctx = new_context(OWNER);
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
 
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1000;
+        ctx.attached_deposit = 10 * YOCTO;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
+        ContractSaleComponent.ops_contract_buy(Some(expiration));
 
-        ctx.attached_deposit = 500;
-        ctx.epoch_height = 10;
+        ctx.predecessor_account_id = "BUYER2".to_string();
+        ctx.attached_deposit = 1;
+        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_raise_bid(Some(ExpirationSetting::Relative(
-            ExpirationDuration::Epochs(10),
-        )));
+        ContractSaleComponent.ops_contract_clear_bid_expiration();
+    }
+}
+
+#[cfg(test)]
+mod tests_token_bids {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use near_sdk::{serde_json, test_utils, VMContext};
+    use oysterpack_smart_near::component::*;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+    const BUYER_1: &str = "buyer1";
+    const BUYER_2: &str = "buyer2";
+    const TOKEN: &str = "stake.pool";
+
+    fn arrange() -> VMContext {
+        let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_sale_token_whitelist(to_valid_account_id(TOKEN));
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_tokens(),
+            vec![TOKEN.to_string()]
+        );
+        ctx
+    }
+
+    fn token_bid(ctx: &mut VMContext, buyer: &str, amount: u128, msg: ContractSaleTransferMessage) {
+        ctx.predecessor_account_id = TOKEN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        ContractSaleComponent::bid_with_tokens(to_valid_account_id(buyer), amount.into(), msg)
+    }
+
+    const BUY: ContractSaleTransferMessage =
+        ContractSaleTransferMessage::ContractBuy { expiration: None };
+    const RAISE_BID: ContractSaleTransferMessage =
+        ContractSaleTransferMessage::ContractRaiseBid { expiration: None };
+
+    fn ft_transfers() -> Vec<(String, FtTransferArgs)> {
+        deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::FunctionCall(action) if action.method_name == "ft_transfer" => {
+                    assert_eq!(action.deposit, 1);
+                    Some((
+                        receipt.receiver_id.clone(),
+                        serde_json::from_str(action.args.as_str()).unwrap(),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn outbid_token_bid_is_refunded() {
+        // Arrange
+        let mut ctx = arrange();
+        token_bid(&mut ctx, BUYER_1, 1000, BUY);
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.buyer, BUYER_1);
+        assert_eq!(bid.bid.amount, 1000.into());
+        assert_eq!(bid.token_id, Some(TOKEN.to_string()));
+        // token bids are not tracked in the NEAR bid balance
+        assert_eq!(ContractBid::near_balance(), YoctoNear::ZERO);
+
+        // Act
+        token_bid(&mut ctx, BUYER_2, 2000, BUY);
 
         // Assert
         let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert_eq!(bid.bid.amount, 1500.into());
-        match bid.bid.expiration.unwrap() {
-            Expiration::Epoch(epoch) => assert_eq!(epoch, 20.into()),
-            _ => panic!("expected Expiration::Epoch"),
+        assert_eq!(bid.buyer, BUYER_2);
+        assert_eq!(bid.bid.amount, 2000.into());
+        let transfers = ft_transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, TOKEN);
+        assert_eq!(transfers[0].1.receiver_id, BUYER_1);
+        assert_eq!(transfers[0].1.amount, 1000.into());
+        assert_eq!(
+            test_utils::get_logs(),
+            vec![
+                LOG_EVENT_CONTRACT_BID_CANCELLED.message("higher bid has been placed"),
+                LOG_EVENT_CONTRACT_BID_PLACED.message(format!("bid: 2000 {}", TOKEN))
+            ]
+        );
+
+        // Act - raise the bid with tokens
+        token_bid(&mut ctx, BUYER_2, 500, RAISE_BID);
+        assert_eq!(
+            ContractSaleComponent.ops_contract_bid().unwrap().bid.amount,
+            2500.into()
+        );
+
+        // Act - cancel the bid
+        ctx.predecessor_account_id = BUYER_2.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_cancel_bid();
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        let transfers = ft_transfers();
+        assert_eq!(transfers[0].1.receiver_id, BUYER_2);
+        assert_eq!(transfers[0].1.amount, 2500.into());
+    }
+
+    #[test]
+    fn contract_sold_for_tokens() {
+        // Arrange
+        let mut ctx = arrange();
+        ContractSaleComponent.ops_contract_sell_for_token(to_valid_account_id(TOKEN), 1000.into());
+        assert!(ContractSaleComponent.ops_contract_sale_price().is_none());
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_token_price(),
+            Some(ContractSaleTokenPrice {
+                token_id: TOKEN.to_string(),
+                amount: 1000.into()
+            })
+        );
+
+        // Act
+        token_bid(&mut ctx, BUYER_1, 1000, BUY);
+
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1);
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert!(ContractSaleComponent
+            .ops_contract_sale_token_price()
+            .is_none());
+        let receipts = deserialize_receipts();
+        assert_eq!(receipts[0].receiver_id, OWNER);
+        match &receipts[0].actions[0] {
+            // the owner's available NEAR balance is transferred out, minus 1 yoctoNEAR that is
+            // attached to the ft_transfer call
+            Action::Transfer(transfer) => assert!(transfer.deposit > 0),
+            _ => panic!("expected TransferAction"),
         }
+        let transfers = ft_transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, TOKEN);
+        assert_eq!(transfers[0].1.receiver_id, OWNER);
+        assert_eq!(transfers[0].1.amount, 1000.into());
     }
 
     #[test]
-    fn prexisting_bid_with_prior_expiration_set() {
+    fn selling_in_a_different_currency_cancels_the_bid() {
         // Arrange
-        let mut ctx = new_context(OWNER);
+        let mut ctx = arrange();
+        token_bid(&mut ctx, BUYER_1, 1000, BUY);
+
+        // Act
+        ctx.predecessor_account_id = OWNER.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_sell(500.into());
 
-        ctx.predecessor_account_id = BUYER.to_string();
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), OWNER);
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_price(),
+            Some(500.into())
+        );
+        let transfers = ft_transfers();
+        assert_eq!(transfers[0].1.receiver_id, BUYER_1);
+        assert_eq!(transfers[0].1.amount, 1000.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_SALE_CURRENCY_MISMATCH]")]
+    fn near_bid_on_token_sale() {
+        let mut ctx = arrange();
+        ContractSaleComponent.ops_contract_sell_for_token(to_valid_account_id(TOKEN), 1000.into());
+
+        ctx.predecessor_account_id = BUYER_1.to_string();
         ctx.attached_deposit = 1000;
-        ctx.epoch_height = 10;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Relative(
-            ExpirationDuration::Epochs(10),
-        )));
+        ContractSaleComponent.ops_contract_buy(None);
+    }
 
-        ctx.attached_deposit = 500;
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_SALE_CURRENCY_MISMATCH]")]
+    fn near_bid_against_active_token_bid() {
+        let mut ctx = arrange();
+        token_bid(&mut ctx, BUYER_1, 1000, BUY);
+
+        ctx.predecessor_account_id = BUYER_2.to_string();
+        ctx.attached_deposit = 2000;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_raise_bid(None);
+        ContractSaleComponent.ops_contract_buy(None);
+    }
 
-        // Assert
-        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert_eq!(bid.bid.amount, 1500.into());
-        match bid.bid.expiration.unwrap() {
-            Expiration::Epoch(epoch) => assert_eq!(epoch, 20.into()),
-            _ => panic!("expected Expiration::Epoch"),
-        }
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_SALE_TOKEN_NOT_WHITELISTED]")]
+    fn token_not_whitelisted() {
+        let mut ctx = arrange();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        assert!(
+            ContractSaleComponent.ops_contract_sale_token_unwhitelist(to_valid_account_id(TOKEN))
+        );
+        token_bid(&mut ctx, BUYER_1, 1000, BUY);
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [BID_IS_EXPIRED]")]
-    fn bid_expired() {
+    fn failed_token_refund_can_be_claimed() {
         // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        let mut ctx = arrange();
+        token_bid(&mut ctx, BUYER_1, 1000, BUY);
+        token_bid(&mut ctx, BUYER_2, 2000, BUY);
+
+        // Act - the refund transfer failed
+        ctx.predecessor_account_id = env::current_account_id();
+        testing_env_with_promise_result_failure(ctx.clone());
+        assert!(
+            !ContractSaleComponent.ops_contract_sale_resolve_token_transfer(
+                BUYER_1.to_string(),
+                TOKEN.to_string(),
+                1000.into()
+            )
+        );
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1000;
-        ctx.epoch_height = 10;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Relative(
-            ExpirationDuration::Epochs(10),
-        )));
+        // Assert
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_unclaimed_tokens(
+                to_valid_account_id(BUYER_1),
+                to_valid_account_id(TOKEN)
+            ),
+            1000.into()
+        );
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[WARN] [CONTRACT_SALE_TOKEN_TRANSFER_FAILURE]")));
 
-        ctx.attached_deposit = 500;
-        ctx.epoch_height = 100;
+        // Act - buyer claims the tokens
+        ctx.predecessor_account_id = BUYER_1.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_raise_bid(None);
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_claim_tokens(to_valid_account_id(TOKEN)),
+            1000.into()
+        );
+
+        // Assert
+        assert_eq!(
+            ContractSaleComponent.ops_contract_sale_unclaimed_tokens(
+                to_valid_account_id(BUYER_1),
+                to_valid_account_id(TOKEN)
+            ),
+            0.into()
+        );
+        let transfers = ft_transfers();
+        assert_eq!(transfers[0].1.receiver_id, BUYER_1);
+        assert_eq!(transfers[0].1.amount, 1000.into());
     }
 }
 
 #[cfg(test)]
-mod tests_lower_contract_bid {
+mod tests_auctions {
     use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use near_sdk::{test_utils, VMContext};
     use oysterpack_smart_near::component::*;
     use oysterpack_smart_near::domain::ExpirationDuration;
-    use oysterpack_smart_near::YOCTO;
     use oysterpack_smart_near_test::*;
 
     const OWNER: &str = "owner";
-    const BUYER: &str = "buyer";
+    const BUYER_1: &str = "buyer1";
+    const BUYER_2: &str = "buyer2";
 
-    #[test]
-    fn lowered() {
-        // Arrange
+    fn english_auction(anti_sniping_extension: Option<ExpirationDuration>) -> VMContext {
         let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        ctx.block_index = 100;
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_auction_start(ContractAuctionSettings::English {
+            reserve_price: 2000.into(),
+            min_increment: 100.into(),
+            duration: ExpirationDuration::Blocks(100),
+            anti_sniping_extension,
+        });
+        ctx
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
+    fn bid(ctx: &mut VMContext, buyer: &str, amount: u128) {
+        ctx.predecessor_account_id = buyer.to_string();
+        ctx.attached_deposit = amount;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
+    }
+
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(transfer) => Some((receipt.receiver_id.clone(), transfer.deposit)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn english_auction_sold_to_highest_bidder() {
+        // Arrange
+        let mut ctx = english_auction(Some(ExpirationDuration::Blocks(10)));
+        assert_eq!(
+            ContractSaleComponent.ops_contract_auction_price(),
+            Some(100.into())
+        );
 
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
         // Act
-        let lowered_bid = ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+        bid(&mut ctx, BUYER_1, 1000);
+        bid(&mut ctx, BUYER_2, 1100);
 
-        // Assert
+        // Assert - the outbid buyer is refunded
+        assert_eq!(transfers(), vec![(BUYER_1.to_string(), 1000)]);
+        assert_eq!(ContractBid::near_balance(), 1100.into());
         assert_eq!(
-            ContractSaleComponent.ops_contract_bid().unwrap().bid.amount,
-            (9 * YOCTO).into()
+            ContractSaleComponent.ops_contract_auction_price(),
+            Some(1200.into())
         );
+
+        // Act - bid placed within the anti-sniping window extends the auction
+        ctx.block_index = 195;
+        bid(&mut ctx, BUYER_1, 2000);
+
+        // Assert
+        assert!(test_utils::get_logs()
+            .contains(&LOG_EVENT_CONTRACT_AUCTION_EXTENDED.message("end: BlockHeight(205)")));
+        let bids = ContractSaleComponent.ops_contract_auction_bids();
         assert_eq!(
-            lowered_bid,
-            ContractSaleComponent.ops_contract_bid().unwrap().bid
+            bids.iter()
+                .map(|bid| (bid.buyer.as_str(), bid.amount.value()))
+                .collect::<Vec<_>>(),
+            vec![(BUYER_1, 1000), (BUYER_2, 1100), (BUYER_1, 2000)]
         );
+
+        // Act
+        ctx.block_index = 206;
+        ctx.predecessor_account_id = BUYER_2.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        assert!(ContractSaleComponent.ops_contract_auction_settle());
+
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1);
+        assert!(ContractSaleComponent.ops_contract_auction().is_none());
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert_eq!(ContractBid::near_balance(), YoctoNear::ZERO);
+        assert_eq!(transfers()[0].0, OWNER);
     }
 
     #[test]
-    fn lowered_with_new_expiration() {
+    fn english_auction_reserve_price_not_met() {
         // Arrange
-        let mut ctx = new_context(OWNER);
+        let mut ctx = english_auction(None);
+        bid(&mut ctx, BUYER_1, 1000);
+
+        // Act
+        ctx.block_index = 201;
+        ctx.attached_deposit = 0;
         testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        assert!(!ContractSaleComponent.ops_contract_auction_settle());
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), OWNER);
+        assert!(ContractSaleComponent.ops_contract_auction().is_none());
+        assert!(ContractSaleComponent.ops_contract_bid().is_none());
+        assert_eq!(transfers(), vec![(BUYER_1.to_string(), 1000)]);
+        assert!(test_utils::get_logs()
+            .contains(&LOG_EVENT_CONTRACT_AUCTION_ENDED.message("reserve price was not met")));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
+    fn english_auction_bid_below_min_increment() {
+        let mut ctx = english_auction(None);
+        bid(&mut ctx, BUYER_1, 1000);
+        bid(&mut ctx, BUYER_2, 1099);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_NOT_ENDED]")]
+    fn english_auction_settled_before_end() {
+        let mut ctx = english_auction(None);
+        bid(&mut ctx, BUYER_1, 1000);
+        ctx.block_index = 200;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        ContractSaleComponent.ops_contract_auction_settle();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_ENDED]")]
+    fn english_auction_bid_after_end() {
+        let mut ctx = english_auction(None);
+        ctx.block_index = 201;
+        bid(&mut ctx, BUYER_1, 1000);
+    }
 
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn english_auction_bids_are_binding() {
+        let mut ctx = english_auction(None);
+        bid(&mut ctx, BUYER_1, 1000);
         ctx.attached_deposit = 1;
-        ctx.block_index = 10;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_lower_bid(
-            YOCTO.into(),
-            Some(ExpirationSetting::Relative(ExpirationDuration::Blocks(10))),
-        );
-
-        // Assert
-        assert_eq!(
-            ContractSaleComponent.ops_contract_bid().unwrap().bid.amount,
-            (9 * YOCTO).into()
-        );
-        match ContractSaleComponent
-            .ops_contract_bid()
-            .unwrap()
-            .bid
-            .expiration
-            .unwrap()
-        {
-            Expiration::Block(block) => assert_eq!(block, 20.into()),
-            _ => panic!("expected expiration on block"),
-        }
+        ContractSaleComponent.ops_contract_cancel_bid();
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [NO_ACTIVE_BID]")]
-    fn no_bid() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn english_auction_cannot_be_cancelled_with_bids() {
+        let mut ctx = english_auction(None);
+        bid(&mut ctx, BUYER_1, 1000);
+        ctx.predecessor_account_id = OWNER.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_auction_cancel();
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn english_auction_ownership_cannot_be_transferred_with_bids() {
+        let mut ctx = english_auction(None);
+        bid(&mut ctx, BUYER_1, 1000);
+        ctx.predecessor_account_id = OWNER.to_string();
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(BUYER_2));
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [BID_IS_EXPIRED]")]
-    fn prior_bid_expired() {
+    fn ownership_transfer_cancels_auction_without_bids() {
+        english_auction(None);
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(BUYER_2));
+        assert!(ContractSaleComponent.ops_contract_auction().is_none());
+        assert_eq!(
+            ContractOwnershipComponent.ops_owner_prospective(),
+            Some(BUYER_2.to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn cannot_sell_during_auction() {
+        english_auction(None);
+        ContractSaleComponent.ops_contract_sell(1000.into());
+    }
+
+    #[test]
+    fn dutch_auction() {
         // Arrange
         let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        ctx.block_index = 100;
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_auction_start(ContractAuctionSettings::Dutch {
+            start_price: 10000.into(),
+            floor_price: 4000.into(),
+            price_decrement: 1000.into(),
+            decay_interval: ExpirationDuration::Blocks(10),
+        });
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = YOCTO;
-        ctx.epoch_height = 5;
+        // Assert - the price decays down to the floor price
+        ctx.block_index = 135;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_buy(Some(ExpirationSetting::Absolute(
-            Expiration::Epoch(10.into()),
-        )));
-
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1;
-        ctx.epoch_height = 11;
+        assert_eq!(
+            ContractSaleComponent.ops_contract_auction_price(),
+            Some(7000.into())
+        );
+        ctx.block_index = 1000;
         testing_env!(ctx.clone());
+        assert_eq!(
+            ContractSaleComponent.ops_contract_auction_price(),
+            Some(4000.into())
+        );
+
         // Act
-        ContractSaleComponent.ops_contract_lower_bid(100.into(), None);
+        ctx.block_index = 135;
+        bid(&mut ctx, BUYER_1, 8000);
+
+        // Assert - the buyer pays the current price, and the excess deposit is refunded
+        assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1);
+        assert!(ContractSaleComponent.ops_contract_auction().is_none());
+        let transfers = transfers();
+        assert_eq!(transfers[0].0, OWNER);
+        assert_eq!(transfers[1], (BUYER_1.to_string(), 1000));
+        assert!(test_utils::get_logs()
+            .contains(&LOG_EVENT_CONTRACT_SOLD.message(format!("buyer={}, price=7000", BUYER_1))));
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [BAD_REQUEST] expiration cannot be set to expired")]
-    fn with_expired_expiration() {
-        // Arrange
+    #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
+    fn dutch_auction_bid_below_price() {
         let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_auction_start(ContractAuctionSettings::Dutch {
+            start_price: 10000.into(),
+            floor_price: 4000.into(),
+            price_decrement: 1000.into(),
+            decay_interval: ExpirationDuration::Blocks(10),
+        });
+        bid(&mut ctx, BUYER_1, 9999);
+    }
+}
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = YOCTO;
-        ctx.epoch_height = 5;
-        testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_buy(None);
+#[cfg(test)]
+mod tests_sealed_bid_auction {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near::component::*;
+    use oysterpack_smart_near::domain::ExpirationDuration;
+    use oysterpack_smart_near_test::*;
 
-        ctx.predecessor_account_id = BUYER.to_string();
+    const OWNER: &str = "owner";
+    const BUYER_1: &str = "buyer1";
+    const BUYER_2: &str = "buyer2";
+
+    /// commit window ends at block 200, and the reveal window ends at block 250
+    fn sealed_bid_auction(reserve_price: u128) -> VMContext {
+        let mut ctx = new_context(OWNER);
         ctx.attached_deposit = 1;
-        ctx.epoch_height = 11;
+        ctx.block_index = 100;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_lower_bid(
-            100.into(),
-            Some(ExpirationSetting::Absolute(Expiration::Epoch(10.into()))),
-        );
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_auction_start(ContractAuctionSettings::Sealed {
+            reserve_price: reserve_price.into(),
+            commit_duration: ExpirationDuration::Blocks(100),
+            reveal_duration: ExpirationDuration::Blocks(50),
+        });
+        ctx
     }
 
-    #[test]
-    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
-    fn not_buyer() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
+    fn commit(ctx: &mut VMContext, buyer: &str, amount: u128, salt: &str, deposit: u128) {
+        ctx.predecessor_account_id = buyer.to_string();
+        ctx.attached_deposit = deposit;
         testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        let commitment = SealedBid::commitment(buyer, amount.into(), salt);
+        ContractSaleComponent.ops_contract_sealed_bid_commit(commitment);
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
+    fn reveal(ctx: &mut VMContext, buyer: &str, amount: u128, salt: &str) {
+        ctx.predecessor_account_id = buyer.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        ContractSaleComponent.ops_contract_sealed_bid_reveal(amount.into(), salt.to_string());
+    }
 
-        ctx.predecessor_account_id = "OTHER".to_string();
+    fn withdraw(ctx: &mut VMContext, buyer: &str) -> YoctoNear {
+        ctx.predecessor_account_id = buyer.to_string();
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+        ContractSaleComponent.ops_contract_sealed_bid_withdraw()
+    }
+
+    fn settle(ctx: &mut VMContext) -> bool {
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        ContractSaleComponent.ops_contract_auction_settle()
+    }
+
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(transfer) => Some((receipt.receiver_id.clone(), transfer.deposit)),
+                _ => None,
+            })
+            .collect()
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn zero_deposit() {
+    fn sealed_bid_auction_sold_to_highest_bidder() {
         // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt1", 2000);
+        commit(&mut ctx, BUYER_2, 1800, "salt2", 1800);
+        assert_eq!(SealedBid::deposits_near_balance(), 3800.into());
+        match ContractSaleComponent.ops_contract_auction() {
+            Some(ContractAuction::Sealed(auction)) => {
+                assert_eq!(auction.commitments, 2);
+                assert!(auction.bids.is_empty());
+            }
+            auction => panic!("expected sealed bid auction: {:?}", auction),
+        }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        // Act
+        ctx.block_index = 201;
+        reveal(&mut ctx, BUYER_1, 1500, "salt1");
+        reveal(&mut ctx, BUYER_2, 1800, "salt2");
+
+        // Assert
+        assert!(test_utils::get_logs()
+            .contains(&LOG_EVENT_CONTRACT_SEALED_BID_REVEALED.message("bid: 1800")));
+        assert_eq!(
+            ContractSaleComponent
+                .ops_contract_sealed_bid(to_valid_account_id(BUYER_2))
+                .unwrap()
+                .revealed,
+            Some(1800.into())
+        );
+
+        // Act
+        ctx.block_index = 251;
+        assert!(settle(&mut ctx));
+
+        // Assert - the winning bid is paid out of the winner's deposit
+        assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_2);
+        assert!(ContractSaleComponent.ops_contract_auction().is_none());
+        assert_eq!(transfers()[0].0, OWNER);
+        assert!(ContractSaleComponent
+            .ops_contract_sealed_bid(to_valid_account_id(BUYER_2))
+            .is_none());
+        assert_eq!(SealedBid::deposits_near_balance(), 2000.into());
+
+        // Act - the losing bidder withdraws the deposit
+        assert_eq!(withdraw(&mut ctx, BUYER_1), 2000.into());
+
+        // Assert
+        assert_eq!(transfers(), vec![(BUYER_1.to_string(), 2001)]);
+        assert_eq!(SealedBid::deposits_near_balance(), YoctoNear::ZERO);
+    }
+
+    #[test]
+    fn sealed_bid_excess_deposit_withdrawn_by_winner() {
+        // Arrange
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 1000);
+        // committing again replaces the commitment and adds to the deposit
+        commit(&mut ctx, BUYER_1, 1200, "salt", 1000);
+        ctx.block_index = 201;
+        reveal(&mut ctx, BUYER_1, 1200, "salt");
+        ctx.block_index = 251;
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 0;
-        testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+        assert!(settle(&mut ctx));
+
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER_1);
+        assert_eq!(withdraw(&mut ctx, BUYER_1), 800.into());
+        assert_eq!(transfers(), vec![(BUYER_1.to_string(), 801)]);
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn two_deposit() {
+    fn sealed_bid_auction_reserve_price_not_met() {
         // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
-
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 900, "salt", 1000);
+        ctx.block_index = 201;
+        reveal(&mut ctx, BUYER_1, 900, "salt");
+        ctx.block_index = 251;
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 2;
-        testing_env!(ctx.clone());
         // Act
-        ContractSaleComponent.ops_contract_lower_bid(YOCTO.into(), None);
+        assert!(!settle(&mut ctx));
+
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), OWNER);
+        assert!(ContractSaleComponent.ops_contract_auction().is_none());
+        assert!(test_utils::get_logs()
+            .contains(&LOG_EVENT_CONTRACT_AUCTION_ENDED.message("reserve price was not met")));
+        assert_eq!(withdraw(&mut ctx, BUYER_1), 1000.into());
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [BAD_REQUEST] bid cannot be zero")]
-    fn lower_to_zero() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+    #[should_panic(expected = "[ERR] [CONTRACT_BID_NOT_ATTACHED]")]
+    fn sealed_bid_deposit_below_reserve_price() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 900, "salt", 900);
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+    #[test]
+    #[should_panic(expected = "[ERR] [SEALED_BID_INVALID_REVEAL]")]
+    fn sealed_bid_reveal_does_not_match_commitment() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 2000);
+        ctx.block_index = 201;
+        reveal(&mut ctx, BUYER_1, 1500, "wrong salt");
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 1;
-        testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_lower_bid((10 * YOCTO).into(), None);
+    #[test]
+    #[should_panic(expected = "[ERR] [SEALED_BID_INVALID_REVEAL]")]
+    fn sealed_bid_not_covered_by_deposit() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 1000);
+        ctx.block_index = 201;
+        reveal(&mut ctx, BUYER_1, 1500, "salt");
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [BAD_REQUEST] bid cannot be zero")]
-    fn overflow() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+    #[should_panic(expected = "[ERR] [SEALED_BID_REVEAL_NOT_ALLOWED]")]
+    fn sealed_bid_revealed_during_commit_window() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 2000);
+        reveal(&mut ctx, BUYER_1, 1500, "salt");
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_ENDED]")]
+    fn sealed_bid_committed_after_commit_window() {
+        let mut ctx = sealed_bid_auction(1000);
+        ctx.block_index = 201;
+        commit(&mut ctx, BUYER_1, 1500, "salt", 2000);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn sealed_bid_withdrawn_before_settlement() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 2000);
+        withdraw(&mut ctx, BUYER_1);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_NOT_ENDED]")]
+    fn sealed_bid_auction_settled_during_reveal_window() {
+        let mut ctx = sealed_bid_auction(1000);
+        ctx.block_index = 250;
+        settle(&mut ctx);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn sealed_bid_auction_cannot_be_cancelled_with_commitments() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 2000);
+        ctx.predecessor_account_id = OWNER.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        ContractSaleComponent.ops_contract_auction_cancel();
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_AUCTION_IN_PROGRESS]")]
+    fn sealed_bid_auction_ownership_cannot_be_transferred_with_commitments() {
+        let mut ctx = sealed_bid_auction(1000);
+        commit(&mut ctx, BUYER_1, 1500, "salt", 2000);
+        ctx.predecessor_account_id = OWNER.to_string();
         ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_lower_bid((11 * YOCTO).into(), None);
+        ContractOwnershipComponent.ops_owner_transfer(to_valid_account_id(BUYER_2));
     }
 }
 
 #[cfg(test)]
-mod tests_contract_bid_expiration {
+mod tests_sale_history {
     use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use near_sdk::{test_utils, VMContext};
     use oysterpack_smart_near::component::*;
     use oysterpack_smart_near::domain::ExpirationDuration;
-    use oysterpack_smart_near::YOCTO;
     use oysterpack_smart_near_test::*;
 
     const OWNER: &str = "owner";
+    const DEPLOYER: &str = "deployer";
+    const FACTORY: &str = "factory";
     const BUYER: &str = "buyer";
 
-    #[test]
-    fn update() {
-        // Arrange
+    fn deploy() -> VMContext {
         let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
         ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ctx
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
-
+    fn grant(ctx: &mut VMContext, account_id: &str, basis_points: u16) -> ContractRoyalty {
+        ctx.predecessor_account_id = OWNER.to_string();
         ctx.attached_deposit = 1;
-        ctx.block_timestamp = 100;
         testing_env!(ctx.clone());
-        // Act
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
-
-        // Assert
-        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert_eq!(bid.bid.expiration, Some(expiration.into()));
+        ContractSaleComponent
+            .ops_contract_royalty_grant(to_valid_account_id(account_id), basis_points.into())
     }
 
-    #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn update_zero_deposit() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
+    fn sell(ctx: &mut VMContext, seller: &str, buyer: &str, price: u128) {
+        ctx.predecessor_account_id = seller.to_string();
+        ctx.attached_deposit = 1;
         testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_sell(price.into());
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
+        ctx.predecessor_account_id = buyer.to_string();
+        ctx.attached_deposit = price;
         testing_env!(ctx.clone());
         ContractSaleComponent.ops_contract_buy(None);
+    }
 
-        ctx.attached_deposit = 0;
-        ctx.block_timestamp = 100;
-        testing_env!(ctx.clone());
-        // Act
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
+    fn transfers() -> Vec<(String, u128)> {
+        deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(transfer) => Some((receipt.receiver_id.clone(), transfer.deposit)),
+                _ => None,
+            })
+            .collect()
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn update_two_deposit() {
+    fn royalties_paid_and_sale_recorded() {
         // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
-
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        let mut ctx = deploy();
+        grant(&mut ctx, DEPLOYER, 500);
+        grant(&mut ctx, FACTORY, 100);
+        assert_eq!(
+            ContractSaleComponent.ops_contract_royalties(),
+            vec![
+                ContractRoyalty {
+                    account_id: DEPLOYER.to_string(),
+                    basis_points: 500.into()
+                },
+                ContractRoyalty {
+                    account_id: FACTORY.to_string(),
+                    basis_points: 100.into()
+                },
+            ]
+        );
+        assert_eq!(ContractSaleComponent.ops_contract_sale_count(), 0.into());
 
-        ctx.attached_deposit = 2;
-        ctx.block_timestamp = 100;
-        testing_env!(ctx.clone());
         // Act
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
-    }
+        sell(&mut ctx, OWNER, BUYER, 10000);
 
-    #[test]
-    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
-    fn update_not_buyer() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        // Assert
+        assert_eq!(ContractOwnershipComponent.ops_owner(), BUYER);
+        let transfers = transfers();
+        assert_eq!(transfers[0], (DEPLOYER.to_string(), 500));
+        assert_eq!(transfers[1], (FACTORY.to_string(), 100));
+        assert_eq!(transfers[2].0, OWNER);
+        assert!(test_utils::get_logs()
+            .contains(&LOG_EVENT_CONTRACT_ROYALTY_PAID.message("account=deployer, amount=500")));
+
+        let history = ContractSaleComponent.ops_contract_sale_history(0.into(), None);
+        assert_eq!(history.len(), 1);
+        let record = &history[0];
+        assert_eq!(record.seller, OWNER);
+        assert_eq!(record.buyer, BUYER);
+        assert_eq!(record.price, 10000.into());
+        assert!(record.token_id.is_none());
+        assert_eq!(
+            record.royalties,
+            vec![
+                (DEPLOYER.to_string(), 500.into()),
+                (FACTORY.to_string(), 100.into())
+            ]
+        );
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        // Act - royalties survive the change in ownership
+        sell(&mut ctx, BUYER, OWNER, 20000);
 
-        ctx.predecessor_account_id = "OTHER".to_string();
-        ctx.attached_deposit = 1;
-        ctx.block_timestamp = 100;
-        testing_env!(ctx.clone());
-        // Act
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
+        // Assert
+        assert_eq!(ContractSaleComponent.ops_contract_sale_count(), 2.into());
+        let history = ContractSaleComponent.ops_contract_sale_history(1.into(), Some(10));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].seller, BUYER);
+        assert_eq!(history[0].royalties[0], (DEPLOYER.to_string(), 1000.into()));
+        assert!(ContractSaleComponent
+            .ops_contract_sale_history(0.into(), Some(1))
+            .iter()
+            .all(|record| record.seller == OWNER));
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [BAD_REQUEST] expiration cannot be set to expired")]
-    fn update_with_expired_setting() {
+    fn royalty_reduced_by_recipient() {
         // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
-
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        ContractSaleComponent.ops_contract_buy(None);
+        let mut ctx = deploy();
+        grant(&mut ctx, DEPLOYER, 500);
+        grant(&mut ctx, DEPLOYER, 600);
 
-        ctx.predecessor_account_id = "OTHER".to_string();
-        ctx.attached_deposit = 1;
-        ctx.block_timestamp = 100;
-        testing_env!(ctx.clone());
         // Act
-        let expiration = ExpirationSetting::Absolute(Expiration::Timestamp(60.into()));
-        ContractSaleComponent.ops_contract_update_bid_expiration(expiration);
-    }
-
-    #[test]
-    fn clear() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
+        ctx.predecessor_account_id = DEPLOYER.to_string();
         testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        let royalty = ContractSaleComponent.ops_contract_royalty_reduce(200.into());
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_buy(Some(expiration));
+        // Assert
+        assert_eq!(royalty.unwrap().basis_points, 200.into());
 
-        ctx.attached_deposit = 1;
-        ctx.block_timestamp = 100;
-        testing_env!(ctx.clone());
         // Act
-
-        ContractSaleComponent.ops_contract_clear_bid_expiration();
+        let royalty = ContractSaleComponent.ops_contract_royalty_reduce(BasisPoints::ZERO);
 
         // Assert
-        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
-        assert!(bid.bid.expiration.is_none());
+        assert!(royalty.is_none());
+        assert!(ContractSaleComponent.ops_contract_royalties().is_empty());
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn clear_zero_deposit() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
-
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_buy(Some(expiration));
+    #[should_panic(expected = "[ERR] [CONTRACT_ROYALTY_INVALID]")]
+    fn royalty_cannot_be_lowered_by_owner() {
+        let mut ctx = deploy();
+        grant(&mut ctx, DEPLOYER, 500);
+        grant(&mut ctx, DEPLOYER, 100);
+    }
 
-        ctx.attached_deposit = 0;
-        ctx.block_timestamp = 100;
-        testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_clear_bid_expiration();
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_ROYALTY_INVALID]")]
+    fn total_royalties_exceed_max() {
+        let mut ctx = deploy();
+        grant(&mut ctx, DEPLOYER, 600);
+        grant(&mut ctx, FACTORY, 401);
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [YOCTONEAR_DEPOSIT_REQUIRED]")]
-    fn clear_two_deposit() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+    #[should_panic(expected = "[ERR] [CONTRACT_ROYALTY_INVALID]")]
+    fn royalty_granted_while_for_sale() {
+        let mut ctx = deploy();
+        ContractSaleComponent.ops_contract_sell(10000.into());
+        grant(&mut ctx, DEPLOYER, 500);
+    }
 
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_ROYALTY_INVALID]")]
+    fn royalty_granted_while_bid_is_active() {
+        let mut ctx = deploy();
         ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_buy(Some(expiration));
-
-        ctx.attached_deposit = 2;
-        ctx.block_timestamp = 100;
+        ctx.attached_deposit = 10000;
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_clear_bid_expiration();
+        ContractSaleComponent.ops_contract_buy(None);
+        grant(&mut ctx, DEPLOYER, 500);
     }
 
     #[test]
-    #[should_panic(expected = "[ERR] [ACCESS_DENIED_MUST_BE_BUYER]")]
-    fn clear_not_buyer() {
-        // Arrange
-        let mut ctx = new_context(OWNER);
-        testing_env!(ctx.clone());
-        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+    #[should_panic(expected = "[ERR] [CONTRACT_ROYALTY_INVALID]")]
+    fn royalty_granted_during_auction() {
+        let mut ctx = deploy();
+        ContractSaleComponent.ops_contract_auction_start(ContractAuctionSettings::English {
+            reserve_price: 2000.into(),
+            min_increment: 100.into(),
+            duration: ExpirationDuration::Blocks(100),
+            anti_sniping_extension: None,
+        });
+        grant(&mut ctx, DEPLOYER, 500);
+    }
 
-        ctx.predecessor_account_id = BUYER.to_string();
-        ctx.attached_deposit = 10 * YOCTO;
-        testing_env!(ctx.clone());
-        let expiration = ExpirationSetting::Relative(ExpirationDuration::Seconds(60));
-        ContractSaleComponent.ops_contract_buy(Some(expiration));
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_ROYALTY_NOT_FOUND]")]
+    fn royalty_reduced_by_non_recipient() {
+        let mut ctx = deploy();
+        grant(&mut ctx, DEPLOYER, 500);
+        ContractSaleComponent.ops_contract_royalty_reduce(BasisPoints::ZERO);
+    }
 
-        ctx.predecessor_account_id = "BUYER2".to_string();
-        ctx.attached_deposit = 1;
-        ctx.block_timestamp = 100;
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_ACCESS_REQUIRED]")]
+    fn royalty_granted_by_non_owner() {
+        let mut ctx = deploy();
+        ctx.predecessor_account_id = DEPLOYER.to_string();
         testing_env!(ctx.clone());
-        // Act
-        ContractSaleComponent.ops_contract_clear_bid_expiration();
+        ContractSaleComponent.ops_contract_royalty_grant(to_valid_account_id(DEPLOYER), 500.into());
     }
 }
@@ -0,0 +1,442 @@
+//! [`ContractUpgradeComponent`]
+
+use crate::components::contract_metrics::ContractMetricsComponent;
+use crate::components::contract_multisig::ContractMultiSigComponent;
+use crate::{
+    ContractMetrics, ContractOwnerObject, ContractUpgrade, StagedContractUpgrade,
+    CONTRACT_UPGRADE_MIGRATE_METHOD, ERR_CONTRACT_UPGRADE_CODE_EMPTY,
+    ERR_CONTRACT_UPGRADE_NOT_STAGED, ERR_CONTRACT_UPGRADE_STORAGE_NOT_COVERED,
+    ERR_CONTRACT_UPGRADE_TIMELOCKED, LOG_EVENT_CONTRACT_UPGRADE_CANCELLED,
+    LOG_EVENT_CONTRACT_UPGRADE_DEPLOYED, LOG_EVENT_CONTRACT_UPGRADE_STAGED,
+};
+use oysterpack_smart_account_management::AuditLog;
+use oysterpack_smart_near::asserts::assert_yocto_near_attached;
+use oysterpack_smart_near::component::{Component, ComponentState};
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::domain::{BlockTime, ExpirationDuration, Gas, StorageUsage};
+use oysterpack_smart_near::near_sdk::{
+    env,
+    json_types::{Base58CryptoHash, Base64VecU8},
+    serde_json::json,
+    CryptoHash, Promise,
+};
+use oysterpack_smart_near::TERA;
+use std::convert::TryInto;
+
+const CONTRACT_UPGRADE_CODE_KEY: u128 = 1438315236306327180919536102548226893;
+
+type CodeDAO = Object<u128, Vec<u8>>;
+
+/// default gas allotted to the [`CONTRACT_UPGRADE_MIGRATE_METHOD`] function call
+pub const CONTRACT_UPGRADE_MIGRATE_GAS: Gas = Gas(50 * TERA);
+
+/// Upgrades are announced at least 5 epochs before they can be deployed, which gives accounts time
+/// to review the staged code and exit if they choose to
+pub const CONTRACT_UPGRADE_MIN_NOTICE: ExpirationDuration =
+    ExpirationDuration::Epochs(MIN_NOTICE_EPOCHS);
+
+const MIN_NOTICE_EPOCHS: u32 = 5;
+
+/// number of blocks per epoch, which is used to compare block based notices against
+/// [`CONTRACT_UPGRADE_MIN_NOTICE`]
+const EPOCH_BLOCKS: u64 = 43_200;
+
+/// approximate epoch duration, which is used to compare time based notices against
+/// [`CONTRACT_UPGRADE_MIN_NOTICE`]
+/// - an epoch is [`EPOCH_BLOCKS`] blocks, but block production time varies (~1-1.2 seconds per
+///   block), which puts an epoch at roughly 12-14 hours
+/// - the upper estimate is used to ensure time based notices are not shorter than the min notice
+const SECONDS_PER_EPOCH: u64 = 14 * 60 * 60;
+
+type MultiSig = ContractMultiSigComponent<()>;
+
+pub struct ContractUpgradeComponent {
+    notice: ExpirationDuration,
+    migrate_gas: Gas,
+}
+
+impl Component for ContractUpgradeComponent {
+    type State = StagedContractUpgrade;
+
+    const STATE_KEY: u128 = 1515926870409712315829617367455404061;
+}
+
+impl ContractUpgradeComponent {
+    /// - `notice` - the time-lock duration that is applied when an upgrade is staged
+    ///   - if the notice is shorter than [`CONTRACT_UPGRADE_MIN_NOTICE`], then the min notice is applied
+    /// - `migrate_gas` - gas allotted to the migrate function call
+    pub fn new(notice: ExpirationDuration, migrate_gas: Gas) -> Self {
+        Self {
+            notice: Self::notice_floor(notice),
+            migrate_gas,
+        }
+    }
+
+    fn notice_floor(notice: ExpirationDuration) -> ExpirationDuration {
+        let epochs = match notice {
+            ExpirationDuration::Epochs(epochs) => epochs as u64,
+            ExpirationDuration::Blocks(blocks) => blocks as u64 / EPOCH_BLOCKS,
+            ExpirationDuration::Seconds(seconds) => seconds as u64 / SECONDS_PER_EPOCH,
+        };
+        if epochs < MIN_NOTICE_EPOCHS as u64 {
+            CONTRACT_UPGRADE_MIN_NOTICE
+        } else {
+            notice
+        }
+    }
+
+    /// deletes the staged code and upgrade announcement
+    ///
+    /// Returns false if no upgrade was staged
+    fn delete_staged_upgrade() -> bool {
+        CodeDAO::delete_by_key(&CONTRACT_UPGRADE_CODE_KEY);
+        ComponentState::<StagedContractUpgrade>::delete_by_key(&Self::STATE_KEY)
+    }
+}
+
+impl ContractUpgrade for ContractUpgradeComponent {
+    fn ops_upgrade_stage(&mut self, code: Base64VecU8) -> StagedContractUpgrade {
+        assert_yocto_near_attached();
+        if MultiSig::is_enabled() {
+            // the upgrade must be staged through an approved multisig proposal
+            MultiSig::assert_approved_if_enabled();
+        } else {
+            ContractOwnerObject::assert_owner_access();
+        }
+
+        let code: Vec<u8> = code.into();
+        ERR_CONTRACT_UPGRADE_CODE_EMPTY.assert(|| !code.is_empty());
+
+        Self::delete_staged_upgrade();
+        let initial_storage_usage = env::storage_usage();
+        let code_hash: CryptoHash = env::sha256(&code).as_slice().try_into().unwrap();
+        let code_size = code.len() as u32;
+        CodeDAO::new(CONTRACT_UPGRADE_CODE_KEY, code).save();
+        let mut staged_upgrade = Self::new_state(StagedContractUpgrade {
+            code_hash: Base58CryptoHash::from(code_hash),
+            code_size,
+            storage_usage: 0.into(),
+            storage_cost: 0.into(),
+            staged_on: BlockTime::from_env(),
+            timelock: self.notice.into(),
+        });
+        staged_upgrade.save();
+        // the announcement itself is included in the storage usage, which is recorded after it has
+        // been saved - updating the storage usage fields does not change the serialized size
+        let storage_usage: StorageUsage = (env::storage_usage() - initial_storage_usage).into();
+        staged_upgrade.storage_usage = storage_usage;
+        staged_upgrade.storage_cost = storage_usage.cost();
+        staged_upgrade.save();
+
+        // the staged code storage is not tracked as account storage, thus it is charged to the owner
+        let near_balances = ContractMetricsComponent.ops_metrics_near_balances();
+        let storage_usage_costs = ContractMetricsComponent.ops_metrics_storage_usage_costs();
+        ERR_CONTRACT_UPGRADE_STORAGE_NOT_COVERED
+            .assert(|| near_balances.owner() >= storage_usage_costs.owner());
+
+        let staged_upgrade = *staged_upgrade;
+        let code_hash = String::from(&staged_upgrade.code_hash);
+        LOG_EVENT_CONTRACT_UPGRADE_STAGED.log(format!(
+            "code_hash: {} | code_size: {} | timelock: {}",
+            code_hash, code_size, staged_upgrade.timelock
+        ));
+        AuditLog::record(
+            "ops_upgrade_stage",
+            None,
+            Some(json!({ "code_hash": code_hash }).to_string()),
+        );
+        staged_upgrade
+    }
+
+    fn ops_upgrade_cancel(&mut self) -> bool {
+        assert_yocto_near_attached();
+        ContractOwnerObject::assert_owner_command_access("ops_upgrade_cancel");
+
+        if Self::delete_staged_upgrade() {
+            LOG_EVENT_CONTRACT_UPGRADE_CANCELLED.log("");
+            AuditLog::record("ops_upgrade_cancel", None, None);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ops_upgrade_deploy(&mut self) {
+        let staged_upgrade = match Self::load_state() {
+            Some(staged_upgrade) => *staged_upgrade,
+            None => {
+                ERR_CONTRACT_UPGRADE_NOT_STAGED.panic();
+                unreachable!()
+            }
+        };
+        ERR_CONTRACT_UPGRADE_TIMELOCKED.assert(|| staged_upgrade.timelock.expired());
+
+        let code = CodeDAO::load(&CONTRACT_UPGRADE_CODE_KEY)
+            .map(|code| (*code).clone())
+            .expect("staged contract upgrade code not found");
+        Self::delete_staged_upgrade();
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call(
+                CONTRACT_UPGRADE_MIGRATE_METHOD.as_bytes().to_vec(),
+                vec![],
+                0,
+                *self.migrate_gas,
+            );
+
+        let code_hash = String::from(&staged_upgrade.code_hash);
+        LOG_EVENT_CONTRACT_UPGRADE_DEPLOYED.log(format!("code_hash: {}", code_hash));
+    }
+
+    fn ops_upgrade_staged(&self) -> Option<StagedContractUpgrade> {
+        Self::load_state().map(|staged_upgrade| *staged_upgrade)
+    }
+
+    fn ops_upgrade_notice(&self) -> ExpirationDuration {
+        self.notice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::contract_ownership::ContractOwnershipComponent;
+    use crate::{ContractMultiSig, MultiSigConfig};
+    use oysterpack_smart_account_management::components::account_management::{
+        AccountManagementComponent, AccountManagementComponentConfig,
+    };
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::domain::Expiration;
+    use oysterpack_smart_near::near_sdk::{test_utils, VMContext};
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+
+    const OWNER: &str = "owner";
+
+    fn deploy() -> (VMContext, ContractUpgradeComponent) {
+        let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        ctx.epoch_height = 100;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        (
+            ctx,
+            ContractUpgradeComponent::new(
+                ExpirationDuration::Epochs(5),
+                CONTRACT_UPGRADE_MIGRATE_GAS,
+            ),
+        )
+    }
+
+    fn deploy_with_multisig() -> (VMContext, ContractUpgradeComponent) {
+        let (ctx, upgrade) = deploy();
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            component_account_storage_mins: None,
+            admin_account: to_valid_account_id(OWNER),
+        });
+        ContractMultiSigComponent::new(AccountManager::default()).ops_multisig_configure(
+            MultiSigConfig {
+                approvers: vec!["alice".to_string(), "bob".to_string()],
+                threshold: 2,
+                proposal_expiration: ExpirationDuration::Epochs(3),
+                commands: None,
+            },
+        );
+        (ctx, upgrade)
+    }
+
+    fn code() -> Vec<u8> {
+        vec![7_u8; 1000]
+    }
+
+    #[test]
+    fn upgrade_workflow() {
+        // Arrange
+        let (mut ctx, mut upgrade) = deploy();
+        assert!(upgrade.ops_upgrade_staged().is_none());
+        assert_eq!(upgrade.ops_upgrade_notice(), ExpirationDuration::Epochs(5));
+
+        // Act - owner stages the upgrade
+        let staged_upgrade = upgrade.ops_upgrade_stage(code().into());
+
+        // Assert
+        assert_eq!(upgrade.ops_upgrade_staged().unwrap(), staged_upgrade);
+        let code_hash: CryptoHash = env::sha256(&code()).as_slice().try_into().unwrap();
+        assert_eq!(staged_upgrade.code_hash, Base58CryptoHash::from(code_hash));
+        assert_eq!(staged_upgrade.code_size, 1000);
+        assert_eq!(staged_upgrade.timelock, Expiration::Epoch(105.into()));
+        assert!(staged_upgrade.storage_usage.value() > 1000);
+        assert_eq!(
+            staged_upgrade.storage_cost,
+            staged_upgrade.storage_usage.cost()
+        );
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [CONTRACT_UPGRADE_STAGED]")));
+
+        // Act - the upgrade is triggered by anyone after the time-lock expires
+        ctx.predecessor_account_id = "anyone".to_string();
+        ctx.attached_deposit = 0;
+        ctx.epoch_height = 106;
+        ctx.storage_usage = env::storage_usage();
+        testing_env!(ctx.clone());
+        let storage_usage_before_deploy = env::storage_usage();
+        upgrade.ops_upgrade_deploy();
+
+        // Assert
+        assert!(upgrade.ops_upgrade_staged().is_none());
+        assert_eq!(
+            env::storage_usage(),
+            storage_usage_before_deploy - staged_upgrade.storage_usage.value()
+        );
+        let receipts = deserialize_receipts();
+        assert_eq!(receipts.len(), 1);
+        let receipt = &receipts[0];
+        assert_eq!(receipt.receiver_id, env::current_account_id());
+        assert_eq!(receipt.actions.len(), 2);
+        match &receipt.actions[0] {
+            Action::DeployContract(action) => assert_eq!(action.code, code()),
+            _ => panic!("expected DeployContract"),
+        }
+        match &receipt.actions[1] {
+            Action::FunctionCall(action) => {
+                assert_eq!(action.method_name, CONTRACT_UPGRADE_MIGRATE_METHOD);
+                assert_eq!(action.gas, CONTRACT_UPGRADE_MIGRATE_GAS.value());
+                assert_eq!(action.deposit, 0);
+            }
+            _ => panic!("expected FunctionCall"),
+        }
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [CONTRACT_UPGRADE_DEPLOYED]")));
+    }
+
+    #[test]
+    fn restage_restarts_timelock() {
+        let (mut ctx, mut upgrade) = deploy();
+        upgrade.ops_upgrade_stage(code().into());
+
+        ctx.epoch_height = 101;
+        testing_env!(ctx.clone());
+        let staged_upgrade = upgrade.ops_upgrade_stage(vec![1_u8; 500].into());
+        assert_eq!(staged_upgrade.timelock, Expiration::Epoch(106.into()));
+        assert_eq!(staged_upgrade.code_size, 500);
+        assert_eq!(upgrade.ops_upgrade_staged().unwrap(), staged_upgrade);
+    }
+
+    #[test]
+    fn cancel() {
+        let (_ctx, mut upgrade) = deploy();
+        assert!(!upgrade.ops_upgrade_cancel());
+
+        let staged_upgrade = upgrade.ops_upgrade_stage(code().into());
+        let storage_usage_before_cancel = env::storage_usage();
+        assert!(upgrade.ops_upgrade_cancel());
+        assert!(upgrade.ops_upgrade_staged().is_none());
+        // the staged code storage is freed up
+        assert!(staged_upgrade.storage_usage.value() > 1000);
+        assert!(env::storage_usage() < storage_usage_before_cancel);
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [CONTRACT_UPGRADE_CANCELLED]")));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_UPGRADE_TIMELOCKED]")]
+    fn deploy_before_timelock_expires() {
+        let (mut ctx, mut upgrade) = deploy();
+        upgrade.ops_upgrade_stage(code().into());
+
+        ctx.epoch_height = 105;
+        testing_env!(ctx.clone());
+        upgrade.ops_upgrade_deploy();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_UPGRADE_NOT_STAGED]")]
+    fn deploy_not_staged() {
+        let (_ctx, mut upgrade) = deploy();
+        upgrade.ops_upgrade_deploy();
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [OWNER_ACCESS_REQUIRED]")]
+    fn stage_requires_owner_access() {
+        let (mut ctx, mut upgrade) = deploy();
+        ctx.predecessor_account_id = "mallory".to_string();
+        testing_env!(ctx.clone());
+        upgrade.ops_upgrade_stage(code().into());
+    }
+
+    #[test]
+    fn notice_floor() {
+        let notice = |notice| {
+            ContractUpgradeComponent::new(notice, CONTRACT_UPGRADE_MIGRATE_GAS).ops_upgrade_notice()
+        };
+        assert_eq!(
+            notice(ExpirationDuration::Epochs(2)),
+            CONTRACT_UPGRADE_MIN_NOTICE
+        );
+        assert_eq!(
+            notice(ExpirationDuration::Blocks(100)),
+            CONTRACT_UPGRADE_MIN_NOTICE
+        );
+        assert_eq!(
+            notice(ExpirationDuration::Seconds(3600)),
+            CONTRACT_UPGRADE_MIN_NOTICE
+        );
+        // 5 x 12 hours falls short of 5 epochs when blocks are produced slower than 1 per second
+        assert_eq!(
+            notice(ExpirationDuration::Seconds(5 * 12 * 3600)),
+            CONTRACT_UPGRADE_MIN_NOTICE
+        );
+        assert_eq!(
+            notice(ExpirationDuration::Blocks(5 * 43_200)),
+            ExpirationDuration::Blocks(5 * 43_200)
+        );
+        assert_eq!(
+            notice(ExpirationDuration::Epochs(10)),
+            ExpirationDuration::Epochs(10)
+        );
+        assert_eq!(
+            notice(ExpirationDuration::Seconds(7 * 24 * 3600)),
+            ExpirationDuration::Seconds(7 * 24 * 3600)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [MULTISIG_APPROVAL_REQUIRED]")]
+    fn stage_requires_multisig_approval() {
+        let (ctx, mut upgrade) = deploy_with_multisig();
+        testing_env!(ctx);
+        upgrade.ops_upgrade_stage(code().into());
+    }
+
+    #[test]
+    fn stage_via_approved_multisig_proposal() {
+        let (mut ctx, mut upgrade) = deploy_with_multisig();
+        ctx.predecessor_account_id = ctx.current_account_id.clone();
+        testing_env!(ctx);
+        let staged_upgrade = upgrade.ops_upgrade_stage(code().into());
+        assert_eq!(upgrade.ops_upgrade_staged().unwrap(), staged_upgrade);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_UPGRADE_CODE_EMPTY]")]
+    fn stage_empty_code() {
+        let (_ctx, mut upgrade) = deploy();
+        upgrade.ops_upgrade_stage(vec![].into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [CONTRACT_UPGRADE_STORAGE_NOT_COVERED]")]
+    fn stage_storage_not_covered_by_owner_balance() {
+        let (mut ctx, mut upgrade) = deploy();
+        ctx.account_balance = 0;
+        testing_env!(ctx.clone());
+        upgrade.ops_upgrade_stage(code().into());
+    }
+}
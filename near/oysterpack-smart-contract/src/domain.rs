@@ -1,11 +1,29 @@
+pub use contract_auction::*;
 pub use contract_bid::*;
+pub use contract_co_ownership::*;
+pub use contract_health_check::*;
+pub use contract_multisig::*;
 pub use contract_near_balances::*;
 pub use contract_owner::*;
+pub use contract_owner_recovery::*;
+pub use contract_owner_withdrawal::*;
+pub use contract_sale_history::*;
+pub use contract_sale_tokens::*;
+pub use contract_sealed_bid::*;
 pub use contract_storage_usage::*;
 pub use contract_storage_usage_costs::*;
 
+mod contract_auction;
 mod contract_bid;
+mod contract_co_ownership;
+mod contract_health_check;
+mod contract_multisig;
 mod contract_near_balances;
 mod contract_owner;
+mod contract_owner_recovery;
+mod contract_owner_withdrawal;
+mod contract_sale_history;
+mod contract_sale_tokens;
+mod contract_sealed_bid;
 mod contract_storage_usage;
 mod contract_storage_usage_costs;
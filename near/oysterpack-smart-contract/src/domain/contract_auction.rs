@@ -0,0 +1,318 @@
+use crate::SealedBid;
+use oysterpack_smart_near::asserts::ERR_INVALID;
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::domain::{BlockTime, Expiration, ExpirationDuration, YoctoNear};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+const CONTRACT_AUCTION_KEY: u128 = 1365873740128471969541064906374598271;
+
+type DAO = Object<u128, ContractAuction>;
+
+/// Contract sale auction that is in progress
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub enum ContractAuction {
+    English(EnglishAuction),
+    Dutch(DutchAuction),
+    Sealed(SealedBidAuction),
+}
+
+impl ContractAuction {
+    pub fn load() -> Option<Self> {
+        DAO::load(&CONTRACT_AUCTION_KEY).map(|auction| (*auction).clone())
+    }
+
+    pub(crate) fn save(&self) {
+        DAO::new(CONTRACT_AUCTION_KEY, self.clone()).save();
+    }
+
+    /// returns true if an auction was in progress
+    pub(crate) fn delete() -> bool {
+        DAO::delete_by_key(&CONTRACT_AUCTION_KEY)
+    }
+
+    /// returns true if bids have been placed or sealed bids have been committed, i.e., the auction
+    /// can no longer be cancelled
+    pub fn has_bids(&self) -> bool {
+        match self {
+            ContractAuction::English(auction) => !auction.bids.is_empty(),
+            ContractAuction::Dutch(_) => false,
+            ContractAuction::Sealed(auction) => auction.commitments > 0,
+        }
+    }
+}
+
+/// Ascending price auction - the highest bid is held in escrow, and is refunded when outbid.
+///
+/// Bids are binding, i.e., they cannot be lowered or cancelled while the auction is in progress.
+/// Once the auction has ended, it is settled - the contract is sold to the highest bidder if the
+/// bid meets the reserve price.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct EnglishAuction {
+    /// if the highest bid does not meet the reserve price, then the contract is not sold
+    pub reserve_price: YoctoNear,
+    /// each bid must be higher than the current bid by at least the min increment
+    pub min_increment: YoctoNear,
+    pub end: Expiration,
+    /// bids placed within the extension window before the auction ends push out the end of the
+    /// auction to the extension window
+    pub anti_sniping_extension: Option<ExpirationDuration>,
+    /// the bid history in the order the bids were placed
+    pub bids: Vec<ContractAuctionBid>,
+}
+
+impl EnglishAuction {
+    /// the minimum amount that the next bid must be
+    pub fn min_bid(&self) -> YoctoNear {
+        self.bids
+            .last()
+            .map_or(self.min_increment, |bid| bid.amount + self.min_increment)
+    }
+
+    /// records the bid, and extends the auction if the bid was placed within the anti-sniping
+    /// extension window
+    ///
+    /// Returns true if the auction end was extended
+    pub(crate) fn record_bid(&mut self, buyer: AccountId, amount: YoctoNear) -> bool {
+        self.bids.push(ContractAuctionBid {
+            buyer,
+            amount,
+            placed_on: BlockTime::from_env(),
+        });
+        match self.anti_sniping_extension {
+            Some(extension) => {
+                let extended_end: Expiration = extension.into();
+                if extended_end > self.end {
+                    self.end = extended_end;
+                    true
+                } else {
+                    false
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+/// Descending price auction - the price decays from the start price down to the floor price.
+/// The first buyer to bid at least the current price buys the contract at the current price.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct DutchAuction {
+    pub start_price: YoctoNear,
+    pub floor_price: YoctoNear,
+    /// the price drops by the decrement each decay interval
+    pub price_decrement: YoctoNear,
+    pub decay_interval: ExpirationDuration,
+    pub started_on: BlockTime,
+}
+
+impl DutchAuction {
+    pub fn price(&self) -> YoctoNear {
+        let now = BlockTime::from_env();
+        let intervals = match self.decay_interval {
+            ExpirationDuration::Epochs(epochs) => {
+                (now.epoch.value() - self.started_on.epoch.value()) / epochs as u64
+            }
+            ExpirationDuration::Blocks(blocks) => {
+                (now.height.value() - self.started_on.height.value()) / blocks as u64
+            }
+            ExpirationDuration::Seconds(seconds) => {
+                (now.timestamp.value() - self.started_on.timestamp.value())
+                    / (1_000_000_000 * seconds as u64)
+            }
+        };
+        let decay = self
+            .price_decrement
+            .value()
+            .saturating_mul(intervals as u128);
+        self.start_price
+            .saturating_sub(decay)
+            .max(self.floor_price.value())
+            .into()
+    }
+}
+
+/// Commit-reveal auction, which does not leak bid prices while bids are being placed:
+/// 1. during the commit window, buyers commit the hash of their bid - see [`SealedBid::commitment`] -
+///    along with a deposit that covers the bid
+/// 2. during the reveal window, buyers reveal their bids
+/// 3. once the reveal window has ended, the auction is settled - the highest valid revealed bid wins
+///    if it meets the reserve price
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct SealedBidAuction {
+    pub id: U64,
+    pub reserve_price: YoctoNear,
+    pub commit_end: Expiration,
+    pub reveal_end: Expiration,
+    /// number of buyers that have committed bids
+    pub commitments: u32,
+    /// revealed bids in the order they were revealed
+    pub bids: Vec<ContractAuctionBid>,
+}
+
+impl SealedBidAuction {
+    pub fn commit_window_open(&self) -> bool {
+        !self.commit_end.expired()
+    }
+
+    pub fn reveal_window_open(&self) -> bool {
+        self.commit_end.expired() && !self.reveal_end.expired()
+    }
+
+    /// on ties, the bid that was revealed first wins
+    pub fn highest_bid(&self) -> Option<&ContractAuctionBid> {
+        self.bids.iter().fold(None, |highest, bid| match highest {
+            Some(highest) if highest.amount >= bid.amount => Some(highest),
+            _ => Some(bid),
+        })
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ContractAuctionBid {
+    pub buyer: AccountId,
+    pub amount: YoctoNear,
+    pub placed_on: BlockTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub enum ContractAuctionSettings {
+    English {
+        reserve_price: YoctoNear,
+        min_increment: YoctoNear,
+        duration: ExpirationDuration,
+        /// must use the same time unit as the auction duration
+        anti_sniping_extension: Option<ExpirationDuration>,
+    },
+    Dutch {
+        start_price: YoctoNear,
+        floor_price: YoctoNear,
+        price_decrement: YoctoNear,
+        decay_interval: ExpirationDuration,
+    },
+    Sealed {
+        reserve_price: YoctoNear,
+        commit_duration: ExpirationDuration,
+        /// the reveal window starts when the commit window ends, and must use the same time unit
+        reveal_duration: ExpirationDuration,
+    },
+}
+
+impl ContractAuctionSettings {
+    /// ## Panics
+    /// if the settings are invalid
+    pub(crate) fn start(self) -> ContractAuction {
+        match self {
+            ContractAuctionSettings::English {
+                reserve_price,
+                min_increment,
+                duration,
+                anti_sniping_extension,
+            } => {
+                ERR_INVALID.assert(
+                    || min_increment > YoctoNear::ZERO,
+                    || "min increment must not be zero",
+                );
+                ERR_INVALID.assert(
+                    || duration_units(duration) > 0,
+                    || "auction duration must not be zero",
+                );
+                if let Some(extension) = anti_sniping_extension {
+                    ERR_INVALID.assert(
+                        || {
+                            duration_units(extension) > 0
+                                && std::mem::discriminant(&extension)
+                                    == std::mem::discriminant(&duration)
+                        },
+                        || "anti-sniping extension must be non-zero and use the same time unit as the auction duration",
+                    );
+                }
+                ContractAuction::English(EnglishAuction {
+                    reserve_price,
+                    min_increment,
+                    end: duration.into(),
+                    anti_sniping_extension,
+                    bids: vec![],
+                })
+            }
+            ContractAuctionSettings::Dutch {
+                start_price,
+                floor_price,
+                price_decrement,
+                decay_interval,
+            } => {
+                ERR_INVALID.assert(
+                    || floor_price > YoctoNear::ZERO && start_price > floor_price,
+                    || "start price must be greater than the floor price, which must not be zero",
+                );
+                ERR_INVALID.assert(
+                    || price_decrement > YoctoNear::ZERO && duration_units(decay_interval) > 0,
+                    || "price decrement and decay interval must not be zero",
+                );
+                ContractAuction::Dutch(DutchAuction {
+                    start_price,
+                    floor_price,
+                    price_decrement,
+                    decay_interval,
+                    started_on: BlockTime::from_env(),
+                })
+            }
+            ContractAuctionSettings::Sealed {
+                reserve_price,
+                commit_duration,
+                reveal_duration,
+            } => {
+                ERR_INVALID.assert(
+                    || {
+                        duration_units(commit_duration) > 0
+                            && duration_units(reveal_duration) > 0
+                            && std::mem::discriminant(&commit_duration)
+                                == std::mem::discriminant(&reveal_duration)
+                    },
+                    || "commit and reveal durations must be non-zero and use the same time unit",
+                );
+                let commit_end: Expiration = commit_duration.into();
+                let reveal_end = match (commit_end, reveal_duration) {
+                    (Expiration::Epoch(epoch), ExpirationDuration::Epochs(epochs)) => {
+                        Expiration::Epoch((epoch.value() + epochs as u64).into())
+                    }
+                    (Expiration::Block(block), ExpirationDuration::Blocks(blocks)) => {
+                        Expiration::Block((block.value() + blocks as u64).into())
+                    }
+                    (Expiration::Timestamp(timestamp), ExpirationDuration::Seconds(seconds)) => {
+                        Expiration::Timestamp(
+                            (timestamp.value() + 1_000_000_000 * seconds as u64).into(),
+                        )
+                    }
+                    _ => unreachable!(),
+                };
+                ContractAuction::Sealed(SealedBidAuction {
+                    id: SealedBid::next_auction_id(),
+                    reserve_price,
+                    commit_end,
+                    reveal_end,
+                    commitments: 0,
+                    bids: vec![],
+                })
+            }
+        }
+    }
+}
+
+fn duration_units(duration: ExpirationDuration) -> u32 {
+    match duration {
+        ExpirationDuration::Epochs(units)
+        | ExpirationDuration::Blocks(units)
+        | ExpirationDuration::Seconds(units) => units,
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{BalanceId, ContractNearBalances, ERR_BID_IS_EXPIRED};
+use oysterpack_smart_near::data::numbers::U128;
 use oysterpack_smart_near::domain::{Expiration, ExpirationSetting, YoctoNear};
 use oysterpack_smart_near::near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
@@ -10,7 +11,7 @@ use oysterpack_smart_near::near_sdk::{
 )]
 #[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
 pub struct ContractBid {
-    pub amount: YoctoNear,
+    pub amount: U128,
     pub expiration: Option<Expiration>,
 }
 
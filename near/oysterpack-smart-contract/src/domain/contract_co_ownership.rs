@@ -0,0 +1,139 @@
+use crate::{Proposal, ERR_INVALID_CO_OWNERSHIP_CONFIG};
+use oysterpack_smart_near::data::{numbers::U256, Object};
+use oysterpack_smart_near::domain::ExpirationDuration;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use std::collections::HashSet;
+
+const CO_OWNERSHIP_KEY: u128 = 1085346137309842746017498862593741101;
+
+type DAO = Object<u128, CoOwnership>;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct CoOwner {
+    pub account_id: AccountId,
+    /// the co-owner's share of the owner earnings is its weight relative to the total weight,
+    /// which is also its voting weight on proposals
+    pub weight: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct CoOwnershipConfig {
+    pub co_owners: Vec<CoOwner>,
+    /// total approval weight that is required to execute a proposal - must be a majority of the
+    /// total weight
+    pub quorum: u32,
+    /// default proposal expiration, which is used when the proposer does not specify one
+    pub proposal_expiration: ExpirationDuration,
+}
+
+impl CoOwnershipConfig {
+    /// ## Panics
+    /// - if there are less than 2 co-owners
+    /// - if co-owners contains duplicates
+    /// - if any co-owner weight is zero
+    /// - if quorum is not a majority of the total weight, or exceeds the total weight
+    pub fn validate(&self) {
+        ERR_INVALID_CO_OWNERSHIP_CONFIG.assert_with_message(
+            || self.co_owners.len() >= 2,
+            || "at least 2 co-owners are required",
+        );
+        ERR_INVALID_CO_OWNERSHIP_CONFIG.assert_with_message(
+            || {
+                let co_owners: HashSet<&AccountId> = self
+                    .co_owners
+                    .iter()
+                    .map(|co_owner| &co_owner.account_id)
+                    .collect();
+                co_owners.len() == self.co_owners.len()
+            },
+            || "co-owners must be unique",
+        );
+        ERR_INVALID_CO_OWNERSHIP_CONFIG.assert_with_message(
+            || self.co_owners.iter().all(|co_owner| co_owner.weight > 0),
+            || "co-owner weight must not be zero",
+        );
+        ERR_INVALID_CO_OWNERSHIP_CONFIG.assert_with_message(
+            || self.quorum * 2 > self.total_weight() && self.quorum <= self.total_weight(),
+            || "quorum must be a majority of the total weight and <= total weight",
+        );
+    }
+
+    pub fn total_weight(&self) -> u32 {
+        self.co_owners
+            .iter()
+            .map(|co_owner| co_owner.weight as u32)
+            .sum()
+    }
+
+    pub fn weight_of(&self, account_id: &str) -> Option<u16> {
+        self.co_owners
+            .iter()
+            .find(|co_owner| co_owner.account_id == account_id)
+            .map(|co_owner| co_owner.weight)
+    }
+
+    pub fn is_co_owner(&self, account_id: &str) -> bool {
+        self.weight_of(account_id).is_some()
+    }
+
+    /// sums the weights of the co-owners that approved the proposal
+    pub fn approval_weight(&self, proposal: &Proposal) -> u32 {
+        proposal
+            .approvals
+            .iter()
+            .filter_map(|account_id| self.weight_of(account_id))
+            .map(|weight| weight as u32)
+            .sum()
+    }
+
+    /// Splits the amount across the co-owners pro rata by weight. Shares are rounded down, and the
+    /// remainder goes to the first co-owner. Zero shares are excluded.
+    pub fn pro_rata(&self, amount: u128) -> Vec<(AccountId, u128)> {
+        let total_weight = U256::from(self.total_weight());
+        let mut shares: Vec<(AccountId, u128)> = self
+            .co_owners
+            .iter()
+            .map(|co_owner| {
+                let share = U256::from(amount) * U256::from(co_owner.weight) / total_weight;
+                (co_owner.account_id.clone(), share.as_u128())
+            })
+            .collect();
+        let remainder = amount - shares.iter().map(|(_, share)| share).sum::<u128>();
+        shares[0].1 += remainder;
+        shares.retain(|(_, share)| *share > 0);
+        shares
+    }
+}
+
+/// Co-ownership state, which is stored while the contract is co-owned
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct CoOwnership {
+    pub config: CoOwnershipConfig,
+    pub next_proposal_id: u64,
+    pub proposals: Vec<Proposal>,
+}
+
+impl CoOwnership {
+    pub fn load() -> Option<Self> {
+        DAO::load(&CO_OWNERSHIP_KEY).map(|co_ownership| (*co_ownership).clone())
+    }
+
+    pub fn exists() -> bool {
+        DAO::exists(&CO_OWNERSHIP_KEY)
+    }
+
+    pub(crate) fn save(&self) {
+        DAO::new(CO_OWNERSHIP_KEY, self.clone()).save();
+    }
+
+    /// returns true if the contract was co-owned
+    pub(crate) fn delete() -> bool {
+        DAO::delete_by_key(&CO_OWNERSHIP_KEY)
+    }
+}
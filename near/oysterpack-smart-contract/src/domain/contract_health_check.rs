@@ -0,0 +1,67 @@
+use oysterpack_smart_near::domain::BlockTime;
+use oysterpack_smart_near::near_sdk::serde::{Deserialize, Serialize};
+
+/// Accounting invariant that did not hold when the health check was run
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct HealthCheckViolation {
+    /// name of the invariant that was checked
+    pub check: String,
+    /// describes the values that were cross-checked
+    pub message: String,
+}
+
+/// Reports the accounting invariants that were checked and any violations that were found
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct HealthCheckReport {
+    pub block_time: BlockTime,
+    /// names of the invariants that were checked
+    pub checks: Vec<String>,
+    pub violations: Vec<HealthCheckViolation>,
+}
+
+impl HealthCheckReport {
+    pub fn new() -> Self {
+        Self {
+            block_time: BlockTime::from_env(),
+            checks: vec![],
+            violations: vec![],
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Records the check, and records a violation if the invariant does not hold.
+    /// The message is only generated for violations.
+    ///
+    /// Returns true if the invariant holds.
+    pub fn check<F>(&mut self, check: &str, holds: bool, message: F) -> bool
+    where
+        F: FnOnce() -> String,
+    {
+        self.checks.push(check.to_string());
+        if !holds {
+            self.violations.push(HealthCheckViolation {
+                check: check.to_string(),
+                message: message(),
+            });
+        }
+        holds
+    }
+
+    /// returns true if a violation was recorded for the specified check
+    pub fn violated(&self, check: &str) -> bool {
+        self.violations
+            .iter()
+            .any(|violation| violation.check == check)
+    }
+}
+
+impl Default for HealthCheckReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{ERR_INVALID_MULTISIG_CONFIG, ERR_PROPOSAL_COMMAND_NOT_ALLOWED};
+use oysterpack_smart_account_management::ApprovalRequiredCommands;
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::domain::{Expiration, ExpirationDuration, Gas, TGas, YoctoNear};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId, Promise,
+};
+use std::collections::HashSet;
+
+const PROPOSAL_METHODS_KEY: u128 = 152969049051729958287664185811516281854;
+
+type ProposalMethodsDAO = Object<u128, Vec<String>>;
+
+/// Defines the accounts that are designated to approve proposals and the number of approvals
+/// that are required to execute a proposal, i.e., M-of-N
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct MultiSigConfig {
+    pub approvers: Vec<AccountId>,
+    /// number of approvals required to execute a proposal
+    pub threshold: u8,
+    /// default proposal expiration, which is used when the proposer does not specify one
+    pub proposal_expiration: ExpirationDuration,
+    /// owner, admin, and operator commands that require multisig approval
+    /// - if None, then every owner, admin, and operator command requires multisig approval
+    pub commands: Option<Vec<String>>,
+}
+
+impl MultiSigConfig {
+    /// ## Panics
+    /// - if there are no approvers
+    /// - if approvers contains duplicates
+    /// - if threshold is zero or greater than the number of approvers
+    pub fn validate(&self) {
+        ERR_INVALID_MULTISIG_CONFIG.assert_with_message(
+            || !self.approvers.is_empty(),
+            || "at least 1 approver is required",
+        );
+        ERR_INVALID_MULTISIG_CONFIG.assert_with_message(
+            || {
+                let approvers: HashSet<&AccountId> = self.approvers.iter().collect();
+                approvers.len() == self.approvers.len()
+            },
+            || "approvers must be unique",
+        );
+        ERR_INVALID_MULTISIG_CONFIG.assert_with_message(
+            || self.threshold > 0 && self.threshold as usize <= self.approvers.len(),
+            || "threshold must be > 0 and <= number of approvers",
+        );
+    }
+
+    pub fn approval_required_commands(&self) -> ApprovalRequiredCommands {
+        self.commands.clone().map_or(
+            ApprovalRequiredCommands::All,
+            ApprovalRequiredCommands::Commands,
+        )
+    }
+
+    pub fn is_approver(&self, account_id: &str) -> bool {
+        self.approvers
+            .iter()
+            .any(|approver| approver.as_str() == account_id)
+    }
+}
+
+/// Function call that is executed against the contract itself once the proposal is approved.
+///
+/// Because the contract is the predecessor for the function call, the contract account must be
+/// authorized to invoke the function.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ProposalCommand {
+    pub method_name: String,
+    /// JSON encoded function args
+    pub args: String,
+    pub deposit: YoctoNear,
+    pub gas: TGas,
+}
+
+impl ProposalCommand {
+    /// Registers the owner, admin, and operator methods that proposals are allowed to invoke,
+    /// replacing any previously registered methods. Contracts register the methods when deployed
+    /// and migrated.
+    ///
+    /// ## Panics
+    /// [`ERR_PROPOSAL_COMMAND_NOT_ALLOWED`] - if any of the methods is a callback
+    pub fn register_methods(methods: &[&str]) {
+        for method in methods {
+            ERR_PROPOSAL_COMMAND_NOT_ALLOWED.assert_with_message(
+                || !Self::is_callback(method),
+                || format!("callbacks cannot be registered: {}", method),
+            );
+        }
+        ProposalMethodsDAO::new(
+            PROPOSAL_METHODS_KEY,
+            methods.iter().map(|method| method.to_string()).collect(),
+        )
+        .save();
+    }
+
+    pub fn registered_methods() -> Vec<String> {
+        ProposalMethodsDAO::load(&PROPOSAL_METHODS_KEY)
+            .map_or_else(Vec::new, |methods| (*methods).clone())
+    }
+
+    /// callbacks are invoked by the contract itself, which is also the predecessor for approved
+    /// proposals - thus, proposals must never be allowed to invoke them
+    fn is_callback(method_name: &str) -> bool {
+        method_name.contains("_resolve_")
+            || method_name.ends_with("_finalize")
+            || method_name.ends_with("_callback")
+            || method_name == "ft_on_transfer"
+    }
+
+    /// ## Panics
+    /// [`ERR_PROPOSAL_COMMAND_NOT_ALLOWED`] - if the method is a callback or is not registered
+    pub(crate) fn assert_allowed(&self) {
+        ERR_PROPOSAL_COMMAND_NOT_ALLOWED.assert_with_message(
+            || {
+                !Self::is_callback(&self.method_name)
+                    && Self::registered_methods().contains(&self.method_name)
+            },
+            || self.method_name.as_str(),
+        );
+    }
+
+    /// invokes the command as a function call against the contract itself
+    pub(crate) fn execute(self) -> Promise {
+        Promise::new(env::current_account_id()).function_call(
+            self.method_name.into_bytes(),
+            self.args.into_bytes(),
+            *self.deposit,
+            *Gas::from(self.gas),
+        )
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct Proposal {
+    pub id: U64,
+    pub proposer: AccountId,
+    pub command: ProposalCommand,
+    pub approvals: Vec<AccountId>,
+    pub expiration: Expiration,
+}
+
+impl Proposal {
+    pub fn expired(&self) -> bool {
+        self.expiration.expired()
+    }
+
+    pub fn approved_by(&self, account_id: &str) -> bool {
+        self.approvals
+            .iter()
+            .any(|approver| approver.as_str() == account_id)
+    }
+
+    /// returns true if the number of approvals meets the specified threshold
+    pub fn approved(&self, threshold: u8) -> bool {
+        self.approvals.len() >= threshold as usize
+    }
+}
@@ -3,9 +3,16 @@ use oysterpack_smart_near::near_sdk::{
     env,
     serde::{Deserialize, Serialize},
 };
-use oysterpack_smart_near::{data::Object, domain::YoctoNear};
+use oysterpack_smart_near::{data::Object, domain::YoctoNear, ErrCode, ErrorConst};
 use std::{collections::HashMap, ops::Deref};
 
+/// Indicates that a component attempted to register a [`BalanceId`] that is already registered by
+/// another component
+pub const ERR_BALANCE_ID_ALREADY_REGISTERED: ErrorConst = ErrorConst(
+    ErrCode("BALANCE_ID_ALREADY_REGISTERED"),
+    "balance ID is already registered by another component",
+);
+
 /// Balance ID is used to track separate NEAR balances
 /// - use ULID to generate unique IDs to avoid collisions between components
 #[derive(
@@ -30,6 +37,33 @@ pub struct BalanceId(pub u128);
 /// - profit sharing fund
 pub type NearBalances = HashMap<BalanceId, YoctoNear>;
 
+/// Components register their balance IDs at deployment in order to make the balances
+/// self-describing
+#[derive(BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct BalanceIdLabel {
+    pub name: String,
+    pub description: String,
+    /// component that owns the balance
+    pub component: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct RegisteredBalanceId {
+    pub id: BalanceId,
+    pub label: BalanceIdLabel,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct LabeledNearBalance {
+    pub id: BalanceId,
+    /// None if the balance ID was not registered
+    pub label: Option<BalanceIdLabel>,
+    pub balance: YoctoNear,
+}
+
 /// Provides a breakdown of the contract's NEAR balances
 #[derive(
     BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Debug, PartialEq, Default,
@@ -38,12 +72,14 @@ pub type NearBalances = HashMap<BalanceId, YoctoNear>;
 pub struct ContractNearBalances {
     total: YoctoNear,
     accounts: YoctoNear,
-    balances: Option<NearBalances>,
+    /// sorted by balance ID
+    balances: Option<Vec<LabeledNearBalance>>,
     owner: YoctoNear,
     locked: YoctoNear,
 }
 
 impl ContractNearBalances {
+    /// balances are labeled using the registered balance IDs
     pub fn new(accounts: YoctoNear, balances: Option<NearBalances>) -> Self {
         let locked: YoctoNear = env::account_locked_balance().into();
         let total: YoctoNear = locked + env::account_balance();
@@ -56,8 +92,25 @@ impl ContractNearBalances {
                     .sum::<u128>()
                     .into()
             });
-
-        let owner = total - accounts - total_contract_near_balances;
+        let balances = balances.map(|balances| {
+            let labels = Self::load_balance_id_labels();
+            let mut balances: Vec<LabeledNearBalance> = balances
+                .into_iter()
+                .map(|(id, balance)| LabeledNearBalance {
+                    id,
+                    label: labels.get(&id).cloned(),
+                    balance,
+                })
+                .collect();
+            balances.sort_by_key(|balance| balance.id.0);
+            balances
+        });
+
+        // accounting drift is reported by the health check - see [`crate::ContractHealthCheck`]
+        let owner = total
+            .saturating_sub(accounts.value())
+            .saturating_sub(total_contract_near_balances.value())
+            .into();
 
         Self {
             total,
@@ -81,7 +134,18 @@ impl ContractNearBalances {
     /// - batched funds, e.g., STAKE batches
     /// - profit sharing funds
     pub fn balances(&self) -> Option<NearBalances> {
-        self.balances.as_ref().map(|balances| balances.clone())
+        self.balances.as_ref().map(|balances| {
+            balances
+                .iter()
+                .map(|balance| (balance.id, balance.balance))
+                .collect()
+        })
+    }
+
+    /// [`ContractNearBalances::balances`] labeled with the registered balance IDs, sorted by
+    /// balance ID
+    pub fn labeled_balances(&self) -> Option<&[LabeledNearBalance]> {
+        self.balances.as_deref()
     }
 
     /// returns portion of total contract NEAR balance that is owned by the contract owner, which is
@@ -159,6 +223,53 @@ impl ContractNearBalances {
     }
 }
 
+const BALANCE_ID_LABELS_KEY: u128 = 1959187390142733196474318025237648691;
+
+type LabelsDAO = Object<u128, HashMap<BalanceId, BalanceIdLabel>>;
+
+impl ContractNearBalances {
+    /// Registers the balance ID label, which replaces any label previously registered by the same
+    /// component. Registration is idempotent, which enables components to register their balance
+    /// IDs on deployment as well as on contract migration.
+    ///
+    /// ## Panics
+    /// [`ERR_BALANCE_ID_ALREADY_REGISTERED`] - if the balance ID is registered by another component
+    pub fn register_balance_id(id: BalanceId, label: BalanceIdLabel) {
+        let mut labels = LabelsDAO::load(&BALANCE_ID_LABELS_KEY)
+            .unwrap_or_else(|| LabelsDAO::new(BALANCE_ID_LABELS_KEY, HashMap::new()));
+        if let Some(current_label) = labels.get(&id) {
+            ERR_BALANCE_ID_ALREADY_REGISTERED.assert_with_message(
+                || current_label.component == label.component,
+                || format!("{} is registered by {}", id.0, current_label.component),
+            );
+            if *current_label == label {
+                return;
+            }
+        }
+        labels.insert(id, label);
+        labels.save();
+    }
+
+    pub fn balance_id_label(id: BalanceId) -> Option<BalanceIdLabel> {
+        LabelsDAO::load(&BALANCE_ID_LABELS_KEY).and_then(|labels| labels.get(&id).cloned())
+    }
+
+    /// returns the registered balance IDs sorted by balance ID
+    pub fn registered_balance_ids() -> Vec<RegisteredBalanceId> {
+        let mut balance_ids: Vec<RegisteredBalanceId> = Self::load_balance_id_labels()
+            .into_iter()
+            .map(|(id, label)| RegisteredBalanceId { id, label })
+            .collect();
+        balance_ids.sort_by_key(|balance_id| balance_id.id.0);
+        balance_ids
+    }
+
+    fn load_balance_id_labels() -> HashMap<BalanceId, BalanceIdLabel> {
+        LabelsDAO::load(&BALANCE_ID_LABELS_KEY)
+            .map_or_else(HashMap::new, |labels| labels.deref().clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,4 +346,75 @@ mod tests {
             20 * YOCTO
         );
     }
+
+    #[test]
+    fn balance_id_registry() {
+        // Arrange
+        test_env::setup();
+        let label = BalanceIdLabel {
+            name: "liquidity".to_string(),
+            description: "liquidity pool".to_string(),
+            component: "LiquidityComponent".to_string(),
+        };
+
+        // Act
+        ContractNearBalances::register_balance_id(EARNINGS_BALANCE_ID, label.clone());
+        ContractNearBalances::register_balance_id(LIQUIDITY_BALANCE_ID, label.clone());
+        ContractNearBalances::register_balance_id(LIQUIDITY_BALANCE_ID, label.clone());
+
+        // Assert - sorted by balance ID
+        let balance_ids = ContractNearBalances::registered_balance_ids();
+        assert_eq!(balance_ids.len(), 2);
+        assert_eq!(balance_ids[0].id, LIQUIDITY_BALANCE_ID);
+        assert_eq!(balance_ids[1].id, EARNINGS_BALANCE_ID);
+        assert_eq!(
+            ContractNearBalances::balance_id_label(LIQUIDITY_BALANCE_ID),
+            Some(label)
+        );
+        assert!(ContractNearBalances::balance_id_label(BalanceId(2)).is_none());
+
+        // Act - balances are labeled
+        ContractNearBalances::incr_balance(LIQUIDITY_BALANCE_ID, YOCTO.into());
+        ContractNearBalances::incr_balance(BalanceId(2), YOCTO.into());
+        let near_balances = ContractNearBalances::new(
+            YoctoNear::ZERO,
+            Some(ContractNearBalances::load_near_balances()),
+        );
+
+        // Assert
+        let labeled_balances = near_balances.labeled_balances().unwrap();
+        assert_eq!(labeled_balances.len(), 2);
+        assert_eq!(labeled_balances[0].id, LIQUIDITY_BALANCE_ID);
+        assert_eq!(
+            labeled_balances[0].label.as_ref().unwrap().name,
+            "liquidity"
+        );
+        assert_eq!(labeled_balances[0].balance, YOCTO.into());
+        assert_eq!(labeled_balances[1].id, BalanceId(2));
+        assert!(labeled_balances[1].label.is_none());
+        assert_eq!(
+            near_balances.balances().unwrap(),
+            ContractNearBalances::load_near_balances()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [BALANCE_ID_ALREADY_REGISTERED]")]
+    fn register_balance_id_owned_by_another_component() {
+        test_env::setup();
+        let label = BalanceIdLabel {
+            name: "liquidity".to_string(),
+            description: "liquidity pool".to_string(),
+            component: "LiquidityComponent".to_string(),
+        };
+        ContractNearBalances::register_balance_id(LIQUIDITY_BALANCE_ID, label.clone());
+
+        ContractNearBalances::register_balance_id(
+            LIQUIDITY_BALANCE_ID,
+            BalanceIdLabel {
+                component: "EarningsComponent".to_string(),
+                ..label
+            },
+        );
+    }
 }
@@ -1,8 +1,10 @@
+use crate::components::contract_multisig::ContractMultiSigComponent;
 use crate::{
-    ContractBid, ERR_CONTRACT_OWNER_TRANSFER_NOT_INITIATED,
-    ERR_CURRENT_OR_PROSPECTIVE_OWNER_ACCESS_REQUIRED, ERR_OWNER_ACCESS_REQUIRED,
-    ERR_PROSPECTIVE_OWNER_ACCESS_REQUIRED,
+    CoOwnership, ContractBid, ProposalCommand, ERR_CONTRACT_OWNER_TRANSFER_NOT_INITIATED,
+    ERR_CO_OWNER_QUORUM_REQUIRED, ERR_CURRENT_OR_PROSPECTIVE_OWNER_ACCESS_REQUIRED,
+    ERR_OWNER_ACCESS_REQUIRED, ERR_PROSPECTIVE_OWNER_ACCESS_REQUIRED,
 };
+use oysterpack_smart_account_management::ApprovalRequiredCommands;
 use oysterpack_smart_near::domain::{AccountIdHash, YoctoNear};
 use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
 use oysterpack_smart_near::near_sdk::{
@@ -56,18 +58,48 @@ impl ContractOwnerObject {
     }
 
     /// asserts that the predecessor account ID is the owner
+    ///
+    /// If the contract is co-owned, then the predecessor account must be the contract account,
+    /// i.e., the action was submitted through an approved co-owner proposal - see [`crate::ContractCoOwnership`]
     pub fn assert_owner_access() -> Self {
         let owner = Self::load();
-        ERR_OWNER_ACCESS_REQUIRED.assert(|| {
-            owner.account_id_hash() == AccountIdHash::from(env::predecessor_account_id())
-        });
+        if CoOwnership::exists() {
+            ERR_CO_OWNER_QUORUM_REQUIRED.assert(|| owner.has_owner_access());
+        } else {
+            ERR_OWNER_ACCESS_REQUIRED.assert(|| owner.has_owner_access());
+        }
         owner
     }
 
+    /// asserts owner access for the specified owner command
+    ///
+    /// If the command can be submitted as a proposal and multisig requires approval for it, then
+    /// the command must be submitted through an approved multisig proposal - see [`ApprovalRequiredCommands`]
+    pub fn assert_owner_command_access(command: &str) -> Self {
+        if ProposalCommand::registered_methods()
+            .iter()
+            .any(|method| method == command)
+        {
+            ApprovalRequiredCommands::assert_approved(command);
+        }
+        Self::assert_owner_access()
+    }
+
     /// returns true if the predecessor account is the owner
     pub fn is_owner() -> bool {
-        let owner = Self::load();
-        owner.account_id_hash() == AccountIdHash::from(env::predecessor_account_id().as_str())
+        Self::load().has_owner_access()
+    }
+
+    /// If multisig is enabled, then approved multisig proposals act on behalf of the owner, i.e.,
+    /// the contract account has owner access.
+    fn has_owner_access(&self) -> bool {
+        let approved_call = env::predecessor_account_id() == env::current_account_id();
+        if CoOwnership::exists() {
+            approved_call
+        } else {
+            self.account_id_hash() == AccountIdHash::from(env::predecessor_account_id().as_str())
+                || (approved_call && ContractMultiSigComponent::<()>::is_enabled())
+        }
     }
 
     /// asserts that a contract transfer is in progress and the predecessor account ID is the
@@ -90,7 +122,7 @@ impl ContractOwnerObject {
     pub fn assert_current_or_prospective_owner_access() -> Self {
         let owner = Self::load();
         ERR_CURRENT_OR_PROSPECTIVE_OWNER_ACCESS_REQUIRED.assert(|| {
-            owner.account_id_hash() == AccountIdHash::from(env::predecessor_account_id())
+            owner.has_owner_access()
                 || owner
                     .prospective_owner_account_id_hash()
                     .map_or(false, |account_id_hash| {
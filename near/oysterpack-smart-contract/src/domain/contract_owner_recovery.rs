@@ -0,0 +1,86 @@
+use crate::ERR_INVALID_OWNER_RECOVERY_CONFIG;
+use oysterpack_smart_near::domain::{BlockTime, Expiration, ExpirationDuration};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use std::collections::HashSet;
+
+/// Guardian accounts that are designated by the owner to recover the contract ownership, i.e., if
+/// the owner loses access to the owner account
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct OwnerRecoveryConfig {
+    pub guardians: Vec<AccountId>,
+    /// number of guardian approvals that are required to start the recovery time-lock
+    pub threshold: u8,
+    /// the owner can veto the recovery until the time-lock expires
+    pub delay: ExpirationDuration,
+}
+
+impl OwnerRecoveryConfig {
+    /// ## Panics
+    /// - if there are no guardians
+    /// - if guardians contains duplicates
+    /// - if threshold is zero or greater than the number of guardians
+    pub fn validate(&self, owner_account_id: &str) {
+        ERR_INVALID_OWNER_RECOVERY_CONFIG.assert_with_message(
+            || !self.guardians.is_empty(),
+            || "at least 1 guardian is required",
+        );
+        ERR_INVALID_OWNER_RECOVERY_CONFIG.assert_with_message(
+            || {
+                let guardians: HashSet<&AccountId> = self.guardians.iter().collect();
+                guardians.len() == self.guardians.len()
+            },
+            || "guardians must be unique",
+        );
+        ERR_INVALID_OWNER_RECOVERY_CONFIG.assert_with_message(
+            || !self.is_guardian(owner_account_id),
+            || "owner cannot be a guardian",
+        );
+        ERR_INVALID_OWNER_RECOVERY_CONFIG.assert_with_message(
+            || self.threshold > 0 && self.threshold as usize <= self.guardians.len(),
+            || "threshold must be > 0 and <= number of guardians",
+        );
+    }
+
+    pub fn is_guardian(&self, account_id: &str) -> bool {
+        self.guardians
+            .iter()
+            .any(|guardian| guardian.as_str() == account_id)
+    }
+}
+
+/// Recovery transfer of the contract ownership that was initiated by a guardian
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct OwnerRecovery {
+    pub new_owner: AccountId,
+    /// the first approval is from the guardian that initiated the recovery
+    pub approvals: Vec<AccountId>,
+    pub initiated_on: BlockTime,
+    /// set once the guardian approval threshold is reached - the recovery can be finalized after
+    /// the time-lock expires
+    pub timelock: Option<Expiration>,
+}
+
+impl OwnerRecovery {
+    pub fn initiated_by(&self) -> &str {
+        &self.approvals[0]
+    }
+
+    pub fn approved_by(&self, account_id: &str) -> bool {
+        self.approvals
+            .iter()
+            .any(|guardian| guardian.as_str() == account_id)
+    }
+
+    /// returns true if the time-lock has started and expired
+    pub fn finalizable(&self) -> bool {
+        self.timelock
+            .as_ref()
+            .map_or(false, |timelock| timelock.expired())
+    }
+}
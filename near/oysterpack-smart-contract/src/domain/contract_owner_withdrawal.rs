@@ -0,0 +1,63 @@
+use oysterpack_smart_near::domain::{BlockTime, Expiration, ExpirationDuration, YoctoNear};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+/// When configured, owner withdrawals above the threshold are not transferred immediately. Instead,
+/// they are queued as [`PendingOwnerWithdrawal`]s, which can only be executed after the delay.
+///
+/// The threshold applies to the total amount that is withdrawn immediately within a rolling window
+/// of the delay, i.e., a large withdrawal cannot be split up into withdrawals below the threshold.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct OwnerWithdrawalTimelockConfig {
+    /// withdrawals are transferred immediately as long as the total amount withdrawn within the
+    /// delay window, including the withdrawal, is up to and including the threshold amount
+    pub threshold: YoctoNear,
+    pub delay: ExpirationDuration,
+    /// besides the owner, the guardian account may cancel pending withdrawals
+    pub guardian: Option<AccountId>,
+}
+
+impl OwnerWithdrawalTimelockConfig {
+    pub fn is_guardian(&self, account_id: &str) -> bool {
+        self.guardian
+            .as_ref()
+            .map_or(false, |guardian| guardian.as_str() == account_id)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct PendingOwnerWithdrawal {
+    pub id: U64,
+    /// owner account that requested the withdrawal, which the funds are transferred to
+    pub account_id: AccountId,
+    pub amount: YoctoNear,
+    pub requested_on: BlockTime,
+    /// the withdrawal can be executed after the time-lock expires
+    pub timelock: Expiration,
+}
+
+/// Owner withdrawal that was transferred immediately, which counts towards the time-lock threshold
+/// until it expires
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RecentOwnerWithdrawal {
+    pub amount: YoctoNear,
+    /// the withdrawal drops out of the rolling window after it expires
+    pub expiration: Expiration,
+}
+
+/// Once the time-lock is configured, changing or disabling it is subject to the current delay.
+/// Thus, a compromised owner key cannot be used to disable the time-lock and withdraw immediately.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct OwnerWithdrawalTimelockConfigChange {
+    /// None means the time-lock is disabled
+    pub config: Option<OwnerWithdrawalTimelockConfig>,
+    /// the change takes effect after the time-lock expires
+    pub timelock: Expiration,
+}
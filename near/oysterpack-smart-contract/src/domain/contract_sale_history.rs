@@ -0,0 +1,92 @@
+use oysterpack_smart_near::data::numbers::U128;
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::domain::{BasisPoints, BlockTime};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+const CONTRACT_SALE_RECORD_KEY: u128 = 1726084352906113987064851193217457253;
+const CONTRACT_SALE_COUNT_KEY: u128 = 1299715638208475619360742018534956113;
+const CONTRACT_ROYALTIES_KEY: u128 = 1573960138449180296152748602318846917;
+
+type RecordDAO = Object<(u128, u64), ContractSaleRecord>;
+type CountDAO = Object<u128, u64>;
+type RoyaltiesDAO = Object<u128, Vec<ContractRoyalty>>;
+
+/// Provenance record, which is persisted each time the contract is sold
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ContractSaleRecord {
+    pub seller: AccountId,
+    pub buyer: AccountId,
+    /// denominated in the token if specified, otherwise in NEAR
+    pub price: U128,
+    pub token_id: Option<AccountId>,
+    /// royalties that were paid out of the sale price
+    pub royalties: Vec<(AccountId, U128)>,
+    pub sold_on: BlockTime,
+}
+
+/// Contract sale history in the order the sales were executed
+pub struct ContractSaleRecords;
+
+impl ContractSaleRecords {
+    pub fn count() -> U64 {
+        CountDAO::load(&CONTRACT_SALE_COUNT_KEY)
+            .map_or(0, |count| *count)
+            .into()
+    }
+
+    pub fn get(index: u64) -> Option<ContractSaleRecord> {
+        RecordDAO::load(&(CONTRACT_SALE_RECORD_KEY, index)).map(|record| (*record).clone())
+    }
+
+    pub(crate) fn append(record: ContractSaleRecord) {
+        let count = Self::count().0;
+        RecordDAO::new((CONTRACT_SALE_RECORD_KEY, count), record).save();
+        CountDAO::new(CONTRACT_SALE_COUNT_KEY, count + 1).save();
+    }
+}
+
+/// Royalty recipients receive their cut of the sale price each time the contract is sold.
+///
+/// Royalties are granted by the owner, but can only be reduced by the recipient - thus, royalties
+/// survive changes in ownership.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ContractRoyalty {
+    pub account_id: AccountId,
+    pub basis_points: BasisPoints,
+}
+
+impl ContractRoyalty {
+    /// the total royalty basis points across all recipients cannot exceed 10%
+    pub const MAX_TOTAL_BASIS_POINTS: BasisPoints = BasisPoints(1000);
+
+    pub fn load_all() -> Vec<ContractRoyalty> {
+        RoyaltiesDAO::load(&CONTRACT_ROYALTIES_KEY)
+            .map_or_else(Vec::new, |royalties| (*royalties).clone())
+    }
+
+    pub(crate) fn save_all(royalties: Vec<ContractRoyalty>) {
+        if royalties.is_empty() {
+            RoyaltiesDAO::delete_by_key(&CONTRACT_ROYALTIES_KEY);
+        } else {
+            RoyaltiesDAO::new(CONTRACT_ROYALTIES_KEY, royalties).save();
+        }
+    }
+
+    pub fn total_basis_points(royalties: &[ContractRoyalty]) -> BasisPoints {
+        royalties.iter().fold(BasisPoints::ZERO, |total, royalty| {
+            total + royalty.basis_points
+        })
+    }
+
+    /// royalty amounts are rounded down
+    pub fn amount(&self, price: U128) -> U128 {
+        self.basis_points.of_rounded_down(price.into()).into()
+    }
+}
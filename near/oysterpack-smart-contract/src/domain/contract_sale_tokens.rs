@@ -0,0 +1,116 @@
+use oysterpack_smart_near::data::{numbers::U128, Object};
+use oysterpack_smart_near::domain::AccountIdHash;
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    AccountId,
+};
+use std::collections::BTreeSet;
+
+const CONTRACT_SALE_TOKENS_KEY: u128 = 1810478230961580233069135346014125867;
+
+type DAO = Object<u128, ContractSaleTokens>;
+
+/// NEP-141 tokens that the contract sale can be priced and bid in.
+///
+/// The sale price and bid amounts are stored on the [`crate::ContractOwner`] - this object tracks
+/// which token the amounts are denominated in. If the token is not set, then the amount is in NEAR.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Default)]
+pub struct ContractSaleTokens {
+    whitelist: BTreeSet<AccountId>,
+    price_token: Option<AccountId>,
+    bid_token: Option<AccountId>,
+}
+
+impl ContractSaleTokens {
+    pub fn load() -> Self {
+        DAO::load(&CONTRACT_SALE_TOKENS_KEY).map_or_else(Self::default, |tokens| (*tokens).clone())
+    }
+
+    pub(crate) fn save(&self) {
+        DAO::new(CONTRACT_SALE_TOKENS_KEY, self.clone()).save();
+    }
+
+    pub fn whitelist(&self) -> Vec<AccountId> {
+        self.whitelist.iter().cloned().collect()
+    }
+
+    pub fn is_whitelisted(&self, token_id: &str) -> bool {
+        self.whitelist.contains(token_id)
+    }
+
+    /// None means the sale price is in NEAR
+    pub fn price_token(&self) -> Option<&AccountId> {
+        self.price_token.as_ref()
+    }
+
+    /// None means the bid is in NEAR
+    pub fn bid_token(&self) -> Option<&AccountId> {
+        self.bid_token.as_ref()
+    }
+
+    /// returns true if the token was not already whitelisted
+    pub(crate) fn add_to_whitelist(&mut self, token_id: &str) -> bool {
+        self.whitelist.insert(token_id.to_string())
+    }
+
+    /// returns true if the token was whitelisted
+    pub(crate) fn remove_from_whitelist(&mut self, token_id: &str) -> bool {
+        self.whitelist.remove(token_id)
+    }
+
+    pub(crate) fn set_price_token(&mut self, token_id: Option<AccountId>) {
+        self.price_token = token_id;
+    }
+
+    pub(crate) fn set_bid_token(&mut self, token_id: Option<AccountId>) {
+        self.bid_token = token_id;
+    }
+}
+
+const CONTRACT_SALE_TOKEN_CLAIMS_KEY: u128 = 1203695407934183106420876225186329401;
+
+type ClaimsDAO = Object<(u128, AccountIdHash, AccountId), U128>;
+
+/// Tokens that failed to be transferred out to the account, e.g., bid refunds or sale proceeds.
+/// The account can claim the tokens once it is able to receive them.
+pub struct ContractSaleTokenClaims;
+
+impl ContractSaleTokenClaims {
+    pub fn get(account_id: &str, token_id: &str) -> U128 {
+        ClaimsDAO::load(&Self::key(account_id, token_id)).map_or(0.into(), |amount| *amount)
+    }
+
+    pub(crate) fn credit(account_id: &str, token_id: &str, amount: U128) {
+        let key = Self::key(account_id, token_id);
+        let balance = ClaimsDAO::load(&key).map_or(0.into(), |balance| *balance);
+        ClaimsDAO::new(
+            key,
+            balance
+                .checked_add(amount.value())
+                .expect("token claim overflow")
+                .into(),
+        )
+        .save();
+    }
+
+    /// removes and returns the account's claim
+    pub(crate) fn take(account_id: &str, token_id: &str) -> U128 {
+        let key = Self::key(account_id, token_id);
+        match ClaimsDAO::load(&key) {
+            None => 0.into(),
+            Some(amount) => {
+                let amount = *amount;
+                ClaimsDAO::delete_by_key(&key);
+                amount
+            }
+        }
+    }
+
+    fn key(account_id: &str, token_id: &str) -> (u128, AccountIdHash, AccountId) {
+        (
+            CONTRACT_SALE_TOKEN_CLAIMS_KEY,
+            account_id.into(),
+            token_id.to_string(),
+        )
+    }
+}
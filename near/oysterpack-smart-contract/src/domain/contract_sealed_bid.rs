@@ -0,0 +1,78 @@
+use crate::{BalanceId, ContractNearBalances};
+use oysterpack_smart_near::data::Object;
+use oysterpack_smart_near::domain::{AccountIdHash, YoctoNear};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+};
+use oysterpack_smart_near::Hash;
+
+const SEALED_BID_KEY: u128 = 1498375018936461043791605727741629341;
+const SEALED_BID_AUCTION_ID_KEY: u128 = 1137469806290362745101813519863847215;
+
+type DAO = Object<(u128, AccountIdHash), SealedBid>;
+type AuctionIdDAO = Object<u128, u64>;
+
+/// Buyer's sealed bid for a [`crate::SealedBidAuction`]
+///
+/// The deposit is held in escrow until the auction is settled, after which it can be withdrawn -
+/// except for the winning bid amount, which is paid to the owner.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct SealedBid {
+    pub auction_id: U64,
+    /// see [`SealedBid::commitment`]
+    pub commitment: Hash,
+    /// must cover the bid amount in order for the revealed bid to be valid
+    pub deposit: YoctoNear,
+    pub revealed: Option<YoctoNear>,
+}
+
+impl SealedBid {
+    /// Used to track the total sealed bid deposits on the contract NEAR balance
+    pub const DEPOSITS_BALANCE_ID: BalanceId = BalanceId(254);
+
+    /// `sha256(account_id + "/" + salt + amount)`, where the amount is encoded as 16 big-endian bytes
+    ///
+    /// The account ID is part of the commitment, which prevents other bidders from copying the
+    /// commitment.
+    pub fn commitment(account_id: &str, amount: YoctoNear, salt: &str) -> Hash {
+        Hash::from((
+            format!("{}/{}", account_id, salt).as_bytes(),
+            amount.value(),
+        ))
+    }
+
+    pub fn load(account_id: &str) -> Option<Self> {
+        DAO::load(&(SEALED_BID_KEY, account_id.into())).map(|bid| (*bid).clone())
+    }
+
+    pub(crate) fn save(&self, account_id: &str) {
+        DAO::new((SEALED_BID_KEY, account_id.into()), self.clone()).save();
+    }
+
+    pub(crate) fn delete(account_id: &str) {
+        DAO::delete_by_key(&(SEALED_BID_KEY, account_id.into()));
+    }
+
+    pub fn deposits_near_balance() -> YoctoNear {
+        ContractNearBalances::near_balance(Self::DEPOSITS_BALANCE_ID)
+    }
+
+    pub(crate) fn incr_deposits_near_balance(amount: YoctoNear) {
+        ContractNearBalances::incr_balance(Self::DEPOSITS_BALANCE_ID, amount);
+    }
+
+    pub(crate) fn decr_deposits_near_balance(amount: YoctoNear) {
+        ContractNearBalances::decr_balance(Self::DEPOSITS_BALANCE_ID, amount);
+    }
+
+    /// each sealed bid auction is assigned a unique ID, which is used to tell apart deposits that
+    /// were made for prior auctions
+    pub(crate) fn next_auction_id() -> U64 {
+        let id = AuctionIdDAO::load(&SEALED_BID_AUCTION_ID_KEY).map_or(1, |id| *id + 1);
+        AuctionIdDAO::new(SEALED_BID_AUCTION_ID_KEY, id).save();
+        id.into()
+    }
+}
@@ -1,6 +1,14 @@
+pub use contract::contract_co_ownership::*;
+pub use contract::contract_health_check::*;
 pub use contract::contract_metrics::*;
+pub use contract::contract_multisig::*;
 pub use contract::contract_operator::*;
+pub use contract::contract_owner_recovery::*;
+pub use contract::contract_owner_withdrawals::*;
 pub use contract::contract_ownership::*;
 pub use contract::contract_sale::*;
+pub use contract::contract_sale_auction::*;
+pub use contract::contract_sale_history::*;
+pub use contract::contract_upgrade::*;
 
 pub mod contract;
@@ -1,5 +1,13 @@
 //! Contract Interfaces
+pub mod contract_co_ownership;
+pub mod contract_health_check;
 pub mod contract_metrics;
+pub mod contract_multisig;
 pub mod contract_operator;
+pub mod contract_owner_recovery;
+pub mod contract_owner_withdrawals;
 pub mod contract_ownership;
 pub mod contract_sale;
+pub mod contract_sale_auction;
+pub mod contract_sale_history;
+pub mod contract_upgrade;
@@ -0,0 +1,131 @@
+use crate::{CoOwnershipConfig, Proposal, ProposalCommand};
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::json_types::U64;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Co-Ownership API
+/// Enables the contract to be jointly owned by multiple weighted co-owners.
+///
+/// While the contract is co-owned:
+/// - owner-only actions require a quorum: co-owners submit them as proposals, which are executed
+///   as function calls against the contract itself once the approval weight reaches the quorum.
+///   The contract account is then the predecessor account, which is granted owner access.
+/// - owner earnings and withdrawals are distributed to the co-owners pro rata by weight - see
+///   [`crate::components::contract_co_ownership::ContractCoOwnershipComponent::owner_payees`]
+/// - the owner account of record is kept, and is the account that co-ownership reverts to when
+///   co-ownership is dissolved
+///
+/// Co-ownership ends when the contract is transferred or sold to a new owner.
+///
+/// Co-ownership and [`crate::ContractMultiSig`] are mutually exclusive because both execute
+/// approved commands with the contract account as the predecessor.
+pub trait ContractCoOwnership {
+    /// Returns None if the contract is not co-owned
+    fn ops_co_ownership(&self) -> Option<CoOwnershipConfig>;
+
+    /// Configures the co-owners, weights, and quorum - None dissolves co-ownership.
+    ///
+    /// - co-ownership is initially configured by the contract owner
+    /// - once configured, the config can only be changed through an approved proposal
+    /// - any pending proposals are cancelled when the config is changed
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CO_OWNERSHIP_CONFIG_CHANGE`]
+    ///
+    /// ## Panics
+    /// - if the contract is not co-owned and the predecessor account is not the owner account
+    /// - [`ERR_CO_OWNER_QUORUM_REQUIRED`] - if the contract is co-owned and the call is not from an
+    ///   approved proposal
+    /// - [`ERR_INVALID_CO_OWNERSHIP_CONFIG`] - if the config is invalid, or if multisig is configured
+    fn ops_co_ownership_configure(&mut self, config: Option<CoOwnershipConfig>);
+
+    /// Submits a proposal, which is automatically approved by the proposer.
+    ///
+    /// If the quorum is reached, then the proposal command is executed.
+    /// If expiration is not specified, then the configured proposal expiration is applied.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CO_OWNER_PROPOSAL_CREATED`]
+    /// - [`LOG_EVENT_CO_OWNER_PROPOSAL_EXECUTED`] - if the quorum is reached
+    ///
+    /// ## Panics
+    /// - [`ERR_CO_OWNERSHIP_NOT_CONFIGURED`]
+    /// - [`ERR_CO_OWNER_ACCESS_REQUIRED`]
+    /// - if the expiration is already expired
+    /// - [`crate::ERR_PROPOSAL_COMMAND_NOT_ALLOWED`] - if the command method is a callback or is not
+    ///   registered - see [`crate::ProposalCommand::register_methods`]
+    fn ops_co_owner_propose(
+        &mut self,
+        command: ProposalCommand,
+        expiration: Option<ExpirationSetting>,
+    ) -> Proposal;
+
+    /// Approves the proposal.
+    ///
+    /// If the quorum is reached, then the proposal command is executed and the proposal is removed.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CO_OWNER_PROPOSAL_APPROVED`]
+    /// - [`LOG_EVENT_CO_OWNER_PROPOSAL_EXECUTED`] - if the quorum is reached
+    ///
+    /// ## Panics
+    /// - [`ERR_CO_OWNERSHIP_NOT_CONFIGURED`]
+    /// - [`ERR_CO_OWNER_ACCESS_REQUIRED`]
+    /// - [`crate::ERR_PROPOSAL_NOT_FOUND`]
+    /// - [`crate::ERR_PROPOSAL_EXPIRED`]
+    /// - [`crate::ERR_PROPOSAL_ALREADY_APPROVED`]
+    fn ops_co_owner_approve(&mut self, proposal_id: U64) -> Proposal;
+
+    /// Cancels the proposal.
+    /// - the proposer may cancel the proposal at any time
+    /// - any co-owner may cancel an expired proposal
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CO_OWNER_PROPOSAL_CANCELLED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_CO_OWNERSHIP_NOT_CONFIGURED`]
+    /// - [`crate::ERR_PROPOSAL_NOT_FOUND`]
+    /// - [`ERR_CO_OWNER_ACCESS_REQUIRED`] - if the predecessor account is not authorized to cancel
+    ///   the proposal
+    fn ops_co_owner_cancel(&mut self, proposal_id: U64);
+
+    /// returns pending proposals, including expired proposals that have not yet been cancelled
+    fn ops_co_owner_proposals(&self) -> Vec<Proposal>;
+}
+
+pub const LOG_EVENT_CO_OWNERSHIP_CONFIG_CHANGE: LogEvent =
+    LogEvent(Level::INFO, "CO_OWNERSHIP_CONFIG_CHANGE");
+
+pub const LOG_EVENT_CO_OWNER_PROPOSAL_CREATED: LogEvent =
+    LogEvent(Level::INFO, "CO_OWNER_PROPOSAL_CREATED");
+
+pub const LOG_EVENT_CO_OWNER_PROPOSAL_APPROVED: LogEvent =
+    LogEvent(Level::INFO, "CO_OWNER_PROPOSAL_APPROVED");
+
+pub const LOG_EVENT_CO_OWNER_PROPOSAL_EXECUTED: LogEvent =
+    LogEvent(Level::INFO, "CO_OWNER_PROPOSAL_EXECUTED");
+
+pub const LOG_EVENT_CO_OWNER_PROPOSAL_CANCELLED: LogEvent =
+    LogEvent(Level::INFO, "CO_OWNER_PROPOSAL_CANCELLED");
+
+pub const ERR_INVALID_CO_OWNERSHIP_CONFIG: ErrorConst = ErrorConst(
+    ErrCode("INVALID_CO_OWNERSHIP_CONFIG"),
+    "co-ownership config is invalid",
+);
+
+pub const ERR_CO_OWNERSHIP_NOT_CONFIGURED: ErrorConst = ErrorConst(
+    ErrCode("CO_OWNERSHIP_NOT_CONFIGURED"),
+    "contract is not co-owned",
+);
+
+pub const ERR_CO_OWNER_ACCESS_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("CO_OWNER_ACCESS_REQUIRED"),
+    "action is restricted to co-owners",
+);
+
+/// Indicates the owner action must be submitted as a co-owner proposal
+pub const ERR_CO_OWNER_QUORUM_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("CO_OWNER_QUORUM_REQUIRED"),
+    "owner action requires co-owner quorum approval",
+);
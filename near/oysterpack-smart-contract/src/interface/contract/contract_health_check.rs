@@ -0,0 +1,43 @@
+use crate::HealthCheckReport;
+
+/// # **Contract Interface**: Contract Health Check API
+///
+/// Cross-checks the contract's NEAR balance accounting against the actual account balances.
+/// [`crate::ContractNearBalances`] derives the owner balance as `total - accounts - balances`,
+/// which means accounting drift would otherwise be silently absorbed by the owner balance.
+///
+/// Contract level checks:
+/// - [`HEALTH_CHECK_CONTRACT_NEAR_BALANCES`]
+/// - [`HEALTH_CHECK_ACCOUNT_NEAR_BALANCES`]
+/// - [`HEALTH_CHECK_STORAGE_USAGE`]
+/// - [`HEALTH_CHECK_STORAGE_STAKING`]
+/// - [`HEALTH_CHECK_OWNER_BALANCE`]
+/// - [`HEALTH_CHECK_CONTRACT_BID_BALANCE`]
+///
+/// Contracts are expected to extend the report with component specific checks.
+pub trait ContractHealthCheck {
+    /// Returns a structured report of any accounting invariant violations.
+    ///
+    /// This is a view function that never panics because of a violation.
+    fn ops_health_check(&self) -> HealthCheckReport;
+}
+
+/// `account_balance + account_locked_balance` covers the registered account NEAR balances plus all
+/// [`crate::BalanceId`] balances
+pub const HEALTH_CHECK_CONTRACT_NEAR_BALANCES: &str = "contract_near_balances";
+
+/// registered account NEAR balances, which are storage deposits, are covered by the liquid
+/// `account_balance`
+pub const HEALTH_CHECK_ACCOUNT_NEAR_BALANCES: &str = "account_near_balances";
+
+/// account storage usage tracked by account metrics does not exceed the contract storage usage
+pub const HEALTH_CHECK_STORAGE_USAGE: &str = "storage_usage";
+
+/// liquid `account_balance` covers the contract storage staking cost
+pub const HEALTH_CHECK_STORAGE_STAKING: &str = "storage_staking";
+
+/// owner balance covers the pending owner withdrawals
+pub const HEALTH_CHECK_OWNER_BALANCE: &str = "owner_balance";
+
+/// the contract bid NEAR balance matches the current NEAR bid
+pub const HEALTH_CHECK_CONTRACT_BID_BALANCE: &str = "contract_bid_balance";
@@ -1,11 +1,14 @@
-use crate::{ContractNearBalances, ContractStorageUsage, ContractStorageUsageCosts};
-use oysterpack_smart_account_management::AccountMetrics;
+use crate::{
+    ContractNearBalances, ContractStorageUsage, ContractStorageUsageCosts, RegisteredBalanceId,
+};
+use oysterpack_smart_account_management::{AccountMetrics, ComponentStorageUsage};
 use oysterpack_smart_near::data::numbers::U128;
 use oysterpack_smart_near::domain::BlockTime;
 use oysterpack_smart_near::near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     serde::{Deserialize, Serialize},
 };
+use std::collections::BTreeMap;
 
 /// # **Contract Interface**: Contract Metrics API
 /// Provides metrics that track storage usage and NEAR balances
@@ -21,6 +24,9 @@ pub trait ContractMetrics {
     fn ops_metrics(&self) -> ContractMetricsSnapshot;
 
     fn ops_metrics_accounts(&self) -> AccountMetrics;
+
+    /// Returns the balance IDs that were registered by the contract components, sorted by balance ID
+    fn ops_metrics_balance_ids(&self) -> Vec<RegisteredBalanceId>;
 }
 
 /// Provides a point in time metrics snapshot
@@ -32,4 +38,6 @@ pub struct ContractMetricsSnapshot {
     pub storage_usage: ContractStorageUsage,
     pub near_balances: ContractNearBalances,
     pub storage_usage_costs: ContractStorageUsageCosts,
+    /// account storage usage broken down by component
+    pub storage_usage_by_component: BTreeMap<String, ComponentStorageUsage>,
 }
@@ -0,0 +1,150 @@
+use crate::{MultiSigConfig, Proposal, ProposalCommand};
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::json_types::U64;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Multi-Signature API
+/// Enables sensitive commands to be submitted as proposals that require M-of-N approvals from
+/// designated approver accounts.
+///
+/// Once a proposal reaches the approval threshold, its command is executed as a function call
+/// against the contract itself. Thus, the contract account becomes the predecessor account for the
+/// command, which is how approved commands plug into the existing permission checks:
+/// - the contract account is granted admin permission when multisig is configured
+/// - the contract account has owner access while multisig is configured, and the owner commands
+///   that are configured to require multisig approval are rejected unless the contract account is
+///   the predecessor - see [`crate::ContractOwnerObject::assert_owner_command_access`]
+/// - the admin and operator commands that are configured to require multisig approval are rejected
+///   by the shared permission checks unless the contract account is the predecessor - see
+///   [`oysterpack_smart_account_management::ApprovalRequiredCommands`]
+/// - other commands that require multisig approval assert that the predecessor is the contract
+///   account - see [`crate::components::contract_multisig::ContractMultiSigComponent::assert_approved_if_enabled`]
+pub trait ContractMultiSig {
+    /// Returns None if multisig is not configured
+    fn ops_multisig_config(&self) -> Option<MultiSigConfig>;
+
+    /// Configures the approvers and approval threshold.
+    ///
+    /// - multisig is initially configured by the contract owner
+    /// - once configured, the config can only be changed through an approved proposal
+    /// - any pending proposals are cancelled when the config is changed
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_MULTISIG_CONFIG_CHANGE`]
+    ///
+    /// ## Panics
+    /// - if multisig is not configured and the predecessor account is not the owner account
+    /// - if multisig is configured and the call is not from an approved proposal
+    /// - if the config is invalid - see [`ERR_INVALID_MULTISIG_CONFIG`]
+    fn ops_multisig_configure(&mut self, config: MultiSigConfig);
+
+    /// Submits a proposal, which is automatically approved by the proposer.
+    ///
+    /// If the approval threshold is reached, then the proposal command is executed.
+    /// If expiration is not specified, then the configured proposal expiration is applied.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_MULTISIG_PROPOSAL_CREATED`]
+    /// - [`LOG_EVENT_MULTISIG_PROPOSAL_EXECUTED`] - if the threshold is reached
+    ///
+    /// ## Panics
+    /// - if multisig is not configured
+    /// - if the predecessor account is not an approver
+    /// - if the expiration is already expired
+    /// - [`ERR_PROPOSAL_COMMAND_NOT_ALLOWED`] - if the command method is a callback or is not
+    ///   registered - see [`crate::ProposalCommand::register_methods`]
+    fn ops_multisig_propose(
+        &mut self,
+        command: ProposalCommand,
+        expiration: Option<ExpirationSetting>,
+    ) -> Proposal;
+
+    /// Approves the proposal.
+    ///
+    /// If the approval threshold is reached, then the proposal command is executed and the proposal
+    /// is removed.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_MULTISIG_PROPOSAL_APPROVED`]
+    /// - [`LOG_EVENT_MULTISIG_PROPOSAL_EXECUTED`] - if the threshold is reached
+    ///
+    /// ## Panics
+    /// - if multisig is not configured
+    /// - if the predecessor account is not an approver
+    /// - if the proposal does not exist
+    /// - if the proposal is expired
+    /// - if the predecessor account has already approved the proposal
+    fn ops_multisig_approve(&mut self, proposal_id: U64) -> Proposal;
+
+    /// Cancels the proposal.
+    /// - the proposer may cancel the proposal at any time
+    /// - any approver may cancel an expired proposal
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_MULTISIG_PROPOSAL_CANCELLED`]
+    ///
+    /// ## Panics
+    /// - if multisig is not configured
+    /// - if the proposal does not exist
+    /// - if the predecessor account is not authorized to cancel the proposal
+    fn ops_multisig_cancel(&mut self, proposal_id: U64);
+
+    fn ops_multisig_proposal(&self, proposal_id: U64) -> Option<Proposal>;
+
+    /// returns pending proposals, including expired proposals that have not yet been cancelled
+    fn ops_multisig_proposals(&self) -> Vec<Proposal>;
+}
+
+pub const LOG_EVENT_MULTISIG_CONFIG_CHANGE: LogEvent =
+    LogEvent(Level::INFO, "MULTISIG_CONFIG_CHANGE");
+
+pub const LOG_EVENT_MULTISIG_PROPOSAL_CREATED: LogEvent =
+    LogEvent(Level::INFO, "MULTISIG_PROPOSAL_CREATED");
+
+pub const LOG_EVENT_MULTISIG_PROPOSAL_APPROVED: LogEvent =
+    LogEvent(Level::INFO, "MULTISIG_PROPOSAL_APPROVED");
+
+pub const LOG_EVENT_MULTISIG_PROPOSAL_EXECUTED: LogEvent =
+    LogEvent(Level::INFO, "MULTISIG_PROPOSAL_EXECUTED");
+
+pub const LOG_EVENT_MULTISIG_PROPOSAL_CANCELLED: LogEvent =
+    LogEvent(Level::INFO, "MULTISIG_PROPOSAL_CANCELLED");
+
+pub const ERR_INVALID_MULTISIG_CONFIG: ErrorConst = ErrorConst(
+    ErrCode("INVALID_MULTISIG_CONFIG"),
+    "multisig config is invalid",
+);
+
+pub const ERR_MULTISIG_NOT_CONFIGURED: ErrorConst = ErrorConst(
+    ErrCode("MULTISIG_NOT_CONFIGURED"),
+    "multisig is not configured",
+);
+
+/// Indicates the command must be submitted as a multisig proposal
+pub const ERR_MULTISIG_APPROVAL_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("MULTISIG_APPROVAL_REQUIRED"),
+    "command requires multisig approval",
+);
+
+pub const ERR_MULTISIG_APPROVER_ACCESS_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("MULTISIG_APPROVER_ACCESS_REQUIRED"),
+    "action is restricted to multisig approvers",
+);
+
+pub const ERR_PROPOSAL_NOT_FOUND: ErrorConst =
+    ErrorConst(ErrCode("PROPOSAL_NOT_FOUND"), "proposal does not exist");
+
+pub const ERR_PROPOSAL_EXPIRED: ErrorConst =
+    ErrorConst(ErrCode("PROPOSAL_EXPIRED"), "proposal is expired");
+
+/// Proposals can only invoke the owner, admin, and operator methods registered by the contract - see
+/// [`crate::ProposalCommand::register_methods`]
+pub const ERR_PROPOSAL_COMMAND_NOT_ALLOWED: ErrorConst = ErrorConst(
+    ErrCode("PROPOSAL_COMMAND_NOT_ALLOWED"),
+    "proposal command method is not allowed",
+);
+
+pub const ERR_PROPOSAL_ALREADY_APPROVED: ErrorConst = ErrorConst(
+    ErrCode("PROPOSAL_ALREADY_APPROVED"),
+    "proposal has already been approved by the account",
+);
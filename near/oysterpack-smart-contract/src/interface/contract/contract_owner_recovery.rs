@@ -0,0 +1,171 @@
+use crate::{OwnerRecovery, OwnerRecoveryConfig};
+use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Owner Recovery API
+///
+/// Enables contract ownership to be recovered if the owner loses access to the owner account.
+/// The owner designates guardian accounts and an approval threshold. Recovery workflow:
+/// 1. a guardian initiates the recovery to a new owner account
+/// 2. once the guardian approvals reach the threshold, the recovery time-lock starts
+/// 3. the current owner can veto the recovery at any time before it is finalized
+/// 4. after the time-lock expires, the new owner finalizes the recovery, which transfers contract
+///    ownership to the new owner
+///
+/// Guardians are designated by the owner. Thus, the recovery config is cleared whenever contract
+/// ownership changes hands.
+pub trait ContractOwnerRecovery {
+    /// Returns None if owner recovery is not configured
+    fn ops_owner_recovery_config(&self) -> Option<OwnerRecoveryConfig>;
+
+    /// Configures the recovery guardians - None disables owner recovery.
+    ///
+    /// Any recovery in progress is cancelled.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_RECOVERY_CONFIG_CHANGE`]
+    /// - [`LOG_EVENT_OWNER_RECOVERY_CANCELLED`] - if a recovery was in progress
+    ///
+    /// ## Panics
+    /// - `ERR_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the owner account
+    /// - [`ERR_INVALID_OWNER_RECOVERY_CONFIG`]
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_recovery_configure(&mut self, config: Option<OwnerRecoveryConfig>);
+
+    /// Returns the owner recovery that is in progress
+    fn ops_owner_recovery(&self) -> Option<OwnerRecovery>;
+
+    /// Initiates the recovery of the contract ownership to the new owner account, which counts as
+    /// the initiating guardian's approval.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_RECOVERY_INITIATED`]
+    /// - [`LOG_EVENT_OWNER_RECOVERY_APPROVED`] - if the threshold is reached
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_RECOVERY_NOT_CONFIGURED`]
+    /// - [`ERR_GUARDIAN_ACCESS_REQUIRED`]
+    /// - [`ERR_OWNER_RECOVERY_IN_PROGRESS`]
+    /// - if the new owner is the current owner
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_recovery_initiate(&mut self, new_owner: ValidAccountId) -> OwnerRecovery;
+
+    /// Approves the recovery that is in progress. When the threshold is reached, the time-lock is
+    /// started.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_RECOVERY_APPROVED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_RECOVERY_NOT_FOUND`]
+    /// - [`ERR_GUARDIAN_ACCESS_REQUIRED`]
+    /// - if the guardian already approved the recovery
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_recovery_approve(&mut self) -> OwnerRecovery;
+
+    /// Enables the guardian that initiated the recovery to cancel it, e.g., if the threshold cannot
+    /// be reached.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_RECOVERY_CANCELLED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_RECOVERY_NOT_FOUND`]
+    /// - [`ERR_GUARDIAN_ACCESS_REQUIRED`] - if the predecessor account is not the initiating guardian
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_recovery_cancel(&mut self);
+
+    /// Used by the current owner to veto the recovery. Returns false if no recovery is in progress.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_RECOVERY_VETOED`]
+    ///
+    /// ## Panics
+    /// - `ERR_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the owner account
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_recovery_veto(&mut self) -> bool;
+
+    /// Used by the new owner to finalize the recovery after the time-lock expires.
+    ///
+    /// ## NOTES
+    /// - any contract ownership transfer in progress is cancelled
+    /// - any open contract sale, bid, and auction are cancelled - an auction that has bids or
+    ///   sealed bid commitments must be settled first, which keeps the sealed bid deposits
+    ///   withdrawable
+    /// - the owner withdrawal time-lock config and guardian, which may have been set with the lost
+    ///   owner key, are cleared and pending owner withdrawals are cancelled
+    /// - co-ownership is dissolved
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_RECOVERY_FINALIZED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_RECOVERY_NOT_FOUND`]
+    /// - `ERR_PROSPECTIVE_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the new owner
+    /// - [`ERR_OWNER_RECOVERY_TIMELOCKED`] - if the threshold has not been reached, or the
+    ///   time-lock has not yet expired
+    /// - `ERR_CONTRACT_AUCTION_IN_PROGRESS` - if the contract auction has bids or sealed bid
+    ///   commitments
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_recovery_finalize(&mut self);
+}
+
+pub const LOG_EVENT_OWNER_RECOVERY_CONFIG_CHANGE: LogEvent =
+    LogEvent(Level::INFO, "OWNER_RECOVERY_CONFIG_CHANGE");
+
+pub const LOG_EVENT_OWNER_RECOVERY_INITIATED: LogEvent =
+    LogEvent(Level::WARN, "OWNER_RECOVERY_INITIATED");
+
+pub const LOG_EVENT_OWNER_RECOVERY_APPROVED: LogEvent =
+    LogEvent(Level::WARN, "OWNER_RECOVERY_APPROVED");
+
+pub const LOG_EVENT_OWNER_RECOVERY_CANCELLED: LogEvent =
+    LogEvent(Level::INFO, "OWNER_RECOVERY_CANCELLED");
+
+pub const LOG_EVENT_OWNER_RECOVERY_VETOED: LogEvent =
+    LogEvent(Level::INFO, "OWNER_RECOVERY_VETOED");
+
+pub const LOG_EVENT_OWNER_RECOVERY_FINALIZED: LogEvent =
+    LogEvent(Level::WARN, "OWNER_RECOVERY_FINALIZED");
+
+pub const ERR_INVALID_OWNER_RECOVERY_CONFIG: ErrorConst = ErrorConst(
+    ErrCode("INVALID_OWNER_RECOVERY_CONFIG"),
+    "owner recovery config is invalid",
+);
+
+pub const ERR_OWNER_RECOVERY_NOT_CONFIGURED: ErrorConst = ErrorConst(
+    ErrCode("OWNER_RECOVERY_NOT_CONFIGURED"),
+    "owner recovery is not configured",
+);
+
+pub const ERR_GUARDIAN_ACCESS_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("GUARDIAN_ACCESS_REQUIRED"),
+    "action is restricted to owner recovery guardians",
+);
+
+pub const ERR_OWNER_RECOVERY_IN_PROGRESS: ErrorConst = ErrorConst(
+    ErrCode("OWNER_RECOVERY_IN_PROGRESS"),
+    "owner recovery is already in progress",
+);
+
+pub const ERR_OWNER_RECOVERY_NOT_FOUND: ErrorConst = ErrorConst(
+    ErrCode("OWNER_RECOVERY_NOT_FOUND"),
+    "owner recovery is not in progress",
+);
+
+pub const ERR_OWNER_RECOVERY_TIMELOCKED: ErrorConst = ErrorConst(
+    ErrCode("OWNER_RECOVERY_TIMELOCKED"),
+    "owner recovery time-lock has not expired",
+);
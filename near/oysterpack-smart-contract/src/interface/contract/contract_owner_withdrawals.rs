@@ -0,0 +1,114 @@
+use crate::{
+    OwnerWithdrawalTimelockConfig, OwnerWithdrawalTimelockConfigChange, PendingOwnerWithdrawal,
+};
+use oysterpack_smart_near::near_sdk::json_types::U64;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Owner Withdrawal Time-Lock API
+///
+/// Protects the owner balance from a compromised owner key. When the time-lock is configured,
+/// [`crate::ContractOwnership::ops_owner_withdraw_balance`] requests that would take the total amount
+/// withdrawn within the rolling delay window above the threshold are queued as pending withdrawals.
+/// Pending withdrawals:
+/// - are reserved from the owner's available balance - see [`crate::ContractOwnerNearBalance`]
+/// - can be cancelled by the current owner or the guardian account before they are executed
+/// - can be executed by any account after the time-lock expires - the funds are always transferred
+///   to the owner account that requested the withdrawal
+///
+/// The time-lock config and guardian are designated by the owner. Thus, the time-lock config and
+/// pending withdrawals are cleared whenever contract ownership changes hands. When the contract is
+/// sold, the seller's pending withdrawals are paid out to the seller with the owner's NEAR funds.
+pub trait ContractOwnerWithdrawals {
+    /// Returns None if owner withdrawals are not time-locked
+    fn ops_owner_withdrawal_timelock(&self) -> Option<OwnerWithdrawalTimelockConfig>;
+
+    /// Returns the scheduled time-lock config change, if any
+    fn ops_owner_withdrawal_timelock_change(&self) -> Option<OwnerWithdrawalTimelockConfigChange>;
+
+    /// Configures the owner withdrawal time-lock - None disables the time-lock.
+    ///
+    /// - if the time-lock is not currently configured, then the config is applied immediately
+    /// - otherwise, the change is scheduled to take effect after the current time-lock delay, and
+    ///   any previously scheduled change is replaced
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE`]
+    ///
+    /// ## Panics
+    /// - `ERR_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the owner account
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_withdrawal_timelock_configure(
+        &mut self,
+        config: Option<OwnerWithdrawalTimelockConfig>,
+    ) -> Option<OwnerWithdrawalTimelockConfigChange>;
+
+    /// Cancels the scheduled time-lock config change.
+    ///
+    /// Returns false if no change was scheduled.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE`]
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_OR_GUARDIAN_ACCESS_REQUIRED`]
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_withdrawal_timelock_cancel_change(&mut self) -> bool;
+
+    fn ops_owner_pending_withdrawals(&self) -> Vec<PendingOwnerWithdrawal>;
+
+    /// Transfers the pending withdrawal to the owner account that requested it.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_WITHDRAWAL_EXECUTED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_WITHDRAWAL_NOT_FOUND`]
+    /// - [`ERR_OWNER_WITHDRAWAL_TIMELOCKED`] - if the time-lock has not yet expired
+    /// - `ERR_OWNER_BALANCE_OVERDRAW` - if the owner balance no longer covers the withdrawal
+    fn ops_owner_withdrawal_execute(&mut self, id: U64);
+
+    /// Cancels the pending withdrawal, which releases the funds back to the owner's available balance.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_OWNER_WITHDRAWAL_CANCELLED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_OWNER_WITHDRAWAL_NOT_FOUND`]
+    /// - [`ERR_OWNER_OR_GUARDIAN_ACCESS_REQUIRED`] - if the predecessor account is neither the
+    ///   current owner nor the guardian
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_owner_withdrawal_cancel(&mut self, id: U64);
+}
+
+pub const LOG_EVENT_OWNER_WITHDRAWAL_TIMELOCK_CHANGE: LogEvent =
+    LogEvent(Level::INFO, "OWNER_WITHDRAWAL_TIMELOCK_CHANGE");
+
+pub const LOG_EVENT_OWNER_WITHDRAWAL_PENDING: LogEvent =
+    LogEvent(Level::INFO, "OWNER_WITHDRAWAL_PENDING");
+
+pub const LOG_EVENT_OWNER_WITHDRAWAL_EXECUTED: LogEvent =
+    LogEvent(Level::INFO, "OWNER_WITHDRAWAL_EXECUTED");
+
+pub const LOG_EVENT_OWNER_WITHDRAWAL_CANCELLED: LogEvent =
+    LogEvent(Level::INFO, "OWNER_WITHDRAWAL_CANCELLED");
+
+pub const ERR_OWNER_WITHDRAWAL_NOT_FOUND: ErrorConst = ErrorConst(
+    ErrCode("OWNER_WITHDRAWAL_NOT_FOUND"),
+    "pending owner withdrawal does not exist",
+);
+
+pub const ERR_OWNER_WITHDRAWAL_TIMELOCKED: ErrorConst = ErrorConst(
+    ErrCode("OWNER_WITHDRAWAL_TIMELOCKED"),
+    "owner withdrawal time-lock has not expired",
+);
+
+pub const ERR_OWNER_OR_GUARDIAN_ACCESS_REQUIRED: ErrorConst = ErrorConst(
+    ErrCode("OWNER_OR_GUARDIAN_ACCESS_REQUIRED"),
+    "action requires owner or guardian access",
+);
@@ -33,6 +33,7 @@ pub trait ContractOwnership {
     /// ## NOTES
     /// - any open contract sale is cancelled
     /// - any active bid is cancelled
+    /// - any contract auction is cancelled, but only if no bids have been placed on it
     ///
     /// ## Log Event
     /// [`LOG_EVENT_CONTRACT_TRANSFER_INITIATED`]
@@ -40,6 +41,7 @@ pub trait ContractOwnership {
     /// ## Panics
     /// - `ERR_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the owner account
     /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    /// - `ERR_CONTRACT_AUCTION_IN_PROGRESS` - if bids have been placed on the contract auction
     /// - if the new owner account ID is not valid
     ///
     /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
@@ -80,6 +82,10 @@ pub trait ContractOwnership {
     ///
     /// If `amount` is None, then all available balance is withdrawn.
     ///
+    /// If owner withdrawals are time-locked and the amount would take the total withdrawn within the
+    /// time-lock delay window above the threshold, then the withdrawal is queued as a pending withdrawal instead of being transferred - see
+    /// [`crate::ContractOwnerWithdrawals`].
+    ///
     /// Returns the updated contract owner NEAR balance.
     ///
     /// ## Panics
@@ -112,7 +118,11 @@ pub const LOG_EVENT_CONTRACT_TRANSFER_FINALIZED: LogEvent =
 #[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
 pub struct ContractOwnerNearBalance {
     pub total: YoctoNear,
+    /// excludes the owner storage costs and pending withdrawals
     pub available: YoctoNear,
+    /// time-locked withdrawals that are reserved from the owner balance
+    /// - see [`crate::ContractOwnerWithdrawals`]
+    pub pending_withdrawals: YoctoNear,
 }
 
 /// Indicates access was denied because owner access was required
@@ -1,6 +1,8 @@
 use crate::ContractBid;
+use oysterpack_smart_near::data::numbers::U128;
 use oysterpack_smart_near::domain::{ExpirationSetting, YoctoNear};
 use oysterpack_smart_near::near_sdk::{
+    json_types::ValidAccountId,
     serde::{Deserialize, Serialize},
     AccountId,
 };
@@ -11,24 +13,64 @@ use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
 ///
 /// When the sale transaction is executed, the sale amount will be released to the current owner and
 /// all of the owner's balance will be transferred out of the contract to the owner's NEAR account.
+/// Royalties are deducted from the sale amount - see [`crate::ContractSaleHistory`].
 ///
-/// TODO: enable buyers to pay with STAKE
+/// ## Paying with NEP-141 tokens
+/// Besides NEAR, the contract can be priced and bid on in tokens that the owner has whitelisted.
+/// Buyers bid with tokens via `ft_transfer_call` - see [`ContractSaleTransferMessage`]. Token
+/// bids are held in escrow by the contract, and are refunded via `ft_transfer` when the bid is
+/// outbid or cancelled. If a token transfer fails, then the tokens can be claimed later via
+/// [`ContractSale::ops_contract_sale_claim_tokens`].
+///
+/// The sale price and the bid are each denominated in a single currency:
+/// - if the contract is listed for sale, then bids must be placed in the sale price currency
+/// - a new bid can only outbid the current active bid in the same currency
+/// - the contract is sold only when the bid and sale price currencies match
 pub trait ContractSale {
-    /// Returns None if the contract is not listed for sale
+    /// Returns None if the contract is not listed for sale, or if the sale price is in tokens
     fn ops_contract_sale_price(&self) -> Option<YoctoNear>;
 
+    /// Returns None if the contract is not listed for sale, or if the sale price is in NEAR
+    fn ops_contract_sale_token_price(&self) -> Option<ContractSaleTokenPrice>;
+
     /// Returns None if there is no current bid on the contract
     fn ops_contract_bid(&self) -> Option<ContractBuyerBid>;
 
+    /// Returns the tokens that the contract can be priced and bid in
+    fn ops_contract_sale_tokens(&self) -> Vec<AccountId>;
+
+    /// Whitelists the NEP-141 token for contract sale pricing and bids.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not the owner account
+    /// - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_sale_token_whitelist(&mut self, token_id: ValidAccountId);
+
+    /// Removes the token from the whitelist, which prevents new bids in the token. Returns false
+    /// if the token was not whitelisted.
+    ///
+    /// The current bid, if any, is not affected.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not the owner account
+    /// - if 1 yoctoNEAR is not attached
+    /// - [`ERR_CONTRACT_SALE_CURRENCY_MISMATCH`] - if the contract is listed for sale in the token
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_sale_token_unwhitelist(&mut self, token_id: ValidAccountId) -> bool;
+
     /// Puts up the contract for sale for the specified sale price.
     ///
     /// - If the contract is already for sale, then the sale price is updated to the new price.
     /// - If there already is a higher bid price, then the contract is sold for the bid price.
     /// - If the current bid is expired, then the bid is cancelled
+    /// - If the current bid is in tokens, then the bid is cancelled
     ///
     /// ## Log Events
     /// - [`LOG_EVENT_CONTRACT_FOR_SALE`]
-    /// - [`LOG_EVENT_CONTRACT_BID_CANCELLED`] - if current bid has expired
+    /// - [`LOG_EVENT_CONTRACT_BID_CANCELLED`] - if current bid has expired or is in a different currency
     /// = [`LOG_EVENT_CONTRACT_SOLD`] - if the current bid is >= the sale price
     ///
     /// ## Panics
@@ -40,6 +82,18 @@ pub trait ContractSale {
     /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
     fn ops_contract_sell(&mut self, price: YoctoNear);
 
+    /// Puts up the contract for sale priced in the specified whitelisted token.
+    ///
+    /// Behaves the same as [`ContractSale::ops_contract_sell`]. If the contract is sold, then the
+    /// tokens are transferred to the current owner via `ft_transfer`.
+    ///
+    /// ## Panics
+    /// - [`ERR_CONTRACT_SALE_TOKEN_NOT_WHITELISTED`]
+    /// - see [`ContractSale::ops_contract_sell`]
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_sell_for_token(&mut self, token_id: ValidAccountId, price: U128);
+
     /// Takes the contract off the market for selling.
     ///
     /// If the contract is not currently up for sale, then there is no effect.
@@ -60,29 +114,36 @@ pub trait ContractSale {
     /// - If there was a previous lower bid in effect, then that buy order will be automatically
     ///   cancelled and the funds will be transferred back to the buyer's registered contract account.
     ///
+    /// To bid with tokens, use `ft_transfer_call` with [`ContractSaleTransferMessage::ContractBuy`].
+    ///
     /// ## Log Events
     /// - [`LOG_EVENT_CONTRACT_BID_PLACED`]
     ///
     /// ## Panics
     /// - if no deposit is attached - at lease 1 yoctoNEAR must be attached
     /// - if the submitted bid price is not higher than the current bid price
+    /// - [`ERR_CONTRACT_SALE_CURRENCY_MISMATCH`] - if the sale price or current active bid is in tokens
     ///
     /// `#[payable]`
     fn ops_contract_buy(&mut self, expiration: Option<ExpirationSetting>);
 
     /// Enables the buyer to raise the contract bid and update the expiration.
     ///
+    /// To raise a token bid, use `ft_transfer_call` with [`ContractSaleTransferMessage::ContractRaiseBid`].
+    ///
     /// ## Panics
     /// - if there is no current bid
     /// - if predecessor ID is not the current buyer
     /// - if no deposit is attached - at lease 1 yoctoNEAR must be attached
+    /// - [`ERR_CONTRACT_SALE_CURRENCY_MISMATCH`] - if the current bid is in tokens
     ///
     /// `#[payable]`
     fn ops_contract_raise_bid(&mut self, expiration: Option<ExpirationSetting>) -> ContractBid;
 
     /// Enables the buyer to lower the contract bid by the specified amount and update the expiration.
     ///
-    /// The amount will be refunded back to the buyer + the 1 yoctoNEAR attached deposit
+    /// The amount will be refunded back to the buyer + the 1 yoctoNEAR attached deposit. If the bid
+    /// is in tokens, then the token amount is refunded via `ft_transfer`.
     ///
     /// ## Panics
     /// - if there is no current bid
@@ -125,13 +186,70 @@ pub trait ContractSale {
     ///
     /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
     fn ops_contract_cancel_bid(&mut self);
+
+    /// Returns the tokens that failed to be transferred to the account, which can be claimed
+    fn ops_contract_sale_unclaimed_tokens(
+        &self,
+        account_id: ValidAccountId,
+        token_id: ValidAccountId,
+    ) -> U128;
+
+    /// Transfers the predecessor account's unclaimed tokens via `ft_transfer`.
+    ///
+    /// Returns the amount of tokens that are being transferred.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_sale_claim_tokens(&mut self, token_id: ValidAccountId) -> U128;
+}
+
+/// # **Contract Interface**: Contract Sale Callbacks
+pub trait ContractSaleCallbacks {
+    /// Resolves token transfers for refunds and sale proceeds. If the transfer failed, then the
+    /// tokens are credited to the account to claim later and [`LOG_EVENT_CONTRACT_SALE_TOKEN_TRANSFER_FAILURE`]
+    /// is logged.
+    ///
+    /// Returns true if the transfer succeeded.
+    ///
+    /// `#[private]`
+    fn ops_contract_sale_resolve_token_transfer(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) -> bool;
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
 pub struct ContractBuyerBid {
     pub buyer: AccountId,
+    /// if the bid is in tokens, then the bid amount is denominated in the token
     pub bid: ContractBid,
+    /// None means the bid is in NEAR
+    pub token_id: Option<AccountId>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct ContractSaleTokenPrice {
+    pub token_id: AccountId,
+    pub amount: U128,
+}
+
+/// `ft_transfer_call` message used to bid on the contract with tokens.
+///
+/// The `sender_id` is the buyer, and the transferred token amount is the bid amount.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub enum ContractSaleTransferMessage {
+    /// see [`ContractSale::ops_contract_buy`]
+    ContractBuy {
+        expiration: Option<ExpirationSetting>,
+    },
+    /// see [`ContractSale::ops_contract_raise_bid`]
+    ContractRaiseBid {
+        expiration: Option<ExpirationSetting>,
+    },
 }
 
 /// event gets logged each time the sale price is changed
@@ -154,6 +272,9 @@ pub const LOG_EVENT_CONTRACT_BID_CANCELLED: LogEvent =
 
 pub const LOG_EVENT_CONTRACT_SOLD: LogEvent = LogEvent(Level::INFO, "CONTRACT_SOLD");
 
+pub const LOG_EVENT_CONTRACT_SALE_TOKEN_TRANSFER_FAILURE: LogEvent =
+    LogEvent(Level::WARN, "CONTRACT_SALE_TOKEN_TRANSFER_FAILURE");
+
 /// Indicates access was denied because owner access was required
 pub const ERR_CONTRACT_SALE_PRICE_MUST_NOT_BE_ZERO: ErrorConst = ErrorConst(
     ErrCode("CONTRACT_SALE_PRICE_MUST_NOT_BE_ZERO"),
@@ -184,3 +305,14 @@ pub const ERR_ACCESS_DENIED_MUST_BE_BUYER: ErrorConst = ErrorConst(
 );
 
 pub const ERR_BID_IS_EXPIRED: ErrorConst = ErrorConst(ErrCode("BID_IS_EXPIRED"), "bid is expired");
+
+pub const ERR_CONTRACT_SALE_TOKEN_NOT_WHITELISTED: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_SALE_TOKEN_NOT_WHITELISTED"),
+    "token is not whitelisted for contract sales",
+);
+
+/// Indicates the bid currency does not match the sale price or current bid currency
+pub const ERR_CONTRACT_SALE_CURRENCY_MISMATCH: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_SALE_CURRENCY_MISMATCH"),
+    "bid currency does not match the contract sale currency",
+);
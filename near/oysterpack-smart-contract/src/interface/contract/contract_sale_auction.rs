@@ -0,0 +1,202 @@
+use crate::{ContractAuction, ContractAuctionBid, ContractAuctionSettings, SealedBid};
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+use oysterpack_smart_near::{ErrCode, ErrorConst, Hash, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Sale Auction API
+/// Sells the contract via an auction, which is paid for in NEAR:
+/// - [`ContractAuction::English`] - buyers bid via [`crate::ContractSale::ops_contract_buy`] and
+///   [`crate::ContractSale::ops_contract_raise_bid`]. The highest bid is held in escrow until it is
+///   outbid or the auction is settled.
+/// - [`ContractAuction::Dutch`] - the first buyer to bid at least the current price via
+///   [`crate::ContractSale::ops_contract_buy`] buys the contract at the current price, and the
+///   difference is refunded
+/// - [`ContractAuction::Sealed`] - buyers commit sealed bids via [`ContractSaleAuction::ops_contract_sealed_bid_commit`]
+///   and then reveal them via [`ContractSaleAuction::ops_contract_sealed_bid_reveal`]. Deposits are
+///   held in escrow until the auction is settled, and then can be withdrawn via
+///   [`ContractSaleAuction::ops_contract_sealed_bid_withdraw`].
+///
+/// While an auction is in progress:
+/// - the contract cannot be listed for sale at a fixed price
+/// - bids cannot be lowered or cancelled, and bid expirations are not supported
+pub trait ContractSaleAuction {
+    /// Returns None if no auction is in progress
+    fn ops_contract_auction(&self) -> Option<ContractAuction>;
+
+    /// Returns the min bid for English auctions, the current price for Dutch auctions, and the
+    /// reserve price for sealed bid auctions.
+    ///
+    /// Returns None if no auction is in progress.
+    fn ops_contract_auction_price(&self) -> Option<YoctoNear>;
+
+    /// Returns the English auction bid history in the order the bids were placed, or the sealed
+    /// bid auction bids in the order they were revealed
+    fn ops_contract_auction_bids(&self) -> Vec<ContractAuctionBid>;
+
+    /// Starts the auction. If the contract is listed for sale, then the sale is cancelled. If there
+    /// is a current bid, then the bid is cancelled and refunded.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_AUCTION_STARTED`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not the owner account
+    /// - if 1 yoctoNEAR is not attached
+    /// - [`ERR_CONTRACT_AUCTION_IN_PROGRESS`]
+    /// - if the contract transfer is in progress
+    /// - if the settings are invalid
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_auction_start(&mut self, settings: ContractAuctionSettings) -> ContractAuction;
+
+    /// Cancels the auction. Returns false if no auction was in progress.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_AUCTION_CANCELLED`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not the owner account
+    /// - if 1 yoctoNEAR is not attached
+    /// - [`ERR_CONTRACT_AUCTION_IN_PROGRESS`] - if bids have been placed on the English auction, or
+    ///   committed on the sealed bid auction
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_auction_cancel(&mut self) -> bool;
+
+    /// Settles the English auction after it has ended, or the sealed bid auction after the reveal
+    /// window has ended - can be called by any account.
+    /// - if the highest bid meets the reserve price, then the contract is sold to the highest bidder
+    /// - otherwise, the highest English auction bid is refunded - sealed bid deposits are withdrawn
+    ///   by the buyers
+    ///
+    /// Returns true if the contract was sold.
+    ///
+    /// ## Log Events
+    /// - [`crate::LOG_EVENT_CONTRACT_SOLD`]
+    /// - [`LOG_EVENT_CONTRACT_AUCTION_ENDED`] - if the contract was not sold
+    ///
+    /// ## Panics
+    /// - [`ERR_NO_CONTRACT_AUCTION`] - if no English or sealed bid auction is in progress
+    /// - [`ERR_CONTRACT_AUCTION_NOT_ENDED`]
+    fn ops_contract_auction_settle(&mut self) -> bool;
+
+    /// Returns the account's sealed bid, which may be for a prior auction if the deposit has not
+    /// yet been withdrawn
+    fn ops_contract_sealed_bid(&self, account_id: ValidAccountId) -> Option<SealedBid>;
+
+    /// Computes the sealed bid commitment - see [`SealedBid::commitment`].
+    ///
+    /// NOTE: calling this function on a public RPC node reveals the bid to the node - buyers should
+    /// compute the commitment locally.
+    fn ops_contract_sealed_bid_commitment(
+        &self,
+        account_id: ValidAccountId,
+        amount: YoctoNear,
+        salt: String,
+    ) -> Hash;
+
+    /// Commits a sealed bid during the commit window. The attached deposit must cover the bid, and
+    /// is added to any deposit that was committed earlier for the auction. Committing again replaces
+    /// the commitment. The first commit's deposit must cover the auction reserve price.
+    ///
+    /// If the account has a deposit from a prior auction, then it is refunded.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_SEALED_BID_COMMITTED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_NO_CONTRACT_AUCTION`] - if no sealed bid auction is in progress
+    /// - [`ERR_CONTRACT_AUCTION_ENDED`] - if the commit window has ended
+    /// - [`ERR_CONTRACT_BID_TOO_LOW`] - if the first commit's deposit is below the reserve price
+    /// - if no deposit is attached
+    /// - if the predecessor account is the owner
+    ///
+    /// `#[payable]`
+    fn ops_contract_sealed_bid_commit(&mut self, commitment: Hash) -> SealedBid;
+
+    /// Reveals the sealed bid during the reveal window.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_SEALED_BID_REVEALED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_NO_CONTRACT_AUCTION`] - if no sealed bid auction is in progress
+    /// - [`ERR_SEALED_BID_REVEAL_NOT_ALLOWED`] - if the reveal window is not open
+    /// - [`ERR_SEALED_BID_NOT_FOUND`] - if the account has not committed a bid for the auction
+    /// - [`ERR_SEALED_BID_INVALID_REVEAL`] - if the bid does not match the commitment, if the
+    ///   deposit does not cover the bid, or if the bid was already revealed
+    /// - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_sealed_bid_reveal(&mut self, amount: YoctoNear, salt: String) -> SealedBid;
+
+    /// Withdraws the sealed bid deposit once the auction is over. The deposit is refunded along with
+    /// the 1 yoctoNEAR attached deposit.
+    ///
+    /// Returns the withdrawn deposit amount.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_SEALED_BID_WITHDRAWN`]
+    ///
+    /// ## Panics
+    /// - [`ERR_SEALED_BID_NOT_FOUND`]
+    /// - [`ERR_CONTRACT_AUCTION_IN_PROGRESS`] - if the auction the bid was committed for is in progress
+    /// - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_sealed_bid_withdraw(&mut self) -> YoctoNear;
+}
+
+pub const LOG_EVENT_CONTRACT_AUCTION_STARTED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_AUCTION_STARTED");
+
+pub const LOG_EVENT_CONTRACT_AUCTION_EXTENDED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_AUCTION_EXTENDED");
+
+pub const LOG_EVENT_CONTRACT_AUCTION_CANCELLED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_AUCTION_CANCELLED");
+
+pub const LOG_EVENT_CONTRACT_AUCTION_ENDED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_AUCTION_ENDED");
+
+pub const LOG_EVENT_CONTRACT_SEALED_BID_COMMITTED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_SEALED_BID_COMMITTED");
+
+pub const LOG_EVENT_CONTRACT_SEALED_BID_REVEALED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_SEALED_BID_REVEALED");
+
+pub const LOG_EVENT_CONTRACT_SEALED_BID_WITHDRAWN: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_SEALED_BID_WITHDRAWN");
+
+pub const ERR_NO_CONTRACT_AUCTION: ErrorConst = ErrorConst(
+    ErrCode("NO_CONTRACT_AUCTION"),
+    "there is no contract auction in progress",
+);
+
+pub const ERR_CONTRACT_AUCTION_IN_PROGRESS: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_AUCTION_IN_PROGRESS"),
+    "action is not allowed while the contract auction is in progress",
+);
+
+pub const ERR_CONTRACT_AUCTION_NOT_ENDED: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_AUCTION_NOT_ENDED"),
+    "contract auction has not yet ended",
+);
+
+pub const ERR_CONTRACT_AUCTION_ENDED: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_AUCTION_ENDED"),
+    "contract auction has ended",
+);
+
+pub const ERR_SEALED_BID_NOT_FOUND: ErrorConst =
+    ErrorConst(ErrCode("SEALED_BID_NOT_FOUND"), "sealed bid does not exist");
+
+pub const ERR_SEALED_BID_REVEAL_NOT_ALLOWED: ErrorConst = ErrorConst(
+    ErrCode("SEALED_BID_REVEAL_NOT_ALLOWED"),
+    "sealed bids can only be revealed during the reveal window",
+);
+
+pub const ERR_SEALED_BID_INVALID_REVEAL: ErrorConst = ErrorConst(
+    ErrCode("SEALED_BID_INVALID_REVEAL"),
+    "revealed bid is not valid",
+);
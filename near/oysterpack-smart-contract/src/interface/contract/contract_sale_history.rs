@@ -0,0 +1,81 @@
+use crate::{ContractRoyalty, ContractSaleRecord};
+use oysterpack_smart_near::domain::BasisPoints;
+use oysterpack_smart_near::near_sdk::json_types::{ValidAccountId, U64};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Sale History API
+///
+/// Each contract sale is recorded as a [`ContractSaleRecord`], which provides the contract's
+/// ownership provenance.
+///
+/// Royalties are paid out of the sale price to the royalty recipients each time the contract is
+/// sold - whether the contract is sold at the sale price, to a bid, or via an auction. The owner
+/// grants royalties, but only the recipient can reduce its royalty.
+pub trait ContractSaleHistory {
+    /// Returns the number of times the contract has been sold
+    fn ops_contract_sale_count(&self) -> U64;
+
+    /// Returns the sale records starting from the specified index in the order the sales were
+    /// executed.
+    ///
+    /// `limit` defaults to 100
+    fn ops_contract_sale_history(
+        &self,
+        from_index: U64,
+        limit: Option<u32>,
+    ) -> Vec<ContractSaleRecord>;
+
+    fn ops_contract_royalties(&self) -> Vec<ContractRoyalty>;
+
+    /// Grants the royalty to the account, or raises the account's existing royalty.
+    ///
+    /// Royalties can only be granted while the contract is not for sale, has no bid, and is not
+    /// being auctioned - buyers must know the royalties up front.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_ROYALTY_CHANGE`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not the owner account
+    /// - if 1 yoctoNEAR is not attached
+    /// - [`ERR_CONTRACT_ROYALTY_INVALID`] - if the basis points are zero, would lower the existing
+    ///   royalty, or would raise the total royalties above [`ContractRoyalty::MAX_TOTAL_BASIS_POINTS`]
+    /// - [`ERR_CONTRACT_ROYALTY_INVALID`] - if the contract is for sale, has a bid, or is being auctioned
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_royalty_grant(
+        &mut self,
+        account_id: ValidAccountId,
+        basis_points: BasisPoints,
+    ) -> ContractRoyalty;
+
+    /// Reduces the predecessor account's royalty - zero removes the royalty.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_ROYALTY_CHANGE`]
+    ///
+    /// ## Panics
+    /// - [`ERR_CONTRACT_ROYALTY_NOT_FOUND`]
+    /// - [`ERR_CONTRACT_ROYALTY_INVALID`] - if the basis points would raise the royalty
+    /// - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_contract_royalty_reduce(&mut self, basis_points: BasisPoints)
+        -> Option<ContractRoyalty>;
+}
+
+pub const LOG_EVENT_CONTRACT_ROYALTY_CHANGE: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_ROYALTY_CHANGE");
+
+pub const LOG_EVENT_CONTRACT_ROYALTY_PAID: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_ROYALTY_PAID");
+
+pub const ERR_CONTRACT_ROYALTY_NOT_FOUND: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_ROYALTY_NOT_FOUND"),
+    "account has not been granted a contract royalty",
+);
+
+pub const ERR_CONTRACT_ROYALTY_INVALID: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_ROYALTY_INVALID"),
+    "contract royalty is invalid",
+);
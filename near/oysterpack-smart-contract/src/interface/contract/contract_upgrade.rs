@@ -0,0 +1,128 @@
+use oysterpack_smart_near::domain::{
+    BlockTime, Expiration, ExpirationDuration, StorageUsage, YoctoNear,
+};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::{Base58CryptoHash, Base64VecU8},
+    serde::{Deserialize, Serialize},
+};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+
+/// # **Contract Interface**: Contract Upgrade API
+///
+/// Upgrades are staged and announced before they can be deployed:
+/// 1. the owner stages the new contract wasm, which starts the upgrade time-lock
+///    - the staged code storage is paid for out of the contract owner balance
+/// 2. during the notice period, anyone can inspect the staged code hash and verify it against the
+///    published source build
+/// 3. once the time-lock expires, anyone can trigger the upgrade, which deploys the staged code on
+///    the contract account and then invokes the [`CONTRACT_UPGRADE_MIGRATE_METHOD`] function
+///    - both actions are batched into a single receipt, i.e., if the migration fails, then the
+///      deployment is rolled back as well
+pub trait ContractUpgrade {
+    /// Stages the contract upgrade wasm, and starts the time-lock.
+    ///
+    /// If an upgrade is already staged, then it is replaced and the time-lock is restarted.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_UPGRADE_STAGED`]
+    ///
+    /// If multisig is configured, then the upgrade must be staged through an approved multisig
+    /// proposal.
+    ///
+    /// ## Panics
+    /// - `ERR_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the owner account
+    /// - `ERR_MULTISIG_APPROVAL_REQUIRED` - if multisig is configured and the upgrade was not staged
+    ///   through an approved proposal
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    /// - [`ERR_CONTRACT_UPGRADE_CODE_EMPTY`]
+    /// - [`ERR_CONTRACT_UPGRADE_STORAGE_NOT_COVERED`] - if the owner balance cannot pay for the
+    ///   staged code storage
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_upgrade_stage(&mut self, code: Base64VecU8) -> StagedContractUpgrade;
+
+    /// Cancels the staged upgrade, which frees up the staged code storage.
+    ///
+    /// Returns false if no upgrade is staged.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_UPGRADE_CANCELLED`]
+    ///
+    /// ## Panics
+    /// - `ERR_OWNER_ACCESS_REQUIRED` - if the predecessor account is not the owner account
+    /// - `ERR_YOCTONEAR_DEPOSIT_REQUIRED` - if 1 yoctoNEAR is not attached
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    fn ops_upgrade_cancel(&mut self) -> bool;
+
+    /// Deploys the staged code on the contract account followed by a call to the
+    /// [`CONTRACT_UPGRADE_MIGRATE_METHOD`] function. The staged upgrade is removed.
+    ///
+    /// Can be triggered by any account once the time-lock has expired.
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_CONTRACT_UPGRADE_DEPLOYED`]
+    ///
+    /// ## Panics
+    /// - [`ERR_CONTRACT_UPGRADE_NOT_STAGED`]
+    /// - [`ERR_CONTRACT_UPGRADE_TIMELOCKED`] - if the time-lock has not yet expired
+    fn ops_upgrade_deploy(&mut self);
+
+    /// Returns None if no upgrade is staged
+    fn ops_upgrade_staged(&self) -> Option<StagedContractUpgrade>;
+
+    /// time-lock notice period that is applied when an upgrade is staged, which is at least
+    /// [`crate::components::contract_upgrade::CONTRACT_UPGRADE_MIN_NOTICE`]
+    fn ops_upgrade_notice(&self) -> ExpirationDuration;
+}
+
+/// Contract function that is invoked after the staged code is deployed
+pub const CONTRACT_UPGRADE_MIGRATE_METHOD: &str = "migrate";
+
+/// Announces the staged contract upgrade
+#[derive(
+    BorshSerialize, BorshDeserialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct StagedContractUpgrade {
+    /// sha256 hash of the staged wasm
+    pub code_hash: Base58CryptoHash,
+    /// staged wasm size in bytes
+    pub code_size: u32,
+    /// storage staking cost for the staged code, which is paid for by the contract owner
+    pub storage_usage: StorageUsage,
+    pub storage_cost: YoctoNear,
+    pub staged_on: BlockTime,
+    /// the upgrade can be deployed after the time-lock expires
+    pub timelock: Expiration,
+}
+
+pub const LOG_EVENT_CONTRACT_UPGRADE_STAGED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_UPGRADE_STAGED");
+
+pub const LOG_EVENT_CONTRACT_UPGRADE_CANCELLED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_UPGRADE_CANCELLED");
+
+pub const LOG_EVENT_CONTRACT_UPGRADE_DEPLOYED: LogEvent =
+    LogEvent(Level::INFO, "CONTRACT_UPGRADE_DEPLOYED");
+
+pub const ERR_CONTRACT_UPGRADE_NOT_STAGED: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_UPGRADE_NOT_STAGED"),
+    "contract upgrade is not staged",
+);
+
+pub const ERR_CONTRACT_UPGRADE_TIMELOCKED: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_UPGRADE_TIMELOCKED"),
+    "contract upgrade time-lock has not expired",
+);
+
+pub const ERR_CONTRACT_UPGRADE_CODE_EMPTY: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_UPGRADE_CODE_EMPTY"),
+    "contract upgrade code is empty",
+);
+
+pub const ERR_CONTRACT_UPGRADE_STORAGE_NOT_COVERED: ErrorConst = ErrorConst(
+    ErrCode("CONTRACT_UPGRADE_STORAGE_NOT_COVERED"),
+    "owner balance is insufficient to pay for the staged contract upgrade storage",
+);
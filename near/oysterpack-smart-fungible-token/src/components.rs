@@ -1 +1,4 @@
+pub mod batch_registration;
+pub mod contract_sale;
 pub mod fungible_token;
+pub mod storage_token;
@@ -0,0 +1,427 @@
+//! [`BatchRegistrationComponent`]
+//! - constructor: [`BatchRegistrationComponent::new`]
+//!   - [`AccountManagementComponent`]
+//!   - [`FungibleTokenComponent`]
+//!   - [`BatchRegistrationConfig`]
+
+use crate::components::fungible_token::FungibleTokenComponent;
+use crate::{
+    BatchRegistration, BatchRegistrationPage, BatchRegistrationRequest, BatchRegistrationResult,
+    TokenDistribution, TokenService, LOG_EVENT_BATCH_REGISTRATION,
+};
+use oysterpack_smart_account_management::{
+    components::account_management::AccountManagementComponent, AccountRepository, AuditLog,
+    StorageManagement,
+};
+use oysterpack_smart_contract::{BalanceId, ContractNearBalances};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{BorshDeserialize, BorshSerialize},
+    env,
+    serde_json::json,
+};
+use oysterpack_smart_near::{asserts::ERR_INVALID, domain::YoctoNear, TERA};
+use std::fmt::Debug;
+
+/// gas that must remain before the next account in the batch is processed
+const GAS_PER_ACCOUNT: u64 = 10 * TERA;
+
+/// Implements [`BatchRegistration`]
+pub struct BatchRegistrationComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    account_manager: AccountManagementComponent<T>,
+    ft: FungibleTokenComponent<T>,
+    config: BatchRegistrationConfig,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct BatchRegistrationConfig {
+    /// if set, then account storage is paid from the budget balance - otherwise account storage is
+    /// paid from the contract owner's balance
+    pub budget: Option<BalanceId>,
+    /// if false, then tokens can only be distributed via transfers
+    pub mint_enabled: bool,
+}
+
+impl<T> BatchRegistrationComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub fn new(
+        account_manager: AccountManagementComponent<T>,
+        ft: FungibleTokenComponent<T>,
+        config: BatchRegistrationConfig,
+    ) -> Self {
+        Self {
+            account_manager,
+            ft,
+            config,
+        }
+    }
+
+    fn budget(&self) -> BalanceId {
+        ERR_INVALID.assert(
+            || self.config.budget.is_some(),
+            || "batch registration budget is not configured",
+        );
+        self.config.budget.unwrap()
+    }
+}
+
+impl<T> BatchRegistration for BatchRegistrationComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_batch_register(&mut self, request: BatchRegistrationRequest) -> BatchRegistrationPage {
+        self.account_manager.assert_admin("ops_batch_register");
+        let admin_id = env::predecessor_account_id();
+
+        let start = request.start.unwrap_or(0) as usize;
+        ERR_INVALID.assert(
+            || start <= request.accounts.len(),
+            || "start is out of range",
+        );
+        if let Some(TokenDistribution::Mint(_)) = request.tokens {
+            ERR_INVALID.assert(
+                || self.config.mint_enabled,
+                || "token minting is not enabled",
+            );
+        }
+        let end = request.limit.map_or(request.accounts.len(), |limit| {
+            request.accounts.len().min(start + limit as usize)
+        });
+
+        let storage_cost = self.account_manager.storage_balance_bounds().min;
+        let mut results = Vec::with_capacity(end - start);
+        let mut budget_exhausted = false;
+        for account_id in request.accounts[start..end].iter() {
+            if env::prepaid_gas() - env::used_gas() < GAS_PER_ACCOUNT {
+                break;
+            }
+            let registered = !self.account_manager.account_exists(account_id.as_ref());
+            if registered {
+                if let Some(budget) = self.config.budget {
+                    if ContractNearBalances::near_balance(budget) < storage_cost {
+                        budget_exhausted = true;
+                        break;
+                    }
+                    ContractNearBalances::decr_balance(budget, storage_cost);
+                }
+                AccountManagementComponent::<T>::register_account_if_not_exists(
+                    account_id.as_ref(),
+                );
+            }
+            let tokens = request.tokens.map(|tokens| match tokens {
+                TokenDistribution::Mint(amount) => {
+                    self.ft.ft_mint(account_id.as_ref(), amount);
+                    amount
+                }
+                TokenDistribution::Transfer(amount) => {
+                    self.ft
+                        .transfer(&admin_id, account_id.clone(), amount, None);
+                    amount
+                }
+            });
+            results.push(BatchRegistrationResult {
+                account_id: account_id.as_ref().to_string(),
+                registered,
+                tokens,
+            });
+        }
+
+        let next = start + results.len();
+        let next = if next < request.accounts.len() {
+            Some(next as u32)
+        } else {
+            None
+        };
+        let registered_count = results.iter().filter(|result| result.registered).count();
+        LOG_EVENT_BATCH_REGISTRATION.log(format!(
+            "processed: {}, registered: {}, next: {:?}",
+            results.len(),
+            registered_count,
+            next
+        ));
+        AuditLog::record(
+            "ops_batch_register",
+            None,
+            Some(
+                json!({
+                    "start": start,
+                    "processed": results.len(),
+                    "registered": registered_count,
+                    "tokens": request.tokens,
+                })
+                .to_string(),
+            ),
+        );
+
+        BatchRegistrationPage {
+            results,
+            next,
+            budget_exhausted,
+        }
+    }
+
+    fn ops_batch_registration_budget_deposit(&mut self) -> YoctoNear {
+        self.account_manager
+            .assert_admin("ops_batch_registration_budget_deposit");
+        let budget = self.budget();
+        let deposit: YoctoNear = env::attached_deposit().into();
+        ERR_INVALID.assert(
+            || deposit > YoctoNear::ZERO,
+            || "deposit is required to fund the budget",
+        );
+        let balance = ContractNearBalances::incr_balance(budget, deposit);
+        AuditLog::record(
+            "ops_batch_registration_budget_deposit",
+            None,
+            Some(json!({ "amount": deposit }).to_string()),
+        );
+        balance
+    }
+
+    fn ops_batch_registration_budget_release(&mut self) -> YoctoNear {
+        self.account_manager
+            .assert_admin("ops_batch_registration_budget_release");
+        let budget = self.budget();
+        let balance = ContractNearBalances::near_balance(budget);
+        ContractNearBalances::clear_balance(budget);
+        AuditLog::record(
+            "ops_batch_registration_budget_release",
+            None,
+            Some(json!({ "amount": balance }).to_string()),
+        );
+        balance
+    }
+
+    fn ops_batch_registration_budget(&self) -> Option<YoctoNear> {
+        self.config.budget.map(ContractNearBalances::near_balance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::fungible_token::FungibleTokenConfig;
+    use crate::{FungibleToken, Metadata, FT_METADATA_SPEC};
+    use oysterpack_smart_account_management::components::account_management::AccountManagementComponentConfig;
+    use oysterpack_smart_near::component::{Deploy, ManagesAccountData};
+    use oysterpack_smart_near::near_sdk::{
+        self, json_types::ValidAccountId, test_utils, VMContext,
+    };
+    use oysterpack_smart_near::YOCTO;
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+    type STAKE = FungibleTokenComponent<()>;
+
+    const ADMIN: &str = "admin";
+    const BUDGET: BalanceId = BalanceId(1957997452337470474290426578815946431);
+
+    fn deploy(config: BatchRegistrationConfig) -> (VMContext, BatchRegistrationComponent<()>) {
+        let ctx = new_context(ADMIN);
+        testing_env!(ctx.clone());
+
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: Some(vec![STAKE::account_storage_min]),
+        });
+        STAKE::deploy(FungibleTokenConfig {
+            metadata: Metadata {
+                spec: FT_METADATA_SPEC.into(),
+                name: "STAKE".into(),
+                symbol: "STAKE".into(),
+                icon: None,
+                reference: None,
+                reference_hash: None,
+                decimals: 24,
+            },
+            token_supply: 0,
+        });
+
+        let batch_registration = BatchRegistrationComponent::new(
+            AccountManager::default(),
+            STAKE::new(AccountManager::default()),
+            config,
+        );
+        (ctx, batch_registration)
+    }
+
+    fn accounts(account_ids: &[&str]) -> Vec<ValidAccountId> {
+        account_ids
+            .iter()
+            .map(|account_id| to_valid_account_id(account_id))
+            .collect()
+    }
+
+    #[test]
+    fn register_and_mint() {
+        let (_ctx, mut batch_registration) = deploy(BatchRegistrationConfig {
+            budget: None,
+            mint_enabled: true,
+        });
+        let total_registered_accounts = AccountManager::account_metrics().total_registered_accounts;
+
+        // Act
+        let page = batch_registration.ops_batch_register(BatchRegistrationRequest {
+            accounts: accounts(&["alice", ADMIN, "bob"]),
+            tokens: Some(TokenDistribution::Mint(100.into())),
+            start: None,
+            limit: None,
+        });
+
+        // Assert
+        assert_eq!(page.next, None);
+        assert!(!page.budget_exhausted);
+        assert_eq!(
+            page.results
+                .iter()
+                .map(|result| (result.account_id.as_str(), result.registered, result.tokens))
+                .collect::<Vec<_>>(),
+            vec![
+                ("alice", true, Some(100.into())),
+                (ADMIN, false, Some(100.into())),
+                ("bob", true, Some(100.into())),
+            ]
+        );
+        assert_eq!(
+            *AccountManager::account_metrics().total_registered_accounts,
+            *total_registered_accounts + 2
+        );
+        let stake = STAKE::new(AccountManager::default());
+        assert_eq!(stake.ft_balance_of(to_valid_account_id("bob")), 100.into());
+        assert_eq!(stake.ft_total_supply(), 300.into());
+        let logs = test_utils::get_logs();
+        assert!(logs.contains(
+            &"[INFO] [BATCH_REGISTRATION] processed: 3, registered: 2, next: None".to_string()
+        ));
+    }
+
+    #[test]
+    fn transfer_in_pages() {
+        let (_ctx, mut batch_registration) = deploy(BatchRegistrationConfig::default());
+        STAKE::new(AccountManager::default()).ft_mint(ADMIN, 1000.into());
+        let request = BatchRegistrationRequest {
+            accounts: accounts(&["alice", "bob", "charlie"]),
+            tokens: Some(TokenDistribution::Transfer(100.into())),
+            start: None,
+            limit: Some(2),
+        };
+
+        // Act
+        let page = batch_registration.ops_batch_register(request.clone());
+
+        // Assert
+        assert_eq!(page.results.len(), 2);
+        assert_eq!(page.next, Some(2));
+
+        // Act - resume
+        let page = batch_registration.ops_batch_register(BatchRegistrationRequest {
+            start: page.next,
+            ..request
+        });
+
+        // Assert
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.results[0].account_id, "charlie");
+        assert_eq!(page.next, None);
+        let stake = STAKE::new(AccountManager::default());
+        assert_eq!(stake.ft_balance_of(to_valid_account_id(ADMIN)), 700.into());
+        assert_eq!(
+            stake.ft_balance_of(to_valid_account_id("charlie")),
+            100.into()
+        );
+    }
+
+    #[test]
+    fn budget_exhausted() {
+        let (mut ctx, mut batch_registration) = deploy(BatchRegistrationConfig {
+            budget: Some(BUDGET),
+            mint_enabled: false,
+        });
+        let storage_cost = AccountManager::default().storage_balance_bounds().min;
+        ctx.attached_deposit = *storage_cost * 2;
+        testing_env!(ctx.clone());
+        assert_eq!(
+            batch_registration.ops_batch_registration_budget_deposit(),
+            (*storage_cost * 2).into()
+        );
+
+        // Act
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        let request = BatchRegistrationRequest {
+            accounts: accounts(&["alice", "bob", "charlie"]),
+            tokens: None,
+            start: None,
+            limit: None,
+        };
+        let page = batch_registration.ops_batch_register(request.clone());
+
+        // Assert
+        assert_eq!(page.results.len(), 2);
+        assert_eq!(page.next, Some(2));
+        assert!(page.budget_exhausted);
+        assert_eq!(
+            batch_registration.ops_batch_registration_budget(),
+            Some(YoctoNear::ZERO)
+        );
+
+        // Act - fund the budget and resume
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        batch_registration.ops_batch_registration_budget_deposit();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        let page = batch_registration.ops_batch_register(BatchRegistrationRequest {
+            start: page.next,
+            ..request
+        });
+
+        // Assert
+        assert_eq!(page.next, None);
+        assert!(!page.budget_exhausted);
+        assert_eq!(
+            batch_registration.ops_batch_registration_budget_release(),
+            (YOCTO - *storage_cost).into()
+        );
+        assert_eq!(
+            batch_registration.ops_batch_registration_budget(),
+            Some(YoctoNear::ZERO)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID] token minting is not enabled")]
+    fn mint_not_enabled() {
+        let (_ctx, mut batch_registration) = deploy(BatchRegistrationConfig::default());
+        batch_registration.ops_batch_register(BatchRegistrationRequest {
+            accounts: accounts(&["alice"]),
+            tokens: Some(TokenDistribution::Mint(100.into())),
+            start: None,
+            limit: None,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
+    fn not_admin() {
+        let (mut ctx, mut batch_registration) = deploy(BatchRegistrationConfig::default());
+        ctx.predecessor_account_id = "alice".to_string();
+        ctx.attached_deposit = YOCTO;
+        testing_env!(ctx.clone());
+        AccountManager::default().storage_deposit(None, None);
+
+        ctx.attached_deposit = 0;
+        testing_env!(ctx);
+        batch_registration.ops_batch_register(BatchRegistrationRequest {
+            accounts: accounts(&["bob"]),
+            tokens: None,
+            start: None,
+            limit: None,
+        });
+    }
+}
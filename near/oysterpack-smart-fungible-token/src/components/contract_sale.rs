@@ -0,0 +1,105 @@
+//! [`TransferReceiver`] for [`ContractSaleComponent`]
+//! - accepts contract bids paid with tokens
+//! - the contract's `ft_on_transfer` must route [`ContractSaleTransferMessage`] transfers to it
+
+use crate::{TokenAmount, TransferCallMessage, TransferReceiver};
+use oysterpack_smart_contract::{
+    components::contract_sale::ContractSaleComponent, ContractSaleTransferMessage,
+};
+use oysterpack_smart_near::{
+    asserts::ERR_CODE_BAD_REQUEST,
+    near_sdk::{json_types::ValidAccountId, serde_json, PromiseOrValue},
+};
+
+impl TransferReceiver for ContractSaleComponent {
+    /// `msg` must be a JSON encoded [`ContractSaleTransferMessage`]
+    ///
+    /// The full token amount is used for the bid, i.e., zero tokens are returned.
+    ///
+    /// ## Panics
+    /// - if `msg` is not a valid [`ContractSaleTransferMessage`]
+    /// - if the bid is rejected - see [`ContractSaleComponent::bid_with_tokens`]
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: ValidAccountId,
+        amount: TokenAmount,
+        msg: TransferCallMessage,
+    ) -> PromiseOrValue<TokenAmount> {
+        let msg: ContractSaleTransferMessage = match serde_json::from_str(&msg) {
+            Ok(msg) => msg,
+            Err(err) => {
+                ERR_CODE_BAD_REQUEST
+                    .error(format!("invalid contract sale transfer message: {}", err))
+                    .panic();
+                unreachable!()
+            }
+        };
+        ContractSaleComponent::bid_with_tokens(sender_id, amount.0, msg);
+        PromiseOrValue::Value(TokenAmount::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oysterpack_smart_contract::components::contract_ownership::ContractOwnershipComponent;
+    use oysterpack_smart_contract::{ContractBid, ContractSale};
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::data::numbers::U128;
+    use oysterpack_smart_near::near_sdk::{self, VMContext};
+    use oysterpack_smart_near::to_valid_account_id;
+    use oysterpack_smart_near_test::*;
+
+    const OWNER: &str = "owner";
+    const BUYER: &str = "buyer";
+    const TOKEN: &str = "stake.pool";
+
+    fn arrange() -> VMContext {
+        let mut ctx = new_context(OWNER);
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        ContractOwnershipComponent::deploy(to_valid_account_id(OWNER));
+        ContractSaleComponent.ops_contract_sale_token_whitelist(to_valid_account_id(TOKEN));
+
+        ctx.predecessor_account_id = TOKEN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        ctx
+    }
+
+    #[test]
+    fn bid_with_tokens() {
+        arrange();
+        let unused = ContractSaleComponent.ft_on_transfer(
+            to_valid_account_id(BUYER),
+            TokenAmount(U128(1000)),
+            r#"{"ContractBuy":{"expiration":null}}"#.into(),
+        );
+        match unused {
+            PromiseOrValue::Value(amount) => assert_eq!(amount, TokenAmount::ZERO),
+            _ => panic!("expected value"),
+        }
+
+        let bid = ContractSaleComponent.ops_contract_bid().unwrap();
+        assert_eq!(bid.buyer, BUYER);
+        assert_eq!(
+            bid.bid,
+            ContractBid {
+                amount: U128(1000),
+                expiration: None
+            }
+        );
+        assert_eq!(bid.token_id, Some(TOKEN.to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [BAD_REQUEST] invalid contract sale transfer message")]
+    fn invalid_msg() {
+        arrange();
+        ContractSaleComponent.ft_on_transfer(
+            to_valid_account_id(BUYER),
+            TokenAmount(U128(1000)),
+            "storage_deposit".into(),
+        );
+    }
+}
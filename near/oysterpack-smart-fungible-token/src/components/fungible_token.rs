@@ -4,21 +4,25 @@
 //! - deployment: [`FungibleTokenComponent::deploy`]
 //!   - config: [`FungibleTokenConfig`]
 //! - use [`FungibleTokenComponent::register_storage_management_event_handler`]  to register event
-//!   handler for [`StorageManagementEvent::PreUnregister`] which integrates with [`AccountManagementComponent`]
+//!   handlers for [`StorageManagementEvent::PreUnregister`] and [`StorageManagementEvent::PreMigrate`]
+//!   which integrate with [`AccountManagementComponent`]
+//! - [`FungibleTokenComponent::account_holds_tokens`] is used to retain accounts with token balances
+//!   when inactive accounts are garbage collected
 
 use crate::{
     contract::operator::{FungibleTokenOperator, OperatorCommand},
-    FungibleToken, FungibleTokenMetadataProvider, Memo, Metadata, ResolveTransferCall, TokenAmount,
-    TokenService, TransferCallMessage, ERR_CODE_FT_RESOLVE_TRANSFER, LOG_EVENT_FT_BURN,
+    FungibleToken, FungibleTokenDelegation, FungibleTokenMetadataProvider, Memo, Metadata,
+    ResolveTransferCall, TokenAmount, TokenService, TransferCallMessage,
+    DELEGATED_ACTION_FT_TRANSFER, ERR_CODE_FT_RESOLVE_TRANSFER, LOG_EVENT_FT_BURN,
     LOG_EVENT_FT_LOCK, LOG_EVENT_FT_MINT, LOG_EVENT_FT_TRANSFER,
     LOG_EVENT_FT_TRANSFER_CALL_FAILURE, LOG_EVENT_FT_TRANSFER_CALL_PARTIAL_REFUND,
     LOG_EVENT_FT_TRANSFER_CALL_RECEIVER_DEBIT, LOG_EVENT_FT_TRANSFER_CALL_REFUND_NOT_APPLIED,
     LOG_EVENT_FT_TRANSFER_CALL_SENDER_CREDIT, LOG_EVENT_FT_UNLOCK,
 };
 use oysterpack_smart_account_management::{
-    components::account_management::AccountManagementComponent, AccountRepository,
-    AccountStorageEvent, StorageManagementEvent, ERR_ACCOUNT_NOT_REGISTERED,
-    ERR_CODE_UNREGISTER_FAILURE,
+    components::account_management::AccountManagementComponent, AccountDelegations,
+    AccountRepository, AccountStorageEvent, AuditLog, ComponentId, StorageManagementEvent,
+    ERR_ACCOUNT_NOT_REGISTERED, ERR_CODE_UNREGISTER_FAILURE,
 };
 use oysterpack_smart_near::eventbus::{self, post};
 use oysterpack_smart_near::near_sdk::{
@@ -89,9 +93,69 @@ where
         memo: Option<Memo>,
     ) {
         assert_yocto_near_attached();
-        ERR_CODE_BAD_REQUEST.assert(|| *amount > 0, || "transfer amount cannot be zero");
+        self.transfer(&env::predecessor_account_id(), receiver_id, amount, memo);
+    }
+
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: ValidAccountId,
+        amount: TokenAmount,
+        memo: Option<Memo>,
+        msg: TransferCallMessage,
+    ) -> Promise {
+        self.ft_transfer(receiver_id.clone(), amount, memo);
 
-        let sender_id = &env::predecessor_account_id();
+        self.create_promise_transfer_receiver_ft_on_transfer(
+            &env::predecessor_account_id(),
+            receiver_id.as_ref(),
+            amount,
+            msg,
+        )
+    }
+
+    fn ft_total_supply(&self) -> TokenAmount {
+        TokenSupply::load(&TOKEN_SUPPLY).map_or(0.into(), |amount| (*amount).into())
+    }
+
+    fn ft_balance_of(&self, account_id: ValidAccountId) -> TokenAmount {
+        AccountFTBalance::balance_of(account_id.as_ref())
+    }
+}
+
+impl<T> FungibleTokenDelegation for FungibleTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    fn ft_transfer_for(
+        &mut self,
+        account_id: ValidAccountId,
+        receiver_id: ValidAccountId,
+        amount: TokenAmount,
+        memo: Option<Memo>,
+    ) {
+        assert_yocto_near_attached();
+        AccountDelegations::consume(
+            account_id.as_ref(),
+            &env::predecessor_account_id(),
+            DELEGATED_ACTION_FT_TRANSFER,
+            *amount,
+        );
+        self.transfer(account_id.as_ref(), receiver_id, amount, memo);
+    }
+}
+
+impl<T> FungibleTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub(crate) fn transfer(
+        &mut self,
+        sender_id: &str,
+        receiver_id: ValidAccountId,
+        amount: TokenAmount,
+        memo: Option<Memo>,
+    ) {
+        ERR_CODE_BAD_REQUEST.assert(|| *amount > 0, || "transfer amount cannot be zero");
         ERR_CODE_BAD_REQUEST.assert(
             || sender_id != receiver_id.as_ref(),
             || "sender and receiver cannot be the same",
@@ -101,6 +165,7 @@ where
             || self.account_manager.account_exists(sender_id),
             || "sender account is not registered",
         );
+        AccountManagementComponent::<T>::assert_account_not_frozen(sender_id);
         ERR_ACCOUNT_NOT_REGISTERED.assert_with_message(
             || self.account_manager.account_exists(receiver_id.as_ref()),
             || "receiver account is not registered",
@@ -113,36 +178,13 @@ where
         AccountFTBalance::set_balance(sender_id, *sender_balance - *amount);
         let receiver_balance = self.ft_balance_of(receiver_id.clone());
         AccountFTBalance::set_balance(receiver_id.as_ref(), *receiver_balance + *amount);
+        AccountManagementComponent::<T>::record_account_activity(sender_id);
+        // the receiver's balance record is created on its first transfer
 
         if let Some(memo) = memo {
             LOG_EVENT_FT_TRANSFER.log(memo);
         }
     }
-
-    fn ft_transfer_call(
-        &mut self,
-        receiver_id: ValidAccountId,
-        amount: TokenAmount,
-        memo: Option<Memo>,
-        msg: TransferCallMessage,
-    ) -> Promise {
-        self.ft_transfer(receiver_id.clone(), amount, memo);
-
-        self.create_promise_transfer_receiver_ft_on_transfer(
-            &env::predecessor_account_id(),
-            receiver_id.as_ref(),
-            amount,
-            msg,
-        )
-    }
-
-    fn ft_total_supply(&self) -> TokenAmount {
-        TokenSupply::load(&TOKEN_SUPPLY).map_or(0.into(), |amount| (*amount).into())
-    }
-
-    fn ft_balance_of(&self, account_id: ValidAccountId) -> TokenAmount {
-        AccountFTBalance::balance_of(account_id.as_ref())
-    }
 }
 
 impl<T> FungibleTokenOperator for FungibleTokenComponent<T>
@@ -150,7 +192,8 @@ where
     T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
 {
     fn ft_operator_command(&mut self, command: OperatorCommand) {
-        self.account_manager.assert_operator();
+        self.account_manager.assert_operator("ft_operator_command");
+        let args = serde_json::to_string(&command).unwrap();
         let mut metadata = MetadataObject::load(&METADATA_KEY).unwrap();
         match command {
             OperatorCommand::SetIcon(icon) => metadata.icon = Some(icon),
@@ -166,6 +209,7 @@ where
             OperatorCommand::SetTransferCallbackGas(gas) => set_transfer_callback_gas(gas),
         }
         metadata.save();
+        AuditLog::record("ft_operator_command", None, Some(args));
     }
 
     fn ft_operator_transfer_callback_gas(&self) -> Gas {
@@ -304,12 +348,16 @@ impl<T> ManagesAccountData for FungibleTokenComponent<T>
 where
     T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
 {
+    /// the balance record is measured directly, i.e., no storage usage events are posted because
+    /// the measurement account is not a registered account
     fn account_storage_min() -> StorageUsage {
         let account_id = "19544499980228477895959808916967586760";
         let initial_storage = env::storage_usage();
-        AccountFTBalance::set_balance(account_id, 1);
+        let balance =
+            AccountFTBalanceObject::new(AccountFTBalance::ft_account_id_hash(account_id), (1, 0));
+        balance.save();
         let account_storage_usage = env::storage_usage() - initial_storage;
-        AccountFTBalance::set_balance(account_id, 0);
+        balance.delete();
         account_storage_usage.into()
     }
 }
@@ -396,25 +444,31 @@ where
         }
     }
 
-    /// Used to register an event handler hook to handle account unregistrations
+    /// Used to register event handler hooks to handle account unregistrations and migrations
     ///
-    /// can be safely called multiple times and will only register the event handler once
+    /// can be safely called multiple times and will only register the event handlers once
     pub fn register_storage_management_event_handler() {
         let mut registered = STORAGE_MANAGEMENT_EVENT_HANDLER_REGISTERED.lock().unwrap();
         if !*registered {
             eventbus::register(Self::on_unregister_account);
+            eventbus::register(Self::on_migrate_account);
             *registered = true;
         }
     }
 
+    /// [`oysterpack_smart_account_management::components::account_garbage_collection::AccountGcGuard`]
+    /// that retains accounts that have a token balance, which includes locked balances
+    pub fn account_holds_tokens(account_id: &str) -> bool {
+        AccountFTBalance::load(account_id).is_some()
+    }
+
     /// EventHandler must be registered to handle [`StorageManagementEvent::PreUnregister`] events
     ///
     /// When an account is forced unregistered, any tokens it owned will be burned, which reduces the total
     /// token supply.
     fn on_unregister_account(event: &StorageManagementEvent) {
-        if let StorageManagementEvent::PreUnregister { force, .. } = event {
-            let account_id = &env::predecessor_account_id();
-            if let Some(ft_balance) = AccountFTBalance::load(&account_id) {
+        if let StorageManagementEvent::PreUnregister { account_id, force } = event {
+            if let Some(ft_balance) = AccountFTBalance::load(account_id) {
                 ERR_CODE_UNREGISTER_FAILURE
                     .assert(|| *force, || "account has non-zero token balance");
                 let amount = *ft_balance;
@@ -427,6 +481,27 @@ where
             }
         }
     }
+
+    /// EventHandler must be registered to handle [`StorageManagementEvent::PreMigrate`] events
+    ///
+    /// The account's token balance, including the locked balance, is moved to the new account.
+    fn on_migrate_account(event: &StorageManagementEvent) {
+        if let StorageManagementEvent::PreMigrate {
+            account_id,
+            new_account_id,
+        } = event
+        {
+            if let Some(mut ft_balance) = AccountFTBalance::load(account_id) {
+                AccountFTBalance(AccountFTBalanceObject::new(
+                    AccountFTBalance::ft_account_id_hash(new_account_id),
+                    *ft_balance.0,
+                ))
+                .save(new_account_id);
+                *ft_balance.0 = (0, 0);
+                ft_balance.save(account_id);
+            }
+        }
+    }
 }
 
 lazy_static! {
@@ -588,6 +663,9 @@ type LockedTokenBalance = u128;
 type AccountFTBalanceObject = Object<Hash, (TokenBalance, LockedTokenBalance)>;
 struct AccountFTBalance(AccountFTBalanceObject);
 
+/// account FT balance storage
+pub const COMPONENT_ID_FT_BALANCE: ComponentId = ComponentId("ft_balance");
+
 impl AccountFTBalance {
     fn ft_account_id_hash(account_id: &str) -> Hash {
         Hash::from((account_id, FT_ACCOUNT_KEY))
@@ -620,6 +698,7 @@ impl AccountFTBalance {
                 post(&AccountStorageEvent::StorageUsageChanged(
                     account_id.into(),
                     (storage_usage_change as i64 * -1).into(),
+                    COMPONENT_ID_FT_BALANCE,
                 ));
             }
         } else {
@@ -630,6 +709,7 @@ impl AccountFTBalance {
                 post(&AccountStorageEvent::StorageUsageChanged(
                     account_id.into(),
                     storage_usage_change.into(),
+                    COMPONENT_ID_FT_BALANCE,
                 ));
             }
         }
@@ -650,6 +730,7 @@ impl AccountFTBalance {
                 post(&AccountStorageEvent::StorageUsageChanged(
                     account_id.into(),
                     storage_usage_change.into(),
+                    COMPONENT_ID_FT_BALANCE,
                 ));
             }
             Some(mut account_balance) => {
@@ -660,6 +741,7 @@ impl AccountFTBalance {
                     post(&AccountStorageEvent::StorageUsageChanged(
                         account_id.into(),
                         (storage_usage_change as i64 * -1).into(),
+                        COMPONENT_ID_FT_BALANCE,
                     ));
                 } else {
                     *account_balance = (balance, (*account_balance).1);
@@ -704,9 +786,11 @@ mod tests_fungible_token {
     const ADMIN: &str = "admin";
 
     fn deploy_comps() {
-        AccountManager::deploy(AccountManagementComponentConfig::new(to_valid_account_id(
-            ADMIN,
-        )));
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: Some(vec![STAKE::account_storage_min]),
+        });
 
         STAKE::deploy(FungibleTokenConfig {
             metadata: Metadata {
@@ -850,6 +934,26 @@ mod tests_fungible_token {
     #[cfg(test)]
     mod test_ft_transfer {
         use super::*;
+        use oysterpack_smart_account_management::AccountFreezeManagement;
+
+        #[test]
+        #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+        fn sender_frozen() {
+            run_test(Some(1000.into()), Some(0.into()), |mut ctx, mut stake| {
+                ctx.predecessor_account_id = ADMIN.to_string();
+                testing_env!(ctx.clone());
+                AccountManager::default().ops_account_freeze(
+                    to_valid_account_id(SENDER),
+                    "compromised".to_string(),
+                    None,
+                );
+
+                ctx.predecessor_account_id = SENDER.to_string();
+                ctx.attached_deposit = 1;
+                testing_env!(ctx.clone());
+                stake.ft_transfer(to_valid_account_id(RECEIVER), 400.into(), None);
+            });
+        }
 
         #[test]
         fn valid_transfer_with_no_memo() {
@@ -1026,6 +1130,98 @@ mod tests_fungible_token {
         }
     }
 
+    #[cfg(test)]
+    mod test_ft_transfer_for {
+        use super::*;
+        use oysterpack_smart_account_management::AccountDelegation;
+        use std::collections::HashMap;
+
+        const DELEGATE: &str = "bot";
+
+        fn delegate(allowance: Option<u128>) {
+            let mut actions = HashMap::new();
+            actions.insert(
+                DELEGATED_ACTION_FT_TRANSFER.to_string(),
+                allowance.map(Into::into),
+            );
+            let mut ctx = new_context(SENDER);
+            // the delegation storage must be covered by the sender's storage balance
+            ctx.attached_deposit = YOCTO;
+            testing_env!(ctx.clone());
+            AccountManager::default().storage_deposit(None, None);
+
+            ctx.attached_deposit = 1;
+            testing_env!(ctx);
+            AccountManager::default().ops_delegation_grant(
+                to_valid_account_id(DELEGATE),
+                actions,
+                None,
+            );
+        }
+
+        #[test]
+        fn valid_transfer() {
+            run_test(Some(1000.into()), Some(0.into()), |mut ctx, mut stake| {
+                delegate(Some(500));
+
+                ctx.predecessor_account_id = DELEGATE.to_string();
+                ctx.attached_deposit = 1;
+                testing_env!(ctx.clone());
+                stake.ft_transfer_for(
+                    to_valid_account_id(SENDER),
+                    to_valid_account_id(RECEIVER),
+                    400.into(),
+                    None,
+                );
+
+                assert_eq!(stake.ft_balance_of(to_valid_account_id(SENDER)), 600.into());
+                assert_eq!(
+                    stake.ft_balance_of(to_valid_account_id(RECEIVER)),
+                    400.into()
+                );
+                let delegation = AccountDelegations::delegation(SENDER, DELEGATE).unwrap();
+                assert_eq!(
+                    delegation.actions[DELEGATED_ACTION_FT_TRANSFER],
+                    Some(100.into())
+                );
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [DELEGATION_ALLOWANCE_EXCEEDED]")]
+        fn allowance_exceeded() {
+            run_test(Some(1000.into()), Some(0.into()), |mut ctx, mut stake| {
+                delegate(Some(500));
+
+                ctx.predecessor_account_id = DELEGATE.to_string();
+                ctx.attached_deposit = 1;
+                testing_env!(ctx.clone());
+                stake.ft_transfer_for(
+                    to_valid_account_id(SENDER),
+                    to_valid_account_id(RECEIVER),
+                    501.into(),
+                    None,
+                );
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [DELEGATION_NOT_AUTHORIZED]")]
+        fn not_delegated() {
+            run_test(Some(1000.into()), Some(0.into()), |mut ctx, mut stake| {
+                ctx.predecessor_account_id = DELEGATE.to_string();
+                ctx.attached_deposit = 1;
+                testing_env!(ctx.clone());
+                stake.ft_transfer_for(
+                    to_valid_account_id(SENDER),
+                    to_valid_account_id(RECEIVER),
+                    400.into(),
+                    None,
+                );
+            });
+        }
+    }
+
     #[cfg(test)]
     mod test_ft_transfer_call {
         use super::*;
@@ -1752,9 +1948,11 @@ mod tests_operator {
     const ADMIN: &str = "admin";
 
     fn deploy_comps() {
-        AccountManager::deploy(AccountManagementComponentConfig::new(to_valid_account_id(
-            ADMIN,
-        )));
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: Some(vec![STAKE::account_storage_min]),
+        });
 
         STAKE::deploy(FungibleTokenConfig {
             metadata: Metadata {
@@ -1790,7 +1988,7 @@ mod tests_operator {
             ctx.attached_deposit = 0;
             ctx.predecessor_account_id = ADMIN.to_string();
             testing_env!(ctx.clone());
-            account_manager.ops_permissions_grant_operator(to_valid_account_id(operator));
+            account_manager.ops_permissions_grant_operator(to_valid_account_id(operator), None);
         }
 
         let mut stake = STAKE::new(account_manager);
@@ -1913,9 +2111,11 @@ mod tests_token_service {
     const ADMIN: &str = "admin";
 
     fn deploy_comps() {
-        AccountManager::deploy(AccountManagementComponentConfig::new(to_valid_account_id(
-            ADMIN,
-        )));
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: None,
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: Some(vec![STAKE::account_storage_min]),
+        });
 
         STAKE::deploy(FungibleTokenConfig {
             metadata: Metadata {
@@ -2318,4 +2518,100 @@ mod tests_token_service {
             });
         }
     }
+
+    #[cfg(test)]
+    mod tests_account_migration {
+        use super::*;
+        use oysterpack_smart_account_management::{
+            AccountMigrationManagement, AccountStorageUsage,
+        };
+
+        const NEW_ACCOUNT: &str = "bob-new";
+
+        #[test]
+        fn token_balances_are_migrated() {
+            run_test(Some(1000.into()), |mut ctx, mut stake| {
+                // Arrange
+                STAKE::register_storage_management_event_handler();
+                stake.ft_lock(ACCOUNT, 400.into());
+                let mut account_manager = AccountManager::default();
+                let storage_usage = account_manager
+                    .ops_storage_usage(to_valid_account_id(ACCOUNT))
+                    .unwrap();
+
+                // the pending migration storage must be covered
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.attached_deposit = 1;
+                testing_env!(ctx.clone());
+                account_manager.ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+                // Act
+                ctx.predecessor_account_id = NEW_ACCOUNT.to_string();
+                testing_env!(ctx.clone());
+                account_manager.ops_account_migrate_accept(to_valid_account_id(ACCOUNT));
+
+                // Assert
+                assert_eq!(stake.ft_balance_of(to_valid_account_id(ACCOUNT)), 0.into());
+                assert!(stake.ft_locked_balance(ACCOUNT).is_none());
+                assert_eq!(
+                    stake.ft_balance_of(to_valid_account_id(NEW_ACCOUNT)),
+                    600.into()
+                );
+                assert_eq!(stake.ft_locked_balance(NEW_ACCOUNT).unwrap(), 400.into());
+                // the account registry stores the account ID
+                let account_id_len_diff = (NEW_ACCOUNT.len() - ACCOUNT.len()) as u64;
+                assert_eq!(
+                    account_manager
+                        .ops_storage_usage(to_valid_account_id(NEW_ACCOUNT))
+                        .unwrap(),
+                    storage_usage + account_id_len_diff.into()
+                );
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_account_gc {
+        use super::*;
+        use oysterpack_smart_account_management::components::account_garbage_collection::AccountGarbageCollectionComponent;
+        use oysterpack_smart_account_management::AccountGarbageCollection;
+
+        #[test]
+        fn accounts_with_tokens_are_retained() {
+            run_test(Some(1000.into()), |mut ctx, mut stake| {
+                // Arrange
+                STAKE::register_storage_management_event_handler();
+                let mut account_gc = AccountGarbageCollectionComponent::new(
+                    AccountManager::default(),
+                    vec![STAKE::account_holds_tokens],
+                );
+                ctx.predecessor_account_id = ADMIN.to_string();
+                testing_env!(ctx.clone());
+                account_gc.ops_accounts_gc_set_idle_epochs(Some(10.into()));
+                ctx.epoch_height = 11;
+                testing_env!(ctx.clone());
+
+                // Act
+                let report = account_gc.ops_accounts_gc(None);
+
+                // Assert
+                assert!(report.unregistered.is_empty());
+
+                // Arrange
+                stake.ft_burn(ACCOUNT, 1000.into());
+                assert!(!STAKE::account_holds_tokens(ACCOUNT));
+
+                // Act
+                let report = account_gc.ops_accounts_gc(None);
+
+                // Assert
+                assert_eq!(report.unregistered.len(), 1);
+                assert_eq!(report.unregistered[0].account_id, ACCOUNT);
+                assert!(!AccountManager::default().account_exists(ACCOUNT));
+            });
+        }
+    }
 }
@@ -0,0 +1,703 @@
+//! [`StorageTokenComponent`]
+//! - constructor: [`StorageTokenComponent::new`]
+//!   - [`AccountManagementComponent`]
+//!   - [`StorageTokenPriceFunctions`]
+//! - [`StorageTokenComponent::register_storage_management_event_handler`] must be invoked when the
+//!   [`AccountManagementComponent`] is constructed, which pays back the account's token deposits
+//!   when the account is unregistered and moves them when the account is migrated
+
+use crate::{
+    Memo, StorageTokenCallbacks, StorageTokenDeposit, StorageTokenDeposits, StorageTokenManagement,
+    StorageTokenPrice, StorageTokenPriceFunctions, StorageTokenWhitelist, TokenAmount,
+    TransferCallMessage, TransferReceiver, ERR_STORAGE_TOKEN_NOT_WHITELISTED,
+    LOG_EVENT_STORAGE_TOKEN_DEPOSIT, LOG_EVENT_STORAGE_TOKEN_REFUND_FAILURE,
+    LOG_EVENT_STORAGE_TOKEN_WITHDRAWAL,
+};
+use oysterpack_smart_account_management::{
+    components::account_management::AccountManagementComponent, AccountRepository, AuditLog,
+    StorageManagement, StorageManagementEvent,
+};
+use oysterpack_smart_near::{
+    asserts::{assert_yocto_near_attached, ERR_INVALID},
+    data::numbers::U256,
+    domain::{Gas, YoctoNear},
+    eventbus, json_function_call, json_function_callback,
+    lazy_static::lazy_static,
+    near_sdk::{
+        borsh::{BorshDeserialize, BorshSerialize},
+        env, is_promise_success,
+        json_types::ValidAccountId,
+        serde::{Deserialize, Serialize},
+        serde_json::json,
+        AccountId, Promise, PromiseOrValue,
+    },
+    to_valid_account_id, TERA,
+};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+const FT_TRANSFER_GAS: u64 = 10 * TERA;
+const RESOLVE_WITHDRAW_GAS: u64 = 10 * TERA;
+
+lazy_static! {
+    static ref STORAGE_MANAGEMENT_EVENT_HANDLER_REGISTERED: Mutex<bool> = Mutex::new(false);
+}
+
+/// Implements:
+/// - [`StorageTokenManagement`]
+/// - [`StorageTokenCallbacks`]
+/// - [`TransferReceiver`] - accepts storage token deposits
+///
+/// Whitelisted tokens that have a price function are priced on chain, i.e., their prices cannot be
+/// set by admins or operators - see [`StorageTokenPriceFunctions`]
+pub struct StorageTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    account_manager: AccountManagementComponent<T>,
+    price_functions: StorageTokenPriceFunctions,
+}
+
+impl<T> StorageTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default,
+{
+    pub fn new(
+        account_manager: AccountManagementComponent<T>,
+        price_functions: StorageTokenPriceFunctions,
+    ) -> Self {
+        Self {
+            account_manager,
+            price_functions,
+        }
+    }
+
+    /// Returns the whitelisted token's price, which is derived by the token's price function if
+    /// one is registered - returns None if the token is not whitelisted
+    pub fn token_price(&self, token_id: &str) -> Option<StorageTokenPrice> {
+        StorageTokenWhitelist::price(token_id).map(|price| self.derive_price(token_id, price))
+    }
+
+    /// the token's price function takes precedence over the specified price
+    fn derive_price(&self, token_id: &str, price: StorageTokenPrice) -> StorageTokenPrice {
+        self.price_functions
+            .get(token_id)
+            .map_or(price, |price_function| price_function())
+    }
+
+    fn assert_price_not_derived(&self, token_id: &str) {
+        ERR_INVALID.assert(
+            || !self.price_functions.contains_key(token_id),
+            || "token price is derived on chain and cannot be set",
+        );
+    }
+
+    /// Used to register event handler hooks to handle account unregistrations and migrations
+    ///
+    /// can be safely called multiple times and will only register the event handlers once
+    pub fn register_storage_management_event_handler() {
+        let mut registered = STORAGE_MANAGEMENT_EVENT_HANDLER_REGISTERED.lock().unwrap();
+        if !*registered {
+            eventbus::register(Self::on_storage_management_event);
+            *registered = true;
+        }
+    }
+
+    /// - [`StorageManagementEvent::PreUnregister`] - the account's token deposits are paid back
+    /// - [`StorageManagementEvent::PreMigrate`] - the account's token deposits are moved to the new
+    ///   account
+    fn on_storage_management_event(event: &StorageManagementEvent) {
+        match event {
+            StorageManagementEvent::PreUnregister { account_id, .. } => {
+                for deposit in StorageTokenDeposits::delete(account_id) {
+                    Self::pay_back(account_id, &deposit);
+                }
+            }
+            StorageManagementEvent::PreMigrate {
+                account_id,
+                new_account_id,
+            } => StorageTokenDeposits::migrate(account_id, new_account_id),
+            _ => {}
+        }
+    }
+
+    /// transfers the tokens back to the account, and then resolves the transfer
+    fn pay_back(account_id: &str, deposit: &StorageTokenDeposit) -> Promise {
+        LOG_EVENT_STORAGE_TOKEN_WITHDRAWAL.log(format!(
+            "account: {}, token: {}, amount: {}, NEAR credit: {}",
+            account_id, deposit.token_id, deposit.token_amount, deposit.near_credit
+        ));
+        json_function_call(
+            &deposit.token_id,
+            "ft_transfer",
+            Some(FtTransferArgs {
+                receiver_id: account_id.to_string(),
+                amount: deposit.token_amount,
+                memo: Some(Memo("storage token withdrawal".to_string())),
+            }),
+            1.into(),
+            Gas(FT_TRANSFER_GAS),
+        )
+        .then(json_function_callback(
+            "ops_storage_token_resolve_withdraw",
+            Some(ResolveWithdrawArgs {
+                account_id: account_id.to_string(),
+                token_id: deposit.token_id.clone(),
+                token_amount: deposit.token_amount,
+                near_credit: deposit.near_credit,
+            }),
+            YoctoNear::ZERO,
+            Gas(RESOLVE_WITHDRAW_GAS),
+        ))
+    }
+
+    fn assert_valid_price(price: &StorageTokenPrice) {
+        ERR_INVALID.assert(
+            || price.is_valid(),
+            || "price token amount and NEAR value must not be zero",
+        );
+    }
+}
+
+impl<T> StorageTokenManagement for StorageTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_storage_token_whitelist(
+        &mut self,
+        token_id: ValidAccountId,
+        price: Option<StorageTokenPrice>,
+    ) {
+        self.account_manager
+            .assert_admin("ops_storage_token_whitelist");
+        match price {
+            Some(price) => {
+                // tokens that are priced on chain are whitelisted at their derived price
+                let price = self.derive_price(token_id.as_ref(), price);
+                Self::assert_valid_price(&price);
+                StorageTokenWhitelist::set_price(token_id.as_ref(), price);
+            }
+            None => {
+                StorageTokenWhitelist::remove(token_id.as_ref());
+            }
+        }
+        AuditLog::record(
+            "ops_storage_token_whitelist",
+            Some(token_id.as_ref()),
+            Some(json!({ "price": price }).to_string()),
+        );
+    }
+
+    fn ops_storage_token_update_price(
+        &mut self,
+        token_id: ValidAccountId,
+        price: StorageTokenPrice,
+    ) {
+        self.account_manager
+            .assert_operator("ops_storage_token_update_price");
+        ERR_STORAGE_TOKEN_NOT_WHITELISTED
+            .assert(|| StorageTokenWhitelist::price(token_id.as_ref()).is_some());
+        self.assert_price_not_derived(token_id.as_ref());
+        Self::assert_valid_price(&price);
+        StorageTokenWhitelist::set_price(token_id.as_ref(), price);
+    }
+
+    fn ops_storage_tokens(&self) -> BTreeMap<AccountId, StorageTokenPrice> {
+        StorageTokenWhitelist::load()
+            .into_iter()
+            .map(|(token_id, price)| {
+                let price = self.derive_price(&token_id, price);
+                (token_id, price)
+            })
+            .collect()
+    }
+
+    fn ops_storage_token_deposits(&self, account_id: ValidAccountId) -> Vec<StorageTokenDeposit> {
+        StorageTokenDeposits::load(account_id.as_ref())
+    }
+
+    fn ops_storage_token_withdraw(
+        &mut self,
+        token_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> Promise {
+        assert_yocto_near_attached();
+        let account_id = env::predecessor_account_id();
+        let deposit = StorageTokenDeposits::get(&account_id, token_id.as_ref());
+        ERR_INVALID.assert(
+            || deposit.is_some(),
+            || "account has no deposit for the token",
+        );
+        let deposit = deposit.unwrap();
+        let amount = amount.unwrap_or_else(|| {
+            self.account_manager
+                .storage_balance_of(to_valid_account_id(&account_id))
+                .map_or(YoctoNear::ZERO, |balance| balance.available)
+                .min(deposit.near_credit)
+        });
+        ERR_INVALID.assert(
+            || amount <= deposit.near_credit,
+            || "amount exceeds the token deposit NEAR credit",
+        );
+
+        self.account_manager
+            .withdraw_token_credit(&account_id, amount);
+        let token_amount = StorageTokenDeposits::withdraw(&account_id, token_id.as_ref(), amount);
+        ERR_INVALID.assert(
+            || token_amount > TokenAmount::ZERO,
+            || "amount is too small to pay back any tokens",
+        );
+
+        Self::pay_back(
+            &account_id,
+            &StorageTokenDeposit {
+                token_id: token_id.as_ref().to_string(),
+                token_amount,
+                near_credit: amount,
+            },
+        )
+    }
+}
+
+impl<T> StorageTokenCallbacks for StorageTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    fn ops_storage_token_resolve_withdraw(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        token_amount: TokenAmount,
+        near_credit: YoctoNear,
+    ) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+
+        if self.account_manager.account_exists(&account_id) {
+            let near_credit = self
+                .account_manager
+                .deposit_token_credit(&account_id, near_credit);
+            StorageTokenDeposits::deposit(&account_id, &token_id, token_amount, near_credit);
+            LOG_EVENT_STORAGE_TOKEN_REFUND_FAILURE.log(format!(
+                "token deposit was restored: account: {}, token: {}, amount: {}",
+                account_id, token_id, token_amount
+            ));
+        } else {
+            LOG_EVENT_STORAGE_TOKEN_REFUND_FAILURE.log(format!(
+                "account is not registered - tokens are held by the contract: account: {}, token: {}, amount: {}",
+                account_id, token_id, token_amount
+            ));
+        }
+        false
+    }
+}
+
+impl<T> TransferReceiver for StorageTokenComponent<T>
+where
+    T: BorshSerialize + BorshDeserialize + Clone + Debug + PartialEq + Default + 'static,
+{
+    /// Credits the `sender_id` account with NEAR storage credit - `msg` is not used
+    ///
+    /// Returns the tokens that were not needed because the account's storage balance reached the
+    /// max bound.
+    ///
+    /// ## Panics
+    /// - [`ERR_STORAGE_TOKEN_NOT_WHITELISTED`] - if the predecessor token contract is not whitelisted
+    /// - if the account is not registered and the NEAR credit does not cover the min storage balance
+    /// - if the NEAR credit does not cover the account's storage usage
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: ValidAccountId,
+        amount: TokenAmount,
+        _msg: TransferCallMessage,
+    ) -> PromiseOrValue<TokenAmount> {
+        let token_id = env::predecessor_account_id();
+        let price = self.token_price(&token_id);
+        ERR_STORAGE_TOKEN_NOT_WHITELISTED.assert(|| price.is_some());
+        let price = price.unwrap();
+        Self::assert_valid_price(&price);
+        let near_value = price.near_value(amount);
+
+        let account_id = sender_id.as_ref();
+        let near_credit = self
+            .account_manager
+            .deposit_token_credit(account_id, near_value);
+        // the tokens that are kept are rounded up
+        let token_amount = if near_credit == near_value {
+            amount
+        } else {
+            let unused = U256::from(*amount) * U256::from(*near_value - *near_credit)
+                / U256::from(*near_value);
+            amount - unused.as_u128().into()
+        };
+        if near_credit > YoctoNear::ZERO {
+            StorageTokenDeposits::deposit(account_id, &token_id, token_amount, near_credit);
+            LOG_EVENT_STORAGE_TOKEN_DEPOSIT.log(format!(
+                "account: {}, token: {}, amount: {}, NEAR credit: {}",
+                account_id, token_id, token_amount, near_credit
+            ));
+        }
+
+        PromiseOrValue::Value(if near_credit > YoctoNear::ZERO {
+            amount - token_amount
+        } else {
+            amount
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+struct FtTransferArgs {
+    receiver_id: AccountId,
+    amount: TokenAmount,
+    memo: Option<Memo>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+struct ResolveWithdrawArgs {
+    account_id: AccountId,
+    token_id: AccountId,
+    token_amount: TokenAmount,
+    near_credit: YoctoNear,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StorageTokenPriceFn;
+    use oysterpack_smart_account_management::components::account_management::AccountManagementComponentConfig;
+    use oysterpack_smart_account_management::{
+        AccountMigrationManagement, StorageTokenCredit, StorageUsageBounds,
+    };
+    use oysterpack_smart_near::component::Deploy;
+    use oysterpack_smart_near::near_sdk::{self, serde_json, test_utils, VMContext};
+    use oysterpack_smart_near::{domain::StorageUsage, YOCTO};
+    use oysterpack_smart_near_test::*;
+
+    type AccountManager = AccountManagementComponent<()>;
+    type StorageToken = StorageTokenComponent<()>;
+
+    const ADMIN: &str = "admin";
+    const ACCOUNT: &str = "alice";
+    const TOKEN: &str = "stake.pool";
+
+    /// 1 token is worth 0.5 NEAR
+    const PRICE: StorageTokenPrice = StorageTokenPrice {
+        token_amount: TokenAmount(U128(YOCTO)),
+        near_value: YoctoNear(YOCTO / 2),
+    };
+
+    use oysterpack_smart_near::data::numbers::U128;
+
+    fn deploy(max: Option<StorageUsage>) -> (VMContext, StorageToken) {
+        let ctx = new_context(ADMIN);
+        testing_env!(ctx.clone());
+        AccountManager::deploy(AccountManagementComponentConfig {
+            storage_usage_bounds: Some(StorageUsageBounds {
+                min: AccountManager::measure_storage_usage(()),
+                max,
+            }),
+            admin_account: to_valid_account_id(ADMIN),
+            component_account_storage_mins: None,
+        });
+        StorageToken::register_storage_management_event_handler();
+        let mut storage_token =
+            StorageToken::new(AccountManager::new(Default::default()), Default::default());
+        storage_token.ops_storage_token_whitelist(to_valid_account_id(TOKEN), Some(PRICE));
+        (ctx, storage_token)
+    }
+
+    fn deposit(ctx: &mut VMContext, storage_token: &mut StorageToken, amount: u128) -> TokenAmount {
+        ctx.predecessor_account_id = TOKEN.to_string();
+        ctx.attached_deposit = 0;
+        testing_env!(ctx.clone());
+        match storage_token.ft_on_transfer(
+            to_valid_account_id(ACCOUNT),
+            amount.into(),
+            TransferCallMessage("".to_string()),
+        ) {
+            PromiseOrValue::Value(unused) => unused,
+            _ => panic!("expected value"),
+        }
+    }
+
+    fn ft_transfers() -> Vec<(String, FtTransferArgs)> {
+        deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::FunctionCall(action) if action.method_name == "ft_transfer" => {
+                    assert_eq!(action.deposit, 1);
+                    Some((
+                        receipt.receiver_id.clone(),
+                        serde_json::from_str(action.args.as_str()).unwrap(),
+                    ))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deposit_registers_account() {
+        // Arrange
+        let (mut ctx, mut storage_token) = deploy(None);
+
+        // Act
+        let unused = deposit(&mut ctx, &mut storage_token, 2 * YOCTO);
+
+        // Assert
+        assert_eq!(unused, TokenAmount::ZERO);
+        assert!(storage_token.account_manager.account_exists(ACCOUNT));
+        assert_eq!(
+            storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT)),
+            vec![StorageTokenDeposit {
+                token_id: TOKEN.to_string(),
+                token_amount: (2 * YOCTO).into(),
+                near_credit: YOCTO.into(),
+            }]
+        );
+        assert_eq!(StorageTokenCredit::load(ACCOUNT), YOCTO.into());
+        let storage_balance = storage_token
+            .account_manager
+            .storage_balance_of(to_valid_account_id(ACCOUNT))
+            .unwrap();
+        assert_eq!(storage_balance.total, YOCTO.into());
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[INFO] [STORAGE_TOKEN_DEPOSIT]")));
+
+        // Act - deposit more tokens
+        deposit(&mut ctx, &mut storage_token, YOCTO);
+        assert_eq!(
+            storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT))[0].near_credit,
+            (YOCTO + YOCTO / 2).into()
+        );
+    }
+
+    #[test]
+    fn unused_tokens_are_returned_when_max_storage_balance_is_reached() {
+        // Arrange
+        testing_env!(new_context(ADMIN));
+        let min = AccountManager::measure_storage_usage(());
+        let (mut ctx, mut storage_token) = deploy(Some((min.value() * 2).into()));
+        let max = storage_token
+            .account_manager
+            .storage_balance_bounds()
+            .max
+            .unwrap();
+
+        // Act
+        let unused = deposit(&mut ctx, &mut storage_token, 2 * YOCTO);
+
+        // Assert
+        let deposits = storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT));
+        assert_eq!(deposits[0].near_credit, max);
+        assert_eq!(unused + deposits[0].token_amount, (2 * YOCTO).into());
+        assert_eq!(*deposits[0].token_amount, 2 * *max);
+
+        // Act - the storage balance is already at the max
+        let unused = deposit(&mut ctx, &mut storage_token, YOCTO);
+        assert_eq!(unused, YOCTO.into());
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [STORAGE_TOKEN_NOT_WHITELISTED]")]
+    fn deposit_from_token_not_whitelisted() {
+        let (mut ctx, mut storage_token) = deploy(None);
+        ctx.predecessor_account_id = ADMIN.to_string();
+        testing_env!(ctx.clone());
+        storage_token.ops_storage_token_whitelist(to_valid_account_id(TOKEN), None);
+        deposit(&mut ctx, &mut storage_token, YOCTO);
+    }
+
+    /// 1 token is worth 2 NEAR
+    fn derived_price() -> StorageTokenPrice {
+        StorageTokenPrice {
+            token_amount: YOCTO.into(),
+            near_value: (2 * YOCTO).into(),
+        }
+    }
+
+    fn with_derived_price(storage_token: StorageToken) -> StorageToken {
+        let mut price_functions = StorageTokenPriceFunctions::new();
+        price_functions.insert(TOKEN.to_string(), derived_price as StorageTokenPriceFn);
+        StorageToken::new(storage_token.account_manager, price_functions)
+    }
+
+    #[test]
+    fn deposit_with_derived_price() {
+        // Arrange
+        let (mut ctx, storage_token) = deploy(None);
+        let mut storage_token = with_derived_price(storage_token);
+        assert_eq!(
+            storage_token.ops_storage_tokens().get(TOKEN).cloned(),
+            Some(derived_price())
+        );
+        // the whitelisted price is not used
+        assert_eq!(StorageTokenWhitelist::price(TOKEN), Some(PRICE));
+
+        // Act
+        deposit(&mut ctx, &mut storage_token, YOCTO);
+
+        // Assert
+        assert_eq!(
+            storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT))[0].near_credit,
+            (2 * YOCTO).into()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INVALID]")]
+    fn update_derived_price() {
+        let (mut ctx, storage_token) = deploy(None);
+        let mut storage_token = with_derived_price(storage_token);
+        ctx.predecessor_account_id = ADMIN.to_string();
+        testing_env!(ctx.clone());
+        storage_token.ops_storage_token_update_price(to_valid_account_id(TOKEN), PRICE);
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INSUFFICIENT_STORAGE_BALANCE]")]
+    fn deposit_must_cover_min_storage_balance() {
+        let (mut ctx, mut storage_token) = deploy(None);
+        // the NEAR credit is half the token amount
+        let min = storage_token.account_manager.storage_balance_bounds().min;
+        deposit(&mut ctx, &mut storage_token, *min);
+    }
+
+    #[test]
+    fn withdraw_pays_back_tokens() {
+        // Arrange
+        let (mut ctx, mut storage_token) = deploy(None);
+        deposit(&mut ctx, &mut storage_token, 2 * YOCTO);
+        let min = storage_token.account_manager.storage_balance_bounds().min;
+
+        // Act
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        storage_token.ops_storage_token_withdraw(to_valid_account_id(TOKEN), None);
+
+        // Assert - the account's storage usage remains covered by the token credit
+        let deposits = storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT));
+        let storage_balance = storage_token
+            .account_manager
+            .storage_balance_of(to_valid_account_id(ACCOUNT))
+            .unwrap();
+        assert_eq!(storage_balance.available, YoctoNear::ZERO);
+        assert_eq!(deposits[0].near_credit, storage_balance.total);
+        assert!(storage_balance.total >= min);
+        let transfers = ft_transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].0, TOKEN);
+        assert_eq!(transfers[0].1.receiver_id, ACCOUNT);
+        assert_eq!(
+            transfers[0].1.amount + deposits[0].token_amount,
+            (2 * YOCTO).into()
+        );
+
+        // Act - the ft_transfer failed
+        let withdrawn_tokens = transfers[0].1.amount;
+        ctx.predecessor_account_id = env::current_account_id();
+        ctx.attached_deposit = 0;
+        testing_env_with_promise_result_failure(ctx.clone());
+        assert!(!storage_token.ops_storage_token_resolve_withdraw(
+            ACCOUNT.to_string(),
+            TOKEN.to_string(),
+            withdrawn_tokens,
+            YoctoNear(YOCTO) - storage_balance.total,
+        ));
+
+        // Assert - the token deposit is restored
+        assert_eq!(
+            storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT)),
+            vec![StorageTokenDeposit {
+                token_id: TOKEN.to_string(),
+                token_amount: (2 * YOCTO).into(),
+                near_credit: YOCTO.into(),
+            }]
+        );
+        assert_eq!(StorageTokenCredit::load(ACCOUNT), YOCTO.into());
+        assert!(test_utils::get_logs()
+            .iter()
+            .any(|log| log.starts_with("[WARN] [STORAGE_TOKEN_REFUND_FAILURE]")));
+    }
+
+    #[test]
+    #[should_panic(expected = "[ERR] [INSUFFICIENT_STORAGE_BALANCE]")]
+    fn withdraw_locked_token_credit() {
+        let (mut ctx, mut storage_token) = deploy(None);
+        deposit(&mut ctx, &mut storage_token, 2 * YOCTO);
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        storage_token.ops_storage_token_withdraw(to_valid_account_id(TOKEN), Some(YOCTO.into()));
+    }
+
+    #[test]
+    fn unregister_pays_back_tokens() {
+        // Arrange
+        let (mut ctx, mut storage_token) = deploy(None);
+        deposit(&mut ctx, &mut storage_token, 2 * YOCTO);
+
+        // Act
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        assert!(storage_token.account_manager.storage_unregister(None));
+
+        // Assert
+        let transfers = ft_transfers();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].1.receiver_id, ACCOUNT);
+        assert_eq!(transfers[0].1.amount, (2 * YOCTO).into());
+        assert!(storage_token
+            .ops_storage_token_deposits(to_valid_account_id(ACCOUNT))
+            .is_empty());
+        // the token credit is not refunded as NEAR
+        let near_refunds: Vec<u128> = deserialize_receipts()
+            .iter()
+            .filter_map(|receipt| match &receipt.actions[0] {
+                Action::Transfer(action) => Some(action.deposit),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(near_refunds, vec![1]);
+    }
+
+    #[test]
+    fn migration_moves_token_deposits() {
+        // Arrange
+        let (mut ctx, mut storage_token) = deploy(None);
+        deposit(&mut ctx, &mut storage_token, 2 * YOCTO);
+        let deposits = storage_token.ops_storage_token_deposits(to_valid_account_id(ACCOUNT));
+
+        // Act
+        ctx.predecessor_account_id = ACCOUNT.to_string();
+        ctx.attached_deposit = 1;
+        testing_env!(ctx.clone());
+        storage_token
+            .account_manager
+            .ops_account_migrate(to_valid_account_id("alice-new"));
+        ctx.predecessor_account_id = "alice-new".to_string();
+        testing_env!(ctx.clone());
+        storage_token
+            .account_manager
+            .ops_account_migrate_accept(to_valid_account_id(ACCOUNT));
+
+        // Assert
+        assert!(storage_token
+            .ops_storage_token_deposits(to_valid_account_id(ACCOUNT))
+            .is_empty());
+        assert_eq!(
+            storage_token.ops_storage_token_deposits(to_valid_account_id("alice-new")),
+            deposits
+        );
+        assert_eq!(StorageTokenCredit::load("alice-new"), YOCTO.into());
+        assert_eq!(StorageTokenCredit::load(ACCOUNT), YoctoNear::ZERO);
+    }
+}
@@ -1,9 +1,11 @@
 mod memo;
 mod metadata;
+mod storage_token;
 mod token_amount;
 mod transfer_call_message;
 
 pub use memo::*;
 pub use metadata::*;
+pub use storage_token::*;
 pub use token_amount::*;
 pub use transfer_call_message::*;
@@ -0,0 +1,227 @@
+use crate::TokenAmount;
+use oysterpack_smart_account_management::{AccountIdHash, AccountStorageEvent, ComponentId};
+use oysterpack_smart_near::near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{
+    data::{numbers::U256, Object},
+    domain::YoctoNear,
+    eventbus, Hash,
+};
+use std::collections::BTreeMap;
+
+const STORAGE_TOKEN_WHITELIST_KEY: u128 = 1958297760391558620190412870251631319;
+const STORAGE_TOKEN_DEPOSITS_KEY: u128 = 1958297801945217446837009816427361731;
+
+type WhitelistDAO = Object<u128, BTreeMap<AccountId, StorageTokenPrice>>;
+type DepositsDAO = Object<(u128, AccountIdHash), Vec<StorageTokenDeposit>>;
+
+/// Price used to convert fungible tokens into NEAR storage credit, i.e., `token_amount` tokens are
+/// worth `near_value`.
+///
+/// For example, the price of a STAKE token is its [`crate::TokenAmount`] value in NEAR, which is
+/// provided by the STAKE pool's `ops_stake_token_value` function.
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct StorageTokenPrice {
+    pub token_amount: TokenAmount,
+    pub near_value: YoctoNear,
+}
+
+impl StorageTokenPrice {
+    pub fn is_valid(&self) -> bool {
+        self.token_amount > TokenAmount::ZERO && self.near_value > YoctoNear::ZERO
+    }
+
+    /// NEAR value of the specified token amount - rounded down
+    pub fn near_value(&self, amount: TokenAmount) -> YoctoNear {
+        (U256::from(*amount) * U256::from(*self.near_value) / U256::from(*self.token_amount))
+            .as_u128()
+            .into()
+    }
+}
+
+/// Derives a whitelisted token's price on chain, e.g., the STAKE pool's own STAKE token price is
+/// derived from `ops_stake_token_value`
+pub type StorageTokenPriceFn = fn() -> StorageTokenPrice;
+
+/// token contract account ID -> price function
+pub type StorageTokenPriceFunctions = BTreeMap<AccountId, StorageTokenPriceFn>;
+
+/// Fungible token contracts that are accepted to pay for account storage
+pub struct StorageTokenWhitelist;
+
+impl StorageTokenWhitelist {
+    /// token contract account ID -> price
+    pub fn load() -> BTreeMap<AccountId, StorageTokenPrice> {
+        WhitelistDAO::load(&STORAGE_TOKEN_WHITELIST_KEY)
+            .map_or_else(BTreeMap::new, |whitelist| (*whitelist).clone())
+    }
+
+    /// returns None if the token is not whitelisted
+    pub fn price(token_id: &str) -> Option<StorageTokenPrice> {
+        Self::load().get(token_id).cloned()
+    }
+
+    pub(crate) fn set_price(token_id: &str, price: StorageTokenPrice) {
+        let mut whitelist = Self::load();
+        whitelist.insert(token_id.to_string(), price);
+        WhitelistDAO::new(STORAGE_TOKEN_WHITELIST_KEY, whitelist).save();
+    }
+
+    /// returns false if the token was not whitelisted
+    pub(crate) fn remove(token_id: &str) -> bool {
+        let mut whitelist = Self::load();
+        if whitelist.remove(token_id).is_none() {
+            return false;
+        }
+        if whitelist.is_empty() {
+            WhitelistDAO::delete_by_key(&STORAGE_TOKEN_WHITELIST_KEY);
+        } else {
+            WhitelistDAO::new(STORAGE_TOKEN_WHITELIST_KEY, whitelist).save();
+        }
+        true
+    }
+}
+
+/// Tokens that the account deposited to pay for storage, and the NEAR storage credit the tokens were
+/// exchanged for
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct StorageTokenDeposit {
+    pub token_id: AccountId,
+    pub token_amount: TokenAmount,
+    pub near_credit: YoctoNear,
+}
+
+/// [`StorageTokenDeposits`] storage
+pub const COMPONENT_ID_STORAGE_TOKEN_DEPOSITS: ComponentId = ComponentId("storage_token_deposits");
+
+/// Per account storage token deposits
+///
+/// The deposits are charged to the account's storage usage, i.e.,
+/// [`AccountStorageEvent::StorageUsageChanged`] events are posted.
+pub struct StorageTokenDeposits;
+
+impl StorageTokenDeposits {
+    /// returns the account's deposits sorted by token ID
+    pub fn load(account_id: &str) -> Vec<StorageTokenDeposit> {
+        DepositsDAO::load(&Self::key(account_id))
+            .map_or_else(Vec::new, |deposits| (*deposits).clone())
+    }
+
+    pub fn get(account_id: &str, token_id: &str) -> Option<StorageTokenDeposit> {
+        Self::load(account_id)
+            .into_iter()
+            .find(|deposit| deposit.token_id == token_id)
+    }
+
+    pub(crate) fn deposit(
+        account_id: &str,
+        token_id: &str,
+        token_amount: TokenAmount,
+        near_credit: YoctoNear,
+    ) {
+        let mut deposits = Self::load(account_id);
+        match deposits
+            .iter_mut()
+            .find(|deposit| deposit.token_id == token_id)
+        {
+            Some(deposit) => {
+                deposit.token_amount = deposit.token_amount + token_amount;
+                deposit.near_credit = deposit.near_credit + near_credit;
+            }
+            None => {
+                deposits.push(StorageTokenDeposit {
+                    token_id: token_id.to_string(),
+                    token_amount,
+                    near_credit,
+                });
+                deposits.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+            }
+        }
+        Self::save(account_id, deposits);
+    }
+
+    /// Debits the NEAR credit from the token deposit and returns the tokens that are owed back to the
+    /// account, which are proportional to the credit - rounded down
+    ///
+    /// ## Panics
+    /// if the NEAR credit exceeds the deposit's NEAR credit
+    pub(crate) fn withdraw(
+        account_id: &str,
+        token_id: &str,
+        near_credit: YoctoNear,
+    ) -> TokenAmount {
+        let mut deposits = Self::load(account_id);
+        let deposit = deposits
+            .iter_mut()
+            .find(|deposit| deposit.token_id == token_id)
+            .expect("storage token deposit not found");
+        assert!(
+            near_credit <= deposit.near_credit,
+            "NEAR credit exceeds the storage token deposit credit"
+        );
+        let token_amount = if near_credit == deposit.near_credit {
+            deposit.token_amount
+        } else {
+            (U256::from(*deposit.token_amount) * U256::from(*near_credit)
+                / U256::from(*deposit.near_credit))
+            .as_u128()
+            .into()
+        };
+        deposit.token_amount = deposit.token_amount - token_amount;
+        deposit.near_credit = deposit.near_credit - near_credit;
+        deposits.retain(|deposit| deposit.near_credit > YoctoNear::ZERO);
+        Self::save(account_id, deposits);
+        token_amount
+    }
+
+    /// deletes and returns the account's deposits
+    pub(crate) fn delete(account_id: &str) -> Vec<StorageTokenDeposit> {
+        let deposits = Self::load(account_id);
+        if !deposits.is_empty() {
+            Self::save(account_id, vec![]);
+        }
+        deposits
+    }
+
+    /// moves the account's deposits to the new account
+    pub(crate) fn migrate(account_id: &str, new_account_id: &str) {
+        let deposits = Self::delete(account_id);
+        if !deposits.is_empty() {
+            Self::save(new_account_id, deposits);
+        }
+    }
+
+    /// empty deposits are deleted
+    fn save(account_id: &str, deposits: Vec<StorageTokenDeposit>) {
+        let key = Self::key(account_id);
+        let storage_usage_before = env::storage_usage();
+        if deposits.is_empty() {
+            DepositsDAO::delete_by_key(&key);
+        } else {
+            DepositsDAO::new(key, deposits).save();
+        }
+        let change = env::storage_usage() as i64 - storage_usage_before as i64;
+        if change != 0 {
+            eventbus::post(&AccountStorageEvent::StorageUsageChanged(
+                key.1,
+                change.into(),
+                COMPONENT_ID_STORAGE_TOKEN_DEPOSITS,
+            ));
+        }
+    }
+
+    fn key(account_id: &str) -> (u128, AccountIdHash) {
+        (
+            STORAGE_TOKEN_DEPOSITS_KEY,
+            AccountIdHash(Hash::from(account_id)),
+        )
+    }
+}
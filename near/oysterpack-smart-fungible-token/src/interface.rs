@@ -1,6 +1,9 @@
+pub use contract::batch_registration::*;
+pub use contract::delegation::*;
 pub use contract::fungible_token::*;
 pub use contract::metadata::*;
 pub use contract::operator::*;
+pub use contract::storage_token::*;
 pub use token_service::*;
 
 pub mod contract;
@@ -1,5 +1,8 @@
 //! Contract Interfaces
 
+pub mod batch_registration;
+pub mod delegation;
 pub mod fungible_token;
 pub mod metadata;
 pub mod operator;
+pub mod storage_token;
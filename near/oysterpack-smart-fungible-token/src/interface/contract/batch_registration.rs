@@ -0,0 +1,100 @@
+use crate::TokenAmount;
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{
+    json_types::ValidAccountId,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+use oysterpack_smart_near::{Level, LogEvent};
+
+/// # **Contract Interface**: Batch Registration API
+///
+/// Enables admins to register accounts in bulk for campaigns, e.g., airdrops. The contract pays for
+/// the account storage, which can be capped by a budget that is tracked as a
+/// [`oysterpack_smart_contract::ContractNearBalances`] balance. Tokens can optionally be distributed
+/// to each account in the batch.
+///
+/// Batches are processed in resumable pages to stay within the transaction gas limit - see
+/// [`BatchRegistrationPage::next`]
+pub trait BatchRegistration {
+    /// Registers the accounts in the batch that are not yet registered and distributes tokens to
+    /// each account in the batch.
+    ///
+    /// Processing stops early when there is not enough gas left to process the next account or when
+    /// the budget is exhausted. The same request should then be resubmitted with `start` set to
+    /// [`BatchRegistrationPage::next`].
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_BATCH_REGISTRATION`]
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if `start` is out of range
+    /// - if tokens are minted but minting is not enabled
+    /// - if tokens are transferred and the predecessor account has insufficient funds
+    fn ops_batch_register(&mut self, request: BatchRegistrationRequest) -> BatchRegistrationPage;
+
+    /// Credits the attached deposit to the batch registration budget and returns the updated budget.
+    ///
+    /// `#[payable]`
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if no deposit is attached
+    /// - if the contract is not configured with a budget
+    fn ops_batch_registration_budget_deposit(&mut self) -> YoctoNear;
+
+    /// Clears the budget and returns the amount that was released back to the contract owner's balance.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if the contract is not configured with a budget
+    fn ops_batch_registration_budget_release(&mut self) -> YoctoNear;
+
+    /// Returns None if the contract is not configured with a budget, which means the contract owner
+    /// pays for the account storage
+    fn ops_batch_registration_budget(&self) -> Option<YoctoNear>;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct BatchRegistrationRequest {
+    pub accounts: Vec<ValidAccountId>,
+    /// tokens distributed to each account in the batch
+    pub tokens: Option<TokenDistribution>,
+    /// index of the first account to process - defaults to 0
+    pub start: Option<u32>,
+    /// max number of accounts to process - if not specified, then as many accounts as the gas allows
+    /// will be processed
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub enum TokenDistribution {
+    /// tokens are minted for each account
+    Mint(TokenAmount),
+    /// tokens are transferred from the predecessor account to each account
+    Transfer(TokenAmount),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct BatchRegistrationResult {
+    pub account_id: AccountId,
+    /// false if the account was already registered
+    pub registered: bool,
+    pub tokens: Option<TokenAmount>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "oysterpack_smart_near::near_sdk::serde")]
+pub struct BatchRegistrationPage {
+    pub results: Vec<BatchRegistrationResult>,
+    /// index of the next account to process - None if all accounts in the batch were processed
+    pub next: Option<u32>,
+    /// true if processing stopped because the budget could not cover the next account's storage
+    pub budget_exhausted: bool,
+}
+
+pub const LOG_EVENT_BATCH_REGISTRATION: LogEvent = LogEvent(Level::INFO, "BATCH_REGISTRATION");
@@ -0,0 +1,30 @@
+use crate::{Memo, TokenAmount};
+use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
+
+/// # **Contract Interface**: Fungible Token Delegation API
+///
+/// Enables delegates to transfer tokens on behalf of accounts that have delegated the
+/// [`DELEGATED_ACTION_FT_TRANSFER`] action to them - see [`oysterpack_smart_account_management::AccountDelegation`]
+///
+/// The delegation allowance is specified as a token amount.
+pub trait FungibleTokenDelegation {
+    /// Transfers tokens from `account_id` to `receiver_id` on behalf of `account_id`, where the
+    /// predecessor account is the delegate.
+    /// - the transfer amount is debited from the delegation allowance
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached
+    ///
+    /// ## Panics
+    /// - if the predecessor account has not been delegated the [`DELEGATED_ACTION_FT_TRANSFER`] action
+    /// - if the delegation is expired or the amount exceeds the remaining allowance
+    /// - for the same reasons as [`crate::FungibleToken::ft_transfer`]
+    fn ft_transfer_for(
+        &mut self,
+        account_id: ValidAccountId,
+        receiver_id: ValidAccountId,
+        amount: TokenAmount,
+        memo: Option<Memo>,
+    );
+}
+
+pub const DELEGATED_ACTION_FT_TRANSFER: &str = "ft_transfer";
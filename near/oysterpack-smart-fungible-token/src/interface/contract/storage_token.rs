@@ -0,0 +1,116 @@
+use crate::{StorageTokenDeposit, StorageTokenPrice, TokenAmount};
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{json_types::ValidAccountId, AccountId, Promise};
+use oysterpack_smart_near::{ErrCode, ErrorConst, Level, LogEvent};
+use std::collections::BTreeMap;
+
+/// # **Contract Interface**: Storage Token API
+///
+/// Enables accounts to pay for storage with fungible tokens instead of NEAR. Tokens are deposited
+/// via `ft_transfer_call` from whitelisted token contracts, i.e., the contract's
+/// [`crate::TransferReceiver::ft_on_transfer`] handles the deposit:
+/// - the tokens are converted into NEAR storage credit using the token's [`StorageTokenPrice`],
+///   which is controlled by the contract
+/// - the deposit is credited to the `sender_id` account - if the account is not registered, then
+///   the account is registered, which requires the NEAR credit to cover the min storage balance
+/// - tokens that are not needed because the storage balance max bound was reached are returned
+///
+/// The NEAR storage credit is not withdrawable as NEAR - it is paid back in the original tokens,
+/// at the rate the tokens were deposited at. When the account is unregistered, the tokens are
+/// transferred back to the account.
+///
+/// **NOTE:** the token deposits are charged to the account's storage usage - thus the NEAR credit
+/// must cover the storage used to track the deposit as well.
+pub trait StorageTokenManagement {
+    /// Adds the token to the whitelist with the specified price, or updates its price.
+    /// Tokens that are priced on chain are whitelisted at their derived price.
+    /// If the price is None, then the token is removed from the whitelist. Existing token deposits
+    /// can still be withdrawn after the token is removed from the whitelist.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an admin
+    /// - if the price token amount or NEAR value is zero
+    fn ops_storage_token_whitelist(
+        &mut self,
+        token_id: ValidAccountId,
+        price: Option<StorageTokenPrice>,
+    );
+
+    /// Used by operators to keep whitelisted token prices current, e.g., STAKE token prices are
+    /// updated from the STAKE pool's `ops_stake_token_value`
+    ///
+    /// Tokens that are priced on chain by the contract, e.g., the STAKE pool's own STAKE token, are
+    /// always valued at their derived price - their prices cannot be updated.
+    ///
+    /// ## Panics
+    /// - if the predecessor account is not an operator
+    /// - [`ERR_STORAGE_TOKEN_NOT_WHITELISTED`]
+    /// - if the token price is derived on chain
+    /// - if the price token amount or NEAR value is zero
+    fn ops_storage_token_update_price(
+        &mut self,
+        token_id: ValidAccountId,
+        price: StorageTokenPrice,
+    );
+
+    /// whitelisted token contract account ID -> price
+    fn ops_storage_tokens(&self) -> BTreeMap<AccountId, StorageTokenPrice>;
+
+    fn ops_storage_token_deposits(&self, account_id: ValidAccountId) -> Vec<StorageTokenDeposit>;
+
+    /// Withdraws the specified amount of NEAR storage credit from the token deposit, which is paid
+    /// back in tokens via `ft_transfer`. If the amount is not specified, then the token deposit's
+    /// available NEAR credit is withdrawn.
+    ///
+    /// If the token transfer fails, then the token deposit is restored.
+    ///
+    /// `#[payable]` - requires exactly 1 yoctoNEAR to be attached, which is attached to `ft_transfer`
+    ///
+    /// ## Log Events
+    /// - [`LOG_EVENT_STORAGE_TOKEN_WITHDRAWAL`]
+    ///
+    /// ## Panics
+    /// - if 1 yoctoNEAR is not attached
+    /// - if the account has no deposit for the token
+    /// - if the account is frozen
+    /// - if the amount exceeds the token deposit's NEAR credit or the account's available storage
+    ///   balance
+    /// - if the amount is too small to pay back any tokens
+    fn ops_storage_token_withdraw(
+        &mut self,
+        token_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> Promise;
+}
+
+/// # **Contract Interface**: Storage Token Callbacks
+pub trait StorageTokenCallbacks {
+    /// Resolves the `ft_transfer` that pays back storage token deposits. If the transfer failed,
+    /// then the token deposit is restored if the account is still registered - otherwise, the
+    /// tokens remain with the contract and [`LOG_EVENT_STORAGE_TOKEN_REFUND_FAILURE`] is logged.
+    ///
+    /// Returns true if the transfer succeeded.
+    ///
+    /// `#[private]`
+    fn ops_storage_token_resolve_withdraw(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        token_amount: TokenAmount,
+        near_credit: YoctoNear,
+    ) -> bool;
+}
+
+pub const LOG_EVENT_STORAGE_TOKEN_DEPOSIT: LogEvent =
+    LogEvent(Level::INFO, "STORAGE_TOKEN_DEPOSIT");
+
+pub const LOG_EVENT_STORAGE_TOKEN_WITHDRAWAL: LogEvent =
+    LogEvent(Level::INFO, "STORAGE_TOKEN_WITHDRAWAL");
+
+pub const LOG_EVENT_STORAGE_TOKEN_REFUND_FAILURE: LogEvent =
+    LogEvent(Level::WARN, "STORAGE_TOKEN_REFUND_FAILURE");
+
+pub const ERR_STORAGE_TOKEN_NOT_WHITELISTED: ErrorConst = ErrorConst(
+    ErrCode("STORAGE_TOKEN_NOT_WHITELISTED"),
+    "token is not whitelisted to pay for storage",
+);
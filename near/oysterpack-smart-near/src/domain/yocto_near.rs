@@ -1,3 +1,4 @@
+use crate::data::numbers::U128;
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     serde::{
@@ -42,6 +43,18 @@ impl From<u128> for YoctoNear {
     }
 }
 
+impl From<U128> for YoctoNear {
+    fn from(value: U128) -> Self {
+        Self(value.value())
+    }
+}
+
+impl From<YoctoNear> for U128 {
+    fn from(value: YoctoNear) -> Self {
+        Self(value.value())
+    }
+}
+
 impl Deref for YoctoNear {
     type Target = u128;
 
@@ -1,6 +1,9 @@
 use crate::*;
-use near_sdk::json_types::ValidAccountId;
-use oysterpack_smart_account_management::{Permissions, PermissionsManagement};
+use near_sdk::json_types::{ValidAccountId, U64};
+use oysterpack_smart_account_management::{
+    ExpiringPermissions, Permissions, PermissionsManagement,
+};
+use oysterpack_smart_near::domain::{ExpirationDuration, ExpirationSetting};
 use std::collections::HashMap;
 
 #[near_bindgen]
@@ -21,24 +24,38 @@ impl PermissionsManagement for Contract {
         Self::account_manager().ops_permissions_is_operator(account_id)
     }
 
-    fn ops_permissions_grant_operator(&mut self, account_id: ValidAccountId) {
-        Self::account_manager().ops_permissions_grant_operator(account_id);
+    fn ops_permissions_grant_operator(
+        &mut self,
+        account_id: ValidAccountId,
+        expiration: Option<ExpirationSetting>,
+    ) {
+        Self::account_manager().ops_permissions_grant_operator(account_id, expiration);
     }
 
     fn ops_permissions_revoke_operator(&mut self, account_id: ValidAccountId) {
         Self::account_manager().ops_permissions_revoke_operator(account_id);
     }
 
-    fn ops_permissions_grant(&mut self, account_id: ValidAccountId, permissions: Permissions) {
-        Self::account_manager().ops_permissions_grant(account_id, permissions);
+    fn ops_permissions_grant(
+        &mut self,
+        account_id: ValidAccountId,
+        permissions: Permissions,
+        expiration: Option<ExpirationSetting>,
+    ) {
+        Self::account_manager().ops_permissions_grant(account_id, permissions, expiration);
     }
 
     fn ops_permissions_grant_permissions(
         &mut self,
         account_id: ValidAccountId,
         permissions: Vec<u8>,
+        expiration: Option<ExpirationSetting>,
     ) {
-        Self::account_manager().ops_permissions_grant_permissions(account_id, permissions);
+        Self::account_manager().ops_permissions_grant_permissions(
+            account_id,
+            permissions,
+            expiration,
+        );
     }
 
     fn ops_permissions_revoke(&mut self, account_id: ValidAccountId, permissions: Permissions) {
@@ -76,4 +93,29 @@ impl PermissionsManagement for Contract {
     fn ops_permissions_contract_permissions(&self) -> Option<HashMap<u8, String>> {
         Self::account_manager().ops_permissions_contract_permissions()
     }
+
+    fn ops_permissions_contract_permission_add(&mut self, permission: u8, name: String) {
+        Self::account_manager().ops_permissions_contract_permission_add(permission, name);
+    }
+
+    fn ops_permissions_contract_permission_rename(&mut self, permission: u8, name: String) {
+        Self::account_manager().ops_permissions_contract_permission_rename(permission, name);
+    }
+
+    fn ops_permissions_contract_permission_retire(&mut self, permission: u8) {
+        Self::account_manager().ops_permissions_contract_permission_retire(permission);
+    }
+
+    fn ops_permissions_contract_permissions_retired(&self) -> Option<Vec<u8>> {
+        Self::account_manager().ops_permissions_contract_permissions_retired()
+    }
+
+    fn ops_permissions_expiring(
+        &self,
+        within: Option<ExpirationDuration>,
+        from_index: Option<U64>,
+        limit: Option<u32>,
+    ) -> ExpiringPermissions {
+        Self::account_manager().ops_permissions_expiring(within, from_index, limit)
+    }
 }
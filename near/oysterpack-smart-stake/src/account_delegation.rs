@@ -0,0 +1,38 @@
+use crate::*;
+use oysterpack_smart_account_management::{AccountDelegation, Delegation};
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::{json_types::U128, AccountId};
+use std::collections::HashMap;
+
+#[near_bindgen]
+impl AccountDelegation for Contract {
+    #[payable]
+    fn ops_delegation_grant(
+        &mut self,
+        delegate: ValidAccountId,
+        actions: HashMap<String, Option<U128>>,
+        expiration: Option<ExpirationSetting>,
+    ) -> Delegation {
+        Self::account_manager().ops_delegation_grant(delegate, actions, expiration)
+    }
+
+    #[payable]
+    fn ops_delegation_revoke(&mut self, delegate: ValidAccountId) -> bool {
+        Self::account_manager().ops_delegation_revoke(delegate)
+    }
+
+    fn ops_delegations(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<HashMap<AccountId, Delegation>> {
+        Self::account_manager().ops_delegations(account_id)
+    }
+
+    fn ops_delegation(
+        &self,
+        account_id: ValidAccountId,
+        delegate: ValidAccountId,
+    ) -> Option<Delegation> {
+        Self::account_manager().ops_delegation(account_id, delegate)
+    }
+}
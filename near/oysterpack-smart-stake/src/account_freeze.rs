@@ -0,0 +1,23 @@
+use crate::*;
+use oysterpack_smart_account_management::{AccountFreeze, AccountFreezeManagement};
+use oysterpack_smart_near::domain::ExpirationSetting;
+
+#[near_bindgen]
+impl AccountFreezeManagement for Contract {
+    fn ops_account_freeze(
+        &mut self,
+        account_id: ValidAccountId,
+        reason: String,
+        expiration: Option<ExpirationSetting>,
+    ) -> AccountFreeze {
+        Self::account_manager().ops_account_freeze(account_id, reason, expiration)
+    }
+
+    fn ops_account_unfreeze(&mut self, account_id: ValidAccountId) -> bool {
+        Self::account_manager().ops_account_unfreeze(account_id)
+    }
+
+    fn ops_account_freeze_status(&self, account_id: ValidAccountId) -> Option<AccountFreeze> {
+        Self::account_manager().ops_account_freeze_status(account_id)
+    }
+}
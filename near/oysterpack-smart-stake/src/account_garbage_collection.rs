@@ -0,0 +1,20 @@
+use crate::*;
+use oysterpack_smart_account_management::{
+    AccountGarbageCollection, AccountGcReport, AccountGcSettings,
+};
+use oysterpack_smart_near::near_sdk::json_types::U64;
+
+#[near_bindgen]
+impl AccountGarbageCollection for Contract {
+    fn ops_accounts_gc(&mut self, limit: Option<u32>) -> AccountGcReport {
+        Self::account_gc().ops_accounts_gc(limit)
+    }
+
+    fn ops_accounts_gc_set_idle_epochs(&mut self, idle_epochs: Option<U64>) {
+        Self::account_gc().ops_accounts_gc_set_idle_epochs(idle_epochs);
+    }
+
+    fn ops_accounts_gc_settings(&self) -> AccountGcSettings {
+        Self::account_gc().ops_accounts_gc_settings()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::*;
+use oysterpack_smart_account_management::{AccountKeyValueStore, AccountKeyValueStoreLimits};
+use oysterpack_smart_near::domain::StorageUsage;
+
+#[near_bindgen]
+impl AccountKeyValueStore for Contract {
+    #[payable]
+    fn ops_kv_set(&mut self, key: String, value: String) -> Option<String> {
+        Self::account_kv_store().ops_kv_set(key, value)
+    }
+
+    #[payable]
+    fn ops_kv_delete(&mut self, key: String) -> bool {
+        Self::account_kv_store().ops_kv_delete(key)
+    }
+
+    fn ops_kv_get(&self, account_id: ValidAccountId, key: String) -> Option<String> {
+        Self::account_kv_store().ops_kv_get(account_id, key)
+    }
+
+    fn ops_kv_keys(&self, account_id: ValidAccountId) -> Vec<String> {
+        Self::account_kv_store().ops_kv_keys(account_id)
+    }
+
+    fn ops_kv_storage_usage(&self, account_id: ValidAccountId) -> StorageUsage {
+        Self::account_kv_store().ops_kv_storage_usage(account_id)
+    }
+
+    fn ops_kv_limits(&self) -> AccountKeyValueStoreLimits {
+        Self::account_kv_store().ops_kv_limits()
+    }
+}
@@ -0,0 +1,24 @@
+use crate::*;
+use oysterpack_smart_account_management::{AccountMigration, AccountMigrationManagement};
+
+#[near_bindgen]
+impl AccountMigrationManagement for Contract {
+    #[payable]
+    fn ops_account_migrate(&mut self, new_account_id: ValidAccountId) -> AccountMigration {
+        Self::account_manager().ops_account_migrate(new_account_id)
+    }
+
+    #[payable]
+    fn ops_account_migrate_accept(&mut self, account_id: ValidAccountId) {
+        Self::account_manager().ops_account_migrate_accept(account_id)
+    }
+
+    #[payable]
+    fn ops_account_migrate_cancel(&mut self) -> bool {
+        Self::account_manager().ops_account_migrate_cancel()
+    }
+
+    fn ops_account_migration(&self, account_id: ValidAccountId) -> Option<AccountMigration> {
+        Self::account_manager().ops_account_migration(account_id)
+    }
+}
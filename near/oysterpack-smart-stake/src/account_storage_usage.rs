@@ -1,7 +1,8 @@
 use crate::*;
 use near_sdk::json_types::ValidAccountId;
-use oysterpack_smart_account_management::AccountStorageUsage;
+use oysterpack_smart_account_management::{AccountStorageUsage, ComponentStorageUsage};
 use oysterpack_smart_near::domain::StorageUsage;
+use std::collections::BTreeMap;
 
 #[near_bindgen]
 impl AccountStorageUsage for Contract {
@@ -12,4 +13,11 @@ impl AccountStorageUsage for Contract {
     fn ops_storage_usage(&self, account_id: ValidAccountId) -> Option<StorageUsage> {
         Self::account_manager().ops_storage_usage(account_id)
     }
+
+    fn ops_storage_usage_by_component(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<String, ComponentStorageUsage>> {
+        Self::account_manager().ops_storage_usage_by_component(account_id)
+    }
 }
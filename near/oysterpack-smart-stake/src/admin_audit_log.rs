@@ -0,0 +1,15 @@
+use crate::*;
+use oysterpack_smart_account_management::components::admin_audit_log::AdminAuditLogComponent;
+use oysterpack_smart_account_management::{AdminAuditLog, AuditLogEntry};
+use oysterpack_smart_near::near_sdk::json_types::U64;
+
+#[near_bindgen]
+impl AdminAuditLog for Contract {
+    fn ops_audit_log(&self, from_id: Option<U64>, limit: Option<u16>) -> Vec<AuditLogEntry> {
+        AdminAuditLogComponent.ops_audit_log(from_id, limit)
+    }
+
+    fn ops_audit_log_count(&self) -> U64 {
+        AdminAuditLogComponent.ops_audit_log_count()
+    }
+}
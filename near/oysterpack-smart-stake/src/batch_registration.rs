@@ -0,0 +1,25 @@
+use crate::*;
+use oysterpack_smart_fungible_token::{
+    BatchRegistration, BatchRegistrationPage, BatchRegistrationRequest,
+};
+use oysterpack_smart_near::domain::YoctoNear;
+
+#[near_bindgen]
+impl BatchRegistration for Contract {
+    fn ops_batch_register(&mut self, request: BatchRegistrationRequest) -> BatchRegistrationPage {
+        Self::batch_registrations().ops_batch_register(request)
+    }
+
+    #[payable]
+    fn ops_batch_registration_budget_deposit(&mut self) -> YoctoNear {
+        Self::batch_registrations().ops_batch_registration_budget_deposit()
+    }
+
+    fn ops_batch_registration_budget_release(&mut self) -> YoctoNear {
+        Self::batch_registrations().ops_batch_registration_budget_release()
+    }
+
+    fn ops_batch_registration_budget(&self) -> Option<YoctoNear> {
+        Self::batch_registrations().ops_batch_registration_budget()
+    }
+}
@@ -0,0 +1,38 @@
+use crate::*;
+use oysterpack_smart_contract::components::contract_co_ownership::ContractCoOwnershipComponent;
+use oysterpack_smart_contract::{
+    CoOwnershipConfig, ContractCoOwnership, Proposal, ProposalCommand,
+};
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::{json_types::U64, near_bindgen};
+
+#[near_bindgen]
+impl ContractCoOwnership for Contract {
+    fn ops_co_ownership(&self) -> Option<CoOwnershipConfig> {
+        ContractCoOwnershipComponent.ops_co_ownership()
+    }
+
+    fn ops_co_ownership_configure(&mut self, config: Option<CoOwnershipConfig>) {
+        ContractCoOwnershipComponent.ops_co_ownership_configure(config);
+    }
+
+    fn ops_co_owner_propose(
+        &mut self,
+        command: ProposalCommand,
+        expiration: Option<ExpirationSetting>,
+    ) -> Proposal {
+        ContractCoOwnershipComponent.ops_co_owner_propose(command, expiration)
+    }
+
+    fn ops_co_owner_approve(&mut self, proposal_id: U64) -> Proposal {
+        ContractCoOwnershipComponent.ops_co_owner_approve(proposal_id)
+    }
+
+    fn ops_co_owner_cancel(&mut self, proposal_id: U64) {
+        ContractCoOwnershipComponent.ops_co_owner_cancel(proposal_id);
+    }
+
+    fn ops_co_owner_proposals(&self) -> Vec<Proposal> {
+        ContractCoOwnershipComponent.ops_co_owner_proposals()
+    }
+}
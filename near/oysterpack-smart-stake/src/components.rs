@@ -1,32 +1,104 @@
 use crate::*;
+use oysterpack_smart_account_management::components::account_garbage_collection::AccountGarbageCollectionComponent;
+use oysterpack_smart_account_management::components::account_key_value_store::AccountKeyValueStoreComponent;
 use oysterpack_smart_account_management::components::account_management::AccountManagementComponent;
-use oysterpack_smart_account_management::ContractPermissions;
+use oysterpack_smart_account_management::components::storage_usage_bounds::StorageUsageBoundsComponent;
+use oysterpack_smart_account_management::{
+    AccountKeyValueStoreLimits, AccountKvStore, ContractPermissions,
+};
+use oysterpack_smart_contract::components::contract_multisig::ContractMultiSigComponent;
 use oysterpack_smart_contract::components::contract_operator::ContractOperatorComponent;
+use oysterpack_smart_contract::components::contract_upgrade::{
+    ContractUpgradeComponent, CONTRACT_UPGRADE_MIGRATE_GAS,
+};
+use oysterpack_smart_contract::{BalanceId, BalanceIdLabel, ProposalCommand};
+use oysterpack_smart_fungible_token::components::batch_registration::{
+    BatchRegistrationComponent, BatchRegistrationConfig,
+};
+use oysterpack_smart_fungible_token::components::storage_token::StorageTokenComponent;
+use oysterpack_smart_near::domain::{ExpirationDuration, StorageUsage};
+use oysterpack_smart_near::YOCTO;
 use oysterpack_smart_staking_pool::components::staking_pool::StakingPoolComponent;
-use oysterpack_smart_staking_pool::{StakeAccountData, PERMISSION_TREASURER};
+use oysterpack_smart_staking_pool::{
+    StakeAccountData, StakingPool, PERMISSION_TREASURER, PERMISSION_TREASURER_BIT,
+};
 use std::collections::HashMap;
 
 pub type AccountData = StakeAccountData;
 
 pub type AccountManager = AccountManagementComponent<AccountData>;
 
+pub type AccountGarbageCollector = AccountGarbageCollectionComponent<AccountData>;
+
+pub type KeyValueStore = AccountKeyValueStoreComponent<AccountData>;
+
 pub type StakeFungibleToken = FungibleTokenComponent<AccountData>;
 
 pub type ContractOperator = ContractOperatorComponent<AccountData>;
 
+pub type MultiSig = ContractMultiSigComponent<AccountData>;
+
+pub type BatchRegistrations = BatchRegistrationComponent<AccountData>;
+
+pub type StorageTokens = StorageTokenComponent<AccountData>;
+
+pub type StorageBounds = StorageUsageBoundsComponent<AccountData>;
+
+/// NEAR budget used to pay for account storage for batch registrations
+pub const BATCH_REGISTRATION_BUDGET: BalanceId = BalanceId(1958032587062207497312596434520475043);
+
+/// contract upgrades are announced at least 5 epochs, i.e., roughly 60 hours, before they can be
+/// deployed, which gives stakers time to review the staged code and unstake if they choose to
+/// - unstaked NEAR is locked for 4 epochs, thus stakers can fully exit before the upgrade is deployed
+pub const CONTRACT_UPGRADE_NOTICE: ExpirationDuration = ExpirationDuration::Epochs(5);
+
+/// per account key-value store limits - 10 KB max storage usage per account
+pub const ACCOUNT_KV_STORE_LIMITS: AccountKeyValueStoreLimits = AccountKeyValueStoreLimits {
+    max_key_len: 64,
+    max_value_len: 1024,
+    max_storage_usage: StorageUsage(10_000),
+};
+
 impl Contract {
     pub(crate) fn account_manager() -> AccountManager {
         StakeFungibleToken::register_storage_management_event_handler();
+        AccountKvStore::register_storage_management_event_handler();
+        StorageTokens::register_storage_management_event_handler();
 
         let contract_permissions = {
             let mut permissions = HashMap::with_capacity(1);
-            permissions.insert(0, PERMISSION_TREASURER);
+            permissions.insert(PERMISSION_TREASURER_BIT, PERMISSION_TREASURER.to_string());
             ContractPermissions(permissions)
         };
 
         AccountManager::new(contract_permissions)
     }
 
+    /// unstaked balances are stored in the account data, which retains the account
+    pub(crate) fn account_gc() -> AccountGarbageCollector {
+        AccountGarbageCollector::new(
+            Self::account_manager(),
+            vec![StakeFungibleToken::account_holds_tokens],
+        )
+    }
+
+    /// min account storage functions registered by the contract components, which the account
+    /// storage min is measured with
+    pub(crate) fn component_account_storage_mins() -> Vec<fn() -> StorageUsage> {
+        vec![StakeFungibleToken::account_storage_min]
+    }
+
+    pub(crate) fn storage_usage_bounds() -> StorageBounds {
+        StorageBounds::new(
+            Self::account_manager(),
+            Self::component_account_storage_mins(),
+        )
+    }
+
+    pub(crate) fn account_kv_store() -> KeyValueStore {
+        KeyValueStore::new(Self::account_manager(), ACCOUNT_KV_STORE_LIMITS)
+    }
+
     pub(crate) fn ft_stake() -> StakeFungibleToken {
         StakeFungibleToken::new(Self::account_manager())
     }
@@ -35,6 +107,114 @@ impl Contract {
         ContractOperator::new(Self::account_manager())
     }
 
+    pub(crate) fn multisig() -> MultiSig {
+        MultiSig::new(Self::account_manager())
+    }
+
+    pub(crate) fn contract_upgrade() -> ContractUpgradeComponent {
+        ContractUpgradeComponent::new(CONTRACT_UPGRADE_NOTICE, CONTRACT_UPGRADE_MIGRATE_GAS)
+    }
+
+    /// Registers the owner, admin, and operator methods that multisig and co-ownership proposals are
+    /// allowed to invoke. Registration is idempotent - it is applied on deployment and migration.
+    pub(crate) fn register_proposal_methods() {
+        ProposalCommand::register_methods(&[
+            // owner
+            "ops_owner_transfer",
+            "ops_owner_cancel_transfer",
+            "ops_owner_withdraw_balance",
+            "ops_owner_grant_admin",
+            "ops_owner_recovery_configure",
+            "ops_owner_recovery_veto",
+            "ops_owner_withdrawal_timelock_configure",
+            "ops_owner_withdrawal_timelock_change",
+            "ops_owner_withdrawal_timelock_cancel_change",
+            "ops_owner_withdrawal_cancel",
+            "ops_co_ownership_configure",
+            "ops_multisig_configure",
+            "ops_upgrade_stage",
+            "ops_upgrade_cancel",
+            "ops_stake_treasury_transfer_to_owner",
+            "ops_stake_grant_treasurer",
+            "ops_stake_revoke_treasurer",
+            // admin
+            "ops_permissions_grant_admin",
+            "ops_permissions_revoke_admin",
+            "ops_permissions_grant_operator",
+            "ops_permissions_revoke_operator",
+            "ops_permissions_grant",
+            "ops_permissions_grant_permissions",
+            "ops_permissions_revoke",
+            "ops_permissions_revoke_permissions",
+            "ops_permissions_revoke_all",
+            "ops_permissions_contract_permission_add",
+            "ops_permissions_contract_permission_rename",
+            "ops_permissions_contract_permission_retire",
+            "ops_account_freeze",
+            "ops_account_unfreeze",
+            "ops_accounts_gc_set_idle_epochs",
+            "ops_batch_register",
+            "ops_batch_registration_budget_deposit",
+            "ops_batch_registration_budget_release",
+            "ops_storage_token_whitelist",
+            // operator
+            "ops_accounts_gc",
+            "ops_storage_usage_bounds_remeasure",
+            "ops_storage_balance_grandfather",
+            "ops_storage_balance_grandfather_revoke",
+            "ops_storage_token_update_price",
+            "ops_operator_lock_storage_balance",
+            "ft_operator_command",
+            "ops_stake_operator_command",
+        ]);
+    }
+
+    /// Registers the contract balance IDs, including the balance IDs that are owned by the
+    /// components. Registration is idempotent - it is applied on deployment and migration.
+    pub(crate) fn register_balance_ids() {
+        ContractOwnershipComponent::register_balance_ids();
+        StakingPoolComponent::register_balance_ids();
+        ContractNearBalances::register_balance_id(
+            BATCH_REGISTRATION_BUDGET,
+            BalanceIdLabel {
+                name: "batch_registration_budget".to_string(),
+                description: "NEAR budget used to pay for batch registration account storage"
+                    .to_string(),
+                component: "BatchRegistrationComponent".to_string(),
+            },
+        );
+    }
+
+    /// STAKE cannot be minted because it must be backed by staked NEAR - tokens can only be
+    /// distributed via transfers
+    pub(crate) fn batch_registrations() -> BatchRegistrations {
+        BatchRegistrations::new(
+            Self::account_manager(),
+            Self::ft_stake(),
+            BatchRegistrationConfig {
+                budget: Some(BATCH_REGISTRATION_BUDGET),
+                mint_enabled: false,
+            },
+        )
+    }
+
+    /// the contract's own STAKE token is priced on chain by the STAKE pool
+    pub(crate) fn storage_tokens() -> StorageTokens {
+        let mut price_functions = StorageTokenPriceFunctions::new();
+        price_functions.insert(
+            env::current_account_id(),
+            Self::stake_token_price as StorageTokenPriceFn,
+        );
+        StorageTokens::new(Self::account_manager(), price_functions)
+    }
+
+    fn stake_token_price() -> StorageTokenPrice {
+        StorageTokenPrice {
+            token_amount: YOCTO.into(),
+            near_value: Self::staking_pool().ops_stake_token_value(Some(YOCTO.into())),
+        }
+    }
+
     pub(crate) fn staking_pool() -> StakingPoolComponent {
         StakingPoolComponent::new(Self::account_manager(), Self::ft_stake())
     }
@@ -0,0 +1,12 @@
+use crate::*;
+use oysterpack_smart_contract::components::contract_health_check::ContractHealthCheckComponent;
+use oysterpack_smart_contract::{ContractHealthCheck, HealthCheckReport};
+
+#[near_bindgen]
+impl ContractHealthCheck for Contract {
+    fn ops_health_check(&self) -> HealthCheckReport {
+        let mut report = ContractHealthCheckComponent.ops_health_check();
+        Self::staking_pool().health_check(&mut report);
+        report
+    }
+}
@@ -3,7 +3,7 @@ use oysterpack_smart_account_management::AccountMetrics;
 use oysterpack_smart_contract::components::contract_metrics::ContractMetricsComponent;
 use oysterpack_smart_contract::{
     ContractMetrics, ContractMetricsSnapshot, ContractNearBalances, ContractStorageUsage,
-    ContractStorageUsageCosts,
+    ContractStorageUsageCosts, RegisteredBalanceId,
 };
 use oysterpack_smart_near::data::numbers::U128;
 
@@ -32,4 +32,8 @@ impl ContractMetrics for Contract {
     fn ops_metrics_accounts(&self) -> AccountMetrics {
         ContractMetricsComponent.ops_metrics_accounts()
     }
+
+    fn ops_metrics_balance_ids(&self) -> Vec<RegisteredBalanceId> {
+        ContractMetricsComponent.ops_metrics_balance_ids()
+    }
 }
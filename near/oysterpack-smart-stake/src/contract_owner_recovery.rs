@@ -0,0 +1,45 @@
+use crate::*;
+use near_sdk::{json_types::ValidAccountId, near_bindgen};
+use oysterpack_smart_contract::components::contract_owner_recovery::ContractOwnerRecoveryComponent;
+use oysterpack_smart_contract::{ContractOwnerRecovery, OwnerRecovery, OwnerRecoveryConfig};
+
+#[near_bindgen]
+impl ContractOwnerRecovery for Contract {
+    fn ops_owner_recovery_config(&self) -> Option<OwnerRecoveryConfig> {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_config()
+    }
+
+    #[payable]
+    fn ops_owner_recovery_configure(&mut self, config: Option<OwnerRecoveryConfig>) {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_configure(config)
+    }
+
+    fn ops_owner_recovery(&self) -> Option<OwnerRecovery> {
+        ContractOwnerRecoveryComponent.ops_owner_recovery()
+    }
+
+    #[payable]
+    fn ops_owner_recovery_initiate(&mut self, new_owner: ValidAccountId) -> OwnerRecovery {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_initiate(new_owner)
+    }
+
+    #[payable]
+    fn ops_owner_recovery_approve(&mut self) -> OwnerRecovery {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_approve()
+    }
+
+    #[payable]
+    fn ops_owner_recovery_cancel(&mut self) {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_cancel()
+    }
+
+    #[payable]
+    fn ops_owner_recovery_veto(&mut self) -> bool {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_veto()
+    }
+
+    #[payable]
+    fn ops_owner_recovery_finalize(&mut self) {
+        ContractOwnerRecoveryComponent.ops_owner_recovery_finalize()
+    }
+}
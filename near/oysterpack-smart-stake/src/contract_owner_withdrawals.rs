@@ -0,0 +1,44 @@
+use crate::*;
+use near_sdk::{json_types::U64, near_bindgen};
+use oysterpack_smart_contract::components::contract_owner_withdrawals::ContractOwnerWithdrawalsComponent;
+use oysterpack_smart_contract::{
+    ContractOwnerWithdrawals, OwnerWithdrawalTimelockConfig, OwnerWithdrawalTimelockConfigChange,
+    PendingOwnerWithdrawal,
+};
+
+#[near_bindgen]
+impl ContractOwnerWithdrawals for Contract {
+    fn ops_owner_withdrawal_timelock(&self) -> Option<OwnerWithdrawalTimelockConfig> {
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock()
+    }
+
+    fn ops_owner_withdrawal_timelock_change(&self) -> Option<OwnerWithdrawalTimelockConfigChange> {
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_change()
+    }
+
+    #[payable]
+    fn ops_owner_withdrawal_timelock_configure(
+        &mut self,
+        config: Option<OwnerWithdrawalTimelockConfig>,
+    ) -> Option<OwnerWithdrawalTimelockConfigChange> {
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_configure(config)
+    }
+
+    #[payable]
+    fn ops_owner_withdrawal_timelock_cancel_change(&mut self) -> bool {
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_timelock_cancel_change()
+    }
+
+    fn ops_owner_pending_withdrawals(&self) -> Vec<PendingOwnerWithdrawal> {
+        ContractOwnerWithdrawalsComponent.ops_owner_pending_withdrawals()
+    }
+
+    fn ops_owner_withdrawal_execute(&mut self, id: U64) {
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_execute(id)
+    }
+
+    #[payable]
+    fn ops_owner_withdrawal_cancel(&mut self, id: U64) {
+        ContractOwnerWithdrawalsComponent.ops_owner_withdrawal_cancel(id)
+    }
+}
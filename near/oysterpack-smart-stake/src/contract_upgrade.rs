@@ -0,0 +1,29 @@
+use crate::*;
+use near_sdk::{json_types::Base64VecU8, near_bindgen};
+use oysterpack_smart_contract::{ContractUpgrade, StagedContractUpgrade};
+use oysterpack_smart_near::domain::ExpirationDuration;
+
+#[near_bindgen]
+impl ContractUpgrade for Contract {
+    #[payable]
+    fn ops_upgrade_stage(&mut self, code: Base64VecU8) -> StagedContractUpgrade {
+        Self::contract_upgrade().ops_upgrade_stage(code)
+    }
+
+    #[payable]
+    fn ops_upgrade_cancel(&mut self) -> bool {
+        Self::contract_upgrade().ops_upgrade_cancel()
+    }
+
+    fn ops_upgrade_deploy(&mut self) {
+        Self::contract_upgrade().ops_upgrade_deploy();
+    }
+
+    fn ops_upgrade_staged(&self) -> Option<StagedContractUpgrade> {
+        Self::contract_upgrade().ops_upgrade_staged()
+    }
+
+    fn ops_upgrade_notice(&self) -> ExpirationDuration {
+        Self::contract_upgrade().ops_upgrade_notice()
+    }
+}
@@ -1,7 +1,7 @@
 use crate::*;
 use oysterpack_smart_fungible_token::{
-    FungibleToken, FungibleTokenMetadataProvider, FungibleTokenOperator, Memo, Metadata,
-    OperatorCommand, ResolveTransferCall, TokenAmount, TransferCallMessage,
+    FungibleToken, FungibleTokenDelegation, FungibleTokenMetadataProvider, FungibleTokenOperator,
+    Memo, Metadata, OperatorCommand, ResolveTransferCall, TokenAmount, TransferCallMessage,
 };
 use oysterpack_smart_near::domain::Gas;
 use oysterpack_smart_near::near_sdk::Promise;
@@ -38,6 +38,20 @@ impl FungibleToken for Contract {
     }
 }
 
+#[near_bindgen]
+impl FungibleTokenDelegation for Contract {
+    #[payable]
+    fn ft_transfer_for(
+        &mut self,
+        account_id: ValidAccountId,
+        receiver_id: ValidAccountId,
+        amount: TokenAmount,
+        memo: Option<Memo>,
+    ) {
+        Self::ft_stake().ft_transfer_for(account_id, receiver_id, amount, memo)
+    }
+}
+
 #[near_bindgen]
 impl ResolveTransferCall for Contract {
     #[private]
@@ -1,12 +1,27 @@
 mod access_control;
+mod account_delegation;
+mod account_freeze;
+mod account_garbage_collection;
+mod account_key_value_store;
+mod account_migration;
 mod account_storage_usage;
+mod admin_audit_log;
+mod batch_registration;
+mod co_ownership;
 mod components;
+mod contract_health_check;
 mod contract_metrics;
 mod contract_operator;
+mod contract_owner_recovery;
+mod contract_owner_withdrawals;
 mod contract_ownership;
+mod contract_upgrade;
 mod fungible_token;
+mod multisig;
 mod staking_pool;
 mod storage_management;
+mod storage_token;
+mod storage_usage_bounds_management;
 
 use components::*;
 use near_sdk::{
@@ -16,8 +31,8 @@ use near_sdk::{
     near_bindgen, serde_json, PanicOnDefault,
 };
 use oysterpack_smart_account_management::{
-    components::account_management::AccountManagementComponentConfig, AccountRepository,
-    StorageUsageBounds,
+    components::account_management::AccountManagementComponentConfig, AccountNearData,
+    AccountRepository, StorageUsageBounds,
 };
 use oysterpack_smart_contract::{
     components::contract_ownership::ContractOwnershipComponent, ContractOwnership,
@@ -66,7 +81,7 @@ impl Contract {
         AccountManager::deploy(AccountManagementComponentConfig {
             storage_usage_bounds: None,
             admin_account: owner.clone(),
-            component_account_storage_mins: Some(vec![StakeFungibleToken::account_storage_min]),
+            component_account_storage_mins: Some(Self::component_account_storage_mins()),
         });
         LOG_EVENT_DEPLOYMENT.log("AccountManagementComponent");
 
@@ -122,6 +137,22 @@ impl Contract {
         });
         LOG_EVENT_DEPLOYMENT.log("StakingPoolComponent");
 
+        Self::register_balance_ids();
+        Self::register_proposal_methods();
+
+        Self
+    }
+
+    /// Invoked by [`oysterpack_smart_contract::ContractUpgrade::ops_upgrade_deploy`] after the
+    /// staged contract code has been deployed.
+    ///
+    /// The contract itself is stateless - component state migrations are applied here.
+    #[init(ignore_state)]
+    #[private]
+    pub fn migrate() -> Self {
+        AccountNearData::migrate_v1();
+        Self::register_balance_ids();
+        Self::register_proposal_methods();
         Self
     }
 }
@@ -0,0 +1,39 @@
+use crate::*;
+use oysterpack_smart_contract::{ContractMultiSig, MultiSigConfig, Proposal, ProposalCommand};
+use oysterpack_smart_near::domain::ExpirationSetting;
+use oysterpack_smart_near::near_sdk::{json_types::U64, near_bindgen};
+
+#[near_bindgen]
+impl ContractMultiSig for Contract {
+    fn ops_multisig_config(&self) -> Option<MultiSigConfig> {
+        Self::multisig().ops_multisig_config()
+    }
+
+    fn ops_multisig_configure(&mut self, config: MultiSigConfig) {
+        Self::multisig().ops_multisig_configure(config);
+    }
+
+    fn ops_multisig_propose(
+        &mut self,
+        command: ProposalCommand,
+        expiration: Option<ExpirationSetting>,
+    ) -> Proposal {
+        Self::multisig().ops_multisig_propose(command, expiration)
+    }
+
+    fn ops_multisig_approve(&mut self, proposal_id: U64) -> Proposal {
+        Self::multisig().ops_multisig_approve(proposal_id)
+    }
+
+    fn ops_multisig_cancel(&mut self, proposal_id: U64) {
+        Self::multisig().ops_multisig_cancel(proposal_id);
+    }
+
+    fn ops_multisig_proposal(&self, proposal_id: U64) -> Option<Proposal> {
+        Self::multisig().ops_multisig_proposal(proposal_id)
+    }
+
+    fn ops_multisig_proposals(&self) -> Vec<Proposal> {
+        Self::multisig().ops_multisig_proposals()
+    }
+}
@@ -1,11 +1,11 @@
 use crate::*;
 use near_sdk::near_bindgen;
-use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::domain::{ExpirationSetting, YoctoNear};
 use oysterpack_smart_near::near_sdk::{AccountId, Promise, PromiseOrValue};
 use oysterpack_smart_staking_pool::{
     Fees, NearStakingPool, NearStakingPoolAccount, StakeAccountBalances, StakeActionCallbacks,
-    StakingPool, StakingPoolBalances, StakingPoolOperator, StakingPoolOperatorCommand, Status,
-    Treasury,
+    StakingPool, StakingPoolBalances, StakingPoolDelegation, StakingPoolOperator,
+    StakingPoolOperatorCommand, Status, Treasury,
 };
 
 #[near_bindgen]
@@ -77,6 +77,33 @@ impl StakingPool for Contract {
     }
 }
 
+#[near_bindgen]
+impl StakingPoolDelegation for Contract {
+    #[payable]
+    fn ops_stake_for(
+        &mut self,
+        account_id: ValidAccountId,
+    ) -> PromiseOrValue<StakeAccountBalances> {
+        Self::staking_pool().ops_stake_for(account_id)
+    }
+
+    fn ops_unstake_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> PromiseOrValue<StakeAccountBalances> {
+        Self::staking_pool().ops_unstake_for(account_id, amount)
+    }
+
+    fn ops_stake_withdraw_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> StakeAccountBalances {
+        Self::staking_pool().ops_stake_withdraw_for(account_id, amount)
+    }
+}
+
 #[near_bindgen]
 impl NearStakingPool for Contract {
     fn get_account_staked_balance(&self, account_id: ValidAccountId) -> YoctoNear {
@@ -171,8 +198,12 @@ impl Treasury for Contract {
         Self::staking_pool().ops_stake_treasury_transfer_to_owner(amount);
     }
 
-    fn ops_stake_grant_treasurer(&mut self, account_id: ValidAccountId) {
-        Self::staking_pool().ops_stake_grant_treasurer(account_id);
+    fn ops_stake_grant_treasurer(
+        &mut self,
+        account_id: ValidAccountId,
+        expiration: Option<ExpirationSetting>,
+    ) {
+        Self::staking_pool().ops_stake_grant_treasurer(account_id, expiration);
     }
 
     fn ops_stake_revoke_treasurer(&mut self, account_id: ValidAccountId) {
@@ -184,15 +215,27 @@ impl Treasury for Contract {
     }
 }
 
+/// `ft_transfer_call` message used to deposit the contract's own STAKE tokens as storage token credit
+pub const STORAGE_DEPOSIT_MSG: &str = "storage_deposit";
+
 #[near_bindgen]
 impl TransferReceiver for Contract {
-    #[private]
+    /// Transfers are routed by token and `msg`:
+    /// - STAKE transfers with [`STORAGE_DEPOSIT_MSG`] are storage token deposits
+    /// - STAKE transfers with any other `msg` are treasury deposits
+    /// - transfers from other token contracts are storage token deposits - `msg` is not used
     fn ft_on_transfer(
         &mut self,
         sender_id: ValidAccountId,
         amount: TokenAmount,
         msg: TransferCallMessage,
     ) -> PromiseOrValue<TokenAmount> {
-        Self::staking_pool().ft_on_transfer(sender_id, amount, msg)
+        if env::predecessor_account_id() == env::current_account_id()
+            && &*msg != STORAGE_DEPOSIT_MSG
+        {
+            Self::staking_pool().ft_on_transfer(sender_id, amount, msg)
+        } else {
+            Self::storage_tokens().ft_on_transfer(sender_id, amount, msg)
+        }
     }
 }
@@ -2,9 +2,13 @@ use crate::*;
 
 use near_sdk::json_types::ValidAccountId;
 use near_sdk::near_bindgen;
+use near_sdk::AccountId;
 use oysterpack_smart_account_management::StorageBalance;
-use oysterpack_smart_account_management::{StorageBalanceBounds, StorageManagement};
+use oysterpack_smart_account_management::{
+    StorageBalanceBounds, StorageManagement, StorageSponsorship,
+};
 use oysterpack_smart_near::domain::YoctoNear;
+use std::collections::BTreeMap;
 
 #[near_bindgen]
 impl StorageManagement for Contract {
@@ -35,3 +39,17 @@ impl StorageManagement for Contract {
         Self::account_manager().storage_balance_of(account_id)
     }
 }
+
+#[near_bindgen]
+impl StorageSponsorship for Contract {
+    fn ops_storage_sponsored_accounts(&self, sponsor: ValidAccountId) -> Vec<AccountId> {
+        Self::account_manager().ops_storage_sponsored_accounts(sponsor)
+    }
+
+    fn ops_storage_sponsors(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Option<BTreeMap<AccountId, YoctoNear>> {
+        Self::account_manager().ops_storage_sponsors(account_id)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::*;
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{AccountId, Promise};
+use std::collections::BTreeMap;
+
+#[near_bindgen]
+impl StorageTokenManagement for Contract {
+    fn ops_storage_token_whitelist(
+        &mut self,
+        token_id: ValidAccountId,
+        price: Option<StorageTokenPrice>,
+    ) {
+        Self::storage_tokens().ops_storage_token_whitelist(token_id, price);
+    }
+
+    fn ops_storage_token_update_price(
+        &mut self,
+        token_id: ValidAccountId,
+        price: StorageTokenPrice,
+    ) {
+        Self::storage_tokens().ops_storage_token_update_price(token_id, price);
+    }
+
+    fn ops_storage_tokens(&self) -> BTreeMap<AccountId, StorageTokenPrice> {
+        Self::storage_tokens().ops_storage_tokens()
+    }
+
+    fn ops_storage_token_deposits(&self, account_id: ValidAccountId) -> Vec<StorageTokenDeposit> {
+        Self::storage_tokens().ops_storage_token_deposits(account_id)
+    }
+
+    #[payable]
+    fn ops_storage_token_withdraw(
+        &mut self,
+        token_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> Promise {
+        Self::storage_tokens().ops_storage_token_withdraw(token_id, amount)
+    }
+}
+
+#[near_bindgen]
+impl StorageTokenCallbacks for Contract {
+    #[private]
+    fn ops_storage_token_resolve_withdraw(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        token_amount: TokenAmount,
+        near_credit: YoctoNear,
+    ) -> bool {
+        Self::storage_tokens().ops_storage_token_resolve_withdraw(
+            account_id,
+            token_id,
+            token_amount,
+            near_credit,
+        )
+    }
+}
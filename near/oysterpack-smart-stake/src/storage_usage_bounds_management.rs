@@ -0,0 +1,33 @@
+use crate::*;
+use near_sdk::json_types::{ValidAccountId, U64};
+use oysterpack_smart_account_management::{
+    StorageBalanceShortfalls, StorageUsageBoundsChange, StorageUsageBoundsManagement,
+};
+use oysterpack_smart_near::domain::YoctoNear;
+
+#[near_bindgen]
+impl StorageUsageBoundsManagement for Contract {
+    fn ops_storage_usage_bounds_remeasure(&mut self) -> StorageUsageBoundsChange {
+        Self::storage_usage_bounds().ops_storage_usage_bounds_remeasure()
+    }
+
+    fn ops_storage_balance_shortfalls(
+        &self,
+        from_index: Option<U64>,
+        limit: Option<u32>,
+    ) -> StorageBalanceShortfalls {
+        Self::storage_usage_bounds().ops_storage_balance_shortfalls(from_index, limit)
+    }
+
+    fn ops_storage_balance_grandfather(&mut self, account_id: ValidAccountId) -> YoctoNear {
+        Self::storage_usage_bounds().ops_storage_balance_grandfather(account_id)
+    }
+
+    fn ops_storage_balance_grandfather_revoke(&mut self, account_id: ValidAccountId) -> bool {
+        Self::storage_usage_bounds().ops_storage_balance_grandfather_revoke(account_id)
+    }
+
+    fn ops_storage_balance_grandfathered(&self, account_id: ValidAccountId) -> Option<YoctoNear> {
+        Self::storage_usage_bounds().ops_storage_balance_grandfathered(account_id)
+    }
+}
@@ -1,20 +1,28 @@
 use crate::{
     Fees, NearStakingPool, NearStakingPoolAccount, OfflineReason, StakeAccountBalances,
     StakeAccountData, StakeActionCallbacks, StakedBalance, StakingPool, StakingPoolBalances,
-    StakingPoolOperator, StakingPoolOperatorCommand, Status, Treasury,
-    ERR_STAKED_BALANCE_TOO_LOW_TO_UNSTAKE, ERR_STAKE_ACTION_FAILED, LOG_EVENT_EARNINGS,
-    LOG_EVENT_LIQUIDITY, LOG_EVENT_NOT_ENOUGH_TO_STAKE, LOG_EVENT_STAKE, LOG_EVENT_STATUS_OFFLINE,
+    StakingPoolDelegation, StakingPoolOperator, StakingPoolOperatorCommand, Status, Treasury,
+    DELEGATED_ACTION_STAKE, DELEGATED_ACTION_UNSTAKE, DELEGATED_ACTION_WITHDRAW,
+    ERR_STAKED_BALANCE_TOO_LOW_TO_UNSTAKE, ERR_STAKE_ACTION_FAILED,
+    HEALTH_CHECK_STAKED_BALANCE_LOCKED, HEALTH_CHECK_STAKE_SUPPLY, HEALTH_CHECK_STAKE_VALUE,
+    HEALTH_CHECK_STAKING_POOL_BALANCES, HEALTH_CHECK_STAKING_POOL_EARNINGS,
+    HEALTH_CHECK_TREASURY_STAKE, LOG_EVENT_EARNINGS, LOG_EVENT_LIQUIDITY,
+    LOG_EVENT_NOT_ENOUGH_TO_STAKE, LOG_EVENT_STAKE, LOG_EVENT_STATUS_OFFLINE,
     LOG_EVENT_STATUS_ONLINE, LOG_EVENT_TREASURY_DEPOSIT, LOG_EVENT_TREASURY_DIVIDEND,
-    LOG_EVENT_UNSTAKE, MAX_FEE, PERMISSION_TREASURER,
+    LOG_EVENT_UNSTAKE, MAX_FEE, PERMISSION_TREASURER_BIT,
 };
 use oysterpack_smart_account_management::{
-    components::account_management::AccountManagementComponent, AccountDataObject, AccountMetrics,
-    AccountRepository, Permission, PermissionsManagement, StorageManagement,
+    components::account_management::AccountManagementComponent, AccountDataObject,
+    AccountDelegations, AccountMetrics, AccountNearData, AccountRepository,
+    ApprovalRequiredCommands, AuditLog, Permission, PermissionsManagement, StorageManagement,
     ERR_ACCOUNT_NOT_REGISTERED, ERR_NOT_AUTHORIZED,
 };
 use oysterpack_smart_contract::{
-    components::contract_ownership::ContractOwnershipComponent, BalanceId, ContractNearBalances,
-    ContractOwnership,
+    components::contract_co_ownership::ContractCoOwnershipComponent,
+    components::contract_multisig::ContractMultiSigComponent,
+    components::contract_ownership::ContractOwnershipComponent, BalanceId, BalanceIdLabel,
+    ContractNearBalances, ContractOwnerObject, ContractOwnership, HealthCheckReport,
+    HEALTH_CHECK_CONTRACT_NEAR_BALANCES,
 };
 use oysterpack_smart_fungible_token::{
     components::fungible_token::FungibleTokenComponent, FungibleToken, Memo, TokenAmount,
@@ -23,11 +31,11 @@ use oysterpack_smart_fungible_token::{
 use oysterpack_smart_near::domain::TGas;
 use oysterpack_smart_near::{
     asserts::{ERR_ILLEGAL_STATE, ERR_INSUFFICIENT_FUNDS, ERR_INVALID, ERR_NEAR_DEPOSIT_REQUIRED},
-    component::{Component, ComponentState, Deploy},
+    component::{Component, ComponentState, Deploy, ManagesAccountData},
     data::numbers::U256,
     domain::{
-        ActionType, BasisPoints, ByteLen, Gas, PublicKey, SenderIsReceiver, TransactionResource,
-        YoctoNear,
+        ActionType, BasisPoints, ByteLen, ExpirationSetting, Gas, PublicKey, SenderIsReceiver,
+        TransactionResource, YoctoNear,
     },
     json_function_callback,
     near_sdk::{
@@ -35,6 +43,7 @@ use oysterpack_smart_near::{
         env, is_promise_success,
         json_types::ValidAccountId,
         serde::{Deserialize, Serialize},
+        serde_json::{self, json},
         AccountId, Promise, PromiseOrValue,
     },
     to_valid_account_id, TERA, YOCTO,
@@ -231,6 +240,42 @@ impl Deploy for StakingPoolComponent {
         };
         let state = Self::new_state(state);
         state.save();
+        Self::register_balance_ids();
+    }
+}
+
+impl StakingPoolComponent {
+    /// Registers the staking pool balance IDs - should also be invoked by contracts on migration in
+    /// order to label the balances of staking pools that were deployed before balance IDs were
+    /// registered
+    pub fn register_balance_ids() {
+        let balance_ids = [
+            (
+                State::TOTAL_STAKED_BALANCE,
+                "total_staked",
+                "NEAR funds that have been staked and confirmed",
+            ),
+            (
+                State::TOTAL_UNSTAKED_BALANCE,
+                "total_unstaked",
+                "unstaked NEAR funds that have not yet been withdrawn",
+            ),
+            (
+                State::UNSTAKED_LIQUIDITY_POOL,
+                "unstaked_liquidity_pool",
+                "liquidity for withdrawing unstaked NEAR funds that are still locked",
+            ),
+        ];
+        for (id, name, description) in balance_ids.iter() {
+            ContractNearBalances::register_balance_id(
+                *id,
+                BalanceIdLabel {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    component: "StakingPoolComponent".to_string(),
+                },
+            );
+        }
     }
 }
 
@@ -282,31 +327,204 @@ impl StakingPool for StakingPoolComponent {
     }
 
     fn ops_stake(&mut self) -> PromiseOrValue<StakeAccountBalances> {
+        self.stake_available_balance(&env::predecessor_account_id(), None)
+    }
+
+    fn ops_unstake(&mut self, amount: Option<YoctoNear>) -> PromiseOrValue<StakeAccountBalances> {
+        self.unstake_account(&env::predecessor_account_id(), amount, None)
+    }
+
+    fn ops_restake(&mut self, amount: Option<YoctoNear>) -> PromiseOrValue<StakeAccountBalances> {
         let account_id = env::predecessor_account_id();
+        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(&account_id));
+        AccountManager::record_account_activity(&account_id);
+
+        self.state_with_updated_earnings();
+
+        match self.account_manager.load_account_data(&account_id) {
+            // account has no unstaked funds to restake
+            None => match amount {
+                None => self.registered_stake_account_balance(&account_id),
+                Some(_) => {
+                    ERR_INSUFFICIENT_FUNDS.panic();
+                    unreachable!()
+                }
+            },
+            Some(mut account) => {
+                let (near_amount, stake_token_amount) = {
+                    let near = amount.unwrap_or_else(|| account.unstaked_balances.total());
+                    let (stake, remainder) = self.near_to_stake(near);
+                    let stake_near_value = near - remainder;
+                    account
+                        .unstaked_balances
+                        .debit_for_restaking(stake_near_value);
+                    account.save();
+                    State::decr_total_unstaked_balance(stake_near_value);
+                    (stake_near_value, stake)
+                };
+                // NOTE: restaking does not add liquidity because no new funds are being deposited
+                self.stake(&account_id, near_amount, stake_token_amount)
+            }
+        }
+    }
+
+    fn ops_stake_withdraw(&mut self, amount: Option<YoctoNear>) -> StakeAccountBalances {
+        self.withdraw_account(&env::predecessor_account_id(), amount, None)
+    }
+
+    fn ops_stake_transfer(
+        &mut self,
+        receiver_id: ValidAccountId,
+        amount: YoctoNear,
+        memo: Option<Memo>,
+    ) -> TokenAmount {
+        self.state_with_updated_earnings();
+        let stake_value = self.near_stake_value_rounded_up(amount);
+        self.stake_token.ft_transfer(receiver_id, stake_value, memo);
+        stake_value
+    }
+
+    fn ops_stake_transfer_call(
+        &mut self,
+        receiver_id: ValidAccountId,
+        amount: YoctoNear,
+        memo: Option<Memo>,
+        msg: TransferCallMessage,
+    ) -> Promise {
+        self.state_with_updated_earnings();
+        let stake_value = self.near_stake_value_rounded_up(amount);
+        self.stake_token
+            .ft_transfer_call(receiver_id, stake_value, memo, msg)
+    }
+
+    fn ops_stake_token_value(&self, amount: Option<TokenAmount>) -> YoctoNear {
+        self.compute_stake_near_value_rounded_down(
+            amount.unwrap_or(YOCTO.into()),
+            State::total_staked_balance() + Self::state().check_for_earnings_in_view_mode(),
+        )
+    }
+
+    fn ops_stake_token_value_with_earnings(&mut self, amount: Option<TokenAmount>) -> YoctoNear {
+        self.state_with_updated_earnings();
+        self.stake_near_value_rounded_down(amount.unwrap_or(YOCTO.into()))
+    }
+
+    fn ops_stake_status(&self) -> Status {
+        Self::state().status
+    }
+
+    fn ops_stake_pool_balances(&self) -> StakingPoolBalances {
+        StakingPoolBalances::new(
+            *StakingPoolComponent::state(),
+            self.stake_token.ft_total_supply(),
+        )
+    }
+
+    fn ops_stake_fees(&self) -> Fees {
+        let state = Self::state();
+        Fees {
+            staking_fee: state.staking_fee,
+            earnings_fee: state.earnings_fee,
+        }
+    }
+
+    fn ops_stake_public_key(&self) -> PublicKey {
+        Self::state().stake_public_key
+    }
+}
+
+impl StakingPoolDelegation for StakingPoolComponent {
+    fn ops_stake_for(
+        &mut self,
+        account_id: ValidAccountId,
+    ) -> PromiseOrValue<StakeAccountBalances> {
+        self.stake_available_balance(account_id.as_ref(), Some(&env::predecessor_account_id()))
+    }
+
+    fn ops_unstake_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> PromiseOrValue<StakeAccountBalances> {
+        self.unstake_account(
+            account_id.as_ref(),
+            amount,
+            Some(&env::predecessor_account_id()),
+        )
+    }
+
+    fn ops_stake_withdraw_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> StakeAccountBalances {
+        self.withdraw_account(
+            account_id.as_ref(),
+            amount,
+            Some(&env::predecessor_account_id()),
+        )
+    }
+}
+
+impl StakingPoolComponent {
+    /// The account's storage balance must also cover the storage for the account's STAKE balance,
+    /// which is allocated when STAKE is minted for the account for the first time.
+    fn required_storage_balance(&self, account_id: &str, account: &AccountNearData) -> YoctoNear {
+        let storage_balance_min = self.account_manager.storage_balance_bounds().min;
+        if self
+            .stake_token
+            .ft_balance_of(to_valid_account_id(account_id))
+            > TokenAmount::ZERO
+        {
+            return storage_balance_min;
+        }
+        let stake_balance_storage_cost =
+            env::storage_byte_cost() * StakeFungibleToken::account_storage_min().value() as u128;
+        storage_balance_min.max(account.storage_usage_cost() + stake_balance_storage_cost)
+    }
+
+    fn stake_available_balance(
+        &mut self,
+        account_id: &str,
+        delegate: Option<&str>,
+    ) -> PromiseOrValue<StakeAccountBalances> {
         let mut account = self
             .account_manager
-            .registered_account_near_data(&account_id);
+            .registered_account_near_data(account_id);
 
         self.state_with_updated_earnings();
 
         // stake the account's total available storage balance + attached deposit
         let (near_amount, stake_token_amount) = {
-            let account_storage_available_balance = account
-                .storage_balance(self.account_manager.storage_balance_bounds().min)
-                .available;
+            let required_storage_balance = self
+                .required_storage_balance(account_id, &account)
+                .max(account.storage_usage_cost());
+            let account_storage_available_balance =
+                account.storage_balance(required_storage_balance).available;
             account.decr_near_balance(account_storage_available_balance);
 
-            let near = account_storage_available_balance + env::attached_deposit();
+            // any storage balance shortfall is covered by the attached deposit
+            let attached_deposit = env::attached_deposit();
+            let storage_balance_shortfall = required_storage_balance
+                .value()
+                .saturating_sub(account.near_balance().value())
+                .min(attached_deposit);
+            account.incr_near_balance(storage_balance_shortfall.into());
+
+            let near =
+                account_storage_available_balance + (attached_deposit - storage_balance_shortfall);
             ERR_NEAR_DEPOSIT_REQUIRED.assert_with_message(
                 || near > YoctoNear::ZERO,
                 || "deposit NEAR into storage balance or attach NEAR deposit",
             );
             let (stake, remainder) = self.near_to_stake(near);
             account.incr_near_balance(remainder);
+            account.record_activity();
             account.save();
 
             (near - remainder, stake)
         };
+        Self::debit_delegation(account_id, delegate, DELEGATED_ACTION_STAKE, near_amount);
 
         if near_amount == YoctoNear::ZERO {
             // INVARIANT CHECK: if `near_amount` is zero, then `stake_token_amount` should be zero
@@ -314,25 +532,37 @@ impl StakingPool for StakingPoolComponent {
             // NOTE: any attached deposit be deposited into the account's storage balance - this, there
             // is no need to panic
             LOG_EVENT_NOT_ENOUGH_TO_STAKE.log("");
-            return self.registered_stake_account_balance(&account_id);
+            return self.registered_stake_account_balance(account_id);
         }
 
         State::add_liquidity(near_amount);
-        self.stake(&account_id, near_amount, stake_token_amount)
+        self.stake(account_id, near_amount, stake_token_amount)
     }
 
-    fn ops_unstake(&mut self, amount: Option<YoctoNear>) -> PromiseOrValue<StakeAccountBalances> {
-        let account_id = env::predecessor_account_id();
-        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(&account_id));
+    fn unstake_account(
+        &mut self,
+        account_id: &str,
+        amount: Option<YoctoNear>,
+        delegate: Option<&str>,
+    ) -> PromiseOrValue<StakeAccountBalances> {
+        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(account_id));
+        AccountManager::assert_account_not_frozen(account_id);
+        AccountManager::record_account_activity(account_id);
 
         let state = self.state_with_updated_earnings();
 
         let stake_balance = self
             .stake_token
-            .ft_balance_of(to_valid_account_id(&account_id));
+            .ft_balance_of(to_valid_account_id(account_id));
         if stake_balance == TokenAmount::ZERO {
             if amount.is_none() {
-                return self.registered_stake_account_balance(&account_id);
+                Self::debit_delegation(
+                    account_id,
+                    delegate,
+                    DELEGATED_ACTION_UNSTAKE,
+                    YoctoNear::ZERO,
+                );
+                return self.registered_stake_account_balance(account_id);
             }
             ERR_INSUFFICIENT_FUNDS.panic_with_message("STAKE balance is zero");
             unreachable!()
@@ -351,6 +581,8 @@ impl StakingPool for StakingPoolComponent {
             }
         };
 
+        Self::debit_delegation(account_id, delegate, DELEGATED_ACTION_UNSTAKE, near_amount);
+
         LOG_EVENT_UNSTAKE.log(format!(
             "near_amount={}, stake_token_amount={}",
             near_amount, stake_token_amount
@@ -358,61 +590,35 @@ impl StakingPool for StakingPoolComponent {
 
         State::decr_total_staked_balance(near_amount);
         State::incr_total_unstaked_balance(near_amount);
-        self.stake_token.ft_burn(&account_id, stake_token_amount);
+        self.stake_token.ft_burn(account_id, stake_token_amount);
         let burned_near_value = self.ops_stake_token_value(Some(stake_token_amount));
         let rounding_diff = burned_near_value.saturating_sub(*near_amount);
-        self.credit_account_unstaked_balance(&account_id, near_amount + rounding_diff);
+        self.credit_account_unstaked_balance(account_id, near_amount + rounding_diff);
 
         match state.status {
             Status::Online => {
-                let promise = Self::create_stake_workflow(state.stake_public_key, &account_id);
+                let promise = Self::create_stake_workflow(state.stake_public_key, account_id);
                 PromiseOrValue::Promise(promise)
             }
             Status::Offline(_) => {
                 LOG_EVENT_STATUS_OFFLINE.log("");
-                self.registered_stake_account_balance(&account_id)
-            }
-        }
-    }
-
-    fn ops_restake(&mut self, amount: Option<YoctoNear>) -> PromiseOrValue<StakeAccountBalances> {
-        let account_id = env::predecessor_account_id();
-        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(&account_id));
-
-        self.state_with_updated_earnings();
-
-        match self.account_manager.load_account_data(&account_id) {
-            // account has no unstaked funds to restake
-            None => match amount {
-                None => self.registered_stake_account_balance(&account_id),
-                Some(_) => {
-                    ERR_INSUFFICIENT_FUNDS.panic();
-                    unreachable!()
-                }
-            },
-            Some(mut account) => {
-                let (near_amount, stake_token_amount) = {
-                    let near = amount.unwrap_or_else(|| account.unstaked_balances.total());
-                    let (stake, remainder) = self.near_to_stake(near);
-                    let stake_near_value = near - remainder;
-                    account
-                        .unstaked_balances
-                        .debit_for_restaking(stake_near_value);
-                    account.save();
-                    State::decr_total_unstaked_balance(stake_near_value);
-                    (stake_near_value, stake)
-                };
-                // NOTE: restaking does not add liquidity because no new funds are being deposited
-                self.stake(&account_id, near_amount, stake_token_amount)
+                self.registered_stake_account_balance(account_id)
             }
         }
     }
 
-    fn ops_stake_withdraw(&mut self, amount: Option<YoctoNear>) -> StakeAccountBalances {
-        let account_id = env::predecessor_account_id();
-        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(&account_id));
+    fn withdraw_account(
+        &mut self,
+        account_id: &str,
+        amount: Option<YoctoNear>,
+        delegate: Option<&str>,
+    ) -> StakeAccountBalances {
+        ERR_ACCOUNT_NOT_REGISTERED.assert(|| self.account_manager.account_exists(account_id));
+        AccountManager::assert_account_not_frozen(account_id);
+        AccountManager::record_account_activity(account_id);
 
         fn debit_available_balance(
+            account_id: &str,
             mut unstaked_balances: AccountDataObject<StakeAccountData>,
             amount: YoctoNear,
         ) {
@@ -432,7 +638,7 @@ impl StakingPool for StakingPoolComponent {
                 state.save();
             }
 
-            Promise::new(env::predecessor_account_id()).transfer(*amount);
+            Promise::new(account_id.to_string()).transfer(*amount);
         }
 
         // earnings are updated to ensure updated balances are returned
@@ -441,92 +647,43 @@ impl StakingPool for StakingPoolComponent {
         match amount {
             // withdraw all available
             None => {
+                let mut withdrawal = YoctoNear::ZERO;
                 if let Some(mut account_staked_data) =
-                    self.account_manager.load_account_data(&account_id)
+                    self.account_manager.load_account_data(account_id)
                 {
                     account_staked_data.unstaked_balances.apply_liquidity();
-                    let amount = account_staked_data.unstaked_balances.available();
-                    if amount > YoctoNear::ZERO {
-                        debit_available_balance(account_staked_data, amount);
+                    withdrawal = account_staked_data.unstaked_balances.available();
+                    if withdrawal > YoctoNear::ZERO {
+                        debit_available_balance(account_id, account_staked_data, withdrawal);
                     }
                 }
+                Self::debit_delegation(account_id, delegate, DELEGATED_ACTION_WITHDRAW, withdrawal);
             }
             // withdraw specified amount
             Some(amount) => {
                 ERR_INVALID.assert(|| amount > YoctoNear::ZERO, || "amount must be > 0");
-                match self.account_manager.load_account_data(&account_id) {
+                Self::debit_delegation(account_id, delegate, DELEGATED_ACTION_WITHDRAW, amount);
+                match self.account_manager.load_account_data(account_id) {
                     Some(mut unstaked_balances) => {
                         unstaked_balances.unstaked_balances.apply_liquidity();
-                        debit_available_balance(unstaked_balances, amount);
+                        debit_available_balance(account_id, unstaked_balances, amount);
                     }
                     None => ERR_INSUFFICIENT_FUNDS.panic(),
                 }
             }
         }
 
-        self.ops_stake_balance(to_valid_account_id(&account_id))
+        self.ops_stake_balance(to_valid_account_id(account_id))
             .unwrap()
     }
 
-    fn ops_stake_transfer(
-        &mut self,
-        receiver_id: ValidAccountId,
-        amount: YoctoNear,
-        memo: Option<Memo>,
-    ) -> TokenAmount {
-        self.state_with_updated_earnings();
-        let stake_value = self.near_stake_value_rounded_up(amount);
-        self.stake_token.ft_transfer(receiver_id, stake_value, memo);
-        stake_value
-    }
-
-    fn ops_stake_transfer_call(
-        &mut self,
-        receiver_id: ValidAccountId,
-        amount: YoctoNear,
-        memo: Option<Memo>,
-        msg: TransferCallMessage,
-    ) -> Promise {
-        self.state_with_updated_earnings();
-        let stake_value = self.near_stake_value_rounded_up(amount);
-        self.stake_token
-            .ft_transfer_call(receiver_id, stake_value, memo, msg)
-    }
-
-    fn ops_stake_token_value(&self, amount: Option<TokenAmount>) -> YoctoNear {
-        self.compute_stake_near_value_rounded_down(
-            amount.unwrap_or(YOCTO.into()),
-            State::total_staked_balance() + Self::state().check_for_earnings_in_view_mode(),
-        )
-    }
-
-    fn ops_stake_token_value_with_earnings(&mut self, amount: Option<TokenAmount>) -> YoctoNear {
-        self.state_with_updated_earnings();
-        self.stake_near_value_rounded_down(amount.unwrap_or(YOCTO.into()))
-    }
-
-    fn ops_stake_status(&self) -> Status {
-        Self::state().status
-    }
-
-    fn ops_stake_pool_balances(&self) -> StakingPoolBalances {
-        StakingPoolBalances::new(
-            *StakingPoolComponent::state(),
-            self.stake_token.ft_total_supply(),
-        )
-    }
-
-    fn ops_stake_fees(&self) -> Fees {
-        let state = Self::state();
-        Fees {
-            staking_fee: state.staking_fee,
-            earnings_fee: state.earnings_fee,
+    /// if the action is performed by a delegate, then the amount is debited from the delegate's
+    /// allowance - see [`AccountDelegations::consume`]
+    fn debit_delegation(account_id: &str, delegate: Option<&str>, action: &str, amount: YoctoNear) {
+        if let Some(delegate) = delegate {
+            AccountDelegations::consume(account_id, delegate, action, *amount);
         }
     }
-
-    fn ops_stake_public_key(&self) -> PublicKey {
-        Self::state().stake_public_key
-    }
 }
 
 impl NearStakingPool for StakingPoolComponent {
@@ -616,13 +773,15 @@ impl NearStakingPool for StakingPoolComponent {
         let (mut near_account, account_data) = self
             .account_manager
             .registered_account(&env::predecessor_account_id());
+        let required_storage_balance =
+            self.required_storage_balance(&env::predecessor_account_id(), &near_account);
         match account_data {
             Some(mut data) => {
                 if data.unstaked_balances.total() < amount {
                     ERR_INSUFFICIENT_FUNDS.assert(|| {
                         data.unstaked_balances.total()
                             + near_account
-                                .storage_balance(self.account_manager.storage_balance_bounds().min)
+                                .storage_balance(required_storage_balance)
                                 .available
                             >= amount
                     });
@@ -639,7 +798,7 @@ impl NearStakingPool for StakingPoolComponent {
             None => {
                 ERR_INSUFFICIENT_FUNDS.assert(|| {
                     near_account
-                        .storage_balance(self.account_manager.storage_balance_bounds().min)
+                        .storage_balance(required_storage_balance)
                         .available
                         >= amount
                 });
@@ -665,7 +824,8 @@ impl NearStakingPool for StakingPoolComponent {
 
 impl StakingPoolOperator for StakingPoolComponent {
     fn ops_stake_operator_command(&mut self, command: StakingPoolOperatorCommand) {
-        self.account_manager.assert_operator();
+        self.account_manager.assert_operator(command.command_name());
+        let args = serde_json::to_string(&command).unwrap();
 
         match command {
             StakingPoolOperatorCommand::StopStaking => Self::stop_staking(OfflineReason::Stopped),
@@ -675,6 +835,7 @@ impl StakingPoolOperator for StakingPoolComponent {
             }
             StakingPoolOperatorCommand::UpdateFees(fees) => Self::update_staking_fees(fees),
         }
+        AuditLog::record("ops_stake_operator_command", None, Some(args));
     }
 }
 
@@ -832,17 +993,19 @@ impl Treasury for StakingPoolComponent {
     fn ops_stake_treasury_transfer_to_owner(&mut self, amount: Option<YoctoNear>) {
         let owner_account_id = ContractOwnershipComponent.ops_owner();
         ERR_NOT_AUTHORIZED.assert(|| {
-            let account_id = env::predecessor_account_id();
-            if owner_account_id == account_id {
+            // approved proposals are executed by the contract itself on behalf of the owner
+            if ContractOwnerObject::is_owner()
+                || ContractMultiSigComponent::<StakeAccountData>::is_approved_call()
+            {
                 return true;
             }
-            let account = self
-                .account_manager
-                .registered_account_near_data(&account_id);
-            account.contains_permissions(self.treasurer_permission().into())
+            self.treasurer_permission().map_or(false, |treasurer| {
+                self.account_manager
+                    .registered_account_near_data(&env::predecessor_account_id())
+                    .contains_permissions(treasurer.into())
+            })
         });
-
-        AccountManager::register_account_if_not_exists(&owner_account_id);
+        ApprovalRequiredCommands::assert_approved("ops_stake_treasury_transfer_to_owner");
 
         let mut state = self.state_with_updated_earnings();
 
@@ -870,7 +1033,7 @@ impl Treasury for StakingPoolComponent {
         // transfer STAKE from treasury to owner account
         {
             self.stake_token.ft_burn(&treasury_account, stake);
-            self.stake_token.ft_mint(&owner_account_id, stake);
+            self.mint_owner_stake(stake);
         }
 
         // debit from the treasury balance
@@ -878,24 +1041,38 @@ impl Treasury for StakingPoolComponent {
             state.treasury_balance -= amount;
             state.save();
         }
+
+        AuditLog::record(
+            "ops_stake_treasury_transfer_to_owner",
+            Some(&owner_account_id),
+            Some(json!({ "amount": amount }).to_string()),
+        );
     }
 
-    fn ops_stake_grant_treasurer(&mut self, account_id: ValidAccountId) {
-        self.account_manager
-            .ops_permissions_grant(account_id, self.treasurer_permission().into());
+    fn ops_stake_grant_treasurer(
+        &mut self,
+        account_id: ValidAccountId,
+        expiration: Option<ExpirationSetting>,
+    ) {
+        ApprovalRequiredCommands::assert_approved("ops_stake_grant_treasurer");
+        self.account_manager.ops_permissions_grant(
+            account_id,
+            self.assert_treasurer_permission().into(),
+            expiration,
+        );
     }
 
     fn ops_stake_revoke_treasurer(&mut self, account_id: ValidAccountId) {
+        ApprovalRequiredCommands::assert_approved("ops_stake_revoke_treasurer");
         self.account_manager
-            .ops_permissions_revoke(account_id, self.treasurer_permission().into());
+            .ops_permissions_revoke(account_id, self.assert_treasurer_permission().into());
     }
 
     fn ops_stake_is_treasurer(&mut self, account_id: ValidAccountId) -> bool {
-        self.account_manager
-            .load_account_near_data(account_id.as_ref())
-            .map_or(false, |account| {
-                account.contains_permissions(self.treasurer_permission().into())
-            })
+        self.treasurer_permission().map_or(false, |treasurer| {
+            self.account_manager
+                .ops_permissions_contains(account_id, treasurer.into())
+        })
     }
 }
 
@@ -937,6 +1114,96 @@ struct ResumeFinalizeCallbackArgs {
     pub total_staked_balance: YoctoNear,
 }
 
+impl StakingPoolComponent {
+    /// Adds the staking pool checks to the health check report. The contract NEAR balance checks
+    /// are expected to have been run, i.e., the contract managed balance checks are skipped if the
+    /// contract NEAR balances are not covered.
+    pub fn health_check(&self, report: &mut HealthCheckReport) {
+        let state = Self::state();
+        let total_staked = State::total_staked_balance();
+        let total_unstaked = State::total_unstaked_balance();
+        let liquidity = State::liquidity();
+        let stake_supply = self.stake_token.ft_total_supply();
+
+        report.check(
+            HEALTH_CHECK_STAKE_SUPPLY,
+            stake_supply == TokenAmount::ZERO || total_staked > YoctoNear::ZERO,
+            || {
+                format!(
+                    "STAKE supply: {} is not backed by staked NEAR",
+                    stake_supply
+                )
+            },
+        );
+        // converting STAKE to NEAR rounds down, which loses less than the value of 1 yocto STAKE
+        let stake_supply_value = self.stake_near_value_rounded_down(stake_supply);
+        let rounding = if stake_supply == TokenAmount::ZERO {
+            0
+        } else {
+            total_staked.value() / *stake_supply + 1
+        };
+        report.check(
+            HEALTH_CHECK_STAKE_VALUE,
+            total_staked.value().abs_diff(stake_supply_value.value()) <= rounding,
+            || {
+                format!(
+                    "STAKE supply value: {} != staked: {}",
+                    stake_supply_value, total_staked
+                )
+            },
+        );
+        let treasury_stake = self
+            .stake_token
+            .ft_balance_of(to_valid_account_id(&env::current_account_id()));
+        report.check(
+            HEALTH_CHECK_TREASURY_STAKE,
+            treasury_stake <= stake_supply,
+            || {
+                format!(
+                    "treasury STAKE: {} > STAKE supply: {}",
+                    treasury_stake, stake_supply
+                )
+            },
+        );
+
+        if !report.violated(HEALTH_CHECK_CONTRACT_NEAR_BALANCES) {
+            let contract_managed_total_balance =
+                State::contract_managed_total_balance_in_view_mode();
+            let staking_pool_balances =
+                total_staked.value() + total_unstaked.value() + liquidity.value();
+            report.check(
+                HEALTH_CHECK_STAKING_POOL_BALANCES,
+                contract_managed_total_balance.value() >= staking_pool_balances,
+                || {
+                    format!(
+                        "contract managed balance: {} < staked: {} + unstaked: {} + liquidity: {}",
+                        contract_managed_total_balance, total_staked, total_unstaked, liquidity
+                    )
+                },
+            );
+            report.check(
+                HEALTH_CHECK_STAKING_POOL_EARNINGS,
+                contract_managed_total_balance >= state.last_contract_managed_total_balance,
+                || {
+                    format!(
+                        "contract managed balance: {} < last contract managed balance: {}",
+                        contract_managed_total_balance, state.last_contract_managed_total_balance
+                    )
+                },
+            );
+        }
+
+        if state.status.is_online() {
+            let locked = env::account_locked_balance();
+            report.check(
+                HEALTH_CHECK_STAKED_BALANCE_LOCKED,
+                locked >= total_staked.value(),
+                || format!("locked: {} < staked: {}", locked, total_staked),
+            );
+        }
+    }
+}
+
 // staking related methods
 impl StakingPoolComponent {
     /// Stakes the NEAR and mints the corresponding STAKE for the account
@@ -1039,10 +1306,27 @@ impl StakingPoolComponent {
         ])
     }
 
-    fn treasurer_permission(&self) -> Permission {
-        self.account_manager
-            .permission_by_name(PERMISSION_TREASURER)
-            .unwrap()
+    /// Returns None if the treasurer permission has been retired
+    fn treasurer_permission(&self) -> Option<Permission> {
+        if self
+            .account_manager
+            .contract_permissions()
+            .0
+            .contains_key(&PERMISSION_TREASURER_BIT)
+        {
+            Some(1 << PERMISSION_TREASURER_BIT)
+        } else {
+            None
+        }
+    }
+
+    /// ## Panics
+    /// [`ERR_NOT_AUTHORIZED`] - if the treasurer permission has been retired
+    fn assert_treasurer_permission(&self) -> Permission {
+        self.treasurer_permission().unwrap_or_else(|| {
+            ERR_NOT_AUTHORIZED.panic_with_message("treasurer permission has been retired");
+            unreachable!()
+        })
     }
 
     fn treasury_stake_balance(&self) -> (TokenAmount, YoctoNear) {
@@ -1075,16 +1359,15 @@ impl StakingPoolComponent {
         }
 
         // collect staking fee - treasury and owner accounts do not get charged staking fees
-        let owner_id = ContractOwnershipComponent.ops_owner();
         if stake_token_amount > TokenAmount::ZERO
             && state.staking_fee > BasisPoints::ZERO
             && account_id != &env::current_account_id()
-            && account_id != &owner_id
+            && !ContractCoOwnershipComponent::is_owner_payee(account_id)
         {
             let staking_fee = self.near_stake_value_rounded_down(amount * state.staking_fee);
             if staking_fee > TokenAmount::ZERO {
                 self.stake_token.ft_burn(&account_id, staking_fee);
-                self.stake_token.ft_mint(&owner_id, staking_fee);
+                self.mint_owner_stake(staking_fee);
             }
         }
 
@@ -1092,6 +1375,14 @@ impl StakingPoolComponent {
         state
     }
 
+    /// owner STAKE is shared by the co-owners pro rata if the contract is co-owned
+    fn mint_owner_stake(&mut self, stake: TokenAmount) {
+        for (account_id, amount) in ContractCoOwnershipComponent::owner_payees(*stake) {
+            AccountManager::register_account_if_not_exists(&account_id);
+            self.stake_token.ft_mint(&account_id, amount.into());
+        }
+    }
+
     fn registered_stake_account_balance(
         &self,
         account_id: &str,
@@ -1182,8 +1473,7 @@ impl StakingPoolComponent {
             // into STAKE, which will end up being distributed into the pool
             let owner_stake_earnings = self.near_stake_value_rounded_down(owner_earnings);
             if owner_stake_earnings > TokenAmount::ZERO {
-                let owner_id = ContractOwnershipComponent.ops_owner();
-                self.stake_token.ft_mint(&owner_id, owner_stake_earnings);
+                self.mint_owner_stake(owner_stake_earnings);
             }
             State::incr_total_staked_balance(owner_earnings);
         }
@@ -1294,7 +1584,8 @@ mod tests_staking_pool {
         ContractPermissions,
     };
     use oysterpack_smart_contract::{
-        components::contract_operator::ContractOperatorComponent, ContractOperator,
+        components::contract_operator::ContractOperatorComponent, ContractMultiSig,
+        ContractOperator, MultiSigConfig, ProposalCommand,
     };
     use oysterpack_smart_fungible_token::components::fungible_token::FungibleTokenConfig;
     use oysterpack_smart_fungible_token::{
@@ -1302,7 +1593,7 @@ mod tests_staking_pool {
         Symbol, FT_METADATA_SPEC,
     };
     use oysterpack_smart_near::{
-        component::*,
+        domain::ExpirationDuration,
         near_sdk::{env, serde_json, test_utils},
         *,
     };
@@ -1364,7 +1655,7 @@ mod tests_staking_pool {
 
         let contract_permissions = {
             let mut permissions = HashMap::with_capacity(1);
-            permissions.insert(0, PERMISSION_TREASURER);
+            permissions.insert(PERMISSION_TREASURER_BIT, PERMISSION_TREASURER.to_string());
             ContractPermissions(permissions)
         };
 
@@ -2084,8 +2375,8 @@ last_contract_managed_total_balance             {}
   "total_unstaked": "0",
   "unstaked_liquidity": "0",
   "treasury_balance": "0",
-  "current_contract_managed_total_balance": "13172980000000000000000000",
-  "last_contract_managed_total_balance": "13172980000000000000000000",
+  "current_contract_managed_total_balance": "13177830000000000000000000",
+  "last_contract_managed_total_balance": "13177830000000000000000000",
   "earnings": "0"
 }"#
                     )
@@ -2251,8 +2542,8 @@ last_contract_managed_total_balance             {}
   "total_unstaked": "0",
   "unstaked_liquidity": "0",
   "treasury_balance": "80000000000000000000000",
-  "current_contract_managed_total_balance": "16172980000000000000000000",
-  "last_contract_managed_total_balance": "16172980000000000000000000",
+  "current_contract_managed_total_balance": "16177830000000000000000000",
+  "last_contract_managed_total_balance": "16177830000000000000000000",
   "earnings": "0"
 }"#
                     )
@@ -2315,8 +2606,8 @@ last_contract_managed_total_balance             {}
   "total_unstaked": "0",
   "unstaked_liquidity": "0",
   "treasury_balance": "80003758250534376247857",
-  "current_contract_managed_total_balance": "17272980000000000000000000",
-  "last_contract_managed_total_balance": "17272980000000000000000000",
+  "current_contract_managed_total_balance": "17277830000000000000000000",
+  "last_contract_managed_total_balance": "17277830000000000000000000",
   "earnings": "0"
 }"#
                     )
@@ -2343,7 +2634,7 @@ last_contract_managed_total_balance             {}
                     serde_json::from_str(
                         r#"{
   "storage_balance": {
-    "total": "3930000000000000000000",
+    "total": "7870000000000000000000",
     "available": "0"
   },
   "staked": {
@@ -2390,10 +2681,10 @@ last_contract_managed_total_balance             {}
                 println!("{:#?}", logs);
                 // no staking fee should be charged to the owner
                 assert_eq!(logs, vec![
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Withdrawal(YoctoNear(9996819160000000000000000000))",
-                    "[INFO] [STAKE] near_amount=9997819160000000000000000000, stake_token_amount=9997819160000000000000000000",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Withdrawal(YoctoNear(9996806430000000000000000000))",
+                    "[INFO] [STAKE] near_amount=9997806430000000000000000000, stake_token_amount=9997806430000000000000000000",
                     "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(104)",
-                    "[INFO] [FT_MINT] account: owner, amount: 9997819160000000000000000000",
+                    "[INFO] [FT_MINT] account: owner, amount: 9997806430000000000000000000",
                     "[WARN] [STATUS_OFFLINE] ",
                 ]);
 
@@ -4944,8 +5235,8 @@ last_contract_managed_total_balance             {}
   "total_unstaked": "0",
   "unstaked_liquidity": "0",
   "treasury_balance": "0",
-  "current_contract_managed_total_balance": "13172980000000000000000000",
-  "last_contract_managed_total_balance": "13172980000000000000000000",
+  "current_contract_managed_total_balance": "13177830000000000000000000",
+  "last_contract_managed_total_balance": "13177830000000000000000000",
   "earnings": "0"
 }"#
                     )
@@ -5089,8 +5380,8 @@ last_contract_managed_total_balance             {}
   "total_unstaked": "0",
   "unstaked_liquidity": "0",
   "treasury_balance": "80000000000000000000000",
-  "current_contract_managed_total_balance": "16172980000000000000000000",
-  "last_contract_managed_total_balance": "16172980000000000000000000",
+  "current_contract_managed_total_balance": "16177830000000000000000000",
+  "last_contract_managed_total_balance": "16177830000000000000000000",
   "earnings": "0"
 }"#
                     )
@@ -5154,7 +5445,7 @@ last_contract_managed_total_balance             {}
                     serde_json::from_str(
                         r#"{
   "storage_balance": {
-    "total": "3930000000000000000000",
+    "total": "7870000000000000000000",
     "available": "0"
   },
   "staked": {
@@ -5206,10 +5497,10 @@ last_contract_managed_total_balance             {}
                 let logs = test_utils::get_logs();
                 println!("{:#?}", logs);
                 assert_eq!(logs, vec![
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Withdrawal(YoctoNear(9996819160000000000000000000))",
-                    "[INFO] [STAKE] near_amount=9997819160000000000000000000, stake_token_amount=9997819160000000000000000000",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Withdrawal(YoctoNear(9996806430000000000000000000))",
+                    "[INFO] [STAKE] near_amount=9997806430000000000000000000, stake_token_amount=9997806430000000000000000000",
                     "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(104)",
-                    "[INFO] [FT_MINT] account: owner, amount: 9997819160000000000000000000",
+                    "[INFO] [FT_MINT] account: owner, amount: 9997806430000000000000000000",
                 ]);
 
                 let pool_balances = staking_pool.ops_stake_pool_balances();
@@ -8073,6 +8364,32 @@ last_contract_managed_total_balance             {}
                 assert_eq!(staking_pool.ops_stake_fees().staking_fee, MAX_FEE);
             }
 
+            #[test]
+            #[should_panic(
+                expected = "[ERR] [APPROVAL_REQUIRED] ops_stake_operator_command.UpdateFees"
+            )]
+            fn update_fee_requires_approval() {
+                let mut ctx = new_context(OWNER);
+                testing_env!(ctx.clone());
+
+                deploy_stake_contract(staking_public_key());
+                ApprovalRequiredCommands::Commands(vec![
+                    "ops_stake_operator_command.UpdateFees".to_string()
+                ])
+                .save();
+
+                ctx.predecessor_account_id = OWNER.to_string();
+                testing_env!(ctx.clone());
+
+                // operator commands that are not configured do not require approval
+                let mut staking_pool = staking_pool();
+                staking_pool.ops_stake_operator_command(StakingPoolOperatorCommand::StopStaking);
+
+                let fees = staking_pool.ops_stake_fees();
+                staking_pool
+                    .ops_stake_operator_command(StakingPoolOperatorCommand::UpdateFees(fees));
+            }
+
             #[test]
             #[should_panic(expected = "[ERR] [INVALID] max staking fee is 1000 BPS (10%)")]
             fn update_fee_above_max() {
@@ -8602,7 +8919,7 @@ last_contract_managed_total_balance             {}
                     ctx.predecessor_account_id = OWNER.to_string();
                     ctx.attached_deposit = 0;
                     testing_env!(ctx.clone());
-                    staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT));
+                    staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT), None);
 
                     ctx.predecessor_account_id = ACCOUNT.to_string();
                     testing_env!(ctx.clone());
@@ -8641,7 +8958,7 @@ last_contract_managed_total_balance             {}
                     ctx.account_balance = env::account_balance();
                     ctx.attached_deposit = 0;
                     testing_env!(ctx.clone());
-                    staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT));
+                    staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT), None);
 
                     ctx.predecessor_account_id = ACCOUNT.to_string();
                     ctx.account_balance = env::account_balance();
@@ -8694,7 +9011,7 @@ last_contract_managed_total_balance             {}
                     ctx.account_balance = env::account_balance();
                     ctx.attached_deposit = 0;
                     testing_env!(ctx.clone());
-                    staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT));
+                    staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT), None);
 
                     ctx.predecessor_account_id = ACCOUNT.to_string();
                     ctx.account_balance = env::account_balance();
@@ -8848,24 +9165,115 @@ last_contract_managed_total_balance             {}
                     ctx.account_balance = env::account_balance();
                     ctx.attached_deposit = YOCTO;
                     testing_env!(ctx.clone());
-                    staking_pool.ops_stake_treasury_deposit();
+                    staking_pool.ops_stake_treasury_deposit();
+
+                    ctx.predecessor_account_id = ACCOUNT.to_string();
+                    ctx.account_balance = env::account_balance();
+                    ctx.attached_deposit = 0;
+                    testing_env!(ctx.clone());
+                    let pool_balances = staking_pool.ops_stake_pool_balances();
+                    println!("{}", serde_json::to_string_pretty(&pool_balances).unwrap());
+                    assert_eq!(pool_balances.treasury_balance, YOCTO.into());
+                    let owner_balance = staking_pool
+                        .ops_stake_balance(to_valid_account_id(OWNER))
+                        .unwrap();
+                    assert!(owner_balance.staked.is_none());
+
+                    ctx.predecessor_account_id = OWNER.to_string();
+                    ctx.account_balance = env::account_balance();
+                    testing_env!(ctx.clone());
+                    staking_pool.ops_stake_treasury_transfer_to_owner(Some((YOCTO + 1).into()));
+                }
+
+                fn configure_multisig() {
+                    ProposalCommand::register_methods(&["ops_stake_treasury_transfer_to_owner"]);
+                    ContractMultiSigComponent::new(account_manager()).ops_multisig_configure(
+                        MultiSigConfig {
+                            approvers: vec!["alice".to_string(), "bob".to_string()],
+                            threshold: 2,
+                            proposal_expiration: ExpirationDuration::Epochs(3),
+                            commands: Some(
+                                vec!["ops_stake_treasury_transfer_to_owner".to_string()],
+                            ),
+                        },
+                    );
+                }
+
+                #[test]
+                fn via_approved_multisig_proposal() {
+                    // Arrange
+                    let mut ctx = new_context(OWNER);
+                    testing_env!(ctx.clone());
+
+                    deploy_stake_contract(staking_public_key());
+                    let mut staking_pool = staking_pool();
+                    configure_multisig();
+
+                    ctx.account_balance = env::account_balance();
+                    ctx.attached_deposit = YOCTO;
+                    testing_env!(ctx.clone());
+                    staking_pool.ops_stake_treasury_deposit();
+
+                    // Act - the proposal is approved
+                    let mut multisig = ContractMultiSigComponent::new(account_manager());
+                    ctx.predecessor_account_id = "alice".to_string();
+                    ctx.account_balance = env::account_balance();
+                    ctx.attached_deposit = 0;
+                    testing_env!(ctx.clone());
+                    let proposal = multisig.ops_multisig_propose(
+                        ProposalCommand {
+                            method_name: "ops_stake_treasury_transfer_to_owner".to_string(),
+                            args: "{}".to_string(),
+                            deposit: YoctoNear::ZERO,
+                            gas: 20.into(),
+                        },
+                        None,
+                    );
+                    ctx.predecessor_account_id = "bob".to_string();
+                    testing_env!(ctx.clone());
+                    multisig.ops_multisig_approve(proposal.id);
 
-                    ctx.predecessor_account_id = ACCOUNT.to_string();
+                    // Assert - the approved command is executed against the contract itself
+                    let receipts = deserialize_receipts();
+                    assert_eq!(receipts.len(), 1);
+                    assert_eq!(receipts[0].receiver_id, env::current_account_id());
+                    match &receipts[0].actions[0] {
+                        Action::FunctionCall(action) => {
+                            assert_eq!(action.method_name, "ops_stake_treasury_transfer_to_owner")
+                        }
+                        _ => panic!("expected FunctionCall"),
+                    }
+
+                    // Act - the approved command is executed
+                    ctx.predecessor_account_id = env::current_account_id();
                     ctx.account_balance = env::account_balance();
-                    ctx.attached_deposit = 0;
                     testing_env!(ctx.clone());
+                    staking_pool.ops_stake_treasury_transfer_to_owner(None);
+
+                    // Assert
                     let pool_balances = staking_pool.ops_stake_pool_balances();
-                    println!("{}", serde_json::to_string_pretty(&pool_balances).unwrap());
-                    assert_eq!(pool_balances.treasury_balance, YOCTO.into());
+                    assert_eq!(pool_balances.treasury_balance, YoctoNear::ZERO);
                     let owner_balance = staking_pool
                         .ops_stake_balance(to_valid_account_id(OWNER))
                         .unwrap();
-                    assert!(owner_balance.staked.is_none());
+                    assert_eq!(owner_balance.staked.unwrap().near_value, YOCTO.into());
+                }
+
+                #[test]
+                #[should_panic(
+                    expected = "[ERR] [APPROVAL_REQUIRED] ops_stake_treasury_transfer_to_owner"
+                )]
+                fn as_owner_when_multisig_approval_required() {
+                    let mut ctx = new_context(OWNER);
+                    testing_env!(ctx.clone());
+
+                    deploy_stake_contract(staking_public_key());
+                    let mut staking_pool = staking_pool();
+                    configure_multisig();
 
-                    ctx.predecessor_account_id = OWNER.to_string();
                     ctx.account_balance = env::account_balance();
                     testing_env!(ctx.clone());
-                    staking_pool.ops_stake_treasury_transfer_to_owner(Some((YOCTO + 1).into()));
+                    staking_pool.ops_stake_treasury_transfer_to_owner(None);
                 }
             }
 
@@ -8889,12 +9297,76 @@ last_contract_managed_total_balance             {}
                 testing_env!(ctx.clone());
                 assert!(!staking_pool.ops_stake_is_treasurer(to_valid_account_id(ACCOUNT)));
 
-                staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT));
+                staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT), None);
                 assert!(staking_pool.ops_stake_is_treasurer(to_valid_account_id(ACCOUNT)));
 
                 staking_pool.ops_stake_revoke_treasurer(to_valid_account_id(ACCOUNT));
                 assert!(!staking_pool.ops_stake_is_treasurer(to_valid_account_id(ACCOUNT)));
             }
+
+            #[test]
+            fn treasurer_permission_renamed_or_retired() {
+                // Arrange
+                let mut ctx = new_context(OWNER);
+                testing_env!(ctx.clone());
+
+                deploy_stake_contract(staking_public_key());
+                let mut staking_pool = staking_pool();
+                let mut account_manager = account_manager();
+
+                ctx.predecessor_account_id = ACCOUNT.to_string();
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.predecessor_account_id = OWNER.to_string();
+                ctx.attached_deposit = 0;
+                testing_env!(ctx.clone());
+                staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT), None);
+
+                // Act - rename
+                account_manager.ops_permissions_contract_permission_rename(
+                    PERMISSION_TREASURER_BIT,
+                    "stake-treasurer".to_string(),
+                );
+                // Assert
+                assert!(staking_pool.ops_stake_is_treasurer(to_valid_account_id(ACCOUNT)));
+
+                // Act - retire
+                account_manager
+                    .ops_permissions_contract_permission_retire(PERMISSION_TREASURER_BIT);
+                // Assert
+                assert!(!staking_pool.ops_stake_is_treasurer(to_valid_account_id(ACCOUNT)));
+            }
+
+            #[test]
+            #[should_panic(expected = "[ERR] [NOT_AUTHORIZED]")]
+            fn transfer_to_owner_with_retired_treasurer_permission() {
+                // Arrange
+                let mut ctx = new_context(OWNER);
+                testing_env!(ctx.clone());
+
+                deploy_stake_contract(staking_public_key());
+                let mut staking_pool = staking_pool();
+                let mut account_manager = account_manager();
+
+                ctx.predecessor_account_id = ACCOUNT.to_string();
+                ctx.attached_deposit = YOCTO;
+                testing_env!(ctx.clone());
+                account_manager.storage_deposit(None, None);
+
+                ctx.predecessor_account_id = OWNER.to_string();
+                ctx.attached_deposit = 0;
+                testing_env!(ctx.clone());
+                staking_pool.ops_stake_grant_treasurer(to_valid_account_id(ACCOUNT), None);
+                account_manager
+                    .ops_permissions_contract_permission_retire(PERMISSION_TREASURER_BIT);
+
+                // Act
+                ctx.predecessor_account_id = ACCOUNT.to_string();
+                testing_env!(ctx.clone());
+                staking_pool.ops_stake_treasury_transfer_to_owner(None);
+            }
         }
     }
 
@@ -9092,8 +9564,8 @@ last_contract_managed_total_balance             {}
   "total_unstaked": "0",
   "unstaked_liquidity": "0",
   "treasury_balance": "1111111111111111111111112",
-  "current_contract_managed_total_balance": "13172980000000000000000002",
-  "last_contract_managed_total_balance": "13172980000000000000000002",
+  "current_contract_managed_total_balance": "13177830000000000000000002",
+  "last_contract_managed_total_balance": "13177830000000000000000002",
   "earnings": "0"
 }"#
                 )
@@ -9108,7 +9580,7 @@ last_contract_managed_total_balance             {}
                 serde_json::from_str(
                     r#"{
   "storage_balance": {
-    "total": "3930000000000000000000",
+    "total": "7870000000000000000000",
     "available": "0"
   },
   "staked": {
@@ -9375,7 +9847,7 @@ last_contract_managed_total_balance             {}
                 vec![
                     "[INFO] [EARNINGS] 1000000000000000000000000",
                     "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(104)",
-                    "[INFO] [FT_MINT] account: owner, amount: 5015281435196141122921",
+                    "[INFO] [FT_MINT] account: owner, amount: 5005373007824915621074",
                 ]
             );
 
@@ -10100,8 +10572,9 @@ last_contract_managed_total_balance             {}
                 let logs = test_utils::get_logs();
                 println!("{:#?}", logs);
                 assert_eq!(logs, vec![
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(97)",
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Registered(StorageBalance { total: YoctoNear(1000000000000000000000000), available: YoctoNear(996070000000000000000000) })",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(124)",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(159)",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Registered(StorageBalance { total: YoctoNear(1000000000000000000000000), available: YoctoNear(992130000000000000000000) })",
                 ]);
 
                 let account_manager = account_manager();
@@ -10148,8 +10621,9 @@ last_contract_managed_total_balance             {}
                 let logs = test_utils::get_logs();
                 println!("{:#?}", logs);
                 assert_eq!(logs, vec![
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(97)",
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Registered(StorageBalance { total: YoctoNear(1000000000000000000000000), available: YoctoNear(996070000000000000000000) })",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(124)",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(159)",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Registered(StorageBalance { total: YoctoNear(1000000000000000000000000), available: YoctoNear(992130000000000000000000) })",
                 ]);
 
                 ctx.predecessor_account_id = ACCOUNT.to_string();
@@ -10160,13 +10634,13 @@ last_contract_managed_total_balance             {}
                 let logs = test_utils::get_logs();
                 println!("{:#?}", logs);
                 assert_eq!(logs, vec![
-                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Withdrawal(YoctoNear(996070000000000000000000))",
-                    "[INFO] [STAKE] near_amount=1996070000000000000000000, stake_token_amount=1996070000000000000000000",
+                    "[INFO] [ACCOUNT_STORAGE_CHANGED] Withdrawal(YoctoNear(992130000000000000000000))",
+                    "[INFO] [STAKE] near_amount=1992130000000000000000000, stake_token_amount=1992130000000000000000000",
                     "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(104)",
-                    "[INFO] [FT_MINT] account: bob, amount: 1996070000000000000000000",
-                    "[INFO] [FT_BURN] account: bob, amount: 15968560000000000000000",
+                    "[INFO] [FT_MINT] account: bob, amount: 1992130000000000000000000",
+                    "[INFO] [FT_BURN] account: bob, amount: 15937040000000000000000",
                     "[INFO] [ACCOUNT_STORAGE_CHANGED] StorageUsageChange(104)",
-                    "[INFO] [FT_MINT] account: owner, amount: 15968560000000000000000",
+                    "[INFO] [FT_MINT] account: owner, amount: 15937040000000000000000",
                     "[WARN] [STATUS_OFFLINE] ",
                 ]);
             }
@@ -10832,4 +11306,323 @@ last_contract_managed_total_balance             {}
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests_delegation {
+        use super::*;
+        use oysterpack_smart_account_management::AccountDelegation;
+        use oysterpack_smart_near::near_sdk::{json_types::U128, VMContext};
+
+        const DELEGATE: &str = "bot";
+
+        fn setup(actions: Vec<(&str, Option<U128>)>) -> (VMContext, StakingPoolComponent) {
+            let mut ctx = new_context(OWNER);
+            testing_env!(ctx.clone());
+
+            deploy_stake_contract(staking_public_key());
+            let mut account_manager = account_manager();
+
+            // register account
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = YOCTO;
+            testing_env!(ctx.clone());
+            account_manager.storage_deposit(None, Some(true));
+
+            ctx.attached_deposit = 1;
+            testing_env!(ctx.clone());
+            account_manager.ops_delegation_grant(
+                to_valid_account_id(DELEGATE),
+                actions
+                    .into_iter()
+                    .map(|(action, allowance)| (action.to_string(), allowance))
+                    .collect(),
+                None,
+            );
+
+            ctx.predecessor_account_id = DELEGATE.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            (ctx, staking_pool())
+        }
+
+        #[test]
+        fn stake_and_unstake_for_account() {
+            let (mut ctx, mut staking_pool) = setup(vec![
+                (DELEGATED_ACTION_STAKE, None),
+                (DELEGATED_ACTION_UNSTAKE, Some(YOCTO.into())),
+            ]);
+
+            // Act - stake the attached deposit on behalf of the account
+            ctx.attached_deposit = 2 * YOCTO;
+            testing_env!(ctx.clone());
+            if let PromiseOrValue::Value(balances) =
+                staking_pool.ops_stake_for(to_valid_account_id(ACCOUNT))
+            {
+                // staking fee is 80 BPS
+                assert_eq!(
+                    balances.staked.unwrap().near_value,
+                    (2 * YOCTO - 2 * YOCTO * 80 / 10000).into()
+                );
+            } else {
+                panic!("expected value");
+            }
+
+            // Arrange - the account's unstaked balance storage must be covered by the account
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = YOCTO;
+            testing_env!(ctx.clone());
+            account_manager().storage_deposit(None, None);
+
+            // Act - unstake on behalf of the account
+            ctx.predecessor_account_id = DELEGATE.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            if let PromiseOrValue::Value(balances) =
+                staking_pool.ops_unstake_for(to_valid_account_id(ACCOUNT), Some((YOCTO / 2).into()))
+            {
+                assert_eq!(balances.unstaked.unwrap().total, (YOCTO / 2).into());
+            } else {
+                panic!("expected value");
+            }
+
+            // Assert - the delegate's stake balance was not touched
+            assert!(staking_pool
+                .ops_stake_balance(to_valid_account_id(DELEGATE))
+                .is_none());
+            let delegation = AccountDelegations::delegation(ACCOUNT, DELEGATE).unwrap();
+            assert_eq!(
+                delegation.actions[DELEGATED_ACTION_UNSTAKE],
+                Some((YOCTO / 2).into())
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [DELEGATION_ALLOWANCE_EXCEEDED]")]
+        fn unstake_for_account_allowance_exceeded() {
+            let (mut ctx, mut staking_pool) = setup(vec![
+                (DELEGATED_ACTION_STAKE, None),
+                (DELEGATED_ACTION_UNSTAKE, Some((YOCTO / 2).into())),
+            ]);
+
+            ctx.attached_deposit = 2 * YOCTO;
+            testing_env!(ctx.clone());
+            staking_pool.ops_stake_for(to_valid_account_id(ACCOUNT));
+
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            staking_pool.ops_unstake_for(to_valid_account_id(ACCOUNT), Some(YOCTO.into()));
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [DELEGATION_NOT_AUTHORIZED]")]
+        fn withdraw_for_account_not_delegated() {
+            let (_ctx, mut staking_pool) = setup(vec![(DELEGATED_ACTION_STAKE, None)]);
+            staking_pool.ops_stake_withdraw_for(to_valid_account_id(ACCOUNT), None);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_account_freeze {
+        use super::*;
+        use oysterpack_smart_account_management::AccountFreezeManagement;
+
+        fn setup() -> (near_sdk::VMContext, StakingPoolComponent) {
+            let mut ctx = new_context(OWNER);
+            testing_env!(ctx.clone());
+
+            deploy_stake_contract(staking_public_key());
+
+            // register account and stake
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 2 * YOCTO;
+            testing_env!(ctx.clone());
+            account_manager().storage_deposit(None, None);
+            staking_pool().ops_stake();
+
+            // freeze the account
+            ctx.predecessor_account_id = OWNER.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            account_manager().ops_account_freeze(
+                to_valid_account_id(ACCOUNT),
+                "legal request".to_string(),
+                None,
+            );
+
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.account_balance = env::account_balance();
+            testing_env!(ctx.clone());
+            (ctx, staking_pool())
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+        fn unstake_while_frozen() {
+            let (_ctx, mut staking_pool) = setup();
+            staking_pool.ops_unstake(None);
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+        fn withdraw_while_frozen() {
+            let (_ctx, mut staking_pool) = setup();
+            staking_pool.ops_stake_withdraw(None);
+        }
+
+        #[test]
+        #[should_panic(expected = "[ERR] [ACCOUNT_FROZEN]")]
+        fn stake_transfer_while_frozen() {
+            let (mut ctx, mut staking_pool) = setup();
+            ctx.attached_deposit = 1;
+            testing_env!(ctx);
+            staking_pool.ops_stake_transfer(to_valid_account_id(OWNER), YOCTO.into(), None);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_account_migration {
+        use super::*;
+        use oysterpack_smart_account_management::AccountMigrationManagement;
+
+        const NEW_ACCOUNT: &str = "bob-new";
+
+        #[test]
+        fn stake_balances_are_migrated() {
+            // Arrange
+            let mut ctx = new_context(OWNER);
+            testing_env!(ctx.clone());
+            deploy_stake_contract(staking_public_key());
+            let mut staking_pool = staking_pool();
+
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 3 * YOCTO;
+            testing_env!(ctx.clone());
+            account_manager().storage_deposit(None, None);
+            staking_pool.ops_stake();
+
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = YOCTO;
+            testing_env!(ctx.clone());
+            account_manager().storage_deposit(None, None);
+
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            staking_pool.ops_unstake(Some((YOCTO / 2).into()));
+            let balances = staking_pool
+                .ops_stake_balance(to_valid_account_id(ACCOUNT))
+                .unwrap();
+            assert!(balances.staked.is_some());
+            assert!(balances.unstaked.is_some());
+
+            ctx.attached_deposit = 1;
+            testing_env!(ctx.clone());
+            account_manager().ops_account_migrate(to_valid_account_id(NEW_ACCOUNT));
+
+            // Act
+            ctx.predecessor_account_id = NEW_ACCOUNT.to_string();
+            testing_env!(ctx.clone());
+            account_manager().ops_account_migrate_accept(to_valid_account_id(ACCOUNT));
+
+            // Assert
+            assert!(staking_pool
+                .ops_stake_balance(to_valid_account_id(ACCOUNT))
+                .is_none());
+            assert_eq!(
+                staking_pool.ops_stake_balance(to_valid_account_id(NEW_ACCOUNT)),
+                Some(balances)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod tests_health_check {
+        use super::*;
+        use oysterpack_smart_contract::{
+            components::contract_health_check::ContractHealthCheckComponent, ContractHealthCheck,
+        };
+        use oysterpack_smart_near::near_sdk::VMContext;
+
+        fn stake() -> VMContext {
+            let mut ctx = new_context(OWNER);
+            testing_env!(ctx.clone());
+            deploy_stake_contract(staking_public_key());
+
+            ctx.predecessor_account_id = ACCOUNT.to_string();
+            ctx.account_balance = env::account_balance();
+            ctx.attached_deposit = 3 * YOCTO;
+            testing_env!(ctx.clone());
+            account_manager().storage_deposit(None, None);
+            staking_pool().ops_stake();
+
+            // the mocked account balance does not include the attached deposit
+            ctx.account_balance = env::account_balance() + ctx.attached_deposit;
+            ctx.attached_deposit = 0;
+            testing_env!(ctx.clone());
+            ctx
+        }
+
+        fn health_check() -> HealthCheckReport {
+            let mut report = ContractHealthCheckComponent.ops_health_check();
+            staking_pool().health_check(&mut report);
+            report
+        }
+
+        #[test]
+        fn healthy() {
+            stake();
+
+            let report = health_check();
+
+            assert!(report.is_healthy(), "{:?}", report);
+            assert!(report
+                .checks
+                .contains(&HEALTH_CHECK_STAKING_POOL_BALANCES.to_string()));
+            // the pool is offline
+            assert!(!report
+                .checks
+                .contains(&HEALTH_CHECK_STAKED_BALANCE_LOCKED.to_string()));
+        }
+
+        #[test]
+        fn staked_balance_drift() {
+            stake();
+            ContractNearBalances::clear_balance(State::TOTAL_STAKED_BALANCE);
+
+            let report = health_check();
+
+            assert!(report.violated(HEALTH_CHECK_STAKE_SUPPLY));
+        }
+
+        #[test]
+        fn staked_balance_not_backed_by_stake_supply() {
+            let ctx = new_context(OWNER);
+            testing_env!(ctx);
+            deploy_stake_contract(staking_public_key());
+            ContractNearBalances::set_balance(State::TOTAL_STAKED_BALANCE, YOCTO.into());
+
+            let report = health_check();
+
+            assert!(!report.violated(HEALTH_CHECK_STAKE_SUPPLY));
+            assert!(report.violated(HEALTH_CHECK_STAKE_VALUE));
+        }
+
+        #[test]
+        fn contract_managed_balance_decreased() {
+            let mut ctx = stake();
+            ctx.account_balance -= YOCTO;
+            testing_env!(ctx);
+
+            let report = health_check();
+
+            assert!(report.violated(HEALTH_CHECK_STAKING_POOL_EARNINGS));
+        }
+    }
 }
@@ -2,6 +2,7 @@ pub use contract::near_staking_pool::*;
 pub use contract::operator::*;
 pub use contract::stake_action_callbacks::*;
 pub use contract::staking_pool::*;
+pub use contract::staking_pool_delegation::*;
 pub use contract::treasury::*;
 
 pub mod contract;
@@ -2,4 +2,5 @@ pub mod near_staking_pool;
 pub mod operator;
 pub mod stake_action_callbacks;
 pub mod staking_pool;
+pub mod staking_pool_delegation;
 pub mod treasury;
@@ -6,9 +6,13 @@ use oysterpack_smart_near::near_sdk::serde::{Deserialize, Serialize};
 pub trait StakingPoolOperator {
     /// Executes the specified operator command
     ///
+    /// Each command variant is checked for approval separately, using the command name - see
+    /// [`StakingPoolOperatorCommand::command_name`]
+    ///
     /// ## Panics
     /// - if predecessor account is not registered
     /// - if predecessor account is not authorized - requires operator permission
+    /// - if the command requires approval and was not submitted through an approved proposal
     fn ops_stake_operator_command(&mut self, command: StakingPoolOperatorCommand);
 }
 
@@ -24,6 +28,26 @@ pub enum StakingPoolOperatorCommand {
     UpdateFees(Fees),
 }
 
+impl StakingPoolOperatorCommand {
+    /// command names that are used to configure which operator commands require approval, e.g.,
+    /// multisig approval can be required to update fees without requiring it to stop staking
+    pub const COMMAND_NAMES: [&'static str; 4] = [
+        "ops_stake_operator_command.StopStaking",
+        "ops_stake_operator_command.StartStaking",
+        "ops_stake_operator_command.UpdatePublicKey",
+        "ops_stake_operator_command.UpdateFees",
+    ];
+
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            Self::StopStaking => Self::COMMAND_NAMES[0],
+            Self::StartStaking => Self::COMMAND_NAMES[1],
+            Self::UpdatePublicKey(_) => Self::COMMAND_NAMES[2],
+            Self::UpdateFees(_) => Self::COMMAND_NAMES[3],
+        }
+    }
+}
+
 /// 10%
 pub const MAX_FEE: BasisPoints = BasisPoints(1000);
 
@@ -199,3 +199,20 @@ pub const LOG_EVENT_LIQUIDITY: LogEvent = LogEvent(Level::INFO, "LIQUIDITY");
 
 pub const ERR_STAKED_BALANCE_TOO_LOW_TO_UNSTAKE: ErrorConst =
     ErrorConst(ErrCode("STAKED_BALANCE_TOO_LOW_TO_UNSTAKE"), "");
+
+/// Staking pool checks that contracts add to the [`oysterpack_smart_contract::ContractHealthCheck`]
+/// report via [`crate::components::staking_pool::StakingPoolComponent::health_check`]
+///
+/// STAKE supply is backed by staked NEAR
+pub const HEALTH_CHECK_STAKE_SUPPLY: &str = "stake_supply";
+/// the NEAR value of the STAKE supply matches the total staked balance within rounding
+pub const HEALTH_CHECK_STAKE_VALUE: &str = "stake_value";
+/// the treasury STAKE balance does not exceed the STAKE supply
+pub const HEALTH_CHECK_TREASURY_STAKE: &str = "treasury_stake";
+/// the contract managed balance covers the staked and unstaked balances plus the liquidity pool
+pub const HEALTH_CHECK_STAKING_POOL_BALANCES: &str = "staking_pool_balances";
+/// the contract managed balance has not decreased since earnings were last checked
+pub const HEALTH_CHECK_STAKING_POOL_EARNINGS: &str = "staking_pool_earnings";
+/// while online, the locked balance covers the total staked balance
+/// - may be reported transiently while a stake action is in flight
+pub const HEALTH_CHECK_STAKED_BALANCE_LOCKED: &str = "staked_balance_locked";
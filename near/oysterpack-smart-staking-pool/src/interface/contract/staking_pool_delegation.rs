@@ -0,0 +1,47 @@
+use crate::StakeAccountBalances;
+use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::near_sdk::{json_types::ValidAccountId, PromiseOrValue};
+
+/// # **Contract Interface**: Staking Pool Delegation API
+///
+/// Enables delegates, e.g., bots or custodial services, to stake, unstake, and withdraw on behalf
+/// of accounts that have delegated the corresponding actions to them via
+/// [`oysterpack_smart_account_management::AccountDelegation`].
+///
+/// Delegation allowances for staking actions are specified in yoctoNEAR.
+///
+/// ## Panics
+/// All functions panic if:
+/// - the predecessor account has not been delegated the action by `account_id`
+/// - the delegation is expired
+/// - the NEAR amount exceeds the remaining allowance
+pub trait StakingPoolDelegation {
+    /// Delegated version of [`crate::StakingPool::ops_stake`]
+    /// - requires the [`DELEGATED_ACTION_STAKE`] action
+    /// - any attached deposit is staked on behalf of `account_id`
+    ///
+    /// `#[payable]`
+    fn ops_stake_for(&mut self, account_id: ValidAccountId)
+        -> PromiseOrValue<StakeAccountBalances>;
+
+    /// Delegated version of [`crate::StakingPool::ops_unstake`]
+    /// - requires the [`DELEGATED_ACTION_UNSTAKE`] action
+    fn ops_unstake_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> PromiseOrValue<StakeAccountBalances>;
+
+    /// Delegated version of [`crate::StakingPool::ops_stake_withdraw`]
+    /// - requires the [`DELEGATED_ACTION_WITHDRAW`] action
+    /// - funds are always transferred to `account_id`, i.e., never to the delegate
+    fn ops_stake_withdraw_for(
+        &mut self,
+        account_id: ValidAccountId,
+        amount: Option<YoctoNear>,
+    ) -> StakeAccountBalances;
+}
+
+pub const DELEGATED_ACTION_STAKE: &str = "stake";
+pub const DELEGATED_ACTION_UNSTAKE: &str = "unstake";
+pub const DELEGATED_ACTION_WITHDRAW: &str = "withdraw";
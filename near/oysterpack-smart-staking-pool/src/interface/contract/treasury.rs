@@ -1,5 +1,5 @@
 use crate::StakeAccountBalances;
-use oysterpack_smart_near::domain::YoctoNear;
+use oysterpack_smart_near::domain::{ExpirationSetting, YoctoNear};
 use oysterpack_smart_near::near_sdk::json_types::ValidAccountId;
 use oysterpack_smart_near::near_sdk::PromiseOrValue;
 
@@ -46,11 +46,18 @@ pub trait Treasury {
     fn ops_stake_treasury_transfer_to_owner(&mut self, amount: Option<YoctoNear>);
 
     /// grants treasurer permission to specified account
+    /// - if `expiration` is specified, then the treasurer permission is automatically revoked once
+    ///   it expires
     ///
     /// ## Panics
     /// - if not invoked by admin
     /// - if specified account is not registered
-    fn ops_stake_grant_treasurer(&mut self, account_id: ValidAccountId);
+    /// - if `expiration` is already expired
+    fn ops_stake_grant_treasurer(
+        &mut self,
+        account_id: ValidAccountId,
+        expiration: Option<ExpirationSetting>,
+    );
 
     /// revokes treasurer permission to specified account
     ///
@@ -64,3 +71,6 @@ pub trait Treasury {
 }
 
 pub const PERMISSION_TREASURER: &str = "treasurer";
+
+/// stable permission bit for [`PERMISSION_TREASURER`] - the permission name may be changed at runtime
+pub const PERMISSION_TREASURER_BIT: u8 = 0;